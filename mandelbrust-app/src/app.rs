@@ -7,11 +7,15 @@ use std::time::Duration;
 use eframe::egui;
 use tracing::info;
 
-use mandelbrust_core::{Complex, ComplexDD, DoubleDouble, FractalParams, Julia, Viewport};
+use mandelbrust_core::{
+    registry, Complex, ComplexDD, DoubleDouble, FloatExp, Formula, FormulaDefinition,
+    FractalDescriptor, FractalParams, Julia, LyapunovSequence, ParamMap, Polynomial, Precision,
+    Viewport,
+};
 use mandelbrust_render::{
-    builtin_palettes, AaSamples, ColorParams, ColoringMode as RenderColoringMode, ExtrasBuffer,
-    InteriorMode as RenderInteriorMode, IterationBuffer, Palette, RenderCancel, RenderResult,
-    StartFrom as RenderStartFrom,
+    builtin_palettes, AaSamples, ColorParams, ColoringMode as RenderColoringMode, ComponentScan,
+    DensityBuffer, ExtrasBuffer, InteriorMode as RenderInteriorMode, IterationBuffer, Palette,
+    RenderCancel, RenderResult, StartFrom as RenderStartFrom,
 };
 
 use crate::app_state::AppScreen;
@@ -23,7 +27,8 @@ use crate::display_color::{
 };
 use crate::preferences::{AppPreferences, LastView};
 use crate::render_bridge::{
-    julia_grid_worker, render_worker, JuliaGridRequest, RenderPhase, RenderRequest, RenderResponse,
+    component_worker, julia_grid_worker, render_worker, ComponentRequest, JuliaGridRequest,
+    RenderPhase, RenderRequest, RenderResponse,
 };
 
 // ---------------------------------------------------------------------------
@@ -36,7 +41,9 @@ pub(crate) const MAX_HISTORY: usize = 200;
 pub(crate) const PREVIEW_DOWNSCALE: u32 = 4;
 pub(crate) const ADAPTIVE_ITER_RATE: f64 = 30.0;
 pub(crate) const DD_THRESHOLD_SCALE: f64 = 1e-13;
-pub(crate) const DD_WARN_SCALE: f64 = 1e-28;
+pub(crate) const QD_THRESHOLD_SCALE: f64 = 1e-28;
+pub(crate) const QD_LIMIT_SCALE: f64 = 1e-55;
+pub(crate) const QD_WARN_SCALE: f64 = 1e-58;
 pub(crate) const HUD_MARGIN: f32 = 8.0;
 pub(crate) const HUD_CORNER_RADIUS: f32 = 6.0;
 
@@ -47,7 +54,7 @@ pub(crate) struct BookmarkSnap {
     pub(crate) id: String,
     pub(crate) name: String,
     pub(crate) summary: String,
-    pub(crate) mode: String,
+    pub(crate) mode: FractalMode,
     pub(crate) labels: Vec<String>,
     pub(crate) thumbnail_png: String,
 }
@@ -59,18 +66,45 @@ pub(crate) const THUMBNAIL_CACHE_CAPACITY: usize = 64;
 // Fractal mode
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum FractalMode {
-    Mandelbrot,
-    Julia,
+/// The fractal family on screen. Everything mode-specific (menus, the
+/// parameters panel, saving, rendering) goes through its registry entry.
+pub(crate) type FractalMode = &'static FractalDescriptor;
+
+/// Newton roots from a saved file; an empty list (older files) or one that
+/// is not a valid polynomial falls back to the cube roots of unity.
+pub(crate) fn restore_roots(roots: &[Complex]) -> Vec<Complex> {
+    match Polynomial::from_roots(roots.to_vec()) {
+        Ok(_) => roots.to_vec(),
+        Err(_) => Polynomial::default().roots().to_vec(),
+    }
 }
 
-impl FractalMode {
-    pub(crate) fn label(self) -> &'static str {
-        match self {
-            Self::Mandelbrot => "Mandelbrot",
-            Self::Julia => "Julia",
+// ---------------------------------------------------------------------------
+// Precision tier
+// ---------------------------------------------------------------------------
+
+/// Arithmetic used for a frame, picked automatically from the zoom depth
+/// among the tiers `mode` has formulas for.
+///
+/// Families with perturbation use it past `f64`, except within the
+/// quad-double range, where brute force is exact enough to render without
+/// glitch heuristics. Without perturbation, a family stays on its deepest
+/// brute-force tier at any depth.
+pub(crate) fn precision_for_view(mode: FractalMode, scale: FloatExp) -> Precision {
+    if scale >= DD_THRESHOLD_SCALE || !mode.supports(Precision::DoubleDouble) {
+        Precision::F64
+    } else if !mode.supports(Precision::QuadDouble) {
+        Precision::DoubleDouble
+    } else if scale >= QD_THRESHOLD_SCALE {
+        if mode.supports(Precision::Perturbation) {
+            Precision::Perturbation
+        } else {
+            Precision::DoubleDouble
         }
+    } else if scale >= QD_LIMIT_SCALE || !mode.supports(Precision::Perturbation) {
+        Precision::QuadDouble
+    } else {
+        Precision::Perturbation
     }
 }
 
@@ -81,10 +115,23 @@ impl FractalMode {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum BookmarkTab {
     All,
+    /// Parameter-plane bookmarks (Mandelbrot, Multibrot, Burning Ship, …).
     Mandelbrot,
+    /// Bookmarks with a fixed constant `c` (every Julia mode).
     Julia,
 }
 
+impl BookmarkTab {
+    /// The tab listing bookmarks of `mode`'s family.
+    pub(crate) fn for_mode(mode: FractalMode) -> Self {
+        if mode.is_julia() {
+            Self::Julia
+        } else {
+            Self::Mandelbrot
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum LabelFilterMode {
     Off,
@@ -157,7 +204,24 @@ pub(crate) struct MandelbRustApp {
 
     // Fractal state
     pub(crate) mode: FractalMode,
-    pub(crate) julia_c: Complex,
+    pub(crate) julia_c: ComplexDD,
+    /// Toolbar text for the parts of [`julia_c`](Self::julia_c), at full
+    /// precision; applied once it parses.
+    pub(crate) julia_c_draft: [String; 2],
+    /// The constant the drafts were written from, so they are rewritten
+    /// when `julia_c` changes elsewhere (picking, bookmarks, dragging).
+    pub(crate) julia_c_drafted: ComplexDD,
+    /// Scalar parameters of every family by key (exponent, relaxation, …),
+    /// so switching away and back keeps them.
+    pub(crate) param_values: ParamMap,
+    /// Roots of the Newton and Nova polynomial.
+    pub(crate) roots: Vec<Complex>,
+    /// The last user formula that compiled; the editor holds the draft.
+    pub(crate) formula: FormulaDefinition,
+    /// The A/B sequence of the Lyapunov mode.
+    pub(crate) sequence: LyapunovSequence,
+    /// Toolbar text for [`sequence`](Self::sequence); applied once it parses.
+    pub(crate) sequence_draft: String,
     pub(crate) params: FractalParams,
     pub(crate) viewport: Viewport,
 
@@ -176,18 +240,42 @@ pub(crate) struct MandelbRustApp {
     pub(crate) tiles_rendered: usize,
     pub(crate) tiles_mirrored: usize,
     pub(crate) tiles_border_traced: usize,
+    pub(crate) skipped_iterations: u32,
 
     // Coloring
     pub(crate) palettes: Vec<Palette>,
     pub(crate) display_color: DisplayColorSettings,
     pub(crate) current_iterations: Option<IterationBuffer>,
     pub(crate) current_extras: Option<ExtrasBuffer>,
+    /// Hits of the density view so far; replaces the iteration buffer.
+    pub(crate) current_density: Option<DensityBuffer>,
 
     // User-defined palettes
     pub(crate) user_palette_defs: Vec<mandelbrust_core::palette_data::PaletteDefinition>,
     pub(crate) user_palette_cache: Vec<Palette>,
     pub(crate) palette_editor_state: crate::ui::palette_editor::PaletteEditorState,
     pub(crate) show_palette_editor_window: bool,
+    pub(crate) formula_editor: crate::ui::formula_editor::FormulaEditorState,
+    pub(crate) show_formula_editor: bool,
+    pub(crate) density: crate::ui::density::DensitySettings,
+    pub(crate) show_density_settings: bool,
+    pub(crate) external_rays: crate::ui::external_rays::ExternalRaysState,
+    pub(crate) address: crate::ui::address::AddressState,
+    /// The Misiurewicz point last snapped to.
+    pub(crate) misiurewicz: Option<mandelbrust_core::MisiurewiczPoint>,
+    /// Running minibrot search started by "Jump to nearest minibrot", with
+    /// the view it was started from.
+    pub(crate) minibrot_search:
+        Option<(Viewport, mpsc::Receiver<Option<mandelbrust_core::Minibrot>>)>,
+    /// Running search started by "Snap to Misiurewicz point", with the view
+    /// it was started from.
+    pub(crate) misiurewicz_search: Option<(
+        Viewport,
+        mpsc::Receiver<Option<mandelbrust_core::MisiurewiczPoint>>,
+    )>,
+    pub(crate) show_external_rays: bool,
+    pub(crate) orbit_inspector: crate::ui::orbit_inspector::OrbitInspectorState,
+    pub(crate) show_orbit_inspector: bool,
     pub(crate) color_settings_tab: ColorSettingsTab,
     pub(crate) settings_tab: SettingsTab,
 
@@ -209,7 +297,7 @@ pub(crate) struct MandelbRustApp {
     pub(crate) bookmarks_preview: Option<egui::TextureHandle>,
     pub(crate) drag_active: bool,
     pub(crate) pan_offset: egui::Vec2,
-    pub(crate) cursor_complex: Option<Complex>,
+    pub(crate) cursor_complex: Option<ComplexDD>,
     pub(crate) zoom_rect_start: Option<egui::Pos2>,
 
     // View history
@@ -274,6 +362,13 @@ pub(crate) struct MandelbRustApp {
     pub(crate) rx_julia_grid_resp: mpsc::Receiver<(u32, u32, RenderResult)>,
     pub(crate) grid_cancel: Arc<RenderCancel>,
 
+    // Hyperbolic component labels
+    pub(crate) components: crate::ui::components::ComponentLabelsState,
+    pub(crate) show_component_labels: bool,
+    pub(crate) tx_components_req: mpsc::Sender<ComponentRequest>,
+    pub(crate) rx_components_resp: mpsc::Receiver<(Viewport, ComponentScan)>,
+    pub(crate) components_cancel: Arc<RenderCancel>,
+
     // J preview panel
     pub(crate) tx_jpreview: mpsc::Sender<(RenderResult, u64)>,
     pub(crate) rx_jpreview: mpsc::Receiver<(RenderResult, u64)>,
@@ -281,7 +376,7 @@ pub(crate) struct MandelbRustApp {
     pub(crate) j_preview_loading: bool,
     pub(crate) j_preview_revision: u64,
     pub(crate) j_preview_cancel: Arc<RenderCancel>,
-    pub(crate) last_j_preview_cursor: Option<Complex>,
+    pub(crate) last_j_preview_cursor: Option<ComplexDD>,
 
     // Image export
     pub(crate) export_state: crate::ui::export::ExportState,
@@ -303,6 +398,10 @@ impl MandelbRustApp {
         let (tx_julia_grid_resp, rx_julia_grid_resp) = mpsc::channel();
         let grid_cancel = Arc::new(RenderCancel::new());
 
+        let (tx_components_req, rx_components_req) = mpsc::channel();
+        let (tx_components_resp, rx_components_resp) = mpsc::channel();
+        let components_cancel = Arc::new(RenderCancel::new());
+
         let (tx_jpreview, rx_jpreview) = mpsc::channel();
         let j_preview_cancel = Arc::new(RenderCancel::new());
 
@@ -316,22 +415,32 @@ impl MandelbRustApp {
             julia_grid_worker(rx_julia_grid_req, tx_julia_grid_resp);
         });
 
+        let ctx = egui_ctx.clone();
+        thread::spawn(move || {
+            component_worker(ctx, rx_components_req, tx_components_resp);
+        });
+
         let w = prefs.window_width as u32;
         let h = prefs.window_height as u32;
 
+        let last_view = prefs.last_view.as_ref().filter(|_| prefs.restore_last_view);
+        let mut param_values = registry::default_values();
+        if let Some(lv) = last_view {
+            param_values.extend(lv.param_values());
+        }
+        let roots = restore_roots(last_view.map_or(&[], |lv| &lv.newton_roots));
+        let formula = last_view
+            .map(|lv| lv.formula.clone())
+            .filter(|f| Formula::compile(f, FractalParams::default()).is_ok())
+            .unwrap_or_default();
+        let sequence = last_view.map(|lv| lv.sequence.clone()).unwrap_or_default();
         let (mode, julia_c, params, viewport, mut display_color, aa_level) = if prefs
             .restore_last_view
         {
             if let Some(ref lv) = prefs.last_view {
-                let m = match lv.mode.as_str() {
-                    "Julia" => FractalMode::Julia,
-                    _ => FractalMode::Mandelbrot,
-                };
-                let center_dd = ComplexDD::new(
-                    DoubleDouble::new(lv.center_re, lv.center_re_lo),
-                    DoubleDouble::new(lv.center_im, lv.center_im_lo),
-                );
-                let vp = Viewport::new_dd(center_dd, lv.scale, w, h)
+                let m = lv.descriptor();
+                let vp = lv
+                    .viewport(w, h)
                     .unwrap_or_else(|_| Viewport::default_mandelbrot(w, h));
                 let p = FractalParams::new(lv.max_iterations, lv.escape_radius).unwrap_or_default();
                 let dc = DisplayColorSettings {
@@ -341,12 +450,15 @@ impl MandelbRustApp {
                 };
                 info!(
                     "Restoring last view: {} at zoom {:.2e}",
-                    lv.mode,
-                    1.0 / lv.scale
+                    m.name,
+                    lv.scale.recip()
                 );
                 (
                     m,
-                    Complex::new(lv.julia_c_re, lv.julia_c_im),
+                    ComplexDD::new(
+                        DoubleDouble::new(lv.julia_c_re, lv.julia_c_re_lo),
+                        DoubleDouble::new(lv.julia_c_im, lv.julia_c_im_lo),
+                    ),
                     p,
                     vp,
                     dc,
@@ -390,8 +502,16 @@ impl MandelbRustApp {
 
             mode,
             julia_c,
+            julia_c_draft: crate::ui::toolbar::julia_c_text(julia_c),
+            julia_c_drafted: julia_c,
+            param_values,
+            roots,
+            formula_editor: crate::ui::formula_editor::FormulaEditorState::new(&formula),
+            formula,
+            sequence_draft: sequence.to_string(),
+            sequence,
             params,
-            viewport,
+            viewport: viewport.clone(),
 
             tx_request: tx_req,
             rx_response: rx_resp,
@@ -406,16 +526,29 @@ impl MandelbRustApp {
             tiles_rendered: 0,
             tiles_mirrored: 0,
             tiles_border_traced: 0,
+            skipped_iterations: 0,
 
             palettes,
             display_color,
             current_iterations: None,
             current_extras: None,
+            current_density: None,
 
             user_palette_defs,
             user_palette_cache,
             palette_editor_state: crate::ui::palette_editor::PaletteEditorState::default(),
             show_palette_editor_window: false,
+            show_formula_editor: false,
+            density: Default::default(),
+            show_density_settings: false,
+            external_rays: Default::default(),
+            address: Default::default(),
+            misiurewicz: None,
+            minibrot_search: None,
+            misiurewicz_search: None,
+            show_external_rays: false,
+            orbit_inspector: Default::default(),
+            show_orbit_inspector: false,
             color_settings_tab: ColorSettingsTab::default(),
             settings_tab: SettingsTab::default(),
 
@@ -461,10 +594,7 @@ impl MandelbRustApp {
             save_bookmark_labels_selected: HashSet::new(),
             save_bookmark_new_label: String::new(),
             bookmark_search: String::new(),
-            bookmark_tab: match mode {
-                FractalMode::Mandelbrot => BookmarkTab::Mandelbrot,
-                FractalMode::Julia => BookmarkTab::Julia,
-            },
+            bookmark_tab: BookmarkTab::for_mode(mode),
             favorites_only: false,
             editing_bookmark: None,
             editing_name: String::new(),
@@ -495,6 +625,11 @@ impl MandelbRustApp {
             tx_julia_grid_req,
             rx_julia_grid_resp,
             grid_cancel,
+            components: Default::default(),
+            show_component_labels: false,
+            tx_components_req,
+            rx_components_resp,
+            components_cancel,
             tx_jpreview,
             rx_jpreview,
             j_preview_texture: None,
@@ -512,13 +647,28 @@ impl MandelbRustApp {
 
     // -- Palette helpers ---------------------------------------------------
 
-    pub(crate) fn current_palette(&self) -> &Palette {
-        if let Some(ref name) = self.display_color.custom_palette_name {
-            if let Some(idx) = self.user_palette_defs.iter().position(|d| d.name == *name) {
-                return &self.user_palette_cache[idx];
-            }
+    pub(crate) fn current_palette(&self) -> Palette {
+        let palette = self
+            .display_color
+            .custom_palette_name
+            .as_ref()
+            .and_then(|name| self.user_palette_defs.iter().position(|d| d.name == *name))
+            .map_or(&self.palettes[self.display_color.palette_index], |idx| {
+                &self.user_palette_cache[idx]
+            });
+        self.with_chaos_palette(palette.clone(), &self.display_color)
+    }
+
+    /// Attach the chaos gradient `dc` selects for Lyapunov coloring.
+    pub(crate) fn with_chaos_palette(
+        &self,
+        palette: Palette,
+        dc: &DisplayColorSettings,
+    ) -> Palette {
+        match self.palettes.get(dc.chaos_palette_index) {
+            Some(chaos) => palette.with_chaos_gradient(chaos),
+            None => palette,
         }
-        &self.palettes[self.display_color.palette_index]
     }
 
     #[allow(dead_code)]
@@ -540,10 +690,20 @@ impl MandelbRustApp {
             DisplayColoringMode::Standard => RenderColoringMode::Standard,
             DisplayColoringMode::Histogram => RenderColoringMode::Histogram,
             DisplayColoringMode::DistanceEstimation => RenderColoringMode::DistanceEstimation,
+            DisplayColoringMode::OrbitTrap => RenderColoringMode::OrbitTrap,
+            DisplayColoringMode::StripeAverage
+            | DisplayColoringMode::TriangleInequality
+            | DisplayColoringMode::Curvature => RenderColoringMode::OrbitAverage,
+            DisplayColoringMode::BinaryDecomposition => RenderColoringMode::BinaryDecomposition,
+            DisplayColoringMode::AngleHue => RenderColoringMode::AngleHue,
+            DisplayColoringMode::FieldLines => RenderColoringMode::FieldLines,
         };
         let interior_mode = match self.display_color.interior_mode {
             DisplayInteriorMode::Black => RenderInteriorMode::Black,
             DisplayInteriorMode::StripeAverage => RenderInteriorMode::StripeAverage,
+            DisplayInteriorMode::Period => RenderInteriorMode::Period,
+            DisplayInteriorMode::Multiplier => RenderInteriorMode::Multiplier,
+            DisplayInteriorMode::InteriorDistance => RenderInteriorMode::InteriorDistance,
         };
         ColorParams {
             smooth: self.display_color.smooth_coloring,
@@ -553,9 +713,32 @@ impl MandelbRustApp {
             low_threshold_end: self.display_color.low_threshold_end,
             coloring_mode,
             interior_mode,
+            degree: self.degree(),
+            field_lines: self.display_color.field_lines,
         }
     }
 
+    /// Degree of the current formula, for the smooth-coloring log base.
+    pub(crate) fn degree(&self) -> f64 {
+        self.mode
+            .build(
+                &self.fractal_inputs(),
+                self.params,
+                Precision::F64,
+                &self.viewport,
+            )
+            .degree()
+    }
+
+    /// Values of the current family's parameters, for saving.
+    pub(crate) fn mode_param_values(&self) -> ParamMap {
+        self.mode
+            .params
+            .iter()
+            .map(|p| (p.key.to_string(), p.value(&self.param_values)))
+            .collect()
+    }
+
     pub(crate) fn colorize_current(
         &self,
         iter_buf: &IterationBuffer,
@@ -572,12 +755,23 @@ impl MandelbRustApp {
 
     /// Whether the current coloring mode requires extras (re-render needed).
     pub(crate) fn needs_extras(&self) -> bool {
-        self.display_color.coloring_mode == DisplayColoringMode::DistanceEstimation
-            || self.display_color.interior_mode == DisplayInteriorMode::StripeAverage
+        self.display_color.coloring_mode.needs_extras()
+            || self.display_color.interior_mode != DisplayInteriorMode::Black
     }
 
     pub(crate) fn recolorize(&mut self, ctx: &egui::Context) {
-        if let Some(ref iter_buf) = self.current_iterations {
+        if let Some(ref density) = self.current_density {
+            let buffer = self
+                .current_palette()
+                .colorize_density(density, &self.density.tone);
+            let image = egui::ColorImage::from_rgba_unmultiplied(
+                [buffer.width as usize, buffer.height as usize],
+                &buffer.pixels,
+            );
+            self.texture = Some(ctx.load_texture("fractal", image, egui::TextureOptions::LINEAR));
+            self.draw_offset = egui::Vec2::ZERO;
+            self.update_resume_preview(ctx, &buffer.pixels, buffer.width, buffer.height);
+        } else if let Some(ref iter_buf) = self.current_iterations {
             let buffer = self.colorize_current(iter_buf, self.current_aa.as_ref());
             let image = egui::ColorImage::from_rgba_unmultiplied(
                 [buffer.width as usize, buffer.height as usize],
@@ -595,7 +789,7 @@ impl MandelbRustApp {
         if !self.adaptive_iterations {
             return self.params;
         }
-        let default_scale = 3.6 / 1280.0_f64;
+        let default_scale = FloatExp::from(3.6 / 1280.0_f64);
         let zoom = default_scale / self.viewport.scale;
         if zoom <= 1.0 {
             return self.params;
@@ -611,19 +805,31 @@ impl MandelbRustApp {
 
     pub(crate) fn capture_last_view(&self) -> LastView {
         LastView {
-            mode: self.mode.label().to_string(),
-            center_re: self.viewport.center_dd.re.hi,
-            center_im: self.viewport.center_dd.im.hi,
-            center_re_lo: self.viewport.center_dd.re.lo,
-            center_im_lo: self.viewport.center_dd.im.lo,
+            fractal: self.mode.id.to_string(),
+            center_re: self.viewport.center_qd.re.limbs[0],
+            center_im: self.viewport.center_qd.im.limbs[0],
+            center_re_lo: self.viewport.center_qd.re.limbs[1],
+            center_im_lo: self.viewport.center_qd.im.limbs[1],
+            center_re_lo2: self.viewport.center_qd.re.limbs[2],
+            center_re_lo3: self.viewport.center_qd.re.limbs[3],
+            center_im_lo2: self.viewport.center_qd.im.limbs[2],
+            center_im_lo3: self.viewport.center_qd.im.limbs[3],
+            center: Some(self.viewport.center_big.clone()),
             scale: self.viewport.scale,
             max_iterations: self.params.max_iterations,
             escape_radius: self.params.escape_radius,
             palette_index: self.display_color.palette_index,
             smooth_coloring: self.display_color.smooth_coloring,
             aa_level: self.aa_level,
-            julia_c_re: self.julia_c.re,
-            julia_c_im: self.julia_c.im,
+            julia_c_re: self.julia_c.re.hi,
+            julia_c_im: self.julia_c.im.hi,
+            julia_c_re_lo: self.julia_c.re.lo,
+            julia_c_im_lo: self.julia_c.im.lo,
+            params: self.param_values.clone(),
+            newton_roots: self.roots.clone(),
+            formula: self.formula.clone(),
+            sequence: self.sequence.clone(),
+            legacy: Default::default(),
         }
     }
 }
@@ -661,6 +867,10 @@ impl MandelbRustApp {
         }
         self.poll_responses(ctx);
         self.poll_julia_grid_responses(ctx);
+        self.poll_component_responses();
+        self.poll_minibrot_search();
+        self.poll_misiurewicz_search();
+        self.request_components_if_needed();
         if self.julia_explorer_restart_pending {
             self.julia_explorer_restart_pending = false;
             self.start_julia_grid_request();
@@ -673,6 +883,9 @@ impl MandelbRustApp {
         if self.show_hud && !self.show_julia_c_explorer && self.preferences.show_j_preview {
             self.request_j_preview_if_needed(ctx);
         }
+        if self.show_hud && !self.show_julia_c_explorer {
+            self.request_address_if_needed();
+        }
 
         egui::CentralPanel::default()
             .frame(egui::Frame::NONE)
@@ -776,6 +989,10 @@ impl MandelbRustApp {
                     );
                 }
 
+                self.draw_component_labels(&painter, response.rect);
+                self.draw_external_rays(&painter, response.rect);
+                self.draw_orbit(&painter, response.rect);
+
                 if let Some(start) = self.zoom_rect_start {
                     if let Some(end) = response.hover_pos() {
                         let sel_rect = egui::Rect::from_two_pos(start, end);
@@ -795,7 +1012,7 @@ impl MandelbRustApp {
             });
 
         if let Some((c_re, c_im)) = self.julia_explorer_picked_c.take() {
-            self.julia_c = Complex::new(c_re, c_im);
+            self.julia_c = ComplexDD::from(Complex::new(c_re, c_im));
             self.bump_minimap_revision();
             self.needs_render = true;
             self.show_julia_c_explorer = false;
@@ -806,6 +1023,11 @@ impl MandelbRustApp {
         self.show_hud(ctx);
         self.show_bookmark_window(ctx);
         self.show_julia_c_explorer_window(ctx);
+        self.show_formula_editor_window(ctx);
+        self.show_density_window(ctx);
+        self.show_external_rays_window(ctx);
+        self.show_component_labels_window(ctx);
+        self.show_orbit_inspector_window(ctx);
         self.show_update_or_save_choice(ctx);
         self.show_save_bookmark_dialog(ctx);
 
@@ -845,17 +1067,13 @@ impl eframe::App for MandelbRustApp {
         let text_editing = ctx.memory(|m| m.focused().is_some());
         if !text_editing {
             match self.screen {
-                AppScreen::BookmarkBrowser => {
-                    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-                        self.screen = AppScreen::MainMenu;
-                        self.browser_selected_bookmark = None;
-                    }
+                AppScreen::BookmarkBrowser if ctx.input(|i| i.key_pressed(egui::Key::Escape)) => {
+                    self.screen = AppScreen::MainMenu;
+                    self.browser_selected_bookmark = None;
                 }
-                AppScreen::JuliaCExplorer => {
-                    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-                        self.screen = AppScreen::MainMenu;
-                        self.grid_cancel.cancel();
-                    }
+                AppScreen::JuliaCExplorer if ctx.input(|i| i.key_pressed(egui::Key::Escape)) => {
+                    self.screen = AppScreen::MainMenu;
+                    self.grid_cancel.cancel();
                 }
                 _ => {}
             }
@@ -881,7 +1099,7 @@ pub(crate) fn defaults_for(
     prefs: &AppPreferences,
 ) -> (
     FractalMode,
-    Complex,
+    ComplexDD,
     FractalParams,
     Viewport,
     DisplayColorSettings,
@@ -893,8 +1111,8 @@ pub(crate) fn defaults_for(
         ..DisplayColorSettings::default()
     };
     (
-        FractalMode::Mandelbrot,
-        Julia::default_c(),
+        &registry::MANDELBROT,
+        ComplexDD::from(Julia::default_c()),
         FractalParams::default().with_max_iterations(prefs.default_max_iterations),
         Viewport::default_mandelbrot(w, h),
         display_color,
@@ -4,11 +4,16 @@ use std::path::PathBuf;
 use std::sync::mpsc;
 
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use mandelbrust_core::{
+    Complex, ComplexBig, ComplexQD, FloatExp, FormulaDefinition, FractalDescriptor,
+    LyapunovSequence, ParamMap, QuadDouble, Viewport,
+};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
 use crate::display_color::DisplayColorSettings;
 use crate::io_worker::IoRequest;
+use crate::preferences::{saved_descriptor, saved_params, saved_viewport, LegacyFractalFields};
 
 // ---------------------------------------------------------------------------
 // Bookmark
@@ -21,8 +26,10 @@ use crate::io_worker::IoRequest;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bookmark {
     pub name: String,
-    /// `"Mandelbrot"` or `"Julia"`.
-    pub mode: String,
+    /// Registry id of the fractal family, e.g. `"mandelbrot"` or
+    /// `"burning_ship_julia"`.
+    #[serde(default)]
+    pub fractal: String,
     pub center_re: f64,
     pub center_im: f64,
     /// Low-order bits for double-double center precision (~31 digits total).
@@ -31,7 +38,23 @@ pub struct Bookmark {
     /// Low-order bits for double-double center precision (~31 digits total).
     #[serde(default)]
     pub center_im_lo: f64,
-    pub scale: f64,
+    /// Third and fourth limbs for quad-double center precision (~62 digits
+    /// total). Older files without them load as double-double centers.
+    #[serde(default)]
+    pub center_re_lo2: f64,
+    #[serde(default)]
+    pub center_re_lo3: f64,
+    #[serde(default)]
+    pub center_im_lo2: f64,
+    #[serde(default)]
+    pub center_im_lo3: f64,
+    /// Full-precision center as decimal strings, needed below ~1e-60 where
+    /// the limbs above run out. Files without it load from the limbs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub center: Option<ComplexBig>,
+    /// Complex-plane units per pixel. Stored as a JSON number while it fits
+    /// in an `f64`, as a decimal string (e.g. `"2.5e-1000"`) beyond that.
+    pub scale: FloatExp,
     pub max_iterations: u32,
     pub escape_radius: f64,
     pub palette_index: usize,
@@ -42,6 +65,26 @@ pub struct Bookmark {
     pub aa_level: u32,
     pub julia_c_re: f64,
     pub julia_c_im: f64,
+    /// Low-order bits of the Julia constant (double-double, ~31 digits).
+    /// Older files without them load the `f64` constant.
+    #[serde(default)]
+    pub julia_c_re_lo: f64,
+    #[serde(default)]
+    pub julia_c_im_lo: f64,
+    /// Values of the family's parameters, by [`ParamSpec`] key.
+    ///
+    /// [`ParamSpec`]: mandelbrust_core::ParamSpec
+    #[serde(default, skip_serializing_if = "ParamMap::is_empty")]
+    pub params: ParamMap,
+    /// Roots of the Newton and Nova polynomial; empty for other modes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub newton_roots: Vec<Complex>,
+    /// The user formula of a Formula-mode bookmark.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formula: Option<FormulaDefinition>,
+    /// The A/B sequence of a Lyapunov bookmark.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<LyapunovSequence>,
     /// Hierarchical labels using `/` as separator (e.g. "Spirals/Double").
     #[serde(default, alias = "tags")]
     pub labels: Vec<String>,
@@ -57,13 +100,64 @@ pub struct Bookmark {
     /// Not written to new files.
     #[serde(default, skip_serializing)]
     pub thumbnail_file: String,
+    #[serde(flatten)]
+    pub legacy: LegacyFractalFields,
 }
 
 impl Bookmark {
+    pub fn descriptor(&self) -> &'static FractalDescriptor {
+        saved_descriptor(&self.fractal, &self.legacy)
+    }
+
+    /// The saved view at the given canvas size.
+    pub fn viewport(&self, width: u32, height: u32) -> mandelbrust_core::Result<Viewport> {
+        let limbs = ComplexQD::new(
+            QuadDouble::new(
+                self.center_re,
+                self.center_re_lo,
+                self.center_re_lo2,
+                self.center_re_lo3,
+            ),
+            QuadDouble::new(
+                self.center_im,
+                self.center_im_lo,
+                self.center_im_lo2,
+                self.center_im_lo3,
+            ),
+        );
+        saved_viewport(self.center.as_ref(), limbs, self.scale, width, height)
+    }
+
+    /// Rewrite a bookmark from before the fractal registry in the current
+    /// format, so saving it again keeps its family and parameters.
+    pub fn upgrade(&mut self) {
+        let descriptor = self.descriptor();
+        self.params = saved_params(&self.params, &self.legacy)
+            .into_iter()
+            .filter(|(key, _)| descriptor.param(key).is_some())
+            .collect();
+        self.fractal = descriptor.id.to_string();
+        self.legacy = LegacyFractalFields::default();
+    }
+
     /// Human-readable summary for list views.
     pub fn summary(&self) -> String {
-        let zoom = 1.0 / self.scale;
-        format!("{} — zoom {zoom:.2e}", self.mode)
+        let zoom = self.scale.recip();
+        if let Some(formula) = &self.formula {
+            return format!("{} — zoom {zoom:.2e}", formula.formula.trim());
+        }
+        let descriptor = self.descriptor();
+        let mut summary = descriptor.name.to_string();
+        for spec in descriptor.params {
+            summary += &format!(" {} = {}", spec.symbol, spec.value(&self.params));
+        }
+        if !self.newton_roots.is_empty() {
+            summary += &format!(" ({} roots)", self.newton_roots.len());
+        }
+        if let Some(sequence) = &self.sequence {
+            summary += &format!(" {sequence}");
+        }
+        format!("{summary} — zoom {zoom:.2e}")
     }
 
     /// Whether this bookmark was loaded from the legacy format and has a
@@ -74,9 +168,9 @@ impl Bookmark {
 }
 
 /// Suggest smart default labels for a bookmark based on its state.
-pub fn suggest_default_labels(mode: &str, scale: f64, max_iterations: u32) -> Vec<String> {
+pub fn suggest_default_labels(mode: &str, scale: FloatExp, max_iterations: u32) -> Vec<String> {
     let mut labels = vec![mode.to_lowercase()];
-    let zoom = 1.0 / scale;
+    let zoom = scale.recip();
     if zoom > 1e10 {
         labels.push("Deep zoom".to_string());
     } else if zoom > 1e4 {
@@ -305,7 +399,8 @@ impl BookmarkStore {
             }
             match fs::read_to_string(&path) {
                 Ok(json) => match serde_json::from_str::<Bookmark>(&json) {
-                    Ok(bm) => {
+                    Ok(mut bm) => {
+                        bm.upgrade();
                         let fname = path
                             .file_name()
                             .unwrap_or_default()
@@ -666,6 +761,7 @@ fn migrate_legacy(bookmarks_dir: &std::path::Path) {
     let mut used_filenames: Vec<String> = Vec::new();
 
     for mut bm in old_bookmarks {
+        bm.upgrade();
         // Embed the thumbnail if the legacy separate-file reference exists.
         if bm.has_legacy_thumbnail() {
             let thumb_path = thumb_dir.join(&bm.thumbnail_file);
@@ -1,6 +1,7 @@
 //! Display and color settings: a single, serializable model used everywhere
 //! coloring or display is decided (main view, export, profiles, bookmarks).
 
+use mandelbrust_core::{ExtrasOptions, OrbitAverage, OrbitTrap};
 use serde::{Deserialize, Serialize};
 
 // ---------------------------------------------------------------------------
@@ -49,6 +50,30 @@ pub enum ColoringMode {
     Standard,
     Histogram,
     DistanceEstimation,
+    OrbitTrap,
+    StripeAverage,
+    TriangleInequality,
+    Curvature,
+    BinaryDecomposition,
+    AngleHue,
+    FieldLines,
+}
+
+impl ColoringMode {
+    /// The exterior orbit average this mode colors by, if any.
+    pub fn orbit_average(self) -> Option<OrbitAverage> {
+        match self {
+            Self::StripeAverage => Some(OrbitAverage::Stripe),
+            Self::TriangleInequality => Some(OrbitAverage::TriangleInequality),
+            Self::Curvature => Some(OrbitAverage::Curvature),
+            _ => None,
+        }
+    }
+
+    /// Whether the mode reads the extras buffer.
+    pub fn needs_extras(self) -> bool {
+        !matches!(self, Self::Standard | Self::Histogram)
+    }
 }
 
 /// How interior (non-escaping) pixels are colored.
@@ -58,6 +83,19 @@ pub enum InteriorMode {
     #[default]
     Black,
     StripeAverage,
+    Period,
+    Multiplier,
+    InteriorDistance,
+}
+
+impl InteriorMode {
+    /// Whether the mode colors by the attracting cycle of interior points.
+    pub fn analyzes_cycles(self) -> bool {
+        matches!(
+            self,
+            Self::Period | Self::Multiplier | Self::InteriorDistance
+        )
+    }
 }
 
 /// Full display/color configuration: palette choice, cycle mode, start-from
@@ -69,6 +107,9 @@ pub struct DisplayColorSettings {
     /// When set, a user-defined palette with this name is used instead of `palette_index`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_palette_name: Option<String>,
+    /// Built-in palette for the chaotic side (`λ > 0`) of Lyapunov images.
+    #[serde(default = "default_chaos_palette_index")]
+    pub chaos_palette_index: usize,
     /// How the palette repeats over the iteration range.
     #[serde(default)]
     pub palette_mode: PaletteMode,
@@ -89,11 +130,23 @@ pub struct DisplayColorSettings {
     /// How interior pixels are colored.
     #[serde(default)]
     pub interior_mode: InteriorMode,
-    /// Stripe density for interior stripe-average coloring.
+    /// Stripe density for interior and exterior stripe-average coloring.
     #[serde(default = "default_stripe_density")]
     pub stripe_density: f64,
+    /// Blend exterior averages across the last iteration to remove banding.
+    #[serde(default = "default_smooth_average")]
+    pub smooth_average: bool,
+    /// Field lines per turn for field-line coloring.
+    #[serde(default = "default_field_lines")]
+    pub field_lines: u32,
+    /// Trap measured by orbit-trap coloring.
+    #[serde(default)]
+    pub orbit_trap: OrbitTrap,
 }
 
+fn default_chaos_palette_index() -> usize {
+    2 // Ocean
+}
 fn default_low_threshold_start() -> u32 {
     10
 }
@@ -103,12 +156,19 @@ fn default_low_threshold_end() -> u32 {
 fn default_stripe_density() -> f64 {
     1.0
 }
+fn default_smooth_average() -> bool {
+    true
+}
+fn default_field_lines() -> u32 {
+    mandelbrust_render::DEFAULT_FIELD_LINES
+}
 
 impl Default for DisplayColorSettings {
     fn default() -> Self {
         Self {
             palette_index: 0,
             custom_palette_name: None,
+            chaos_palette_index: default_chaos_palette_index(),
             palette_mode: PaletteMode::default(),
             start_from: StartFrom::default(),
             low_threshold_start: default_low_threshold_start(),
@@ -117,6 +177,9 @@ impl Default for DisplayColorSettings {
             coloring_mode: ColoringMode::default(),
             interior_mode: InteriorMode::default(),
             stripe_density: default_stripe_density(),
+            smooth_average: default_smooth_average(),
+            field_lines: default_field_lines(),
+            orbit_trap: OrbitTrap::default(),
         }
     }
 }
@@ -125,14 +188,20 @@ impl DisplayColorSettings {
     /// Effective cycle length in iterations (for ByCycles, depends on max_iterations).
     pub fn cycle_length(&self, max_iterations: u32) -> u32 {
         match self.palette_mode {
-            PaletteMode::ByCycles { n } => {
-                if n == 0 {
-                    max_iterations
-                } else {
-                    max_iterations / n
-                }
-            }
+            PaletteMode::ByCycles { n } => max_iterations.checked_div(n).unwrap_or(max_iterations),
             PaletteMode::ByCycleLength { len } => len,
         }
     }
+
+    /// What the extras pass must measure for these settings. The trap is only
+    /// tracked while orbit-trap coloring is selected.
+    pub fn extras_options(&self) -> ExtrasOptions {
+        ExtrasOptions {
+            stripe_density: self.stripe_density,
+            orbit_trap: (self.coloring_mode == ColoringMode::OrbitTrap).then_some(self.orbit_trap),
+            exterior_average: self.coloring_mode.orbit_average(),
+            smooth_average: self.smooth_average,
+            analyze_interior: self.interior_mode.analyzes_cycles(),
+        }
+    }
 }
@@ -1,15 +1,13 @@
 use eframe::egui;
 
-use mandelbrust_core::ComplexDD;
-
-use crate::app::{FractalMode, MandelbRustApp, PAN_FRACTION, ZOOM_SPEED};
+use crate::app::{MandelbRustApp, PAN_FRACTION, ZOOM_SPEED};
 
 impl MandelbRustApp {
     pub(crate) fn handle_canvas_input(&mut self, ctx: &egui::Context, response: &egui::Response) {
         self.cursor_complex = response.hover_pos().map(|pos| {
             let px = (pos.x - response.rect.min.x) as u32;
             let py = (pos.y - response.rect.min.y) as u32;
-            self.viewport.pixel_to_complex(px, py)
+            self.viewport.pixel_to_complex_dd(px, py)
         });
 
         let scroll_y = ctx.input(|i| i.raw_scroll_delta.y);
@@ -70,10 +68,11 @@ impl MandelbRustApp {
                     let mid_y = (start.y + end.y) / 2.0;
                     let px = (mid_x - rect.min.x) as u32;
                     let py = (mid_y - rect.min.y) as u32;
-                    let delta = self.viewport.pixel_to_delta(px, py);
-                    let new_center = self.viewport.center_dd + ComplexDD::from(delta);
                     self.push_history();
-                    self.viewport.set_center_dd(new_center);
+                    self.viewport.pan_pixels(
+                        px as f64 - self.viewport.width as f64 / 2.0,
+                        py as f64 - self.viewport.height as f64 / 2.0,
+                    );
                     self.viewport.scale *= fraction as f64;
                     self.needs_render = true;
                 }
@@ -81,8 +80,21 @@ impl MandelbRustApp {
             self.zoom_rect_start = None;
         }
 
-        if self.mode == FractalMode::Julia && response.clicked() && ctx.input(|i| i.modifiers.shift)
-        {
+        if self.external_rays.picking && self.external_rays_active() && response.clicked() {
+            if let Some(c) = self.cursor_complex {
+                self.pick_external_angles(c);
+            }
+            return;
+        }
+
+        if self.orbit_inspector.picking && response.clicked() {
+            if let Some(c) = self.cursor_complex {
+                self.inspect_orbit(c);
+            }
+            return;
+        }
+
+        if self.mode.is_julia() && response.clicked() && ctx.input(|i| i.modifiers.shift) {
             if let Some(c) = self.cursor_complex {
                 self.julia_c = c;
                 self.bump_minimap_revision();
@@ -90,14 +102,15 @@ impl MandelbRustApp {
             }
         }
 
-        if self.mode == FractalMode::Mandelbrot
+        let counterpart = self.mode.counterpart().filter(|_| !self.mode.is_julia());
+        if counterpart.is_some()
             && self.preferences.show_j_preview
             && response.clicked()
             && !ctx.input(|i| i.modifiers.shift)
         {
-            if let Some(c) = self.cursor_complex {
+            if let (Some(c), Some(counterpart)) = (self.cursor_complex, counterpart) {
                 self.julia_c = c;
-                self.mode = FractalMode::Julia;
+                self.mode = counterpart;
                 self.push_history();
                 self.viewport = self.default_viewport();
                 self.bump_minimap_revision();
@@ -153,6 +166,12 @@ impl MandelbRustApp {
             if input.key_pressed(egui::Key::R) && !input.modifiers.ctrl {
                 self.reset_view();
             }
+            if input.key_pressed(egui::Key::N) {
+                self.jump_to_nearest_minibrot();
+            }
+            if input.key_pressed(egui::Key::P) {
+                self.snap_to_misiurewicz_point();
+            }
             if input.key_pressed(egui::Key::H) {
                 self.show_hud = !self.show_hud;
             }
@@ -170,10 +189,7 @@ impl MandelbRustApp {
                 self.show_bookmarks = !self.show_bookmarks;
                 if self.show_bookmarks {
                     self.bookmark_store.reload();
-                    self.bookmark_tab = match self.mode {
-                        FractalMode::Mandelbrot => crate::app::BookmarkTab::Mandelbrot,
-                        FractalMode::Julia => crate::app::BookmarkTab::Julia,
-                    };
+                    self.bookmark_tab = crate::app::BookmarkTab::for_mode(self.mode);
                 }
             }
             if input.key_pressed(egui::Key::J) {
@@ -89,7 +89,8 @@ fn scan_bookmark_dir(dir: &std::path::Path) -> (Vec<Bookmark>, Vec<String>) {
         }
         match std::fs::read_to_string(&path) {
             Ok(json) => match serde_json::from_str::<Bookmark>(&json) {
-                Ok(bm) => {
+                Ok(mut bm) => {
+                    bm.upgrade();
                     let fname = path
                         .file_name()
                         .unwrap_or_default()
@@ -80,10 +80,10 @@ pub fn draw_j_preview_panel(ctx: &egui::Context, params: JPreviewDrawParams<'_>)
                     if params.is_mandelbrot_preview {
                         let crosshair_color = egui::Color32::WHITE;
                         let vp = params.preview_viewport;
-                        let px =
-                            (params.julia_c.re - vp.center.re) / vp.scale + (vp.width as f64) * 0.5;
+                        let px = (params.julia_c.re - vp.center.re) / vp.scale.to_f64()
+                            + (vp.width as f64) * 0.5;
                         let py = (vp.height as f64) * 0.5
-                            - (params.julia_c.im - vp.center.im) / vp.scale;
+                            - (params.julia_c.im - vp.center.im) / vp.scale.to_f64();
                         let cx =
                             image_rect.min.x + (px as f32 / vp.width as f32) * image_rect.width();
                         let cy =
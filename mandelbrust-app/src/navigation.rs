@@ -1,16 +1,23 @@
+use std::sync::mpsc;
+
 use eframe::egui;
 
-use mandelbrust_core::{ComplexDD, DoubleDouble, Viewport};
+use mandelbrust_core::{registry, FloatExp, Minibrot, MisiurewiczPoint, Viewport};
+
+use crate::app::{precision_for_view, MandelbRustApp, MAX_HISTORY, QD_WARN_SCALE};
+
+/// Atom sizes shown across the smaller side after jumping to a minibrot; a
+/// whole minibrot spans about 2.5 of them.
+const MINIBROT_FRAME: f64 = 3.0;
 
-use crate::app::{MandelbRustApp, DD_THRESHOLD_SCALE, DD_WARN_SCALE, MAX_HISTORY};
+/// Radius in pixels searched for a Misiurewicz point to snap to.
+const MISIUREWICZ_SEARCH_PX: f64 = 16.0;
 
 impl MandelbRustApp {
     pub(crate) fn commit_pan_offset(&mut self) {
         if self.pan_offset != egui::Vec2::ZERO {
-            self.viewport.offset_center(
-                -(self.pan_offset.x as f64) * self.viewport.scale,
-                self.pan_offset.y as f64 * self.viewport.scale,
-            );
+            self.viewport
+                .pan_pixels(-self.pan_offset.x as f64, -self.pan_offset.y as f64);
             self.pan_offset = egui::Vec2::ZERO;
         }
     }
@@ -18,7 +25,7 @@ impl MandelbRustApp {
     pub(crate) fn push_history(&mut self) {
         self.commit_pan_offset();
         self.history.truncate(self.history_pos + 1);
-        self.history.push(self.viewport);
+        self.history.push(self.viewport.clone());
         self.history_pos = self.history.len() - 1;
         if self.history.len() > MAX_HISTORY {
             self.history.remove(0);
@@ -30,7 +37,7 @@ impl MandelbRustApp {
         self.commit_pan_offset();
         if self.history_pos > 0 {
             self.history_pos -= 1;
-            self.viewport = self.history[self.history_pos];
+            self.viewport = self.history[self.history_pos].clone();
             self.needs_render = true;
         }
     }
@@ -39,25 +46,14 @@ impl MandelbRustApp {
         self.commit_pan_offset();
         if self.history_pos + 1 < self.history.len() {
             self.history_pos += 1;
-            self.viewport = self.history[self.history_pos];
+            self.viewport = self.history[self.history_pos].clone();
             self.needs_render = true;
         }
     }
 
     pub(crate) fn zoom_at_cursor(&mut self, cursor_px: u32, cursor_py: u32, factor: f64) {
-        let delta = self.viewport.pixel_to_delta(cursor_px, cursor_py);
-        let target = self.viewport.center_dd + ComplexDD::from(delta);
-        let factor_dd = DoubleDouble::from(factor);
-        let diff = ComplexDD::new(
-            self.viewport.center_dd.re - target.re,
-            self.viewport.center_dd.im - target.im,
-        );
-        let new_center = ComplexDD::new(
-            target.re + diff.re * factor_dd,
-            target.im + diff.im * factor_dd,
-        );
-        self.viewport.set_center_dd(new_center);
-        self.viewport.scale *= factor;
+        self.viewport
+            .zoom_about(cursor_px as f64, cursor_py as f64, factor);
         self.needs_render = true;
     }
 
@@ -69,19 +65,14 @@ impl MandelbRustApp {
 
     pub(crate) fn pan_by_fraction(&mut self, fx: f64, fy: f64) {
         self.push_history();
-        self.viewport.offset_center(
-            fx * self.viewport.complex_width(),
-            fy * self.viewport.complex_height(),
-        );
+        let (w, h) = (self.viewport.width as f64, self.viewport.height as f64);
+        self.viewport.pan_pixels(fx * w, -fy * h);
         self.needs_render = true;
     }
 
     pub(crate) fn default_viewport(&self) -> Viewport {
         let (w, h) = (self.viewport.width, self.viewport.height);
-        match self.mode {
-            crate::app::FractalMode::Mandelbrot => Viewport::default_mandelbrot(w, h),
-            crate::app::FractalMode::Julia => Viewport::default_julia(w, h),
-        }
+        self.mode.default_viewport(w, h)
     }
 
     pub(crate) fn reset_view(&mut self) {
@@ -90,6 +81,144 @@ impl MandelbRustApp {
         self.needs_render = true;
     }
 
+    /// Center and zoom on the lowest-period minibrot inside the view
+    /// (Mandelbrot mode only). The search runs on a worker thread, one at a
+    /// time; the view is left unchanged if none is found or if it has moved
+    /// by the time the search finishes.
+    pub(crate) fn jump_to_nearest_minibrot(&mut self) {
+        if self.mode != &registry::MANDELBROT || self.minibrot_search.is_some() {
+            return;
+        }
+        self.commit_pan_offset();
+        let side = self.viewport.width.min(self.viewport.height).max(1) as f64;
+        let radius = self.viewport.scale.to_f64() * side / 2.0;
+        let center = self.viewport.center_qd.to_complex_dd();
+        let max_iterations = self.params.max_iterations;
+        let (tx, rx) = mpsc::channel();
+        let ctx = self.egui_ctx.clone();
+        if let Err(e) = std::thread::Builder::new()
+            .name("minibrot-worker".into())
+            .spawn(move || {
+                let _ = tx.send(Minibrot::find(center, radius, max_iterations));
+                ctx.request_repaint();
+            })
+        {
+            tracing::error!("Failed to spawn minibrot thread: {e}");
+            return;
+        }
+        self.minibrot_search = Some((self.viewport.clone(), rx));
+    }
+
+    /// Jump to the minibrot found by [`jump_to_nearest_minibrot`] once the
+    /// search finishes.
+    ///
+    /// [`jump_to_nearest_minibrot`]: Self::jump_to_nearest_minibrot
+    pub(crate) fn poll_minibrot_search(&mut self) {
+        let Some((requested, rx)) = self.minibrot_search.as_ref() else {
+            return;
+        };
+        let found = match rx.try_recv() {
+            Ok(found) => found,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => None,
+        };
+        let stale = *requested != self.viewport;
+        self.minibrot_search = None;
+        if self.mode != &registry::MANDELBROT || stale {
+            return;
+        }
+        let Some(minibrot) = found else {
+            tracing::info!("No minibrot found within the view");
+            return;
+        };
+        tracing::info!(
+            "Jumping to period-{} minibrot (size {:.3e})",
+            minibrot.period,
+            minibrot.size
+        );
+        let side = self.viewport.width.min(self.viewport.height).max(1) as f64;
+        self.push_history();
+        self.viewport.set_center_dd(minibrot.nucleus);
+        self.viewport.scale = FloatExp::from(minibrot.size * MINIBROT_FRAME / side);
+        self.needs_render = true;
+    }
+
+    /// Re-center exactly on the simplest Misiurewicz point near the cursor,
+    /// or near the view center when the cursor is off the canvas (Mandelbrot
+    /// mode only). The zoom is kept, so zooming in afterwards stays centered
+    /// on the spiral. The search runs on a worker thread, one at a time;
+    /// the view is left unchanged if none is found or if it has moved by
+    /// the time the search finishes.
+    pub(crate) fn snap_to_misiurewicz_point(&mut self) {
+        if self.mode != &registry::MANDELBROT || self.misiurewicz_search.is_some() {
+            return;
+        }
+        self.commit_pan_offset();
+        let target = self
+            .cursor_complex
+            .unwrap_or_else(|| self.viewport.center_qd.to_complex_dd());
+        let radius = MISIUREWICZ_SEARCH_PX * self.viewport.scale.to_f64();
+        let max_iterations = self.params.max_iterations;
+        let (tx, rx) = mpsc::channel();
+        let ctx = self.egui_ctx.clone();
+        if let Err(e) = std::thread::Builder::new()
+            .name("misiurewicz-worker".into())
+            .spawn(move || {
+                let _ = tx.send(MisiurewiczPoint::find(target, radius, max_iterations));
+                ctx.request_repaint();
+            })
+        {
+            tracing::error!("Failed to spawn Misiurewicz thread: {e}");
+            return;
+        }
+        self.misiurewicz_search = Some((self.viewport.clone(), rx));
+    }
+
+    /// Re-center on the point found by [`snap_to_misiurewicz_point`] once
+    /// the search finishes.
+    ///
+    /// [`snap_to_misiurewicz_point`]: Self::snap_to_misiurewicz_point
+    pub(crate) fn poll_misiurewicz_search(&mut self) {
+        let Some((requested, rx)) = self.misiurewicz_search.as_ref() else {
+            return;
+        };
+        let found = match rx.try_recv() {
+            Ok(found) => found,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => None,
+        };
+        let stale = *requested != self.viewport;
+        self.misiurewicz_search = None;
+        if self.mode != &registry::MANDELBROT || stale {
+            return;
+        }
+        let Some(point) = found else {
+            tracing::info!("No Misiurewicz point found near the target");
+            return;
+        };
+        tracing::info!(
+            "Snapping to Misiurewicz point of preperiod {} and period {}",
+            point.preperiod,
+            point.period
+        );
+        self.push_history();
+        self.viewport.set_center_dd(point.c);
+        self.misiurewicz = Some(point);
+        self.needs_render = true;
+    }
+
+    /// The Misiurewicz point last snapped to, while the view is still
+    /// centered on it.
+    pub(crate) fn centered_misiurewicz_point(&self) -> Option<MisiurewiczPoint> {
+        if self.mode != &registry::MANDELBROT {
+            return None;
+        }
+        let point = self.misiurewicz?;
+        let offset = (point.c - self.viewport.center_qd.to_complex_dd()).to_complex();
+        (self.pan_offset == egui::Vec2::ZERO && offset.norm() <= self.viewport.scale.to_f64() / 2.0)
+            .then_some(point)
+    }
+
     pub(crate) fn check_resize(&mut self, width: u32, height: u32) {
         if width > 0 && height > 0 && (width != self.panel_size[0] || height != self.panel_size[1])
         {
@@ -101,18 +230,14 @@ impl MandelbRustApp {
     }
 
     pub(crate) fn precision_warning(&self) -> Option<&'static str> {
-        if self.viewport.scale < DD_WARN_SCALE {
-            Some("Approaching double-double precision limits \u{2014} artifacts may appear")
+        if self.viewport.scale < QD_WARN_SCALE {
+            Some("Approaching quad-double precision limits \u{2014} artifacts may appear")
         } else {
             None
         }
     }
 
     pub(crate) fn precision_mode_label(&self) -> &'static str {
-        if self.viewport.scale < DD_THRESHOLD_SCALE {
-            "f64\u{00d7}2"
-        } else {
-            "f64"
-        }
+        precision_for_view(self.mode, self.viewport.scale).label()
     }
 }
@@ -2,6 +2,10 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::mpsc;
 
+use mandelbrust_core::{
+    registry, Complex, ComplexBig, ComplexQD, FloatExp, FormulaDefinition, FractalDescriptor,
+    LyapunovSequence, ParamMap, QuadDouble, Viewport,
+};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
@@ -15,7 +19,9 @@ use crate::io_worker::IoRequest;
 /// Minimal state captured so the app can restore its previous view on startup.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LastView {
-    pub mode: String,
+    /// Registry id of the fractal family, e.g. `"burning_ship_julia"`.
+    #[serde(default)]
+    pub fractal: String,
     pub center_re: f64,
     pub center_im: f64,
     /// Low-order bits for double-double center precision (~31 digits total).
@@ -24,7 +30,20 @@ pub struct LastView {
     /// Low-order bits for double-double center precision (~31 digits total).
     #[serde(default)]
     pub center_im_lo: f64,
-    pub scale: f64,
+    /// Third and fourth limbs for quad-double center precision (~62 digits total).
+    #[serde(default)]
+    pub center_re_lo2: f64,
+    #[serde(default)]
+    pub center_re_lo3: f64,
+    #[serde(default)]
+    pub center_im_lo2: f64,
+    #[serde(default)]
+    pub center_im_lo3: f64,
+    /// Full-precision center as decimal strings; files without it load
+    /// from the limbs above.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub center: Option<ComplexBig>,
+    pub scale: FloatExp,
     pub max_iterations: u32,
     pub escape_radius: f64,
     pub palette_index: usize,
@@ -32,6 +51,110 @@ pub struct LastView {
     pub aa_level: u32,
     pub julia_c_re: f64,
     pub julia_c_im: f64,
+    /// Low-order bits of the Julia constant (double-double, ~31 digits).
+    #[serde(default)]
+    pub julia_c_re_lo: f64,
+    #[serde(default)]
+    pub julia_c_im_lo: f64,
+    /// Scalar parameters of every family, by [`ParamSpec`] key.
+    ///
+    /// [`ParamSpec`]: mandelbrust_core::ParamSpec
+    #[serde(default)]
+    pub params: ParamMap,
+    /// Roots of the Newton and Nova polynomial; empty means the default.
+    #[serde(default)]
+    pub newton_roots: Vec<Complex>,
+    /// The user formula, kept even while another mode is shown.
+    #[serde(default)]
+    pub formula: FormulaDefinition,
+    /// The Lyapunov A/B sequence.
+    #[serde(default)]
+    pub sequence: LyapunovSequence,
+    /// Fields written before the fractal registry; read, never written.
+    #[serde(flatten)]
+    pub legacy: LegacyFractalFields,
+}
+
+impl LastView {
+    pub(crate) fn descriptor(&self) -> &'static FractalDescriptor {
+        saved_descriptor(&self.fractal, &self.legacy)
+    }
+
+    pub(crate) fn param_values(&self) -> ParamMap {
+        saved_params(&self.params, &self.legacy)
+    }
+
+    /// The saved view at the given canvas size.
+    pub(crate) fn viewport(&self, width: u32, height: u32) -> mandelbrust_core::Result<Viewport> {
+        let limbs = ComplexQD::new(
+            QuadDouble::new(
+                self.center_re,
+                self.center_re_lo,
+                self.center_re_lo2,
+                self.center_re_lo3,
+            ),
+            QuadDouble::new(
+                self.center_im,
+                self.center_im_lo,
+                self.center_im_lo2,
+                self.center_im_lo3,
+            ),
+        );
+        saved_viewport(self.center.as_ref(), limbs, self.scale, width, height)
+    }
+}
+
+/// How files from before the fractal registry named the fractal and its
+/// parameters: the mode's display label, and one field per parameter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LegacyFractalFields {
+    #[serde(default, skip_serializing)]
+    pub mode: String,
+    #[serde(default, skip_serializing)]
+    pub exponent: Option<f64>,
+    #[serde(default, skip_serializing)]
+    pub relaxation: Option<f64>,
+}
+
+/// A saved view: from its full-precision center when stored, else from the
+/// quad-double limbs written by older builds.
+pub(crate) fn saved_viewport(
+    center: Option<&ComplexBig>,
+    limbs: ComplexQD,
+    scale: FloatExp,
+    width: u32,
+    height: u32,
+) -> mandelbrust_core::Result<Viewport> {
+    match center {
+        Some(center) => Viewport::new_big(center.clone(), scale, width, height),
+        None => Viewport::new_qd(limbs, scale, width, height),
+    }
+}
+
+/// The family of a saved view: its registry id, else the legacy label.
+/// Unknown names fall back to the Mandelbrot set.
+pub(crate) fn saved_descriptor(
+    id: &str,
+    legacy: &LegacyFractalFields,
+) -> &'static FractalDescriptor {
+    registry::by_id(id)
+        .or_else(|| registry::by_name(&legacy.mode))
+        .unwrap_or(&registry::MANDELBROT)
+}
+
+/// Saved parameter values, with the legacy per-parameter fields filling in
+/// keys the map lacks.
+pub(crate) fn saved_params(params: &ParamMap, legacy: &LegacyFractalFields) -> ParamMap {
+    let mut values = params.clone();
+    for (key, value) in [
+        ("exponent", legacy.exponent),
+        ("relaxation", legacy.relaxation),
+    ] {
+        if let Some(value) = value {
+            values.entry(key.to_string()).or_insert(value);
+        }
+    }
+    values
 }
 
 // ---------------------------------------------------------------------------
@@ -130,6 +253,8 @@ fn default_window_height() -> f32 {
 fn default_max_iterations() -> u32 {
     256
 }
+/// Quadratic `z² + c`, the exponent implied by files written before the
+/// Multibrot modes existed.
 fn default_true() -> bool {
     true
 }
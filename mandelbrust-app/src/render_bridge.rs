@@ -1,15 +1,19 @@
 use std::sync::mpsc;
 use std::sync::Arc;
+use std::time::Duration;
 
 use eframe::egui;
 use tracing::debug;
 
 use mandelbrust_core::{
-    Complex, ComplexDD, FractalParams, Julia, JuliaDD, Mandelbrot, MandelbrotDD, Viewport,
+    registry, Complex, ExtrasOptions, FractalInputs, FractalParams, Julia, Precision, Viewport,
+};
+use mandelbrust_render::{
+    compute_aa, find_components, render, render_density, ComponentOptions, ComponentScan,
+    DensityBuffer, DensityOptions, RenderCancel, RenderOptions, RenderResult,
 };
-use mandelbrust_render::{compute_aa, render, RenderCancel, RenderOptions, RenderResult};
 
-use crate::app::{FractalMode, MandelbRustApp, DD_THRESHOLD_SCALE, PREVIEW_DOWNSCALE};
+use crate::app::{precision_for_view, FractalMode, MandelbRustApp, PREVIEW_DOWNSCALE};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -39,11 +43,13 @@ pub(crate) struct RenderRequest {
     pub(crate) viewport: Viewport,
     pub(crate) params: FractalParams,
     pub(crate) mode: FractalMode,
-    pub(crate) julia_c: Complex,
+    pub(crate) inputs: FractalInputs,
     pub(crate) aa_level: u32,
     pub(crate) compute_extras: bool,
     pub(crate) allow_border_tracing: bool,
-    pub(crate) stripe_density: f64,
+    pub(crate) extras: ExtrasOptions,
+    /// Render orbit densities instead of escape times.
+    pub(crate) density: Option<DensityOptions>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -51,12 +57,25 @@ pub(crate) struct RenderModeOptions {
     pub(crate) aa_level: u32,
     pub(crate) compute_extras: bool,
     pub(crate) allow_border_tracing: bool,
-    pub(crate) stripe_density: f64,
+    pub(crate) extras: ExtrasOptions,
 }
 
 pub(crate) enum RenderResponse {
-    Preview { id: u64, result: RenderResult },
-    Final { id: u64, result: RenderResult },
+    Preview {
+        id: u64,
+        result: RenderResult,
+    },
+    Final {
+        id: u64,
+        result: RenderResult,
+    },
+    /// Hits after a pass of a density render; `complete` on the last pass.
+    Density {
+        id: u64,
+        density: DensityBuffer,
+        elapsed: Duration,
+        complete: bool,
+    },
 }
 
 pub(crate) struct JuliaGridRequest {
@@ -71,11 +90,31 @@ pub(crate) struct JuliaGridRequest {
     pub(crate) cancel: Arc<RenderCancel>,
 }
 
+/// A hyperbolic component scan of a Mandelbrot view.
+pub(crate) struct ComponentRequest {
+    pub(crate) viewport: Viewport,
+    pub(crate) params: FractalParams,
+    pub(crate) inputs: FractalInputs,
+    pub(crate) options: ComponentOptions,
+    pub(crate) cancel: Arc<RenderCancel>,
+}
+
 // ---------------------------------------------------------------------------
 // impl MandelbRustApp — render dispatch & polling
 // ---------------------------------------------------------------------------
 
 impl MandelbRustApp {
+    /// Snapshot of the formula inputs for a render job.
+    pub(crate) fn fractal_inputs(&self) -> FractalInputs {
+        FractalInputs {
+            values: self.param_values.clone(),
+            julia_c: self.julia_c,
+            roots: self.roots.clone(),
+            formula: self.formula.clone(),
+            sequence: self.sequence.clone(),
+        }
+    }
+
     pub(crate) fn request_render(&mut self) {
         self.cancel.cancel();
         self.render_id += 1;
@@ -89,20 +128,21 @@ impl MandelbRustApp {
         debug!(
             id = self.render_id,
             max_iter = params.max_iterations,
-            scale = self.viewport.scale,
+            scale = %self.viewport.scale,
             "Requesting render"
         );
 
         let req = RenderRequest {
             id: self.render_id,
-            viewport: self.viewport,
+            viewport: self.viewport.clone(),
             params,
             mode: self.mode,
-            julia_c: self.julia_c,
+            inputs: self.fractal_inputs(),
             aa_level: self.aa_level,
             compute_extras: self.needs_extras(),
             allow_border_tracing: !self.display_color.smooth_coloring,
-            stripe_density: self.display_color.stripe_density,
+            extras: self.display_color.extras_options(),
+            density: self.density_options(),
         };
 
         let _ = self.tx_request.send(req);
@@ -111,14 +151,15 @@ impl MandelbRustApp {
     }
 
     pub(crate) fn request_drag_preview(&mut self) {
+        // A density run is too slow to preview; the last frame slides along.
+        if self.density_active() {
+            return;
+        }
         self.cancel.cancel();
         self.render_id += 1;
 
-        let mut viewport = self.viewport;
-        viewport.offset_center(
-            -(self.pan_offset.x as f64) * viewport.scale,
-            self.pan_offset.y as f64 * viewport.scale,
-        );
+        let mut viewport = self.viewport.clone();
+        viewport.pan_pixels(-self.pan_offset.x as f64, -self.pan_offset.y as f64);
 
         let params = self.effective_params();
         let req = RenderRequest {
@@ -126,11 +167,12 @@ impl MandelbRustApp {
             viewport,
             params,
             mode: self.mode,
-            julia_c: self.julia_c,
+            inputs: self.fractal_inputs(),
             aa_level: 0,
             compute_extras: false,
             allow_border_tracing: !self.display_color.smooth_coloring,
-            stripe_density: self.display_color.stripe_density,
+            extras: self.display_color.extras_options(),
+            density: None,
         };
 
         let _ = self.tx_request.send(req);
@@ -159,6 +201,16 @@ impl MandelbRustApp {
                         self.render_phase = RenderPhase::Done;
                     }
                 }
+                RenderResponse::Density {
+                    id,
+                    density,
+                    elapsed,
+                    complete,
+                } => {
+                    if id == self.render_id {
+                        self.apply_density(ctx, density, elapsed, complete);
+                    }
+                }
             }
         }
     }
@@ -173,11 +225,13 @@ impl MandelbRustApp {
         self.tiles_rendered = result.tiles_rendered;
         self.tiles_mirrored = result.tiles_mirrored;
         self.tiles_border_traced = result.tiles_border_traced;
+        self.skipped_iterations = result.skipped_iterations;
 
         // Store extras and AA BEFORE colorization so colorize_current
         // sees the data that belongs to *this* result, not stale state.
         self.current_extras = result.extras;
         self.current_aa = result.aa_samples;
+        self.current_density = None;
 
         let buffer = self.colorize_current(&result.iterations, self.current_aa.as_ref());
         let image = egui::ColorImage::from_rgba_unmultiplied(
@@ -195,6 +249,27 @@ impl MandelbRustApp {
         self.draw_offset = egui::Vec2::ZERO;
     }
 
+    pub(crate) fn apply_density(
+        &mut self,
+        ctx: &egui::Context,
+        density: DensityBuffer,
+        elapsed: Duration,
+        complete: bool,
+    ) {
+        self.render_time = elapsed;
+        self.current_iterations = None;
+        self.current_extras = None;
+        self.current_aa = None;
+        self.current_density = Some(density);
+        self.recolorize(ctx);
+        self.drag_preview = None;
+        self.render_phase = if complete {
+            RenderPhase::Done
+        } else {
+            RenderPhase::Refining
+        };
+    }
+
     pub(crate) fn apply_drag_preview(&mut self, ctx: &egui::Context, result: RenderResult) {
         let params = self.color_params();
         let buffer = self.current_palette().colorize(&result.iterations, &params);
@@ -208,6 +283,7 @@ impl MandelbRustApp {
 
     pub(crate) fn cancel_render(&mut self) {
         self.cancel.cancel();
+        self.cancel_component_scan();
         if self.render_phase == RenderPhase::Rendering || self.render_phase == RenderPhase::Refining
         {
             self.render_phase = RenderPhase::Done;
@@ -266,6 +342,46 @@ impl MandelbRustApp {
         };
         let _ = self.tx_julia_grid_req.send(req);
     }
+
+    /// Scan the view for hyperbolic components if the labels are on and the
+    /// view or settings changed since the last scan.
+    pub(crate) fn request_components_if_needed(&mut self) {
+        if !self.component_labels_active() || self.drag_active {
+            return;
+        }
+        let key = (self.viewport.clone(), self.components.options.clone());
+        if self.components.requested.as_ref() == Some(&key) {
+            return;
+        }
+        self.components_cancel.cancel();
+        let new_cancel = Arc::new(RenderCancel::new());
+        self.components_cancel = new_cancel.clone();
+        let req = ComponentRequest {
+            viewport: self.viewport.clone(),
+            params: self.effective_params(),
+            inputs: self.fractal_inputs(),
+            options: key.1.clone(),
+            cancel: new_cancel,
+        };
+        let _ = self.tx_components_req.send(req);
+        self.components.requested = Some(key);
+        self.components.scanning = true;
+    }
+
+    pub(crate) fn poll_component_responses(&mut self) {
+        while let Ok((viewport, scan)) = self.rx_components_resp.try_recv() {
+            if scan.cancelled {
+                continue;
+            }
+            self.components.result = Some((viewport, scan.components));
+            self.components.scanning = false;
+        }
+    }
+
+    pub(crate) fn cancel_component_scan(&mut self) {
+        self.components_cancel.cancel();
+        self.components.scanning = false;
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -280,7 +396,7 @@ fn drain_latest(initial: RenderRequest, rx: &mpsc::Receiver<RenderRequest>) -> R
     req
 }
 
-fn do_render<F: mandelbrust_core::Fractal + Sync>(
+fn do_render<F: mandelbrust_core::Fractal + Sync + ?Sized>(
     fractal: &F,
     viewport: &Viewport,
     cancel: &Arc<RenderCancel>,
@@ -299,55 +415,26 @@ fn do_render<F: mandelbrust_core::Fractal + Sync>(
 pub(crate) fn render_for_mode(
     mode: FractalMode,
     params: FractalParams,
-    julia_c: Complex,
+    inputs: &FractalInputs,
     viewport: &Viewport,
     cancel: &Arc<RenderCancel>,
     mode_opts: RenderModeOptions,
 ) -> RenderResult {
-    let use_symmetry = mode == FractalMode::Mandelbrot;
-    let use_dd = viewport.scale < DD_THRESHOLD_SCALE;
+    // Past the f64 limit the Mandelbrot set switches to perturbation (one
+    // high-precision reference orbit at the center, f64 deltas per pixel,
+    // and a series approximation skipping the iterations all pixels share),
+    // except in the quad-double range, which is rendered directly in QD.
+    // See `precision_for_view`.
+    let precision = precision_for_view(mode, viewport.scale);
+    let fractal = mode.build(inputs, params, precision, viewport);
+    // The renderer only mirrors fractals that report conjugate symmetry.
     let opts = RenderOptions {
-        use_real_axis_symmetry: use_symmetry,
+        use_real_axis_symmetry: true,
         compute_extras: mode_opts.compute_extras,
         allow_border_tracing: mode_opts.allow_border_tracing,
-        stripe_density: mode_opts.stripe_density,
+        extras: mode_opts.extras,
     };
-    match (mode, use_dd) {
-        (FractalMode::Mandelbrot, false) => do_render(
-            &Mandelbrot::new(params),
-            viewport,
-            cancel,
-            mode_opts.aa_level,
-            &opts,
-        ),
-        (FractalMode::Mandelbrot, true) => do_render(
-            &MandelbrotDD::new(params, viewport.center_dd),
-            viewport,
-            cancel,
-            mode_opts.aa_level,
-            &opts,
-        ),
-        (FractalMode::Julia, false) => do_render(
-            &Julia::new(julia_c, params),
-            viewport,
-            cancel,
-            mode_opts.aa_level,
-            &RenderOptions {
-                use_real_axis_symmetry: false,
-                ..opts
-            },
-        ),
-        (FractalMode::Julia, true) => do_render(
-            &JuliaDD::new(ComplexDD::from(julia_c), params, viewport.center_dd),
-            viewport,
-            cancel,
-            mode_opts.aa_level,
-            &RenderOptions {
-                use_real_axis_symmetry: false,
-                ..opts
-            },
-        ),
-    }
+    do_render(&*fractal, viewport, cancel, mode_opts.aa_level, &opts)
 }
 
 pub(crate) fn render_worker(
@@ -359,19 +446,40 @@ pub(crate) fn render_worker(
     while let Ok(initial) = rx.recv() {
         let mut req = drain_latest(initial, &rx);
 
+        if let Some(opts) = &req.density {
+            let start = std::time::Instant::now();
+            let mut passes = 0;
+            let mut disconnected = false;
+            render_density(&req.viewport, &req.params, opts, &cancel, |density| {
+                passes += 1;
+                let response = RenderResponse::Density {
+                    id: req.id,
+                    density: density.clone(),
+                    elapsed: start.elapsed(),
+                    complete: passes == opts.passes,
+                };
+                disconnected |= tx.send(response).is_err();
+                ctx.request_repaint();
+            });
+            if disconnected {
+                return;
+            }
+            continue;
+        }
+
         loop {
             let preview_vp = req.viewport.downscaled(PREVIEW_DOWNSCALE);
             let preview = render_for_mode(
                 req.mode,
                 req.params,
-                req.julia_c,
+                &req.inputs,
                 &preview_vp,
                 &cancel,
                 RenderModeOptions {
                     aa_level: 0,
                     compute_extras: false,
                     allow_border_tracing: req.allow_border_tracing,
-                    stripe_density: req.stripe_density,
+                    extras: req.extras,
                 },
             );
 
@@ -398,14 +506,14 @@ pub(crate) fn render_worker(
             let full = render_for_mode(
                 req.mode,
                 req.params,
-                req.julia_c,
+                &req.inputs,
                 &req.viewport,
                 &cancel,
                 RenderModeOptions {
                     aa_level: req.aa_level,
                     compute_extras: req.compute_extras,
                     allow_border_tracing: req.allow_border_tracing,
-                    stripe_density: req.stripe_density,
+                    extras: req.extras,
                 },
             );
 
@@ -429,6 +537,32 @@ pub(crate) fn render_worker(
     }
 }
 
+pub(crate) fn component_worker(
+    ctx: egui::Context,
+    rx: mpsc::Receiver<ComponentRequest>,
+    tx: mpsc::Sender<(Viewport, ComponentScan)>,
+) {
+    while let Ok(initial) = rx.recv() {
+        let mut req = initial;
+        while let Ok(newer) = rx.try_recv() {
+            req = newer;
+        }
+        // Nuclei are solved in double-double, so deeper views are sampled
+        // at that precision too.
+        let mode = &registry::MANDELBROT;
+        let precision = match precision_for_view(mode, req.viewport.scale) {
+            Precision::F64 => Precision::F64,
+            _ => Precision::DoubleDouble,
+        };
+        let fractal = mode.build(&req.inputs, req.params, precision, &req.viewport);
+        let scan = find_components(&*fractal, &req.viewport, &req.options, &req.cancel);
+        if tx.send((req.viewport, scan)).is_err() {
+            return;
+        }
+        ctx.request_repaint();
+    }
+}
+
 pub(crate) fn julia_grid_worker(
     rx: mpsc::Receiver<JuliaGridRequest>,
     tx: mpsc::Sender<(u32, u32, RenderResult)>,
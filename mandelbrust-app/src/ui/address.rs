@@ -0,0 +1,214 @@
+//! Symbolic readout of the view (Mandelbrot mode): the hyperbolic component
+//! or Misiurewicz point nearest the view center, with its angled internal
+//! address, external angles and kneading sequence, and the Misiurewicz
+//! point snapped to if the view is still centered on it.
+//!
+//! Measuring the landing angles traces rays, which takes a while for high
+//! periods, so the description is computed on a worker thread: one request
+//! at a time, restarted when the view has moved since.
+
+use std::sync::mpsc;
+
+use eframe::egui;
+use tracing::error;
+
+use mandelbrust_core::{
+    registry, AngledInternalAddress, ComplexDD, ComponentAddress, ExternalAngle, KneadingSequence,
+    Minibrot, MisiurewiczAddress, MisiurewiczPoint, Viewport,
+};
+
+use crate::app::MandelbRustApp;
+use crate::ui::external_rays::{angle_short_label, elide};
+
+/// Characters of an address or kneading sequence shown before eliding.
+const SHOWN_SYMBOLS: usize = 40;
+
+/// Label parent under which bookmarks file their address.
+const BOOKMARK_LABEL_PARENT: &str = "Address";
+
+/// Label parent under which bookmarks file their Misiurewicz point.
+const MISIUREWICZ_LABEL_PARENT: &str = "Misiurewicz";
+
+/// The location a view is described by.
+enum Described {
+    Component(ComponentAddress),
+    Misiurewicz(MisiurewiczAddress),
+}
+
+impl Described {
+    /// Whichever of the two lies nearer `center`.
+    fn nearest(
+        center: ComplexDD,
+        component: Option<ComponentAddress>,
+        point: Option<MisiurewiczAddress>,
+    ) -> Option<Self> {
+        let distance = |c: ComplexDD| (c - center).to_complex().norm();
+        match (component, point) {
+            (Some(component), Some(point))
+                if distance(point.point.c) < distance(component.component.nucleus) =>
+            {
+                Some(Self::Misiurewicz(point))
+            }
+            (Some(component), _) => Some(Self::Component(component)),
+            (None, point) => point.map(Self::Misiurewicz),
+        }
+    }
+
+    fn address(&self) -> &AngledInternalAddress {
+        match self {
+            Self::Component(c) => &c.address,
+            Self::Misiurewicz(m) => &m.address,
+        }
+    }
+
+    /// The rays landing on the root or point, lowest first.
+    fn angles(&self) -> Vec<&ExternalAngle> {
+        match self {
+            Self::Component(c) => vec![&c.angles.0, &c.angles.1],
+            Self::Misiurewicz(m) => m.angles.iter().collect(),
+        }
+    }
+
+    fn kneading(&self) -> &KneadingSequence {
+        match self {
+            Self::Component(c) => &c.kneading,
+            Self::Misiurewicz(m) => &m.kneading,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct AddressState {
+    /// The view the latest request was made for.
+    requested: Option<Viewport>,
+    rx: Option<mpsc::Receiver<(Viewport, Option<Described>)>>,
+    /// The latest description and the view it is for; `None` inside if
+    /// nothing was found there.
+    result: Option<(Viewport, Option<Described>)>,
+}
+
+impl MandelbRustApp {
+    /// The description of the current view, if it has been computed.
+    fn current_address(&self) -> Option<&Described> {
+        match &self.address.result {
+            Some((viewport, described)) if *viewport == self.viewport => described.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Bookmark label naming the current view's component, e.g.
+    /// `Address/1_1⁄3 → 3`. Fraction slashes keep `/` for the hierarchy.
+    pub(crate) fn address_bookmark_label(&self) -> Option<String> {
+        let address = self.current_address()?.address().to_string();
+        Some(format!(
+            "{BOOKMARK_LABEL_PARENT}/{}",
+            address.replace('/', "\u{2044}")
+        ))
+    }
+
+    /// Bookmark label for the Misiurewicz point the view is centered on,
+    /// e.g. `Misiurewicz/preperiod 2, period 2`.
+    pub(crate) fn misiurewicz_bookmark_label(&self) -> Option<String> {
+        let point = self.centered_misiurewicz_point()?;
+        Some(format!(
+            "{MISIUREWICZ_LABEL_PARENT}/preperiod {}, period {}",
+            point.preperiod, point.period
+        ))
+    }
+
+    /// Describe the view on a worker thread if it moved since the last
+    /// request and no request is running.
+    pub(crate) fn request_address_if_needed(&mut self) {
+        if let Some(rx) = self.address.rx.as_ref() {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.address.result = Some(result);
+                    self.address.rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => self.address.rx = None,
+            }
+        }
+        if self.mode != &registry::MANDELBROT
+            || self.address.requested.as_ref() == Some(&self.viewport)
+        {
+            return;
+        }
+
+        let viewport = self.viewport.clone();
+        let side = viewport.width.min(viewport.height).max(1) as f64;
+        let radius = viewport.scale.to_f64() * side / 2.0;
+        let max_iterations = self.effective_max_iterations();
+        let (tx, rx) = mpsc::channel();
+        let ctx = self.egui_ctx.clone();
+        if let Err(e) = std::thread::Builder::new()
+            .name("address-worker".into())
+            .spawn(move || {
+                let center = viewport.center_qd.to_complex_dd();
+                let component = Minibrot::find(center, radius, max_iterations)
+                    .and_then(|m| ComponentAddress::of(m, max_iterations));
+                let point = MisiurewiczPoint::find(center, radius, max_iterations)
+                    .and_then(|p| MisiurewiczAddress::of(p, radius, max_iterations));
+                let described = Described::nearest(center, component, point);
+                let _ = tx.send((viewport, described));
+                ctx.request_repaint();
+            })
+        {
+            error!("Failed to spawn address thread: {e}");
+            return;
+        }
+        self.address.requested = Some(self.viewport.clone());
+        self.address.rx = Some(rx);
+    }
+
+    /// HUD lines for the address readout.
+    pub(crate) fn draw_address_section(&self, ui: &mut egui::Ui) {
+        if self.mode != &registry::MANDELBROT {
+            return;
+        }
+        ui.separator();
+        if let Some(point) = self.centered_misiurewicz_point() {
+            ui.label(format!(
+                "Misiurewicz: preperiod {}, period {}",
+                point.preperiod, point.period
+            ));
+        }
+        let Some((viewport, result)) = &self.address.result else {
+            ui.weak("Address: computing\u{2026}");
+            return;
+        };
+        let Some(described) = result else {
+            ui.weak("Address: no component or Misiurewicz point found in view");
+            return;
+        };
+        // Grayed out while the moved view is being described.
+        let stale = *viewport != self.viewport;
+        let text = |ui: &mut egui::Ui, line: String| {
+            if stale {
+                ui.weak(line);
+            } else {
+                ui.label(line);
+            }
+        };
+        text(
+            ui,
+            format!(
+                "Address: {}",
+                elide(&described.address().to_string(), SHOWN_SYMBOLS)
+            ),
+        );
+        let angles: Vec<String> = described
+            .angles()
+            .into_iter()
+            .map(angle_short_label)
+            .collect();
+        text(ui, format!("Angles: {}", angles.join(", ")));
+        text(
+            ui,
+            format!(
+                "Kneading: {}",
+                elide(&described.kneading().to_string(), SHOWN_SYMBOLS)
+            ),
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use eframe::egui;
 
-use crate::app::{BookmarkSnap, BookmarkTab, LabelFilterMode, MandelbRustApp};
+use crate::app::{BookmarkSnap, BookmarkTab, FractalMode, LabelFilterMode, MandelbRustApp};
 use crate::app_state::AppScreen;
 use crate::bookmarks;
 
@@ -24,7 +24,7 @@ impl MandelbRustApp {
                 id: self.bookmark_store.bookmark_id(i).to_string(),
                 name: bm.name.clone(),
                 summary: bm.summary(),
-                mode: bm.mode.clone(),
+                mode: bm.descriptor(),
                 labels: bm.labels.clone(),
                 thumbnail_png: bm.thumbnail_png.clone(),
             })
@@ -121,7 +121,7 @@ impl MandelbRustApp {
                         .filter(|s| {
                             passes_bookmark_filter(
                                 &s.name,
-                                &s.mode,
+                                s.mode,
                                 &s.labels,
                                 tab,
                                 fav_only,
@@ -242,7 +242,7 @@ impl MandelbRustApp {
 
 pub(crate) fn passes_bookmark_filter(
     name: &str,
-    mode: &str,
+    mode: FractalMode,
     labels: &[String],
     tab: BookmarkTab,
     fav_only: bool,
@@ -252,8 +252,7 @@ pub(crate) fn passes_bookmark_filter(
 ) -> bool {
     let tab_ok = match tab {
         BookmarkTab::All => true,
-        BookmarkTab::Mandelbrot => mode == "Mandelbrot",
-        BookmarkTab::Julia => mode == "Julia",
+        BookmarkTab::Mandelbrot | BookmarkTab::Julia => BookmarkTab::for_mode(mode) == tab,
     };
     let fav_ok = !fav_only || labels.iter().any(|l| l == "Favorites");
     let q_ok = query.is_empty()
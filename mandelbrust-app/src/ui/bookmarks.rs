@@ -2,7 +2,9 @@ use std::collections::HashSet;
 
 use eframe::egui;
 
-use crate::app::{BookmarkSnap, BookmarkTab, FractalMode, MandelbRustApp};
+use mandelbrust_core::ExtraInput;
+
+use crate::app::{restore_roots, BookmarkSnap, BookmarkTab, MandelbRustApp};
 use crate::bookmarks::{self, Bookmark};
 use crate::ui::bookmark_browser::passes_bookmark_filter;
 
@@ -19,11 +21,16 @@ impl MandelbRustApp {
 
         Bookmark {
             name,
-            mode: self.mode.label().to_string(),
-            center_re: self.viewport.center_dd.re.hi,
-            center_im: self.viewport.center_dd.im.hi,
-            center_re_lo: self.viewport.center_dd.re.lo,
-            center_im_lo: self.viewport.center_dd.im.lo,
+            fractal: self.mode.id.to_string(),
+            center_re: self.viewport.center_qd.re.limbs[0],
+            center_im: self.viewport.center_qd.im.limbs[0],
+            center_re_lo: self.viewport.center_qd.re.limbs[1],
+            center_im_lo: self.viewport.center_qd.im.limbs[1],
+            center_re_lo2: self.viewport.center_qd.re.limbs[2],
+            center_re_lo3: self.viewport.center_qd.re.limbs[3],
+            center_im_lo2: self.viewport.center_qd.im.limbs[2],
+            center_im_lo3: self.viewport.center_qd.im.limbs[3],
+            center: Some(self.viewport.center_big.clone()),
             scale: self.viewport.scale,
             max_iterations: self.params.max_iterations,
             escape_radius: self.params.escape_radius,
@@ -31,13 +38,25 @@ impl MandelbRustApp {
             smooth_coloring: self.display_color.smooth_coloring,
             display_color: Some(self.display_color.clone()),
             aa_level: self.aa_level,
-            julia_c_re: self.julia_c.re,
-            julia_c_im: self.julia_c.im,
+            julia_c_re: self.julia_c.re.hi,
+            julia_c_im: self.julia_c.im.hi,
+            julia_c_re_lo: self.julia_c.re.lo,
+            julia_c_im_lo: self.julia_c.im.lo,
+            params: self.mode_param_values(),
+            newton_roots: if self.mode.extra_input == ExtraInput::Roots {
+                self.roots.clone()
+            } else {
+                Vec::new()
+            },
+            formula: (self.mode.extra_input == ExtraInput::Formula).then(|| self.formula.clone()),
+            sequence: (self.mode.extra_input == ExtraInput::Sequence)
+                .then(|| self.sequence.clone()),
             labels,
             notes: String::new(),
             created_at: bookmarks::now_timestamp(),
             thumbnail_png,
             thumbnail_file: String::new(),
+            legacy: Default::default(),
         }
     }
 
@@ -46,11 +65,17 @@ impl MandelbRustApp {
         self.save_bookmark_name.clear();
         self.save_bookmark_new_label.clear();
         let defaults = bookmarks::suggest_default_labels(
-            self.mode.label(),
+            self.mode.name,
             self.viewport.scale,
             self.params.max_iterations,
         );
         self.save_bookmark_labels_selected = defaults.into_iter().collect();
+        if let Some(label) = self.address_bookmark_label() {
+            self.save_bookmark_labels_selected.insert(label);
+        }
+        if let Some(label) = self.misiurewicz_bookmark_label() {
+            self.save_bookmark_labels_selected.insert(label);
+        }
     }
 
     pub(crate) fn update_bookmark(&mut self, idx: usize) {
@@ -70,12 +95,18 @@ impl MandelbRustApp {
         let bm_id = self.bookmark_store.bookmark_id(idx).to_string();
         self.thumbnail_cache.remove(&bm_id);
 
+        let params = self.mode_param_values();
         self.bookmark_store.update_viewport(idx, |bm| {
-            bm.mode = self.mode.label().to_string();
-            bm.center_re = self.viewport.center_dd.re.hi;
-            bm.center_im = self.viewport.center_dd.im.hi;
-            bm.center_re_lo = self.viewport.center_dd.re.lo;
-            bm.center_im_lo = self.viewport.center_dd.im.lo;
+            bm.fractal = self.mode.id.to_string();
+            bm.center_re = self.viewport.center_qd.re.limbs[0];
+            bm.center_im = self.viewport.center_qd.im.limbs[0];
+            bm.center_re_lo = self.viewport.center_qd.re.limbs[1];
+            bm.center_im_lo = self.viewport.center_qd.im.limbs[1];
+            bm.center_re_lo2 = self.viewport.center_qd.re.limbs[2];
+            bm.center_re_lo3 = self.viewport.center_qd.re.limbs[3];
+            bm.center_im_lo2 = self.viewport.center_qd.im.limbs[2];
+            bm.center_im_lo3 = self.viewport.center_qd.im.limbs[3];
+            bm.center = Some(self.viewport.center_big.clone());
             bm.scale = self.viewport.scale;
             bm.max_iterations = self.params.max_iterations;
             bm.escape_radius = self.params.escape_radius;
@@ -83,8 +114,20 @@ impl MandelbRustApp {
             bm.smooth_coloring = self.display_color.smooth_coloring;
             bm.display_color = Some(self.display_color.clone());
             bm.aa_level = self.aa_level;
-            bm.julia_c_re = self.julia_c.re;
-            bm.julia_c_im = self.julia_c.im;
+            bm.julia_c_re = self.julia_c.re.hi;
+            bm.julia_c_im = self.julia_c.im.hi;
+            bm.julia_c_re_lo = self.julia_c.re.lo;
+            bm.julia_c_im_lo = self.julia_c.im.lo;
+            bm.params = params;
+            bm.newton_roots = if self.mode.extra_input == ExtraInput::Roots {
+                self.roots.clone()
+            } else {
+                Vec::new()
+            };
+            bm.formula =
+                (self.mode.extra_input == ExtraInput::Formula).then(|| self.formula.clone());
+            bm.sequence =
+                (self.mode.extra_input == ExtraInput::Sequence).then(|| self.sequence.clone());
             bm.thumbnail_png = thumbnail_png;
         });
 
@@ -130,11 +173,23 @@ impl MandelbRustApp {
     }
 
     pub(crate) fn jump_to_bookmark(&mut self, bm: &Bookmark) {
-        self.mode = match bm.mode.as_str() {
-            "Julia" => FractalMode::Julia,
-            _ => FractalMode::Mandelbrot,
-        };
-        self.julia_c = mandelbrust_core::Complex::new(bm.julia_c_re, bm.julia_c_im);
+        self.mode = bm.descriptor();
+        self.param_values
+            .extend(bm.params.iter().map(|(k, v)| (k.clone(), *v)));
+        if self.mode.extra_input == ExtraInput::Roots {
+            self.roots = restore_roots(&bm.newton_roots);
+        }
+        if let Some(formula) = &bm.formula {
+            self.load_formula(formula.clone());
+        }
+        if let Some(sequence) = &bm.sequence {
+            self.sequence = sequence.clone();
+            self.sequence_draft = sequence.to_string();
+        }
+        self.julia_c = mandelbrust_core::ComplexDD::new(
+            mandelbrust_core::DoubleDouble::new(bm.julia_c_re, bm.julia_c_re_lo),
+            mandelbrust_core::DoubleDouble::new(bm.julia_c_im, bm.julia_c_im_lo),
+        );
         self.params.max_iterations = bm.max_iterations;
         self.params.set_escape_radius(bm.escape_radius);
         if let Some(ref dc) = bm.display_color {
@@ -152,19 +207,14 @@ impl MandelbRustApp {
         self.bump_minimap_revision();
 
         self.push_history();
-        let center_dd = mandelbrust_core::ComplexDD::new(
-            mandelbrust_core::DoubleDouble::new(bm.center_re, bm.center_re_lo),
-            mandelbrust_core::DoubleDouble::new(bm.center_im, bm.center_im_lo),
-        );
-        self.viewport = mandelbrust_core::Viewport::new_dd(
-            center_dd,
-            bm.scale,
-            self.panel_size[0],
-            self.panel_size[1],
-        )
-        .unwrap_or_else(|_| {
-            mandelbrust_core::Viewport::default_mandelbrot(self.panel_size[0], self.panel_size[1])
-        });
+        self.viewport = bm
+            .viewport(self.panel_size[0], self.panel_size[1])
+            .unwrap_or_else(|_| {
+                mandelbrust_core::Viewport::default_mandelbrot(
+                    self.panel_size[0],
+                    self.panel_size[1],
+                )
+            });
         self.needs_render = true;
         tracing::info!("Jumped to bookmark: {}", bm.name);
     }
@@ -251,7 +301,7 @@ impl MandelbRustApp {
             v
         };
 
-        let auto_name = self.bookmark_store.next_auto_name(self.mode.label());
+        let auto_name = self.bookmark_store.next_auto_name(self.mode.name);
 
         let mut open = true;
         let mut do_save = false;
@@ -360,7 +410,7 @@ impl MandelbRustApp {
                 id: self.bookmark_store.bookmark_id(i).to_string(),
                 name: bm.name.clone(),
                 summary: bm.summary(),
-                mode: bm.mode.clone(),
+                mode: bm.descriptor(),
                 labels: bm.labels.clone(),
                 thumbnail_png: bm.thumbnail_png.clone(),
             })
@@ -439,7 +489,7 @@ impl MandelbRustApp {
                         .filter(|s| {
                             passes_bookmark_filter(
                                 &s.name,
-                                &s.mode,
+                                s.mode,
                                 &s.labels,
                                 tab,
                                 fav_only,
@@ -0,0 +1,176 @@
+//! Hyperbolic component labels (Mandelbrot mode): the period of each
+//! component large enough on screen, written at its nucleus, and optionally
+//! its boundary.
+//!
+//! Scans run on the component worker behind the render bridge, once per
+//! view or settings change; the last result stays drawn, mapped through the
+//! current view, until the next one arrives.
+
+use eframe::egui;
+
+use mandelbrust_core::{registry, ComplexDD, Viewport};
+use mandelbrust_render::{ComponentOptions, LabeledComponent};
+
+use crate::app::MandelbRustApp;
+
+/// Label font size range in points; labels grow with their component.
+const LABEL_SIZE: (f32, f32) = (10.0, 20.0);
+
+const LABEL_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 255, 255);
+const BOUNDARY_COLOR: egui::Color32 = egui::Color32::from_rgb(120, 220, 255);
+
+#[derive(Default)]
+pub(crate) struct ComponentLabelsState {
+    pub enabled: bool,
+    pub options: ComponentOptions,
+    /// View and settings of the latest scan request.
+    pub requested: Option<(Viewport, ComponentOptions)>,
+    /// Components of the latest completed scan and the view it covered.
+    pub result: Option<(Viewport, Vec<LabeledComponent>)>,
+    pub scanning: bool,
+}
+
+impl MandelbRustApp {
+    pub(crate) fn component_labels_active(&self) -> bool {
+        self.components.enabled && self.mode == &registry::MANDELBROT
+    }
+
+    /// Draw the labels and boundaries over the canvas.
+    pub(crate) fn draw_component_labels(&self, painter: &egui::Painter, rect: egui::Rect) {
+        if !self.component_labels_active() {
+            return;
+        }
+        let Some((_, components)) = &self.components.result else {
+            return;
+        };
+
+        let origin = rect.min + self.pan_offset;
+        let to_screen = |c: ComplexDD| {
+            let (x, y) = self.viewport.complex_dd_to_pixel(c);
+            egui::pos2(origin.x + x as f32, origin.y + y as f32)
+        };
+        let scale = self.viewport.scale.to_f64();
+        let options = &self.components.options;
+        for component in components {
+            // Zooming out shrinks components below the threshold before
+            // the next scan drops them.
+            let size_px = component.size / scale;
+            if size_px < options.min_size_px {
+                continue;
+            }
+            if options.boundaries && component.boundary.len() > 1 {
+                let points: Vec<egui::Pos2> =
+                    component.boundary.iter().map(|&c| to_screen(c)).collect();
+                painter.add(egui::Shape::closed_line(
+                    points,
+                    egui::Stroke::new(1.0, BOUNDARY_COLOR),
+                ));
+            }
+            let pos = to_screen(component.nucleus);
+            if !rect.contains(pos) {
+                continue;
+            }
+            let font = egui::FontId::proportional(
+                (size_px as f32 / 2.0).clamp(LABEL_SIZE.0, LABEL_SIZE.1),
+            );
+            let text = component.period.to_string();
+            painter.text(
+                pos + egui::vec2(1.0, 1.0),
+                egui::Align2::CENTER_CENTER,
+                &text,
+                font.clone(),
+                egui::Color32::from_black_alpha(200),
+            );
+            painter.text(pos, egui::Align2::CENTER_CENTER, text, font, LABEL_COLOR);
+        }
+    }
+
+    pub(crate) fn show_component_labels_window(&mut self, ctx: &egui::Context) {
+        if !self.show_component_labels {
+            return;
+        }
+
+        let mut open = true;
+        egui::Window::new("Component Labels")
+            .id(egui::Id::new("component_labels_window"))
+            .open(&mut open)
+            .resizable(false)
+            .default_width(300.0)
+            .frame(
+                egui::Frame::NONE
+                    .fill(egui::Color32::from_black_alpha(230))
+                    .inner_margin(egui::Margin::same(10))
+                    .corner_radius(6.0),
+            )
+            .show(ctx, |ui| {
+                ui.style_mut().visuals.override_text_color =
+                    Some(egui::Color32::from_rgb(220, 220, 220));
+                if self.mode != &registry::MANDELBROT {
+                    ui.weak("Component labels are shown in Mandelbrot mode.");
+                    ui.add_space(4.0);
+                }
+
+                if ui
+                    .checkbox(&mut self.components.enabled, "Show component labels")
+                    .changed()
+                    && !self.components.enabled
+                {
+                    self.cancel_component_scan();
+                    self.components.requested = None;
+                }
+                ui.label(
+                    egui::RichText::new(
+                        "Inside each hyperbolic component the orbit of 0 settles on an \
+                         attracting cycle. The label is the cycle's period, written at the \
+                         nucleus, where the cycle passes through 0; the boundary is where \
+                         the cycle's multiplier reaches modulus 1.",
+                    )
+                    .small(),
+                );
+                ui.add_space(4.0);
+
+                let opts = &mut self.components.options;
+                egui::Grid::new("component_label_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Max period:");
+                        ui.add(egui::DragValue::new(&mut opts.max_period).range(1..=1024));
+                        ui.end_row();
+
+                        ui.label("Min size (px):");
+                        ui.add(
+                            egui::DragValue::new(&mut opts.min_size_px)
+                                .range(1.0..=200.0)
+                                .speed(0.5),
+                        )
+                        .on_hover_text("Smallest component labeled, by its radius on screen");
+                        ui.end_row();
+
+                        ui.label("Sample spacing (px):");
+                        ui.add(egui::DragValue::new(&mut opts.sample_step_px).range(1..=32))
+                            .on_hover_text("Components narrower than this can be missed");
+                        ui.end_row();
+                    });
+                ui.checkbox(&mut opts.boundaries, "Draw boundaries")
+                    .on_hover_text("Trace each boundary through the multiplier map");
+
+                ui.separator();
+                if self.components.scanning {
+                    let (done, total) = self.components_cancel.progress();
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(format!("Scanning\u{2026} {done}/{total} rows"));
+                        if ui.small_button("Cancel").clicked() {
+                            self.cancel_component_scan();
+                        }
+                    });
+                } else if let Some((_, components)) = &self.components.result {
+                    ui.weak(format!("{} components found", components.len()));
+                }
+            });
+
+        if !open {
+            self.show_component_labels = false;
+        }
+    }
+}
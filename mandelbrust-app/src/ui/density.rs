@@ -0,0 +1,202 @@
+//! Density renderer window: Buddhabrot, Anti-Buddhabrot and Nebulabrot
+//! settings, and the switch between the density and escape-time views.
+//!
+//! The density view replaces the Mandelbrot render while it is on. Sampling
+//! settings start a new run; tone-mapping settings only recolor the hits
+//! gathered so far.
+
+use eframe::egui;
+
+use mandelbrust_core::registry;
+use mandelbrust_render::{DensityOptions, DensityToneMap, DensityVariant, Sampling};
+
+use crate::app::MandelbRustApp;
+
+/// Density view settings, kept while the view is off.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DensitySettings {
+    pub enabled: bool,
+    pub options: DensityOptions,
+    pub tone: DensityToneMap,
+}
+
+impl MandelbRustApp {
+    /// Whether frames are density renders rather than escape-time ones.
+    /// Orbits are those of `z² + c`, so only the Mandelbrot mode has one.
+    pub(crate) fn density_active(&self) -> bool {
+        self.density.enabled && self.mode == &registry::MANDELBROT
+    }
+
+    /// Sampling settings for the render thread, when the density view is on.
+    pub(crate) fn density_options(&self) -> Option<DensityOptions> {
+        self.density_active().then(|| self.density.options.clone())
+    }
+
+    pub(crate) fn show_density_window(&mut self, ctx: &egui::Context) {
+        if !self.show_density_settings {
+            return;
+        }
+
+        let mut open = true;
+        let mut rerender = false;
+        let mut recolor = false;
+        egui::Window::new("Density Renderer")
+            .id(egui::Id::new("density_window"))
+            .open(&mut open)
+            .resizable(false)
+            .default_width(300.0)
+            .frame(
+                egui::Frame::NONE
+                    .fill(egui::Color32::from_black_alpha(230))
+                    .inner_margin(egui::Margin::same(10))
+                    .corner_radius(6.0),
+            )
+            .show(ctx, |ui| {
+                ui.style_mut().visuals.override_text_color =
+                    Some(egui::Color32::from_rgb(220, 220, 220));
+
+                if ui
+                    .checkbox(&mut self.density.enabled, "Show density view")
+                    .on_hover_text("Replaces the Mandelbrot render with orbit densities")
+                    .changed()
+                {
+                    if self.density.enabled && self.mode != &registry::MANDELBROT {
+                        self.mode = &registry::MANDELBROT;
+                        self.push_history();
+                        self.viewport = self.default_viewport();
+                        self.bump_minimap_revision();
+                    }
+                    rerender = true;
+                }
+
+                let opts = &mut self.density.options;
+                egui::Grid::new("density_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Variant:");
+                        let mut variant = opts.variant;
+                        egui::ComboBox::from_id_salt("density_variant")
+                            .selected_text(variant.label())
+                            .show_ui(ui, |ui| {
+                                for v in [
+                                    DensityVariant::Buddhabrot,
+                                    DensityVariant::AntiBuddhabrot,
+                                    DensityVariant::Nebulabrot {
+                                        limits: DensityVariant::DEFAULT_NEBULA_LIMITS,
+                                    },
+                                ] {
+                                    let selected = variant.label() == v.label();
+                                    if ui.selectable_label(selected, v.label()).clicked()
+                                        && !selected
+                                    {
+                                        variant = v;
+                                    }
+                                }
+                            });
+                        if variant != opts.variant {
+                            opts.variant = variant;
+                            rerender = true;
+                        }
+                        ui.end_row();
+
+                        if let DensityVariant::Nebulabrot { limits } = &mut opts.variant {
+                            for (name, limit) in ["Red", "Green", "Blue"].iter().zip(limits) {
+                                ui.label(format!("{name} limit:"));
+                                rerender |= ui
+                                    .add(
+                                        egui::DragValue::new(limit)
+                                            .range(2..=1_000_000)
+                                            .speed(10.0),
+                                    )
+                                    .changed();
+                                ui.end_row();
+                            }
+                        } else {
+                            ui.label("Limit:");
+                            ui.weak(format!("{} (Iter)", self.params.max_iterations));
+                            ui.end_row();
+                        }
+
+                        ui.label("Min iterations:");
+                        rerender |= ui
+                            .add(egui::DragValue::new(&mut opts.min_iterations).range(0..=10_000))
+                            .on_hover_text("Escaping orbits shorter than this are not counted")
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("Sampling:");
+                        egui::ComboBox::from_id_salt("density_sampling")
+                            .selected_text(opts.sampling.label())
+                            .show_ui(ui, |ui| {
+                                for s in [Sampling::Stratified, Sampling::Random] {
+                                    rerender |= ui
+                                        .selectable_value(&mut opts.sampling, s, s.label())
+                                        .changed();
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Samples per pass:");
+                        rerender |= ui
+                            .add(
+                                egui::DragValue::new(&mut opts.samples_per_pass)
+                                    .range(10_000..=50_000_000)
+                                    .speed(10_000.0),
+                            )
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("Passes:");
+                        rerender |= ui
+                            .add(egui::DragValue::new(&mut opts.passes).range(1..=1000))
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("Seed:");
+                        ui.horizontal(|ui| {
+                            rerender |= ui.add(egui::DragValue::new(&mut opts.seed)).changed();
+                            if ui
+                                .small_button("New")
+                                .on_hover_text("Pick a fresh seed")
+                                .clicked()
+                            {
+                                opts.seed = fresh_seed();
+                                rerender = true;
+                            }
+                        });
+                        ui.end_row();
+                    });
+
+                ui.separator();
+                let tone = &mut self.density.tone;
+                recolor |= ui
+                    .checkbox(&mut tone.log, "Logarithmic")
+                    .on_hover_text("Compress hit counts so faint orbits stay visible")
+                    .changed();
+                recolor |= ui
+                    .add(egui::Slider::new(&mut tone.gamma, 0.2..=5.0).text("Gamma"))
+                    .changed();
+
+                if let Some(density) = &self.current_density {
+                    ui.weak(format!("{} samples", density.samples));
+                }
+            });
+
+        if rerender {
+            self.needs_render = true;
+        } else if recolor {
+            self.recolorize(ctx);
+        }
+        if !open {
+            self.show_density_settings = false;
+        }
+    }
+}
+
+/// A seed from the clock, for a run unlike the last one.
+fn fresh_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(1)
+}
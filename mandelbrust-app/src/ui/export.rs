@@ -8,8 +8,12 @@ use std::time::Duration;
 use eframe::egui;
 use tracing::{debug, error, info};
 
-use mandelbrust_core::{Complex, FractalParams, Viewport};
-use mandelbrust_render::{ExportMetadata, RenderCancel};
+use mandelbrust_core::{
+    DoubleDouble, ExtraInput, ExtrasOptions, FractalInputs, FractalParams, Viewport,
+};
+use mandelbrust_render::{
+    render_density, DensityOptions, DensityToneMap, ExportMetadata, RenderBuffer, RenderCancel,
+};
 
 use crate::app::{FractalMode, MandelbRustApp};
 use crate::app_dir;
@@ -408,6 +412,13 @@ impl MandelbRustApp {
                         (DisplayColoringMode::Standard, "Standard"),
                         (DisplayColoringMode::Histogram, "Histogram"),
                         (DisplayColoringMode::DistanceEstimation, "Distance"),
+                        (DisplayColoringMode::OrbitTrap, "Orbit trap"),
+                        (DisplayColoringMode::StripeAverage, "Stripes"),
+                        (DisplayColoringMode::TriangleInequality, "TIA"),
+                        (DisplayColoringMode::Curvature, "Curvature"),
+                        (DisplayColoringMode::BinaryDecomposition, "Binary"),
+                        (DisplayColoringMode::AngleHue, "Angle"),
+                        (DisplayColoringMode::FieldLines, "Field lines"),
                     ] {
                         ui.selectable_value(
                             &mut self.export_state.display_color.coloring_mode,
@@ -424,6 +435,9 @@ impl MandelbRustApp {
                     for (mode, label) in [
                         (DisplayInteriorMode::Black, "Black"),
                         (DisplayInteriorMode::StripeAverage, "Stripe avg"),
+                        (DisplayInteriorMode::Period, "Period"),
+                        (DisplayInteriorMode::Multiplier, "Multiplier"),
+                        (DisplayInteriorMode::InteriorDistance, "Distance"),
                     ] {
                         ui.selectable_value(
                             &mut self.export_state.display_color.interior_mode,
@@ -433,8 +447,9 @@ impl MandelbRustApp {
                     }
                 });
 
-                if self.export_state.display_color.interior_mode
-                    == DisplayInteriorMode::StripeAverage
+                let dc = &self.export_state.display_color;
+                if dc.interior_mode == DisplayInteriorMode::StripeAverage
+                    || dc.coloring_mode == DisplayColoringMode::StripeAverage
                 {
                     ui.horizontal(|ui| {
                         ui.add_space(48.0);
@@ -540,7 +555,7 @@ impl MandelbRustApp {
     }
 
     fn default_export_name(&self) -> String {
-        let fractal = self.mode.label();
+        let fractal = self.mode.name;
         let iter = self.export_state.export_max_iterations();
         let w = self.export_state.export_width();
         let h = self.export_state.export_height();
@@ -592,8 +607,7 @@ impl MandelbRustApp {
             sanitize_filename(&self.export_state.image_name)
         };
 
-        let fractal_dir_name = self.mode.label().to_lowercase();
-        let out_dir = app_dir::images_directory().join(&fractal_dir_name);
+        let out_dir = app_dir::images_directory().join(self.mode.id);
         if let Err(e) = std::fs::create_dir_all(&out_dir) {
             error!("Failed to create export directory: {e}");
             self.export_state.export_notification = Some((
@@ -609,14 +623,15 @@ impl MandelbRustApp {
         let params = FractalParams::new(max_iter, self.params.escape_radius)
             .unwrap_or_else(|_| FractalParams::new(max_iter, 2.0).unwrap_or_default());
 
-        let viewer_complex_w = self.viewport.complex_width();
-        let viewer_complex_h = self.viewport.complex_height();
-        let export_scale = (viewer_complex_w / w as f64).max(viewer_complex_h / h as f64);
-        let viewport =
-            Viewport::new_dd(self.viewport.center_dd, export_scale, w, h).unwrap_or(self.viewport);
+        let export_scale = self.viewport.scale
+            * (self.viewport.width as f64 / w as f64).max(self.viewport.height as f64 / h as f64);
+        let viewport = Viewport::new_big(self.viewport.center_big.clone(), export_scale, w, h)
+            .unwrap_or_else(|_| self.viewport.clone());
 
         let mode = self.mode;
-        let julia_c = self.julia_c;
+        let inputs = self.fractal_inputs();
+        let density = self.density_options().map(|opts| (opts, self.density.tone));
+        let julia_c = inputs.julia_c;
         let export_dc = &self.export_state.display_color;
         let palette = if let Some(ref name) = export_dc.custom_palette_name {
             self.user_palette_defs
@@ -635,34 +650,56 @@ impl MandelbRustApp {
                 .min(self.palettes.len().saturating_sub(1));
             self.palettes[idx].clone()
         };
-        let color_params = Self::color_params_from_display(export_dc, max_iter);
+        let palette = self.with_chaos_palette(palette, export_dc);
+        let color_params = Self::color_params_from_display(export_dc, max_iter, self.degree());
         let allow_border_tracing = !color_params.smooth;
         let display_color = export_dc.clone();
-        let needs_extras = export_dc.coloring_mode == DisplayColoringMode::DistanceEstimation
-            || export_dc.interior_mode == DisplayInteriorMode::StripeAverage;
-        let stripe_density = export_dc.stripe_density;
+        let needs_extras = export_dc.coloring_mode.needs_extras()
+            || export_dc.interior_mode != DisplayInteriorMode::Black;
+        let extras = export_dc.extras_options();
 
         let center_re = format!("{:.15}", self.viewport.center.re);
         let center_im = format!("{:+.15}", self.viewport.center.im);
-        let zoom_str = format!("{:.6e}", 1.0 / self.viewport.scale);
+        let zoom_str = format!("{:.6e}", self.viewport.scale.recip());
 
         let metadata = ExportMetadata {
-            fractal_type: mode.label().to_string(),
+            fractal_type: mode.name.to_string(),
+            fractal_id: mode.id.to_string(),
             center_re,
             center_im,
             zoom: zoom_str,
             max_iterations: max_iter,
-            escape_radius: params.escape_radius,
-            julia_c_re: if mode == FractalMode::Julia {
-                Some(format!("{:.15}", julia_c.re))
+            escape_radius: if mode.extra_input == ExtraInput::Formula {
+                inputs.formula.bailout
+            } else {
+                params.escape_radius
+            },
+            params: mode
+                .params
+                .iter()
+                .map(|p| (p.key.to_string(), p.value(&inputs.values)))
+                .collect(),
+            julia_c_re: if mode.is_julia() {
+                Some(julia_c.re.to_string_digits(DoubleDouble::DIGITS))
             } else {
                 None
             },
-            julia_c_im: if mode == FractalMode::Julia {
-                Some(format!("{:+.15}", julia_c.im))
+            julia_c_im: if mode.is_julia() {
+                let im = julia_c.im.to_string_digits(DoubleDouble::DIGITS);
+                Some(if julia_c.im.is_negative() {
+                    im
+                } else {
+                    format!("+{im}")
+                })
             } else {
                 None
             },
+            formula: (mode.extra_input == ExtraInput::Formula).then(|| inputs.formula.to_string()),
+            sequence: (mode.extra_input == ExtraInput::Sequence)
+                .then(|| inputs.sequence.to_string()),
+            density: density
+                .as_ref()
+                .map(|(opts, tone)| format!("{opts}; {tone}")),
             aa_level,
             palette_name: palette.name.to_string(),
             smooth_coloring: display_color.smooth_coloring,
@@ -682,7 +719,7 @@ impl MandelbRustApp {
         let job = ExportJob {
             mode,
             params,
-            julia_c,
+            inputs,
             viewport,
             cancel,
             aa_level,
@@ -692,7 +729,8 @@ impl MandelbRustApp {
             path,
             compute_extras: needs_extras,
             allow_border_tracing,
-            stripe_density,
+            extras,
+            density,
         };
 
         let ctx = self.egui_ctx.clone();
@@ -745,6 +783,7 @@ impl MandelbRustApp {
     fn color_params_from_display(
         dc: &DisplayColorSettings,
         max_iterations: u32,
+        degree: f64,
     ) -> mandelbrust_render::ColorParams {
         let start_from = match dc.start_from {
             DisplayStartFrom::None => mandelbrust_render::StartFrom::None,
@@ -757,10 +796,24 @@ impl MandelbRustApp {
             DisplayColoringMode::DistanceEstimation => {
                 mandelbrust_render::ColoringMode::DistanceEstimation
             }
+            DisplayColoringMode::OrbitTrap => mandelbrust_render::ColoringMode::OrbitTrap,
+            DisplayColoringMode::StripeAverage
+            | DisplayColoringMode::TriangleInequality
+            | DisplayColoringMode::Curvature => mandelbrust_render::ColoringMode::OrbitAverage,
+            DisplayColoringMode::BinaryDecomposition => {
+                mandelbrust_render::ColoringMode::BinaryDecomposition
+            }
+            DisplayColoringMode::AngleHue => mandelbrust_render::ColoringMode::AngleHue,
+            DisplayColoringMode::FieldLines => mandelbrust_render::ColoringMode::FieldLines,
         };
         let interior_mode = match dc.interior_mode {
             DisplayInteriorMode::Black => mandelbrust_render::InteriorMode::Black,
             DisplayInteriorMode::StripeAverage => mandelbrust_render::InteriorMode::StripeAverage,
+            DisplayInteriorMode::Period => mandelbrust_render::InteriorMode::Period,
+            DisplayInteriorMode::Multiplier => mandelbrust_render::InteriorMode::Multiplier,
+            DisplayInteriorMode::InteriorDistance => {
+                mandelbrust_render::InteriorMode::InteriorDistance
+            }
         };
         mandelbrust_render::ColorParams {
             smooth: dc.smooth_coloring,
@@ -770,6 +823,8 @@ impl MandelbRustApp {
             low_threshold_end: dc.low_threshold_end,
             coloring_mode,
             interior_mode,
+            degree,
+            field_lines: dc.field_lines,
         }
     }
 }
@@ -781,7 +836,7 @@ impl MandelbRustApp {
 struct ExportJob {
     mode: FractalMode,
     params: FractalParams,
-    julia_c: Complex,
+    inputs: FractalInputs,
     viewport: Viewport,
     cancel: Arc<RenderCancel>,
     aa_level: u32,
@@ -791,21 +846,31 @@ struct ExportJob {
     path: PathBuf,
     compute_extras: bool,
     allow_border_tracing: bool,
-    stripe_density: f64,
+    extras: ExtrasOptions,
+    density: Option<(DensityOptions, DensityToneMap)>,
 }
 
 fn export_worker(job: &ExportJob) -> ExportWorkerResult {
+    if let Some((opts, tone)) = &job.density {
+        let result = render_density(&job.viewport, &job.params, opts, &job.cancel, |_| {});
+        if result.cancelled {
+            return ExportWorkerResult::Error("Export cancelled".into());
+        }
+        let buffer = job.palette.colorize_density(&result.density, tone);
+        return write_png(job, &buffer);
+    }
+
     let result = render_for_mode(
         job.mode,
         job.params,
-        job.julia_c,
+        &job.inputs,
         &job.viewport,
         &job.cancel,
         RenderModeOptions {
             aa_level: job.aa_level,
             compute_extras: job.compute_extras,
             allow_border_tracing: job.allow_border_tracing,
-            stripe_density: job.stripe_density,
+            extras: job.extras,
         },
     );
 
@@ -819,7 +884,10 @@ fn export_worker(job: &ExportJob) -> ExportWorkerResult {
         result.aa_samples.as_ref(),
         &job.color_params,
     );
+    write_png(job, &buffer)
+}
 
+fn write_png(job: &ExportJob, buffer: &RenderBuffer) -> ExportWorkerResult {
     match mandelbrust_render::export_png(
         &buffer.pixels,
         buffer.width,
@@ -0,0 +1,440 @@
+//! External rays window and canvas overlay (Mandelbrot mode).
+//!
+//! Rays are traced lazily: every ray is extended until its last step is
+//! below the current pixel size, so zooming in towards a landing point
+//! keeps refining it. Each point costs a Newton solve over the whole orbit,
+//! so rays are extended on worker threads, as is picking a point, which
+//! measures the angles of nearby rays.
+
+use std::sync::mpsc;
+
+use eframe::egui;
+use tracing::error;
+
+use mandelbrust_core::external_ray::{external_angles_near, SHARPNESS};
+use mandelbrust_core::{registry, ComplexDD, ExternalAngle, RayTracer};
+
+use crate::app::MandelbRustApp;
+
+/// Points a ray worker traces before sending them to the canvas.
+const POINTS_PER_BATCH: usize = 64;
+
+/// A ray stops growing once it has this many points.
+const MAX_RAY_POINTS: usize = 16_384;
+
+/// A ray is refined until its steps are shorter than this many pixels.
+const PIXEL_STEP: f64 = 0.5;
+
+/// Radius in pixels around a picked point that is searched for rays.
+const PICK_RADIUS_PX: f64 = 3.0;
+
+/// Binary digits shown before an expansion is elided.
+const SHOWN_DIGITS: usize = 40;
+
+const RAY_COLORS: [egui::Color32; 6] = [
+    egui::Color32::from_rgb(255, 220, 80),
+    egui::Color32::from_rgb(80, 200, 255),
+    egui::Color32::from_rgb(255, 110, 90),
+    egui::Color32::from_rgb(140, 230, 120),
+    egui::Color32::from_rgb(220, 140, 255),
+    egui::Color32::from_rgb(255, 170, 60),
+];
+
+/// A ray with the points traced so far.
+pub(crate) struct TracedRay {
+    angle: ExternalAngle,
+    /// `None` while a worker is extending the ray.
+    tracer: Option<RayTracer>,
+    job: Option<mpsc::Receiver<RayProgress>>,
+    points: Vec<ComplexDD>,
+    /// Newton's method broke down or the point budget ran out.
+    finished: bool,
+    color: egui::Color32,
+}
+
+/// Messages from a ray worker: batches of new points, then the tracer
+/// handed back with whether the ray ended.
+enum RayProgress {
+    Points(Vec<ComplexDD>),
+    Done(RayTracer, bool),
+}
+
+/// Angles measured around a picked point; `None` while still measuring.
+pub(crate) struct PickedAngles {
+    pub point: ComplexDD,
+    pub angles: Option<Vec<ExternalAngle>>,
+}
+
+#[derive(Default)]
+pub(crate) struct ExternalRaysState {
+    pub input: String,
+    /// Parse error of `input`, if any.
+    pub error: Option<String>,
+    pub rays: Vec<TracedRay>,
+    /// Canvas clicks pick points instead of loading Julia sets.
+    pub picking: bool,
+    pub picked: Option<PickedAngles>,
+    pick_rx: Option<mpsc::Receiver<Vec<ExternalAngle>>>,
+    next_color: usize,
+}
+
+impl TracedRay {
+    /// Finished, or already stepping less than `min_step` at a time.
+    fn is_done(&self, min_step: f64) -> bool {
+        self.finished
+            || matches!(self.points.as_slice(), [.., a, b]
+                if (*b - *a).to_complex().norm() < min_step)
+    }
+
+    /// Levels traced so far: the number of angle doublings.
+    fn depth(&self) -> usize {
+        self.points.len() / SHARPNESS as usize
+    }
+
+    /// Take in what the worker has traced since the last frame.
+    fn poll(&mut self) {
+        let Some(rx) = self.job.take() else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(RayProgress::Points(points)) => self.points.extend(points),
+                Ok(RayProgress::Done(tracer, finished)) => {
+                    self.tracer = Some(tracer);
+                    self.finished |= finished;
+                    return;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    return;
+                }
+            }
+        }
+        self.job = Some(rx);
+    }
+
+    /// Extend the ray on a worker thread until it steps less than
+    /// `min_step` at a time.
+    fn extend(&mut self, min_step: f64, ctx: &egui::Context) {
+        let room = MAX_RAY_POINTS.saturating_sub(self.points.len());
+        if room == 0 {
+            self.finished = true;
+        }
+        if self.finished || self.is_done(min_step) {
+            return;
+        }
+        let Some(tracer) = self.tracer.take() else {
+            return;
+        };
+        let last = self.points.last().copied();
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        match std::thread::Builder::new()
+            .name("ray-trace-worker".into())
+            .spawn(move || trace_ray(tracer, last, min_step, room, &tx, &ctx))
+        {
+            Ok(_) => self.job = Some(rx),
+            Err(e) => {
+                error!("Failed to spawn ray tracing thread: {e}");
+                self.finished = true;
+            }
+        }
+    }
+}
+
+/// Step `tracer` on from the ray's `last` point until a step is shorter
+/// than `min_step`, `room` points have been added or Newton's method breaks
+/// down. Stops early once the ray is dropped.
+fn trace_ray(
+    mut tracer: RayTracer,
+    mut last: Option<ComplexDD>,
+    min_step: f64,
+    room: usize,
+    tx: &mpsc::Sender<RayProgress>,
+    ctx: &egui::Context,
+) {
+    let mut batch = Vec::with_capacity(POINTS_PER_BATCH);
+    let mut traced = 0;
+    let finished = loop {
+        if traced == room {
+            break true;
+        }
+        let Some(c) = tracer.next() else {
+            break true;
+        };
+        let step = last.map(|l| (c - l).to_complex().norm());
+        last = Some(c);
+        batch.push(c);
+        traced += 1;
+        if step.is_some_and(|step| step < min_step) {
+            break false;
+        }
+        if batch.len() == POINTS_PER_BATCH {
+            if tx
+                .send(RayProgress::Points(std::mem::take(&mut batch)))
+                .is_err()
+            {
+                return;
+            }
+            ctx.request_repaint();
+        }
+    };
+    let _ = tx.send(RayProgress::Points(batch));
+    let _ = tx.send(RayProgress::Done(tracer, finished));
+    ctx.request_repaint();
+}
+
+impl ExternalRaysState {
+    fn add_ray(&mut self, angle: ExternalAngle) {
+        self.rays.push(TracedRay {
+            tracer: Some(RayTracer::new(angle.clone())),
+            angle,
+            job: None,
+            points: Vec::new(),
+            finished: false,
+            color: RAY_COLORS[self.next_color % RAY_COLORS.len()],
+        });
+        self.next_color += 1;
+    }
+}
+
+/// `p/q ≈ 0.333333` when the fraction fits, else the decimal value alone.
+fn angle_value_label(angle: &ExternalAngle) -> String {
+    match angle.to_fraction() {
+        Some((num, den)) => format!("{num}/{den} \u{2248} {:.6}", angle.to_f64()),
+        None => format!("\u{2248} {:.9}", angle.to_f64()),
+    }
+}
+
+/// The binary expansion, elided after [`SHOWN_DIGITS`] digits.
+fn angle_digits_label(angle: &ExternalAngle) -> String {
+    elide(&angle.to_string(), SHOWN_DIGITS + 2)
+}
+
+/// `p/q` when the fraction fits, else the elided binary expansion.
+pub(crate) fn angle_short_label(angle: &ExternalAngle) -> String {
+    match angle.to_fraction() {
+        Some((num, den)) => format!("{num}/{den}"),
+        None => angle_digits_label(angle),
+    }
+}
+
+/// `text` cut to `max_chars` characters, with an ellipsis if it was longer.
+pub(crate) fn elide(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}\u{2026}", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+impl MandelbRustApp {
+    pub(crate) fn external_rays_active(&self) -> bool {
+        self.mode == &registry::MANDELBROT
+    }
+
+    /// Measure the angles of the rays near `c` on a worker thread.
+    pub(crate) fn pick_external_angles(&mut self, c: ComplexDD) {
+        let radius = PICK_RADIUS_PX * self.viewport.scale.to_f64();
+        let max_iterations = self.effective_max_iterations();
+        let (tx, rx) = mpsc::channel();
+        let ctx = self.egui_ctx.clone();
+        if let Err(e) = std::thread::Builder::new()
+            .name("ray-pick-worker".into())
+            .spawn(move || {
+                let _ = tx.send(external_angles_near(c, radius, max_iterations));
+                ctx.request_repaint();
+            })
+        {
+            error!("Failed to spawn ray pick thread: {e}");
+            return;
+        }
+        self.external_rays.pick_rx = Some(rx);
+        self.external_rays.picked = Some(PickedAngles {
+            point: c,
+            angles: None,
+        });
+    }
+
+    fn poll_pick_result(&mut self) {
+        let state = &mut self.external_rays;
+        let Some(rx) = state.pick_rx.as_ref() else {
+            return;
+        };
+        if let Ok(angles) = rx.try_recv() {
+            state.pick_rx = None;
+            if let Some(picked) = state.picked.as_mut() {
+                picked.angles = Some(angles);
+            }
+        }
+    }
+
+    /// Collect the points traced since the last frame and extend the rays
+    /// towards the current pixel size.
+    fn extend_external_rays(&mut self) {
+        let min_step = PIXEL_STEP * self.viewport.scale.to_f64();
+        for ray in &mut self.external_rays.rays {
+            ray.poll();
+            ray.extend(min_step, &self.egui_ctx);
+        }
+    }
+
+    /// Draw the traced rays and the picked point over the canvas.
+    pub(crate) fn draw_external_rays(&mut self, painter: &egui::Painter, rect: egui::Rect) {
+        self.poll_pick_result();
+        if !self.external_rays_active() {
+            return;
+        }
+        self.extend_external_rays();
+
+        let origin = rect.min + self.pan_offset;
+        let to_screen = |c: ComplexDD| {
+            let (x, y) = self.viewport.complex_dd_to_pixel(c);
+            egui::pos2(origin.x + x as f32, origin.y + y as f32)
+        };
+        let bounds = rect.expand(rect.width().max(rect.height()));
+        for ray in &self.external_rays.rays {
+            let stroke = egui::Stroke::new(1.5, ray.color);
+            let mut run: Vec<egui::Pos2> = Vec::new();
+            for &c in &ray.points {
+                let p = to_screen(c);
+                if p.is_finite() && bounds.contains(p) {
+                    run.push(p);
+                } else {
+                    if run.len() > 1 {
+                        painter.add(egui::Shape::line(std::mem::take(&mut run), stroke));
+                    }
+                    run.clear();
+                }
+            }
+            if run.len() > 1 {
+                painter.add(egui::Shape::line(run, stroke));
+            }
+        }
+
+        if let Some(picked) = &self.external_rays.picked {
+            let p = to_screen(picked.point);
+            if rect.contains(p) {
+                let stroke = egui::Stroke::new(1.5, egui::Color32::WHITE);
+                painter.circle_stroke(p, PICK_RADIUS_PX as f32 + 2.0, stroke);
+            }
+        }
+    }
+
+    pub(crate) fn show_external_rays_window(&mut self, ctx: &egui::Context) {
+        if !self.show_external_rays {
+            return;
+        }
+
+        let mut open = true;
+        egui::Window::new("External Rays")
+            .id(egui::Id::new("external_rays_window"))
+            .open(&mut open)
+            .resizable(false)
+            .default_width(320.0)
+            .frame(
+                egui::Frame::NONE
+                    .fill(egui::Color32::from_black_alpha(230))
+                    .inner_margin(egui::Margin::same(10))
+                    .corner_radius(6.0),
+            )
+            .show(ctx, |ui| {
+                ui.style_mut().visuals.override_text_color =
+                    Some(egui::Color32::from_rgb(220, 220, 220));
+                if !self.external_rays_active() {
+                    ui.weak("External rays are shown in Mandelbrot mode.");
+                    ui.add_space(4.0);
+                }
+                let state = &mut self.external_rays;
+
+                ui.horizontal(|ui| {
+                    ui.label("Angle:");
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut state.input)
+                            .code_editor()
+                            .hint_text("1/3 or 0.0(01)")
+                            .desired_width(160.0),
+                    );
+                    let submitted =
+                        response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if ui.button("Trace").clicked() || submitted {
+                        match ExternalAngle::parse(&state.input) {
+                            Ok(angle) => {
+                                state.error = None;
+                                state.add_ray(angle);
+                            }
+                            Err(e) => state.error = Some(e.to_string()),
+                        }
+                    }
+                });
+                if let Some(error) = &state.error {
+                    ui.colored_label(egui::Color32::from_rgb(255, 110, 90), error);
+                }
+
+                let mut remove = None;
+                for (i, ray) in state.rays.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(ray.color, "\u{25CF}");
+                        ui.label(angle_value_label(&ray.angle))
+                            .on_hover_text(ray.angle.to_string());
+                        ui.weak(format!("depth {}", ray.depth()));
+                        if ui
+                            .add(egui::Button::new("\u{2212}").small())
+                            .on_hover_text("Remove ray")
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = remove {
+                    state.rays.remove(i);
+                }
+                if !state.rays.is_empty() && ui.small_button("Clear rays").clicked() {
+                    state.rays.clear();
+                }
+
+                ui.separator();
+                ui.checkbox(&mut state.picking, "Pick angles on click")
+                    .on_hover_text("Click near the boundary to measure the angles of nearby rays");
+                let mut trace = None;
+                if let Some(picked) = &state.picked {
+                    let c = picked.point.to_complex();
+                    ui.weak(format!("Near {:.10} {:+.10}i", c.re, c.im));
+                    match &picked.angles {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label("Measuring\u{2026}");
+                            });
+                        }
+                        Some(angles) if angles.is_empty() => {
+                            ui.label("No exterior points there; try closer to the boundary.");
+                        }
+                        Some(angles) => {
+                            for angle in angles {
+                                ui.horizontal(|ui| {
+                                    ui.monospace(angle_digits_label(angle))
+                                        .on_hover_text(angle_value_label(angle));
+                                    if ui.small_button("Trace").clicked() {
+                                        trace = Some(angle.clone());
+                                    }
+                                    if ui.small_button("Copy").clicked() {
+                                        ui.ctx().copy_text(angle.to_string());
+                                    }
+                                });
+                            }
+                        }
+                    }
+                }
+                if let Some(angle) = trace {
+                    state.add_ray(angle);
+                }
+            });
+
+        if !open {
+            self.show_external_rays = false;
+            self.external_rays.picking = false;
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Formula editor: the step, initial value, bailout and named parameters of
+//! the user formula mode.
+//!
+//! The draft is recompiled on every edit. A draft that compiles replaces
+//! the rendered formula right away; otherwise the error is shown under the
+//! fields and the last good formula keeps rendering.
+
+use eframe::egui;
+
+use mandelbrust_core::{
+    registry, Complex, Formula, FormulaDefinition, FormulaParam, FractalParams,
+};
+
+use crate::app::MandelbRustApp;
+
+/// Most named parameters a formula can have.
+const MAX_PARAMS: usize = 8;
+
+/// Persistent state for the formula editor.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FormulaEditorState {
+    pub draft: FormulaDefinition,
+    /// Compile error of the draft, if any.
+    pub error: Option<String>,
+}
+
+impl FormulaEditorState {
+    pub(crate) fn new(formula: &FormulaDefinition) -> Self {
+        Self {
+            draft: formula.clone(),
+            error: None,
+        }
+    }
+}
+
+impl MandelbRustApp {
+    /// Put `formula` (e.g. from a bookmark) in the editor and render it if
+    /// it compiles.
+    pub(crate) fn load_formula(&mut self, formula: FormulaDefinition) {
+        self.formula_editor.draft = formula;
+        self.apply_formula_draft();
+    }
+
+    /// Compile the draft and make it the rendered formula on success.
+    /// Returns whether the rendered formula changed.
+    fn apply_formula_draft(&mut self) -> bool {
+        let draft = &self.formula_editor.draft;
+        match Formula::compile(draft, FractalParams::default()) {
+            Ok(_) => {
+                self.formula_editor.error = None;
+                if *draft == self.formula {
+                    return false;
+                }
+                self.formula = draft.clone();
+                true
+            }
+            Err(e) => {
+                self.formula_editor.error = Some(e.to_string());
+                false
+            }
+        }
+    }
+
+    pub(crate) fn show_formula_editor_window(&mut self, ctx: &egui::Context) {
+        if !self.show_formula_editor {
+            return;
+        }
+
+        let mut open = true;
+        let mut edited = false;
+        egui::Window::new("Formula Editor")
+            .id(egui::Id::new("formula_editor_window"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(340.0)
+            .frame(
+                egui::Frame::NONE
+                    .fill(egui::Color32::from_black_alpha(230))
+                    .inner_margin(egui::Margin::same(10))
+                    .corner_radius(6.0),
+            )
+            .show(ctx, |ui| {
+                ui.style_mut().visuals.override_text_color =
+                    Some(egui::Color32::from_rgb(220, 220, 220));
+                let draft = &mut self.formula_editor.draft;
+
+                ui.label("Step:");
+                edited |= ui
+                    .add(
+                        egui::TextEdit::multiline(&mut draft.formula)
+                            .code_editor()
+                            .desired_rows(2)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .changed();
+
+                egui::Grid::new("formula_settings")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Initial z:");
+                        edited |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut draft.initial_z)
+                                    .code_editor()
+                                    .desired_width(160.0),
+                            )
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("Bailout:");
+                        edited |= ui
+                            .add(
+                                egui::DragValue::new(&mut draft.bailout)
+                                    .range(0.5..=1.0e6)
+                                    .speed(0.05)
+                                    .max_decimals(3),
+                            )
+                            .on_hover_text("|z| beyond which a point counts as escaped")
+                            .changed();
+                        ui.end_row();
+                    });
+
+                ui.add_space(4.0);
+                ui.label("Parameters:");
+                let mut remove = None;
+                for (i, param) in draft.params.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        edited |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut param.name)
+                                    .code_editor()
+                                    .desired_width(50.0),
+                            )
+                            .changed();
+                        ui.label("=");
+                        for part in [&mut param.value.re, &mut param.value.im] {
+                            edited |= ui
+                                .add(egui::DragValue::new(part).speed(0.005).max_decimals(6))
+                                .changed();
+                        }
+                        ui.label("i");
+                        if ui
+                            .add(egui::Button::new("\u{2212}").small())
+                            .on_hover_text("Remove parameter")
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = remove {
+                    draft.params.remove(i);
+                    edited = true;
+                }
+                if draft.params.len() < MAX_PARAMS && ui.small_button("Add parameter").clicked() {
+                    let name = (b'a'..=b'z')
+                        .map(|b| (b as char).to_string())
+                        .find(|n| {
+                            !matches!(n.as_str(), "c" | "e" | "i" | "z")
+                                && draft.params.iter().all(|p| p.name != *n)
+                        })
+                        .unwrap_or_default();
+                    draft.params.push(FormulaParam {
+                        name,
+                        value: Complex::ZERO,
+                    });
+                    edited = true;
+                }
+
+                ui.add_space(4.0);
+                match &self.formula_editor.error {
+                    Some(error) => {
+                        ui.colored_label(egui::Color32::from_rgb(255, 110, 90), error);
+                    }
+                    None => {
+                        ui.weak(
+                            "Use z, c (the pixel), i, pi, e, + - * / ^, |x| and \
+                             sin cos sinh cosh exp log sqrt abs conj re im",
+                        );
+                    }
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Reset to z^2 + c").clicked() {
+                        self.formula_editor.draft = FormulaDefinition::default();
+                        edited = true;
+                    }
+                    if self.mode != &registry::FORMULA && ui.button("Render").clicked() {
+                        self.mode = &registry::FORMULA;
+                        self.push_history();
+                        self.viewport = self.default_viewport();
+                        self.bump_minimap_revision();
+                        self.needs_render = true;
+                    }
+                });
+            });
+
+        if edited && self.apply_formula_draft() && self.mode == &registry::FORMULA {
+            self.bump_minimap_revision();
+            self.needs_render = true;
+        }
+        if !open {
+            self.show_formula_editor = false;
+        }
+    }
+}
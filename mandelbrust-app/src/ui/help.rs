@@ -45,6 +45,8 @@ impl MandelbRustApp {
                                     ("A", "Cycle anti-aliasing (Off / 2x2 / 4x4)"),
                                     ("E", "Open export dialog"),
                                     ("R", "Reset view"),
+                                    ("N", "Jump to nearest minibrot (Mandelbrot)"),
+                                    ("P", "Snap to Misiurewicz point near cursor (Mandelbrot)"),
                                     ("Esc", "Cancel render / close dialogs"),
                                     ("Arrow keys", "Pan viewport"),
                                     ("+ / -", "Zoom in / out"),
@@ -1,6 +1,8 @@
 use eframe::egui;
 
-use crate::app::{FractalMode, MandelbRustApp, HUD_CORNER_RADIUS, HUD_MARGIN};
+use mandelbrust_core::ExtraInput;
+
+use crate::app::{MandelbRustApp, HUD_CORNER_RADIUS, HUD_MARGIN};
 use crate::j_preview;
 use crate::render_bridge::RenderPhase;
 
@@ -28,18 +30,45 @@ impl MandelbRustApp {
                         ui.style_mut().visuals.override_text_color =
                             Some(egui::Color32::from_rgb(220, 220, 220));
 
-                        ui.label(format!("Mode: {}", self.mode.label()));
-                        if self.mode == FractalMode::Julia {
+                        ui.label(format!("Mode: {}", self.mode.name));
+                        if let Some(density) = self.current_density.as_ref() {
+                            ui.label(format!(
+                                "{}: {} samples",
+                                self.density.options.variant.label(),
+                                density.samples
+                            ));
+                        }
+                        for spec in self.mode.params {
+                            ui.label(format!(
+                                "{}: {}",
+                                spec.label,
+                                spec.value(&self.param_values)
+                            ));
+                        }
+                        match self.mode.extra_input {
+                            ExtraInput::Roots => {
+                                ui.label(format!("Roots: {}", self.roots.len()));
+                            }
+                            ExtraInput::Formula => {
+                                ui.label(format!("Formula: {}", self.formula.formula.trim()));
+                            }
+                            ExtraInput::Sequence => {
+                                ui.label(format!("Sequence: {}", self.sequence));
+                            }
+                            ExtraInput::None | ExtraInput::JuliaConstant => {}
+                        }
+                        if self.mode.is_julia() {
                             ui.label(format!(
                                 "Julia c: {:.6} {:+.6}i",
-                                self.julia_c.re, self.julia_c.im
+                                self.julia_c.re.to_f64(),
+                                self.julia_c.im.to_f64()
                             ));
                         }
                         ui.label(format!(
                             "Center: {:.10} {:+.10}i",
                             self.viewport.center.re, self.viewport.center.im
                         ));
-                        let zoom_level = 1.0 / self.viewport.scale;
+                        let zoom_level = self.viewport.scale.recip();
                         ui.label(format!("Zoom: {zoom_level:.2e}"));
                         ui.label(format!("Iterations: {}", self.params.max_iterations));
                         ui.label(format!("Precision: {}", self.precision_mode_label()));
@@ -54,6 +83,8 @@ impl MandelbRustApp {
                             }
                         ));
 
+                        self.draw_address_section(ui);
+
                         if let Some(warning) = self.precision_warning() {
                             ui.colored_label(egui::Color32::from_rgb(255, 180, 50), warning);
                         }
@@ -86,6 +117,9 @@ impl MandelbRustApp {
                             "{} tiles, {} mirrored, {} bt",
                             self.tiles_rendered, self.tiles_mirrored, self.tiles_border_traced,
                         ));
+                        if self.skipped_iterations > 0 {
+                            ui.label(format!("{} iterations skipped", self.skipped_iterations));
+                        }
 
                         if let Some(ref aa) = self.current_aa {
                             ui.label(format!(
@@ -99,7 +133,7 @@ impl MandelbRustApp {
             });
 
         // -- J preview panel (Phase 10.5) --
-        if self.preferences.show_j_preview {
+        if self.preferences.show_j_preview && self.mode.counterpart().is_some() {
             let size = self.preferences.minimap_size.side_pixels() as f32;
             let j_alpha =
                 (hud_alpha as f32 * self.preferences.minimap_opacity.clamp(0.0, 1.0)).round() as u8;
@@ -115,8 +149,8 @@ impl MandelbRustApp {
                     texture,
                     loading: self.j_preview_loading,
                     preview_viewport: self.j_preview_viewport(),
-                    julia_c: self.julia_c,
-                    is_mandelbrot_preview: self.mode == FractalMode::Julia,
+                    julia_c: self.julia_c.to_complex(),
+                    is_mandelbrot_preview: self.mode.is_julia(),
                 },
             );
         }
@@ -1,6 +1,8 @@
 use eframe::egui;
 
-use crate::app::{FractalMode, MandelbRustApp};
+use mandelbrust_core::registry;
+
+use crate::app::MandelbRustApp;
 use crate::app_state::AppScreen;
 
 const CYAN: egui::Color32 = egui::Color32::from_rgb(80, 200, 255);
@@ -39,8 +41,9 @@ impl MandelbRustApp {
         }
 
         if let Some((c_re, c_im)) = self.julia_explorer_picked_c.take() {
-            self.julia_c = mandelbrust_core::Complex::new(c_re, c_im);
-            self.mode = FractalMode::Julia;
+            self.julia_c =
+                mandelbrust_core::ComplexDD::from(mandelbrust_core::Complex::new(c_re, c_im));
+            self.mode = &registry::JULIA;
             self.push_history();
             self.viewport = self.default_viewport();
             self.bump_minimap_revision();
@@ -3,7 +3,7 @@ use tracing::{debug, error};
 
 use mandelbrust_core::DoubleDouble;
 
-use crate::app::{defaults_for, FractalMode, MandelbRustApp};
+use crate::app::{defaults_for, MandelbRustApp};
 use crate::app_dir;
 use crate::app_state::AppScreen;
 
@@ -182,27 +182,26 @@ Julia C Explorer.";
     }
 
     fn format_resume_details(&self) -> String {
-        let mode = self.mode.label();
+        let mode = self.mode.name;
         let mut s = format!("**Fractal:** {mode}");
-        if self.mode == FractalMode::Julia {
-            let c_re = format_f64_trimmed(self.julia_c.re);
-            let c_im = format_f64_signed_trimmed(self.julia_c.im);
+        for spec in self.mode.params {
+            let value = spec.value(&self.param_values);
+            s.push_str(&format!("\n\n**{}:** {value}", spec.label));
+        }
+        if self.mode.is_julia() {
+            let c_re = format_dd_trimmed(self.julia_c.re);
+            let c_im = format_dd_signed_trimmed(self.julia_c.im);
             s.push_str(&format!("\n\n**C Coordinates:**\n{c_re}\n{c_im}i"));
         }
 
         let center_re = format_dd_trimmed(self.viewport.center_dd.re);
-        let center_im_raw = format_dd_trimmed(self.viewport.center_dd.im);
-        let center_im = if center_im_raw.starts_with('-') {
-            center_im_raw
-        } else {
-            format!("+{center_im_raw}")
-        };
+        let center_im = format_dd_signed_trimmed(self.viewport.center_dd.im);
 
         s.push_str(&format!(
             "\n\n**Center:**\n{center_re}\n{center_im}i\
              \n\n**Zoom:** {:.2e}\
              \n\n**Iterations:** {}",
-            1.0 / self.viewport.scale,
+            self.viewport.scale.recip(),
             self.params.max_iterations,
         ));
         s
@@ -284,7 +283,7 @@ Julia C Explorer.";
         self.mode = mode;
         self.julia_c = julia_c;
         self.params = params;
-        self.viewport = viewport;
+        self.viewport = viewport.clone();
         self.display_color = display_color;
         self.aa_level = aa_level;
         self.current_aa = None;
@@ -300,66 +299,38 @@ Julia C Explorer.";
 }
 
 // ---------------------------------------------------------------------------
-// DoubleDouble / f64 formatting with trailing-zero trimming
+// DoubleDouble formatting with trailing-zero trimming
 // ---------------------------------------------------------------------------
 
-/// Format a DoubleDouble value as a decimal string with full precision,
-/// trimming trailing zeros after the decimal point.
+/// Format a DoubleDouble value with all its significant digits, trimming
+/// trailing zeros after the decimal point.
 fn format_dd_trimmed(dd: DoubleDouble) -> String {
-    let negative = dd.is_negative();
-    let mut val = dd.abs();
-
-    let int_part = val.hi.trunc();
-    val -= DoubleDouble::from(int_part);
-    if val.is_negative() {
-        val = DoubleDouble::ZERO;
-    }
-
-    let mut result = if negative {
-        format!("-{}", int_part as u64)
-    } else {
-        format!("{}", int_part as u64)
-    };
-    result.push('.');
-
-    for _ in 0..30 {
-        val *= DoubleDouble::from(10.0);
-        let digit = val.hi.trunc().clamp(0.0, 9.0) as u8;
-        val -= DoubleDouble::from(digit as f64);
-        if val.is_negative() {
-            val = DoubleDouble::ZERO;
-        }
-        result.push((b'0' + digit) as char);
-    }
-
-    trim_trailing_zeros(&mut result);
-    result
-}
-
-/// Format an f64 with full precision, trimming trailing zeros.
-fn format_f64_trimmed(v: f64) -> String {
-    let mut s = format!("{v:.15}");
+    let mut s = dd.to_string_digits(DoubleDouble::DIGITS);
     trim_trailing_zeros(&mut s);
     s
 }
 
-/// Like `format_f64_trimmed` but with a leading `+` for non-negative values.
-fn format_f64_signed_trimmed(v: f64) -> String {
-    let mut s = if v >= 0.0 {
-        format!("+{v:.15}")
+/// Like `format_dd_trimmed` but with a leading `+` for non-negative values.
+fn format_dd_signed_trimmed(dd: DoubleDouble) -> String {
+    let s = format_dd_trimmed(dd);
+    if s.starts_with('-') {
+        s
     } else {
-        format!("{v:.15}")
-    };
-    trim_trailing_zeros(&mut s);
-    s
+        format!("+{s}")
+    }
 }
 
+/// Trim trailing fractional zeros, keeping any `e±k` exponent suffix.
 fn trim_trailing_zeros(s: &mut String) {
+    let exponent = s.find('e').map(|pos| s.split_off(pos));
     if s.contains('.') {
         let trimmed = s.trim_end_matches('0');
         let trimmed = trimmed.strip_suffix('.').unwrap_or(trimmed);
         s.truncate(trimmed.len());
     }
+    if let Some(exponent) = exponent {
+        s.push_str(&exponent);
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1,4 +1,5 @@
 use eframe::egui;
+use mandelbrust_core::{registry, DoubleDouble, ExtraInput};
 
 use crate::app::{ActiveDialog, BookmarkTab, FractalMode, MandelbRustApp};
 use crate::app_state::AppScreen;
@@ -12,7 +13,7 @@ impl MandelbRustApp {
             egui::MenuBar::new().ui(ui, |ui| {
                 self.menu_file(ui, ctx);
                 self.menu_edit(ui, ctx);
-                self.menu_fractal(ui, ctx);
+                self.menu_fractal(ui);
                 self.menu_view(ui);
                 self.menu_help(ui);
             });
@@ -51,10 +52,7 @@ impl MandelbRustApp {
                     self.show_bookmarks = !self.show_bookmarks;
                     if self.show_bookmarks {
                         self.bookmark_store.reload();
-                        self.bookmark_tab = match self.mode {
-                            FractalMode::Mandelbrot => BookmarkTab::Mandelbrot,
-                            FractalMode::Julia => BookmarkTab::Julia,
-                        };
+                        self.bookmark_tab = BookmarkTab::for_mode(self.mode);
                     }
                 } else {
                     self.screen = AppScreen::BookmarkBrowser;
@@ -88,31 +86,58 @@ impl MandelbRustApp {
                 ui.close();
                 self.reset_view();
             }
+            let minibrot_item = shortcut_item("Jump to Nearest Minibrot", "N");
+            let is_mandelbrot = self.mode == &registry::MANDELBROT;
+            if ui.add_enabled(is_mandelbrot, minibrot_item).clicked() {
+                ui.close();
+                self.jump_to_nearest_minibrot();
+            }
+            let misiurewicz_item = shortcut_item("Snap to Misiurewicz Point", "P");
+            if ui.add_enabled(is_mandelbrot, misiurewicz_item).clicked() {
+                ui.close();
+                self.snap_to_misiurewicz_point();
+            }
         });
     }
 
-    fn menu_fractal(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+    fn menu_fractal(&mut self, ui: &mut egui::Ui) {
         ui.menu_button("Fractal", |ui| {
-            let is_mandelbrot = self.mode == FractalMode::Mandelbrot;
-            if ui
-                .add_enabled(!is_mandelbrot, egui::Button::new("Switch to Mandelbrot"))
-                .clicked()
-            {
-                ui.close();
-                self.switch_to_mandelbrot();
-            }
-            if ui
-                .add_enabled(is_mandelbrot, egui::Button::new("Switch to Julia"))
-                .clicked()
-            {
-                ui.close();
-                self.switch_to_julia(ctx);
+            for mode in registry::FRACTALS {
+                let label = format!("Switch to {}", mode.name);
+                if ui
+                    .add_enabled(self.mode != mode, egui::Button::new(label))
+                    .clicked()
+                {
+                    ui.close();
+                    self.switch_to(mode);
+                }
             }
             ui.separator();
             if ui.button("Julia C Explorer").clicked() {
                 ui.close();
                 self.show_julia_c_explorer = !self.show_julia_c_explorer;
             }
+            if ui.button("Formula Editor").clicked() {
+                ui.close();
+                self.show_formula_editor = !self.show_formula_editor;
+            }
+            if ui.button("Density Renderer").clicked() {
+                ui.close();
+                self.show_density_settings = !self.show_density_settings;
+            }
+            if ui.button("External Rays").clicked() {
+                ui.close();
+                self.show_external_rays = !self.show_external_rays;
+            }
+            if ui.button("Component Labels").clicked() {
+                ui.close();
+                self.show_component_labels = !self.show_component_labels;
+            }
+            if ui.button("Orbit Inspector").clicked() {
+                ui.close();
+                self.show_orbit_inspector = !self.show_orbit_inspector;
+                self.orbit_inspector.picking = self.show_orbit_inspector;
+            }
         });
     }
 
@@ -205,34 +230,46 @@ impl MandelbRustApp {
     }
 
     fn format_coordinates_for_clipboard(&self) -> String {
-        let zoom = 1.0 / self.viewport.scale;
+        let zoom = self.viewport.scale.recip();
         let mut text = format!(
             "Mode: {}\nCenter: {:.15} {:+.15}i\nZoom: {zoom:.6e}\nIterations: {}",
-            self.mode.label(),
+            self.mode.name,
             self.viewport.center.re,
             self.viewport.center.im,
             self.params.max_iterations,
         );
-        if self.mode == FractalMode::Julia {
-            text.push_str(&format!(
-                "\nJulia c: {:.15} {:+.15}i",
-                self.julia_c.re, self.julia_c.im
-            ));
+        for spec in self.mode.params {
+            let value = spec.value(&self.param_values);
+            text.push_str(&format!("\n{}: {value}", spec.label));
+        }
+        match self.mode.extra_input {
+            ExtraInput::Roots => {
+                let roots: Vec<String> = self
+                    .roots
+                    .iter()
+                    .map(|r| format!("{} {:+}i", r.re, r.im))
+                    .collect();
+                text.push_str(&format!("\nRoots: {}", roots.join(", ")));
+            }
+            ExtraInput::Formula => text.push_str(&format!("\nFormula: {}", self.formula)),
+            ExtraInput::Sequence => text.push_str(&format!("\nSequence: {}", self.sequence)),
+            ExtraInput::None | ExtraInput::JuliaConstant => {}
+        }
+        if self.mode.is_julia() {
+            let re = self.julia_c.re.to_string_digits(DoubleDouble::DIGITS);
+            let im = self.julia_c.im.to_string_digits(DoubleDouble::DIGITS);
+            let sign = if self.julia_c.im.is_negative() {
+                ""
+            } else {
+                "+"
+            };
+            text.push_str(&format!("\nJulia c: {re} {sign}{im}i"));
         }
         text
     }
 
-    fn switch_to_mandelbrot(&mut self) {
-        self.mode = FractalMode::Mandelbrot;
-        self.push_history();
-        self.viewport = self.default_viewport();
-        self.bump_minimap_revision();
-        self.needs_render = true;
-    }
-
-    fn switch_to_julia(&mut self, ctx: &egui::Context) {
-        let _ = ctx;
-        self.mode = FractalMode::Julia;
+    fn switch_to(&mut self, mode: FractalMode) {
+        self.mode = mode;
         self.push_history();
         self.viewport = self.default_viewport();
         self.bump_minimap_revision();
@@ -3,19 +3,16 @@ use std::thread;
 
 use eframe::egui;
 
-use mandelbrust_core::{Complex, Viewport};
+use mandelbrust_core::{Complex, ExtrasOptions, FractalInputs, Viewport};
 use mandelbrust_render::RenderCancel;
 
-use crate::app::{FractalMode, MandelbRustApp};
+use crate::app::MandelbRustApp;
 use crate::render_bridge::{render_for_mode, RenderModeOptions};
 
 impl MandelbRustApp {
     pub(crate) fn minimap_viewport(&self) -> Viewport {
         let size = self.preferences.minimap_size.side_pixels();
-        match self.mode {
-            FractalMode::Mandelbrot => Viewport::default_mandelbrot(size, size),
-            FractalMode::Julia => Viewport::default_julia(size, size),
-        }
+        self.mode.default_viewport(size, size)
     }
 
     pub(crate) fn bump_minimap_revision(&mut self) {
@@ -49,21 +46,21 @@ impl MandelbRustApp {
         let tx = self.tx_minimap.clone();
         let revision = current_rev;
         let mode = self.mode;
-        let julia_c = self.julia_c;
+        let inputs = self.fractal_inputs();
         const MINIMAP_AA: u32 = 4;
         thread::spawn(move || {
             let cancel = Arc::new(RenderCancel::new());
             let result = render_for_mode(
                 mode,
                 params,
-                julia_c,
+                &inputs,
                 &viewport,
                 &cancel,
                 RenderModeOptions {
                     aa_level: MINIMAP_AA,
                     compute_extras: false,
                     allow_border_tracing: true,
-                    stripe_density: 1.0,
+                    extras: ExtrasOptions::default(),
                 },
             );
             let _ = tx.send((result, revision));
@@ -97,96 +94,100 @@ impl MandelbRustApp {
 
     pub(crate) fn j_preview_viewport(&self) -> Viewport {
         let size = self.preferences.minimap_size.side_pixels();
-        match self.mode {
-            FractalMode::Mandelbrot => Viewport::default_julia(size, size),
-            FractalMode::Julia => Viewport::default_mandelbrot(size, size),
-        }
+        self.mode
+            .counterpart()
+            .unwrap_or(self.mode)
+            .default_viewport(size, size)
     }
 
     pub(crate) fn request_j_preview_if_needed(&mut self, ctx: &egui::Context) {
+        let Some(preview_mode) = self.mode.counterpart() else {
+            return;
+        };
         if !self.preferences.show_j_preview {
             return;
         }
         const J_PREVIEW_AA: u32 = 4;
         let size = self.preferences.minimap_size.side_pixels();
 
-        match self.mode {
-            FractalMode::Mandelbrot => {
-                let Some(cursor_c) = self.cursor_complex else {
-                    return;
-                };
-                if self.j_preview_loading {
-                    return;
-                }
-                if self.last_j_preview_cursor == Some(cursor_c) {
-                    return;
-                }
-                self.last_j_preview_cursor = Some(cursor_c);
-                self.j_preview_loading = true;
-                self.j_preview_revision = self.j_preview_revision.wrapping_add(1);
-                let revision = self.j_preview_revision;
-                let params = self
-                    .params
-                    .with_max_iterations(self.preferences.julia_preview_iterations);
-                let viewport = Viewport::default_julia(size, size);
-                let tx = self.tx_jpreview.clone();
-                let cancel = self.j_preview_cancel.clone();
-                thread::spawn(move || {
-                    let result = render_for_mode(
-                        FractalMode::Julia,
-                        params,
-                        cursor_c,
-                        &viewport,
-                        &cancel,
-                        RenderModeOptions {
-                            aa_level: J_PREVIEW_AA,
-                            compute_extras: false,
-                            allow_border_tracing: true,
-                            stripe_density: 1.0,
-                        },
-                    );
-                    let _ = tx.send((result, revision));
-                });
+        if !self.mode.is_julia() {
+            let Some(cursor_c) = self.cursor_complex else {
+                return;
+            };
+            if self.j_preview_loading {
+                return;
             }
-            FractalMode::Julia => {
-                if self.j_preview_loading {
-                    return;
-                }
-                let current_rev = self.j_preview_revision;
-                let texture_rev = self
-                    .j_preview_texture
-                    .as_ref()
-                    .map(|(_, r)| *r)
-                    .unwrap_or(current_rev.wrapping_add(1));
-                if texture_rev == current_rev {
-                    return;
-                }
-                self.j_preview_loading = true;
-                let params = self
-                    .params
-                    .with_max_iterations(self.preferences.minimap_iterations);
-                let viewport = Viewport::default_mandelbrot(size, size);
-                let tx = self.tx_jpreview.clone();
-                let revision = current_rev;
-                let julia_c = self.julia_c;
-                let cancel = self.j_preview_cancel.clone();
-                thread::spawn(move || {
-                    let result = render_for_mode(
-                        FractalMode::Mandelbrot,
-                        params,
-                        julia_c,
-                        &viewport,
-                        &cancel,
-                        RenderModeOptions {
-                            aa_level: J_PREVIEW_AA,
-                            compute_extras: false,
-                            allow_border_tracing: true,
-                            stripe_density: 1.0,
-                        },
-                    );
-                    let _ = tx.send((result, revision));
-                });
+            if self.last_j_preview_cursor == Some(cursor_c) {
+                return;
             }
+            self.last_j_preview_cursor = Some(cursor_c);
+            self.j_preview_loading = true;
+            self.j_preview_revision = self.j_preview_revision.wrapping_add(1);
+            let revision = self.j_preview_revision;
+            let params = self
+                .params
+                .with_max_iterations(self.preferences.julia_preview_iterations);
+            let viewport = preview_mode.default_viewport(size, size);
+            let tx = self.tx_jpreview.clone();
+            let cancel = self.j_preview_cancel.clone();
+            let inputs = FractalInputs {
+                julia_c: cursor_c,
+                ..self.fractal_inputs()
+            };
+            thread::spawn(move || {
+                let result = render_for_mode(
+                    preview_mode,
+                    params,
+                    &inputs,
+                    &viewport,
+                    &cancel,
+                    RenderModeOptions {
+                        aa_level: J_PREVIEW_AA,
+                        compute_extras: false,
+                        allow_border_tracing: true,
+                        extras: ExtrasOptions::default(),
+                    },
+                );
+                let _ = tx.send((result, revision));
+            });
+        } else {
+            if self.j_preview_loading {
+                return;
+            }
+            let current_rev = self.j_preview_revision;
+            let texture_rev = self
+                .j_preview_texture
+                .as_ref()
+                .map(|(_, r)| *r)
+                .unwrap_or(current_rev.wrapping_add(1));
+            if texture_rev == current_rev {
+                return;
+            }
+            self.j_preview_loading = true;
+            let params = self
+                .params
+                .with_max_iterations(self.preferences.minimap_iterations);
+            let viewport = preview_mode.default_viewport(size, size);
+            let tx = self.tx_jpreview.clone();
+            let revision = current_rev;
+            let inputs = self.fractal_inputs();
+            let cancel = self.j_preview_cancel.clone();
+            thread::spawn(move || {
+                let result = render_for_mode(
+                    preview_mode,
+                    params,
+                    &inputs,
+                    &viewport,
+                    &cancel,
+                    RenderModeOptions {
+                        aa_level: J_PREVIEW_AA,
+                        compute_extras: false,
+                        allow_border_tracing: true,
+                        extras: ExtrasOptions::default(),
+                    },
+                );
+                let _ = tx.send((result, revision));
+            });
         }
         ctx.request_repaint();
     }
@@ -250,8 +251,9 @@ impl MandelbRustApp {
                         );
 
                         let to_minimap = |c: Complex| {
-                            let px = (c.re - vp.center.re) / vp.scale + (vp.width as f64) * 0.5;
-                            let py = (vp.height as f64) * 0.5 - (c.im - vp.center.im) / vp.scale;
+                            let scale = vp.scale.to_f64();
+                            let px = (c.re - vp.center.re) / scale + (vp.width as f64) * 0.5;
+                            let py = (vp.height as f64) * 0.5 - (c.im - vp.center.im) / scale;
                             let sx = image_rect.min.x
                                 + (px as f32 / vp.width as f32) * image_rect.width();
                             let sy = image_rect.min.y
@@ -306,8 +308,8 @@ impl MandelbRustApp {
 
                         let cx = self.viewport.center.re;
                         let cy = self.viewport.center.im;
-                        let w = self.viewport.width as f64 * self.viewport.scale;
-                        let h = self.viewport.height as f64 * self.viewport.scale;
+                        let w = self.viewport.complex_width();
+                        let h = self.viewport.complex_height();
                         let (min_x, min_y) = to_minimap(Complex::new(cx - w * 0.5, cy + h * 0.5));
                         let (max_x, max_y) = to_minimap(Complex::new(cx + w * 0.5, cy - h * 0.5));
                         let min_x = min_x.clamp(image_rect.min.x, image_rect.max.x);
@@ -1,13 +1,19 @@
+pub(crate) mod address;
 pub(crate) mod bookmark_browser;
 pub(crate) mod bookmarks;
 pub(crate) mod color_picker;
+pub(crate) mod components;
+pub(crate) mod density;
 pub(crate) mod export;
+pub(crate) mod external_rays;
+pub(crate) mod formula_editor;
 pub(crate) mod help;
 pub(crate) mod hud;
 pub(crate) mod julia_explorer;
 pub(crate) mod main_menu;
 pub(crate) mod menu_bar;
 pub(crate) mod minimap;
+pub(crate) mod orbit_inspector;
 pub(crate) mod palette_editor;
 pub(crate) mod settings;
 pub(crate) mod toolbar;
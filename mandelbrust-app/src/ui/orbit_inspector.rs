@@ -0,0 +1,371 @@
+//! Orbit inspector: the orbit `z₀ … zₙ` of a clicked point, drawn over the
+//! canvas and charted in a window with what the renderer measures there.
+//!
+//! Each pick iterates once more on a worker thread, in double-double when
+//! the view is deeper than `f64` resolves, so the drawn orbit matches the
+//! pixel that was clicked.
+
+use std::sync::mpsc;
+
+use eframe::egui;
+use tracing::error;
+
+use mandelbrust_core::{
+    registry, Complex, ComplexDD, ExtrasOptions, FractalInputs, FractalParams, IterationExtras,
+    IterationResult, Precision, Viewport,
+};
+use mandelbrust_render::smooth_iteration;
+
+use crate::app::{precision_for_view, FractalMode, MandelbRustApp};
+
+/// Longest orbit recorded; interior points stop here even when the
+/// iteration limit is higher.
+const MAX_ORBIT_POINTS: u32 = 65_536;
+
+const CHART_HEIGHT: f32 = 80.0;
+
+const ORBIT_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 220, 80);
+const STEP_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 110, 90);
+
+/// A picked point, its orbit and the renderer's measurements there.
+pub(crate) struct OrbitAnalysis {
+    pub mode: FractalMode,
+    pub point: ComplexDD,
+    /// Empty when the fractal does not iterate a single orbit.
+    pub orbit: Vec<ComplexDD>,
+    pub result: IterationResult,
+    pub extras: IterationExtras,
+    /// Smooth iteration value of an escaped point.
+    pub smooth: Option<f64>,
+    /// `|zₙ|` and `arg zₙ` for the charts.
+    modulus: Vec<f64>,
+    argument: Vec<f64>,
+}
+
+#[derive(Default)]
+pub(crate) struct OrbitInspectorState {
+    /// Canvas clicks pick points instead of loading Julia sets.
+    pub picking: bool,
+    /// The point being analysed, until its result arrives.
+    pub pending: Option<ComplexDD>,
+    pub analysis: Option<OrbitAnalysis>,
+    /// Iteration highlighted by the slider.
+    pub step: usize,
+    rx: Option<mpsc::Receiver<OrbitAnalysis>>,
+}
+
+/// Iterate `point` as the renderer would for the given view.
+fn analyse_orbit(
+    mode: FractalMode,
+    inputs: &FractalInputs,
+    params: FractalParams,
+    mut viewport: Viewport,
+    point: ComplexDD,
+) -> OrbitAnalysis {
+    let precision = match precision_for_view(mode, viewport.scale) {
+        Precision::F64 => Precision::F64,
+        _ => Precision::DoubleDouble,
+    };
+    // Centered on the point, the delta of a delta-coordinate fractal is 0.
+    viewport.set_center_dd(point);
+    let fractal = mode.build(inputs, params, precision, &viewport);
+    let arg = if fractal.uses_delta_coordinates() {
+        Complex::ZERO
+    } else {
+        point.to_complex()
+    };
+
+    let limit = params.max_iterations.min(MAX_ORBIT_POINTS);
+    let orbit = fractal.orbit(arg, limit).unwrap_or_default();
+    let extras_options = ExtrasOptions {
+        analyze_interior: true,
+        ..ExtrasOptions::default()
+    };
+    let (result, extras) = fractal.iterate_with_extras(arg, &extras_options);
+    let smooth = match result {
+        IterationResult::Escaped {
+            iterations,
+            norm_sq,
+        } => Some(smooth_iteration(iterations, norm_sq, fractal.degree())),
+        _ => None,
+    };
+    let (modulus, argument) = orbit
+        .iter()
+        .map(|z| {
+            let z = z.to_complex();
+            (z.norm(), z.im.atan2(z.re))
+        })
+        .unzip();
+    OrbitAnalysis {
+        mode,
+        point,
+        orbit,
+        result,
+        extras,
+        smooth,
+        modulus,
+        argument,
+    }
+}
+
+/// Plot `values` over the iterations, with a marker at `step`. Clicking or
+/// dragging on the chart moves the marker.
+fn chart(ui: &mut egui::Ui, values: &[f64], range: (f64, f64), step: &mut usize) {
+    let width = ui.available_width();
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(width, CHART_HEIGHT),
+        egui::Sense::click_and_drag(),
+    );
+    let rect = response.rect;
+    painter.rect_filled(rect, 2.0, egui::Color32::from_black_alpha(120));
+    if values.len() < 2 {
+        return;
+    }
+    let last = (values.len() - 1) as f32;
+    let span = (range.1 - range.0).max(f64::MIN_POSITIVE);
+    let to_screen = |i: usize, v: f64| {
+        let t = ((v - range.0) / span).clamp(0.0, 1.0) as f32;
+        egui::pos2(
+            rect.min.x + rect.width() * i as f32 / last,
+            rect.max.y - rect.height() * t,
+        )
+    };
+    let points: Vec<egui::Pos2> = values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.is_finite())
+        .map(|(i, &v)| to_screen(i, v))
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.0, ORBIT_COLOR),
+    ));
+
+    let x = rect.min.x + rect.width() * *step as f32 / last;
+    painter.line_segment(
+        [egui::pos2(x, rect.min.y), egui::pos2(x, rect.max.y)],
+        egui::Stroke::new(1.0, STEP_COLOR),
+    );
+    if let Some(pos) = response.interact_pointer_pos() {
+        let t = ((pos.x - rect.min.x) / rect.width()).clamp(0.0, 1.0);
+        *step = (t * last).round() as usize;
+    }
+}
+
+impl MandelbRustApp {
+    /// Analyse the orbit of `point` on a worker thread.
+    pub(crate) fn inspect_orbit(&mut self, point: ComplexDD) {
+        let mode = self.mode;
+        let inputs = self.fractal_inputs();
+        let params = self.effective_params();
+        let viewport = self.viewport.clone();
+        let (tx, rx) = mpsc::channel();
+        let ctx = self.egui_ctx.clone();
+        if let Err(e) = std::thread::Builder::new()
+            .name("orbit-worker".into())
+            .spawn(move || {
+                let _ = tx.send(analyse_orbit(mode, &inputs, params, viewport, point));
+                ctx.request_repaint();
+            })
+        {
+            error!("Failed to spawn orbit thread: {e}");
+            return;
+        }
+        self.orbit_inspector.rx = Some(rx);
+        self.orbit_inspector.pending = Some(point);
+    }
+
+    fn poll_orbit_result(&mut self) {
+        let state = &mut self.orbit_inspector;
+        let Some(rx) = state.rx.as_ref() else {
+            return;
+        };
+        if let Ok(analysis) = rx.try_recv() {
+            state.rx = None;
+            state.pending = None;
+            state.step = analysis.orbit.len().saturating_sub(1);
+            state.analysis = Some(analysis);
+        }
+    }
+
+    /// Draw the inspected orbit over the canvas, brighter up to the
+    /// highlighted iteration.
+    pub(crate) fn draw_orbit(&mut self, painter: &egui::Painter, rect: egui::Rect) {
+        self.poll_orbit_result();
+        let Some(analysis) = &self.orbit_inspector.analysis else {
+            return;
+        };
+        if analysis.mode != self.mode || analysis.orbit.is_empty() {
+            return;
+        }
+
+        let origin = rect.min + self.pan_offset;
+        let to_screen = |c: ComplexDD| {
+            let (x, y) = self.viewport.complex_dd_to_pixel(c);
+            egui::pos2(origin.x + x as f32, origin.y + y as f32)
+        };
+        let bounds = rect.expand(rect.width().max(rect.height()));
+        let step = self.orbit_inspector.step.min(analysis.orbit.len() - 1);
+        let dim = egui::Stroke::new(1.0, ORBIT_COLOR.gamma_multiply(0.35));
+        let bright = egui::Stroke::new(1.5, ORBIT_COLOR);
+        for (i, pair) in analysis.orbit.windows(2).enumerate() {
+            let (a, b) = (to_screen(pair[0]), to_screen(pair[1]));
+            if a.is_finite() && b.is_finite() && bounds.contains(a) && bounds.contains(b) {
+                painter.line_segment([a, b], if i < step { bright } else { dim });
+            }
+        }
+
+        let stroke = egui::Stroke::new(1.5, egui::Color32::WHITE);
+        let picked = to_screen(analysis.point);
+        if rect.contains(picked) {
+            painter.circle_stroke(picked, 5.0, stroke);
+        }
+        let current = to_screen(analysis.orbit[step]);
+        if rect.contains(current) {
+            painter.circle_filled(current, 3.5, STEP_COLOR);
+            painter.circle_stroke(current, 3.5, stroke);
+        }
+    }
+
+    pub(crate) fn show_orbit_inspector_window(&mut self, ctx: &egui::Context) {
+        if !self.show_orbit_inspector {
+            return;
+        }
+
+        let mut open = true;
+        let mut recompute = None;
+        egui::Window::new("Orbit Inspector")
+            .id(egui::Id::new("orbit_inspector_window"))
+            .open(&mut open)
+            .resizable(false)
+            .default_width(320.0)
+            .frame(
+                egui::Frame::NONE
+                    .fill(egui::Color32::from_black_alpha(230))
+                    .inner_margin(egui::Margin::same(10))
+                    .corner_radius(6.0),
+            )
+            .show(ctx, |ui| {
+                ui.style_mut().visuals.override_text_color =
+                    Some(egui::Color32::from_rgb(220, 220, 220));
+                let state = &mut self.orbit_inspector;
+                ui.checkbox(&mut state.picking, "Inspect on click")
+                    .on_hover_text("Click the canvas to follow the orbit of that point");
+
+                if let Some(point) = state.pending {
+                    let c = point.to_complex();
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(format!("Iterating {:.10} {:+.10}i\u{2026}", c.re, c.im));
+                    });
+                    return;
+                }
+                let Some(analysis) = &state.analysis else {
+                    ui.weak("Click a point to see its orbit.");
+                    return;
+                };
+
+                ui.separator();
+                let c = analysis.point.to_complex();
+                ui.horizontal(|ui| {
+                    ui.weak(format!("{:.10} {:+.10}i", c.re, c.im));
+                    if ui
+                        .small_button("Recompute")
+                        .on_hover_text("Iterate again with the current fractal and settings")
+                        .clicked()
+                    {
+                        recompute = Some(analysis.point);
+                    }
+                });
+                if analysis.mode != self.mode {
+                    ui.label("Picked in another fractal.");
+                    return;
+                }
+                if analysis.orbit.is_empty() {
+                    ui.label(if analysis.mode == &registry::LYAPUNOV {
+                        "This fractal does not follow a single orbit."
+                    } else {
+                        "No orbit was recorded for this point."
+                    });
+                    return;
+                }
+
+                egui::Grid::new("orbit_inspector_results")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Result:");
+                        ui.label(match analysis.result {
+                            IterationResult::Escaped { iterations, .. } => {
+                                format!("escaped at iteration {iterations}")
+                            }
+                            IterationResult::Interior => "did not escape".to_string(),
+                            IterationResult::Converged { iterations, .. } => {
+                                format!("converged at iteration {iterations}")
+                            }
+                            IterationResult::Measured { value } => format!("{value:.6}"),
+                        });
+                        ui.end_row();
+
+                        if let Some(smooth) = analysis.smooth {
+                            ui.label("Smooth iteration:");
+                            ui.label(format!("{smooth:.4}"));
+                            ui.end_row();
+                        }
+
+                        if analysis.extras.period > 0 {
+                            ui.label("Period:");
+                            ui.label(format!(
+                                "{} (|\u{03BB}| = {:.4})",
+                                analysis.extras.period, analysis.extras.multiplier
+                            ));
+                            ui.end_row();
+                        }
+
+                        let distance = match analysis.result {
+                            IterationResult::Interior => analysis.extras.interior_distance,
+                            _ => analysis.extras.distance,
+                        };
+                        if distance.is_finite() && distance > 0.0 {
+                            ui.label("Distance estimate:");
+                            ui.label(format!("{distance:.4e}"));
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                let last = analysis.orbit.len() - 1;
+                state.step = state.step.min(last);
+                ui.add(egui::Slider::new(&mut state.step, 0..=last).text("Iteration"));
+                let z = analysis.orbit[state.step].to_complex();
+                ui.monospace(format!("z{} = {:.10} {:+.10}i", state.step, z.re, z.im));
+
+                let max_modulus = analysis
+                    .modulus
+                    .iter()
+                    .copied()
+                    .filter(|m| m.is_finite())
+                    .fold(0.0, f64::max);
+                ui.add_space(4.0);
+                ui.label(format!("|z\u{2099}|, up to {max_modulus:.4}"));
+                chart(ui, &analysis.modulus, (0.0, max_modulus), &mut state.step);
+                ui.label("arg z\u{2099}, from \u{2212}\u{03C0} to \u{03C0}");
+                chart(
+                    ui,
+                    &analysis.argument,
+                    (-std::f64::consts::PI, std::f64::consts::PI),
+                    &mut state.step,
+                );
+                if last as u32 == MAX_ORBIT_POINTS {
+                    ui.weak(format!("Showing the first {MAX_ORBIT_POINTS} iterations."));
+                }
+            });
+
+        if let Some(point) = recompute {
+            self.inspect_orbit(point);
+        }
+        if !open {
+            self.show_orbit_inspector = false;
+            self.orbit_inspector.picking = false;
+        }
+    }
+}
@@ -8,6 +8,7 @@
 use eframe::egui;
 
 use mandelbrust_core::palette_data::{ColorStop, PaletteDefinition, Rgb};
+use mandelbrust_core::ExtraInput;
 use mandelbrust_render::Palette;
 
 use super::color_picker::{show_color_picker, ColorPickerState};
@@ -154,6 +155,30 @@ impl MandelbRustApp {
             });
         }
 
+        if self.mode.extra_input == ExtraInput::Sequence {
+            ui.add_space(4.0);
+            ui.heading("Chaotic side");
+            let selected = self
+                .palettes
+                .get(self.display_color.chaos_palette_index)
+                .map_or("", |p| p.name.as_str());
+            let mut index = self.display_color.chaos_palette_index;
+            egui::ComboBox::from_id_salt("chaos_palette")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (i, pal) in self.palettes.iter().enumerate() {
+                        ui.selectable_value(&mut index, i, pal.name.as_str());
+                    }
+                })
+                .response
+                .on_hover_text("Gradient for positive Lyapunov exponents");
+            if index != self.display_color.chaos_palette_index {
+                self.display_color.chaos_palette_index = index;
+                changed = true;
+                self.pending_minimap_bump = true;
+            }
+        }
+
         changed
     }
 
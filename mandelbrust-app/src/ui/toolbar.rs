@@ -1,6 +1,9 @@
 use eframe::egui;
+use mandelbrust_core::{
+    registry, ComplexDD, DoubleDouble, ExtraInput, LyapunovSequence, TrapShape,
+};
 
-use crate::app::{ColorSettingsTab, FractalMode, MandelbRustApp, HUD_CORNER_RADIUS, HUD_MARGIN};
+use crate::app::{ColorSettingsTab, MandelbRustApp, HUD_CORNER_RADIUS, HUD_MARGIN};
 use crate::color_profiles;
 use crate::display_color::{
     ColoringMode as DisplayColoringMode, InteriorMode as DisplayInteriorMode,
@@ -254,6 +257,7 @@ impl MandelbRustApp {
                     .show(ctx, |ui| {
                         ui.style_mut().visuals.override_text_color =
                             Some(egui::Color32::from_rgb(220, 220, 220));
+                        let c = c.to_complex();
                         ui.label(format!("{:.10} {:+.10}i", c.re, c.im));
                     });
             }
@@ -274,59 +278,189 @@ impl MandelbRustApp {
 
                         ui.horizontal(|ui| {
                             ui.label("Fractal:");
-                            ui.selectable_value(
-                                &mut self.mode,
-                                FractalMode::Mandelbrot,
-                                "Mandelbrot",
-                            );
-                            if ui
-                                .selectable_label(self.mode == FractalMode::Julia, "Julia")
-                                .clicked()
-                            {
-                                self.show_julia_c_explorer = true;
-                            }
+                            egui::ComboBox::from_id_salt("hud_fractal_mode")
+                                .selected_text(self.mode.name)
+                                .show_ui(ui, |ui| {
+                                    for mode in registry::FRACTALS {
+                                        // Julia goes through the C explorer,
+                                        // which switches mode once c is picked.
+                                        if mode == &registry::JULIA {
+                                            if ui
+                                                .selectable_label(self.mode == mode, mode.name)
+                                                .clicked()
+                                            {
+                                                self.show_julia_c_explorer = true;
+                                            }
+                                        } else {
+                                            ui.selectable_value(&mut self.mode, mode, mode.name);
+                                        }
+                                    }
+                                });
                         });
                         mode_changed = self.mode != old_mode;
 
-                        if self.mode == FractalMode::Julia {
-                            const JULIA_C_RANGE: f64 = 2.0;
-                            let mut re = self.julia_c.re;
-                            let mut im = self.julia_c.im;
-                            let re_range = -JULIA_C_RANGE..=JULIA_C_RANGE;
-                            let im_range = -JULIA_C_RANGE..=JULIA_C_RANGE;
-                            const C_DECIMALS: usize = 10;
-
+                        for spec in self.mode.params {
+                            let mut value = spec.value(&self.param_values);
                             ui.horizontal(|ui| {
-                                ui.label("Re(c):");
+                                ui.label(format!("{} {}:", spec.label, spec.symbol));
                                 if ui
                                     .add(
-                                        egui::DragValue::new(&mut re)
-                                            .range(re_range)
-                                            .fixed_decimals(C_DECIMALS),
+                                        egui::DragValue::new(&mut value)
+                                            .range(spec.min..=spec.max)
+                                            .speed(spec.speed)
+                                            .max_decimals(3),
                                     )
+                                    .on_hover_text(spec.hint)
                                     .changed()
                                 {
-                                    self.julia_c.re = re;
+                                    self.param_values.insert(spec.key.to_string(), value);
                                     self.bump_minimap_revision();
                                     params_changed = true;
                                 }
                             });
+                        }
+
+                        if self.mode.extra_input == ExtraInput::JuliaConstant {
+                            const JULIA_C_RANGE: f64 = 2.0;
+                            const C_DECIMALS: usize = 10;
+                            if self.julia_c_drafted != self.julia_c {
+                                self.julia_c_draft = julia_c_text(self.julia_c);
+                                self.julia_c_drafted = self.julia_c;
+                            }
+
+                            for (i, label) in ["Re(c):", "Im(c):"].into_iter().enumerate() {
+                                let part = [self.julia_c.re, self.julia_c.im][i];
+                                let mut value = part.to_f64();
+                                let mut edited = None;
+                                ui.horizontal(|ui| {
+                                    ui.label(label);
+                                    // Dragging sets a plain f64; the low limb
+                                    // is kept until the value itself moves.
+                                    if ui
+                                        .add(
+                                            egui::DragValue::new(&mut value)
+                                                .range(-JULIA_C_RANGE..=JULIA_C_RANGE)
+                                                .fixed_decimals(C_DECIMALS),
+                                        )
+                                        .changed()
+                                        && value != part.hi
+                                    {
+                                        edited = Some(DoubleDouble::from(value));
+                                    }
+                                });
+                                let typed = ui
+                                    .add(
+                                        egui::TextEdit::singleline(&mut self.julia_c_draft[i])
+                                            .desired_width(f32::INFINITY)
+                                            .font(egui::TextStyle::Monospace),
+                                    )
+                                    .on_hover_text("Exact value, up to 32 significant digits")
+                                    .changed();
+                                if typed {
+                                    edited = self.julia_c_draft[i].trim().parse().ok();
+                                }
+                                if let Some(v) = edited.filter(|&v| v != part) {
+                                    if i == 0 {
+                                        self.julia_c.re = v;
+                                    } else {
+                                        self.julia_c.im = v;
+                                    }
+                                    if typed {
+                                        self.julia_c_drafted = self.julia_c;
+                                    }
+                                    self.bump_minimap_revision();
+                                    params_changed = true;
+                                }
+                            }
+                            ui.weak("Shift+Click to pick c");
+                        }
+
+                        if self.mode.extra_input == ExtraInput::Roots {
+                            const ROOT_RANGE: f64 = 4.0;
+                            const MAX_ROOTS: usize = 8;
+                            let can_remove = self.roots.len() > 2;
+                            let mut remove = None;
+                            let mut roots_changed = false;
+                            for (i, root) in self.roots.iter_mut().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("Root {}:", i + 1));
+                                    for part in [&mut root.re, &mut root.im] {
+                                        roots_changed |= ui
+                                            .add(
+                                                egui::DragValue::new(part)
+                                                    .range(-ROOT_RANGE..=ROOT_RANGE)
+                                                    .speed(0.005)
+                                                    .max_decimals(6),
+                                            )
+                                            .changed();
+                                    }
+                                    ui.label("i");
+                                    if ui
+                                        .add_enabled(
+                                            can_remove,
+                                            egui::Button::new("\u{2212}").small(),
+                                        )
+                                        .on_hover_text("Remove root")
+                                        .clicked()
+                                    {
+                                        remove = Some(i);
+                                    }
+                                });
+                            }
+                            if let Some(i) = remove {
+                                self.roots.remove(i);
+                                roots_changed = true;
+                            }
+                            if self.roots.len() < MAX_ROOTS && ui.small_button("Add root").clicked()
+                            {
+                                self.roots.push(mandelbrust_core::Complex::ZERO);
+                                roots_changed = true;
+                            }
+
+                            if roots_changed {
+                                self.bump_minimap_revision();
+                                params_changed = true;
+                            }
+                        }
+
+                        if self.mode.extra_input == ExtraInput::Formula {
+                            ui.horizontal(|ui| {
+                                ui.monospace(self.formula.formula.trim());
+                                if ui.small_button("Edit\u{2026}").clicked() {
+                                    self.show_formula_editor = true;
+                                }
+                            });
+                        }
+
+                        if self.mode.extra_input == ExtraInput::Sequence {
                             ui.horizontal(|ui| {
-                                ui.label("Im(c):");
+                                ui.label("Sequence:");
                                 if ui
                                     .add(
-                                        egui::DragValue::new(&mut im)
-                                            .range(im_range)
-                                            .fixed_decimals(C_DECIMALS),
+                                        egui::TextEdit::singleline(&mut self.sequence_draft)
+                                            .desired_width(120.0)
+                                            .font(egui::TextStyle::Monospace),
                                     )
+                                    .on_hover_text("Rates in order: A = Re, B = Im")
                                     .changed()
                                 {
-                                    self.julia_c.im = im;
-                                    self.bump_minimap_revision();
-                                    params_changed = true;
+                                    if let Ok(sequence) =
+                                        LyapunovSequence::parse(&self.sequence_draft)
+                                    {
+                                        if sequence != self.sequence {
+                                            self.sequence = sequence;
+                                            self.bump_minimap_revision();
+                                            params_changed = true;
+                                        }
+                                    }
                                 }
                             });
-                            ui.weak("Shift+Click to pick c");
+                            if let Err(e) = LyapunovSequence::parse(&self.sequence_draft) {
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(255, 110, 90),
+                                    e.to_string(),
+                                );
+                            }
                         }
 
                         ui.add_space(2.0);
@@ -357,16 +491,23 @@ impl MandelbRustApp {
                             params_changed = true;
                         }
 
-                        let mut escape_r = self.params.escape_radius as f32;
-                        let old_escape = escape_r;
-                        ui.add(
-                            egui::Slider::new(&mut escape_r, 2.0..=1000.0)
-                                .text("Esc R")
-                                .logarithmic(true),
-                        );
-                        if (escape_r - old_escape).abs() > 0.01 {
-                            self.params.set_escape_radius(escape_r as f64);
-                            params_changed = true;
+                        // A user formula carries its own bailout, and Lyapunov
+                        // orbits have none.
+                        if !matches!(
+                            self.mode.extra_input,
+                            ExtraInput::Formula | ExtraInput::Sequence
+                        ) {
+                            let mut escape_r = self.params.escape_radius as f32;
+                            let old_escape = escape_r;
+                            ui.add(
+                                egui::Slider::new(&mut escape_r, 2.0..=1000.0)
+                                    .text("Esc R")
+                                    .logarithmic(true),
+                            );
+                            if (escape_r - old_escape).abs() > 0.01 {
+                                self.params.set_escape_radius(escape_r as f64);
+                                params_changed = true;
+                            }
                         }
 
                         ui.checkbox(&mut self.adaptive_iterations, "Adaptive iterations");
@@ -378,6 +519,9 @@ impl MandelbRustApp {
             });
 
         if mode_changed {
+            if self.mode.extra_input == ExtraInput::Formula {
+                self.show_formula_editor = true;
+            }
             self.push_history();
             self.viewport = self.default_viewport();
             self.bump_minimap_revision();
@@ -555,6 +699,41 @@ impl MandelbRustApp {
                 "Distance",
                 "Color by estimated distance to the fractal boundary",
             ),
+            (
+                DisplayColoringMode::OrbitTrap,
+                "Orbit trap",
+                "Color by how closely each orbit approaches a trap shape",
+            ),
+            (
+                DisplayColoringMode::StripeAverage,
+                "Stripe average",
+                "Color escaped pixels by the average stripe angle of their orbit",
+            ),
+            (
+                DisplayColoringMode::TriangleInequality,
+                "Triangle inequality",
+                "Color escaped pixels by the triangle inequality average (TIA)",
+            ),
+            (
+                DisplayColoringMode::Curvature,
+                "Curvature",
+                "Color escaped pixels by how sharply their orbit turns on average",
+            ),
+            (
+                DisplayColoringMode::BinaryDecomposition,
+                "Binary decomposition",
+                "Split every escape band by the sign of the final z",
+            ),
+            (
+                DisplayColoringMode::AngleHue,
+                "Angle hue",
+                "Take the palette position from the angle of the final z",
+            ),
+            (
+                DisplayColoringMode::FieldLines,
+                "Field lines",
+                "Overlay lines that follow the external angles",
+            ),
         ] {
             if ui
                 .selectable_label(self.display_color.coloring_mode == mode, label)
@@ -565,15 +744,40 @@ impl MandelbRustApp {
             }
         }
         if self.display_color.coloring_mode != old_mode {
-            if self.display_color.coloring_mode == DisplayColoringMode::DistanceEstimation
-                && self.current_extras.is_none()
-            {
+            // Traps and averages are only measured while their mode is
+            // selected, so the current extras never hold them.
+            let needs_render = match self.display_color.coloring_mode {
+                DisplayColoringMode::Standard | DisplayColoringMode::Histogram => false,
+                DisplayColoringMode::DistanceEstimation
+                | DisplayColoringMode::BinaryDecomposition
+                | DisplayColoringMode::AngleHue
+                | DisplayColoringMode::FieldLines => self.current_extras.is_none(),
+                _ => true,
+            };
+            if needs_render {
                 *params_changed = true;
             } else {
                 *palette_changed = true;
             }
             self.bump_minimap_revision();
         }
+        if self.display_color.coloring_mode == DisplayColoringMode::OrbitTrap {
+            self.draw_orbit_trap_controls(ui, params_changed);
+        }
+        if self.display_color.coloring_mode.orbit_average().is_some() {
+            self.draw_orbit_average_controls(ui, params_changed);
+        }
+        if self.display_color.coloring_mode == DisplayColoringMode::FieldLines {
+            let mut lines = self.display_color.field_lines;
+            if ui
+                .add(egui::Slider::new(&mut lines, 2..=64).text("Lines per turn"))
+                .changed()
+            {
+                self.display_color.field_lines = lines;
+                *palette_changed = true;
+                self.bump_minimap_revision();
+            }
+        }
 
         ui.add_space(8.0);
 
@@ -623,6 +827,85 @@ impl MandelbRustApp {
         }
     }
 
+    fn draw_orbit_average_controls(&mut self, ui: &mut egui::Ui, params_changed: &mut bool) {
+        let mut changed = false;
+        if self.display_color.coloring_mode == DisplayColoringMode::StripeAverage {
+            let mut density = self.display_color.stripe_density as f32;
+            if ui
+                .add(
+                    egui::Slider::new(&mut density, 0.1..=20.0)
+                        .text("Stripe density")
+                        .logarithmic(true),
+                )
+                .changed()
+            {
+                self.display_color.stripe_density = density as f64;
+                changed = true;
+            }
+        }
+        changed |= ui
+            .checkbox(&mut self.display_color.smooth_average, "Smooth average")
+            .on_hover_text("Blend across the last iteration to remove banding")
+            .changed();
+        if changed {
+            *params_changed = true;
+            self.bump_minimap_revision();
+        }
+    }
+
+    fn draw_orbit_trap_controls(&mut self, ui: &mut egui::Ui, params_changed: &mut bool) {
+        let trap = &mut self.display_color.orbit_trap;
+        let before = *trap;
+        egui::Grid::new("orbit_trap_grid")
+            .num_columns(2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Trap:");
+                egui::ComboBox::from_id_salt("orbit_trap_shape")
+                    .selected_text(trap.shape.label())
+                    .show_ui(ui, |ui| {
+                        for shape in TrapShape::ALL {
+                            ui.selectable_value(&mut trap.shape, shape, shape.label());
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Center:");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut trap.center.re).speed(0.01));
+                    ui.add(egui::DragValue::new(&mut trap.center.im).speed(0.01));
+                    ui.label("i");
+                });
+                ui.end_row();
+
+                if trap.shape.has_size() {
+                    ui.label("Size:");
+                    ui.add(
+                        egui::DragValue::new(&mut trap.size)
+                            .speed(0.01)
+                            .range(0.0..=100.0),
+                    );
+                    ui.end_row();
+                }
+
+                if trap.shape.has_angle() {
+                    ui.label("Angle:");
+                    let mut degrees = trap.angle.to_degrees();
+                    if ui
+                        .add(egui::DragValue::new(&mut degrees).speed(1.0).suffix("°"))
+                        .changed()
+                    {
+                        trap.angle = degrees.to_radians();
+                    }
+                    ui.end_row();
+                }
+            });
+        if *trap != before {
+            *params_changed = true;
+            self.bump_minimap_revision();
+        }
+    }
+
     // ===========================================================================
     // Interior tab
     // ===========================================================================
@@ -646,6 +929,21 @@ impl MandelbRustApp {
                 "Stripe average",
                 "Color interior by orbit stripe patterns (needs extras)",
             ),
+            (
+                DisplayInteriorMode::Period,
+                "Period",
+                "One hue per period of the attracting cycle (Mandelbrot)",
+            ),
+            (
+                DisplayInteriorMode::Multiplier,
+                "Multiplier",
+                "Color by the cycle multiplier |λ|, from nucleus to edge (Mandelbrot)",
+            ),
+            (
+                DisplayInteriorMode::InteriorDistance,
+                "Interior distance",
+                "Color by the estimated distance to the boundary (Mandelbrot)",
+            ),
         ] {
             if ui
                 .selectable_label(self.display_color.interior_mode == mode, label)
@@ -656,9 +954,11 @@ impl MandelbRustApp {
             }
         }
         if self.display_color.interior_mode != old_interior {
-            if self.display_color.interior_mode == DisplayInteriorMode::StripeAverage
-                && self.current_extras.is_none()
-            {
+            let new_interior = self.display_color.interior_mode;
+            // Cycles are only analyzed while a cycle mode is selected.
+            let needs_render = (new_interior.analyzes_cycles() && !old_interior.analyzes_cycles())
+                || (new_interior != DisplayInteriorMode::Black && self.current_extras.is_none());
+            if needs_render {
                 *params_changed = true;
             } else {
                 *palette_changed = true;
@@ -682,3 +982,8 @@ impl MandelbRustApp {
         }
     }
 }
+
+/// Full-precision text for the real and imaginary parts of a Julia constant.
+pub(crate) fn julia_c_text(c: ComplexDD) -> [String; 2] {
+    [c.re, c.im].map(|x| x.to_string_digits(DoubleDouble::DIGITS))
+}
@@ -0,0 +1,580 @@
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::double_double::DoubleDouble;
+use crate::fractal::{
+    is_cycle, record_orbit, ExtrasOptions, Fractal, FractalParams, IterationExtras,
+    IterationResult, Symmetry,
+};
+use crate::numeric::{ComplexNum, Real};
+
+/// Real 2×2 Jacobian `[[∂re/∂x, ∂re/∂y], [∂im/∂x, ∂im/∂y]]`.
+type Jacobian = [[f64; 2]; 2];
+
+const IDENTITY: Jacobian = [[1.0, 0.0], [0.0, 1.0]];
+
+/// Quadratic maps that fold the plane with absolute values or a conjugate
+/// before squaring. None of them is holomorphic, so derivatives are tracked
+/// as real Jacobians.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbsVariant {
+    /// `(|x| + i|y|)² + c`, iterated conjugated so the ship sails upright.
+    BurningShip,
+    /// The Mandelbar set, `z̄² + c`.
+    Tricorn,
+    /// `|Re z²| + i·Im z² + c`.
+    Celtic,
+    /// `|Re z²| + i·|Im z²| + c`, iterated conjugated like the Burning Ship.
+    Buffalo,
+}
+
+impl AbsVariant {
+    pub const ALL: [Self; 4] = [
+        Self::BurningShip,
+        Self::Tricorn,
+        Self::Celtic,
+        Self::Buffalo,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::BurningShip => "Burning Ship",
+            Self::Tricorn => "Tricorn",
+            Self::Celtic => "Celtic",
+            Self::Buffalo => "Buffalo",
+        }
+    }
+
+    /// Symmetries of the parameter plane.
+    pub const fn symmetry(self) -> Symmetry {
+        match self {
+            Self::BurningShip | Self::Buffalo => Symmetry {
+                flipped: true,
+                ..Symmetry::NONE
+            },
+            // conj(ωz)² = ω·z̄² for ω = e^(2πi/3)
+            Self::Tricorn => Symmetry {
+                conjugate: true,
+                rotational_order: 3,
+                flipped: false,
+            },
+            Self::Celtic => Symmetry::CONJUGATE,
+        }
+    }
+
+    /// One step of the map: `f(z) + c`.
+    #[inline]
+    pub fn step<C: ComplexNum>(self, z: C, c: C) -> C {
+        let (x, y) = (z.re(), z.im());
+        let re = x * x - y * y;
+        match self {
+            Self::BurningShip => C::new(re + c.re(), c.im() - x.abs() * 2.0 * y.abs()),
+            Self::Tricorn => C::new(re + c.re(), c.im() - x * 2.0 * y),
+            Self::Celtic => C::new(re.abs() + c.re(), x * 2.0 * y + c.im()),
+            Self::Buffalo => C::new(re.abs() + c.re(), c.im() - x.abs() * 2.0 * y.abs()),
+        }
+    }
+
+    /// Jacobian of `f` at `z`. `signum(0) = 1` picks one side of each fold.
+    #[inline]
+    fn jacobian(self, z: Complex) -> Jacobian {
+        let (x, y) = (z.re, z.im);
+        // Rows for `re = ±(x² − y²)` and for `im = −2|x||y|`.
+        let fold = (x * x - y * y).signum();
+        let square = [2.0 * x, -2.0 * y];
+        let folded = [2.0 * x * fold, -2.0 * y * fold];
+        let abs_im = [-2.0 * x.signum() * y.abs(), -2.0 * x.abs() * y.signum()];
+        match self {
+            Self::BurningShip => [square, abs_im],
+            Self::Tricorn => [square, [-2.0 * y, -2.0 * x]],
+            Self::Celtic => [folded, [2.0 * y, 2.0 * x]],
+            Self::Buffalo => [folded, abs_im],
+        }
+    }
+}
+
+#[inline]
+fn mat_mul(a: Jacobian, b: Jacobian) -> Jacobian {
+    [
+        [
+            a[0][0] * b[0][0] + a[0][1] * b[1][0],
+            a[0][0] * b[0][1] + a[0][1] * b[1][1],
+        ],
+        [
+            a[1][0] * b[0][0] + a[1][1] * b[1][0],
+            a[1][0] * b[0][1] + a[1][1] * b[1][1],
+        ],
+    ]
+}
+
+/// `|dz|` of a Jacobian: the RMS of its singular values, which is exact
+/// for (anti-)conformal maps.
+#[inline]
+fn jacobian_norm(j: Jacobian) -> f64 {
+    let sum_sq = j[0][0] * j[0][0] + j[0][1] * j[0][1] + j[1][0] * j[1][0] + j[1][1] * j[1][1];
+    (sum_sq * 0.5).sqrt()
+}
+
+/// Shared escape-time loop. `julia` selects the Jacobian recurrence:
+/// `J = Df·J` from `J₀ = I` for Julia sets, `J = Df·J + I` from `J₀ = 0`
+/// for the parameter plane.
+fn escape_time<C: ComplexNum>(
+    variant: AbsVariant,
+    params: &FractalParams,
+    z0: C,
+    c: C,
+    julia: bool,
+    stripe_density: Option<f64>,
+) -> (IterationResult, IterationExtras) {
+    let escape_radius_sq = C::Real::from_f64(params.escape_radius_sq());
+    let max_iter = params.max_iterations;
+
+    let mut z = z0;
+    let mut jac = if julia { IDENTITY } else { [[0.0; 2]; 2] };
+    let mut stripe_sum = 0.0f64;
+
+    // Brent's cycle detection state.
+    let mut old_z = z;
+    let mut period: u32 = 0;
+    let mut check: u32 = 3;
+
+    for n in 0..max_iter {
+        if stripe_density.is_some() {
+            jac = mat_mul(variant.jacobian(z.to_complex()), jac);
+            if !julia {
+                jac[0][0] += 1.0;
+                jac[1][1] += 1.0;
+            }
+        }
+
+        z = variant.step(z, c);
+
+        let norm_sq = z.norm_sq();
+        if let Some(density) = stripe_density {
+            let z_f64 = z.to_complex();
+            stripe_sum += 0.5 * (density * z_f64.im.atan2(z_f64.re)).sin() + 0.5;
+        }
+
+        if norm_sq > escape_radius_sq {
+            let norm_sq = norm_sq.to_f64();
+            let z_f64 = z.to_complex();
+            let z_norm = norm_sq.sqrt();
+            let dz_norm = jacobian_norm(jac);
+            let distance = if stripe_density.is_some() && dz_norm > 0.0 {
+                z_norm * z_norm.ln() / dz_norm
+            } else {
+                0.0
+            };
+            return (
+                IterationResult::Escaped {
+                    iterations: n,
+                    norm_sq,
+                },
+                IterationExtras {
+                    distance,
+                    stripe_avg: 0.0,
+                    final_arg: z_f64.im.atan2(z_f64.re),
+                    ..IterationExtras::default()
+                },
+            );
+        }
+
+        if n >= 32 && n & 3 == 0 {
+            if is_cycle(z, old_z) {
+                let stripe_avg = if n > 0 { stripe_sum / n as f64 } else { 0.0 };
+                return (
+                    IterationResult::Interior,
+                    IterationExtras {
+                        distance: 0.0,
+                        stripe_avg,
+                        ..IterationExtras::default()
+                    },
+                );
+            }
+            period += 1;
+            if period > check {
+                old_z = z;
+                period = 0;
+                check = check.saturating_mul(2);
+            }
+        }
+    }
+
+    let stripe_avg = if max_iter > 0 {
+        stripe_sum / max_iter as f64
+    } else {
+        0.0
+    };
+    (
+        IterationResult::Interior,
+        IterationExtras {
+            distance: 0.0,
+            stripe_avg,
+            ..IterationExtras::default()
+        },
+    )
+}
+
+/// The parameter plane of an [`AbsVariant`], starting from `z₀ = 0`.
+///
+/// Like [`Mandelbrot`](crate::Mandelbrot), extended precisions store the
+/// viewport `center` and receive deltas.
+#[derive(Debug, Clone)]
+pub struct AbsMandelbrot<T: Real = f64> {
+    params: FractalParams,
+    variant: AbsVariant,
+    center: T::Complex,
+}
+
+/// Double-double parameter plane (~31 digits).
+pub type AbsMandelbrotDD = AbsMandelbrot<DoubleDouble>;
+
+impl AbsMandelbrot {
+    pub fn new(variant: AbsVariant, params: FractalParams) -> Self {
+        Self::with_center(variant, params, Complex::ZERO)
+    }
+}
+
+impl<T: Real> AbsMandelbrot<T> {
+    /// Construct with a viewport center. Ignored for `f64`, whose points
+    /// are absolute.
+    pub fn with_center(variant: AbsVariant, params: FractalParams, center: T::Complex) -> Self {
+        Self {
+            params,
+            variant,
+            center,
+        }
+    }
+
+    pub fn variant(&self) -> AbsVariant {
+        self.variant
+    }
+
+    #[inline]
+    fn point(&self, point: Complex) -> T::Complex {
+        if T::USES_DELTA_COORDINATES {
+            self.center + T::Complex::from_complex(point)
+        } else {
+            T::Complex::from_complex(point)
+        }
+    }
+}
+
+impl<T: Real> Fractal for AbsMandelbrot<T> {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let c = self.point(point);
+        escape_time(self.variant, &self.params, T::Complex::ZERO, c, false, None).0
+    }
+
+    fn iterate_with_extras(
+        &self,
+        point: Complex,
+        opts: &ExtrasOptions,
+    ) -> (IterationResult, IterationExtras) {
+        let c = self.point(point);
+        escape_time(
+            self.variant,
+            &self.params,
+            T::Complex::ZERO,
+            c,
+            false,
+            Some(opts.stripe_density),
+        )
+    }
+
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let c = self.point(point);
+        let escape_radius_sq = self.params.escape_radius_sq();
+        Some(record_orbit(
+            T::Complex::ZERO,
+            limit,
+            escape_radius_sq,
+            |z| self.variant.step(z, c),
+        ))
+    }
+
+    fn params(&self) -> &FractalParams {
+        &self.params
+    }
+
+    fn uses_delta_coordinates(&self) -> bool {
+        T::USES_DELTA_COORDINATES
+    }
+
+    fn symmetry(&self) -> Symmetry {
+        self.variant.symmetry()
+    }
+}
+
+/// The Julia sets of an [`AbsVariant`]: fixed `c`, `z₀` the point on the
+/// plane.
+#[derive(Debug, Clone)]
+pub struct AbsJulia<T: Real = f64> {
+    params: FractalParams,
+    variant: AbsVariant,
+
+    /// The fixed constant `c` that defines this Julia set.
+    c: T::Complex,
+
+    center: T::Complex,
+}
+
+/// Double-double Julia set of an [`AbsVariant`] (~31 digits).
+pub type AbsJuliaDD = AbsJulia<DoubleDouble>;
+
+impl AbsJulia {
+    pub fn new(variant: AbsVariant, c: Complex, params: FractalParams) -> Self {
+        Self::with_center(variant, c, params, Complex::ZERO)
+    }
+}
+
+impl<T: Real> AbsJulia<T> {
+    /// Construct with a viewport center. Ignored for `f64`, whose points
+    /// are absolute.
+    pub fn with_center(
+        variant: AbsVariant,
+        c: T::Complex,
+        params: FractalParams,
+        center: T::Complex,
+    ) -> Self {
+        Self {
+            params,
+            variant,
+            c,
+            center,
+        }
+    }
+
+    pub fn variant(&self) -> AbsVariant {
+        self.variant
+    }
+
+    /// The constant `c` defining this Julia set.
+    pub fn c(&self) -> T::Complex {
+        self.c
+    }
+
+    #[inline]
+    fn start(&self, point: Complex) -> T::Complex {
+        if T::USES_DELTA_COORDINATES {
+            self.center + T::Complex::from_complex(point)
+        } else {
+            T::Complex::from_complex(point)
+        }
+    }
+}
+
+impl<T: Real> Fractal for AbsJulia<T> {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let z0 = self.start(point);
+        escape_time(self.variant, &self.params, z0, self.c, true, None).0
+    }
+
+    fn iterate_with_extras(
+        &self,
+        point: Complex,
+        opts: &ExtrasOptions,
+    ) -> (IterationResult, IterationExtras) {
+        let z0 = self.start(point);
+        escape_time(
+            self.variant,
+            &self.params,
+            z0,
+            self.c,
+            true,
+            Some(opts.stripe_density),
+        )
+    }
+
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let escape_radius_sq = self.params.escape_radius_sq();
+        let z0 = self.start(point);
+        Some(record_orbit(z0, limit, escape_radius_sq, |z| {
+            self.variant.step(z, self.c)
+        }))
+    }
+
+    fn params(&self) -> &FractalParams {
+        &self.params
+    }
+
+    fn uses_delta_coordinates(&self) -> bool {
+        T::USES_DELTA_COORDINATES
+    }
+
+    /// Every variant depends on `z` only through `z²`, `|x|` and `|y|`, all
+    /// unchanged by `z ↦ −z`.
+    fn symmetry(&self) -> Symmetry {
+        Symmetry {
+            rotational_order: 2,
+            ..Symmetry::NONE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mandelbrot::Mandelbrot;
+    use crate::test_util::grid;
+
+    fn conj(z: Complex) -> Complex {
+        Complex::new(z.re, -z.im)
+    }
+
+    #[test]
+    fn tricorn_step_squares_the_conjugate() {
+        let (z, c) = (Complex::new(0.3, -0.7), Complex::new(-0.1, 0.2));
+        let expected = conj(z) * conj(z) + c;
+        let got = AbsVariant::Tricorn.step(z, c);
+        assert!((got - expected).norm_sq() < 1e-30);
+    }
+
+    #[test]
+    fn flipped_variants_conjugate_the_textbook_formula() {
+        let textbook = |v: AbsVariant, z: Complex, c: Complex| {
+            let (x, y) = (z.re, z.im);
+            match v {
+                AbsVariant::BurningShip => Complex::new(x * x - y * y, 2.0 * x.abs() * y.abs()) + c,
+                _ => Complex::new((x * x - y * y).abs(), 2.0 * x.abs() * y.abs()) + c,
+            }
+        };
+        let (z, c) = (Complex::new(-0.4, 0.9), Complex::new(0.25, -0.6));
+        for v in [AbsVariant::BurningShip, AbsVariant::Buffalo] {
+            assert!(v.symmetry().flipped);
+            assert_eq!(v.step(conj(z), conj(c)), conj(textbook(v, z, c)));
+        }
+    }
+
+    #[test]
+    fn jacobian_matches_finite_differences() {
+        const H: f64 = 1e-7;
+        let z = Complex::new(0.63, -0.41);
+        for v in AbsVariant::ALL {
+            let j = v.jacobian(z);
+            let f = |z: Complex| v.step(z, Complex::ZERO);
+            let dx = (f(z + Complex::new(H, 0.0)) - f(z - Complex::new(H, 0.0))) * (0.5 / H);
+            let dy = (f(z + Complex::new(0.0, H)) - f(z - Complex::new(0.0, H))) * (0.5 / H);
+            for (got, want) in [
+                (j[0][0], dx.re),
+                (j[1][0], dx.im),
+                (j[0][1], dy.re),
+                (j[1][1], dy.im),
+            ] {
+                assert!((got - want).abs() < 1e-6, "{}: {got} vs {want}", v.name());
+            }
+        }
+    }
+
+    #[test]
+    fn real_axis_matches_mandelbrot() {
+        // With y = 0 every fold is the identity on x², so the orbit is the
+        // Mandelbrot orbit.
+        let params = FractalParams::default();
+        let m = Mandelbrot::new(params);
+        for v in AbsVariant::ALL {
+            let f = AbsMandelbrot::new(v, params);
+            for i in 0..=40 {
+                let c = Complex::new(-2.2 + i as f64 * 0.1, 0.0);
+                assert_eq!(f.iterate(c), m.iterate(c), "{} at {c}", v.name());
+            }
+        }
+    }
+
+    #[test]
+    fn conjugate_symmetric_variants_mirror() {
+        let params = FractalParams::default();
+        for v in AbsVariant::ALL
+            .into_iter()
+            .filter(|v| v.symmetry().conjugate)
+        {
+            let f = AbsMandelbrot::new(v, params);
+            for c in grid((-2.1, -1.6), (0.23, 0.27), (16, 12)) {
+                assert_eq!(f.iterate(c), f.iterate(conj(c)), "{} at {c}", v.name());
+            }
+        }
+    }
+
+    #[test]
+    fn burning_ship_is_not_mirror_symmetric() {
+        let f = AbsMandelbrot::new(AbsVariant::BurningShip, FractalParams::default());
+        let differing = grid((-2.1, -1.6), (0.23, 0.27), (16, 12))
+            .into_iter()
+            .filter(|&c| f.iterate(c).class() != f.iterate(conj(c)).class())
+            .count();
+        assert!(differing > 10);
+    }
+
+    #[test]
+    fn tricorn_has_threefold_symmetry() {
+        let f = AbsMandelbrot::new(AbsVariant::Tricorn, FractalParams::default());
+        let (sin, cos) = (2.0 * std::f64::consts::PI / 3.0).sin_cos();
+        let omega = Complex::new(cos, sin);
+        let points = grid((-2.1, -1.6), (0.23, 0.27), (16, 12));
+        let mismatches = points
+            .iter()
+            .filter(|&&c| f.iterate(c).class() != f.iterate(c * omega).class())
+            .count();
+        // Rounding the rotated point can flip pixels right at the boundary.
+        assert!(mismatches <= 3, "{mismatches} of {} differ", points.len());
+    }
+
+    #[test]
+    fn precisions_agree_on_grid() {
+        let params = FractalParams::default();
+        let c_julia = Complex::new(-0.6, 0.35);
+        for v in AbsVariant::ALL {
+            let pairs: [(Box<dyn Fractal>, Box<dyn Fractal>); 2] = [
+                (
+                    Box::new(AbsMandelbrot::new(v, params)),
+                    Box::new(AbsMandelbrotDD::with_center(v, params, ComplexDD::ZERO)),
+                ),
+                (
+                    Box::new(AbsJulia::new(v, c_julia, params)),
+                    Box::new(AbsJuliaDD::with_center(
+                        v,
+                        ComplexDD::from(c_julia),
+                        params,
+                        ComplexDD::ZERO,
+                    )),
+                ),
+            ];
+            for (f, f_dd) in &pairs {
+                assert!(f_dd.uses_delta_coordinates());
+                for p in grid((-2.1, -1.6), (0.23, 0.27), (16, 12)) {
+                    let (r, e) = f.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0));
+                    assert_eq!(r, f.iterate(p), "{}: extras path at {p}", v.name());
+                    let (r_dd, e_dd) =
+                        f_dd.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0));
+                    if matches!(r, IterationResult::Escaped { iterations, .. } if iterations < 10) {
+                        assert_eq!(r.class(), r_dd.class(), "{} at {p}", v.name());
+                        assert!((e.distance - e_dd.distance).abs() <= 1e-9 * e.distance);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn julia_half_turn_symmetry() {
+        for v in AbsVariant::ALL {
+            let j = AbsJulia::new(v, Complex::new(-0.4, 0.3), FractalParams::default());
+            assert_eq!(j.symmetry().rotational_order, 2);
+            for p in grid((-2.1, -1.6), (0.23, 0.27), (16, 12)) {
+                assert_eq!(j.iterate(p), j.iterate(-p), "{} at {p}", v.name());
+            }
+        }
+    }
+
+    #[test]
+    fn distance_estimate_is_positive_outside() {
+        for v in AbsVariant::ALL {
+            let f = AbsMandelbrot::new(v, FractalParams::default());
+            let (r, e) = f.iterate_with_extras(
+                Complex::new(0.9, 0.9),
+                &ExtrasOptions::with_stripe_density(1.0),
+            );
+            assert!(matches!(r, IterationResult::Escaped { .. }));
+            assert!(e.distance > 0.0 && e.distance.is_finite(), "{}", v.name());
+        }
+    }
+}
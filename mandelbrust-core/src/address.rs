@@ -0,0 +1,383 @@
+//! Symbolic descriptions of locations in the Mandelbrot set: kneading
+//! sequences and angled internal addresses, computed combinatorially from
+//! an external angle (after Schleicher, "Internal addresses in the
+//! Mandelbrot set and irreducibility of polynomials").
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::complex_dd::ComplexDD;
+use crate::external_ray::{self, ExternalAngle};
+use crate::minibrot::{self, Minibrot};
+use crate::misiurewicz::MisiurewiczPoint;
+
+/// One symbol of a kneading sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kneading {
+    Zero,
+    One,
+    /// The orbit lands on the boundary of the partition.
+    Star,
+}
+
+impl Kneading {
+    fn symbol(self) -> char {
+        match self {
+            Self::Zero => '0',
+            Self::One => '1',
+            Self::Star => '*',
+        }
+    }
+}
+
+/// Compare two binary expansions given digit by digit over their first
+/// `len` digits, enough for eventually periodic ones to be told apart.
+fn cmp_digits(a: impl Fn(usize) -> bool, b: impl Fn(usize) -> bool, len: usize) -> Ordering {
+    (0..len)
+        .map(|i| a(i).cmp(&b(i)))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Digits to compare between angles derived from `angle` by doubling and
+/// halving.
+fn comparison_len(angle: &ExternalAngle) -> usize {
+    angle.preperiod().len() + angle.period().len().max(1) + 1
+}
+
+/// The kneading sequence `ν₁ν₂ν₃…` of an angle `θ`: `νₖ` tells which side
+/// of the diameter from `θ/2` to `(θ+1)/2` the angle `2^(k−1)·θ` is on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KneadingSequence {
+    preperiod: Vec<Kneading>,
+    period: Vec<Kneading>,
+}
+
+impl KneadingSequence {
+    pub fn of(angle: &ExternalAngle) -> Self {
+        let len = comparison_len(angle);
+        let half = |first: bool| move |i: usize| if i == 0 { first } else { angle.bit(i - 1) };
+        let symbol = |k: usize| {
+            let doubled = |i: usize| angle.bit(k - 1 + i);
+            match (
+                cmp_digits(doubled, half(false), len),
+                cmp_digits(doubled, half(true), len),
+            ) {
+                (Ordering::Equal, _) | (_, Ordering::Equal) => Kneading::Star,
+                (Ordering::Greater, Ordering::Less) => Kneading::One,
+                _ => Kneading::Zero,
+            }
+        };
+        let m = angle.preperiod().len();
+        let p = angle.period().len().max(1);
+        Self {
+            preperiod: (1..=m).map(symbol).collect(),
+            period: (m + 1..=m + p).map(symbol).collect(),
+        }
+    }
+
+    /// `νₖ`, counting from 1.
+    pub fn symbol(&self, k: usize) -> Kneading {
+        match (k - 1).checked_sub(self.preperiod.len()) {
+            None => self.preperiod[k - 1],
+            Some(j) => self.period[j % self.period.len()],
+        }
+    }
+}
+
+impl fmt::Display for KneadingSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbols = |s: &[Kneading]| s.iter().map(|k| k.symbol()).collect::<String>();
+        write!(f, "{}({})", symbols(&self.preperiod), symbols(&self.period))
+    }
+}
+
+/// One entry `S_angle` of an angled internal address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressEntry {
+    pub period: u32,
+    /// Internal angle `p/q` of the sub-limb leading to the next entry;
+    /// `None` on the last entry.
+    pub angle: Option<(u32, u32)>,
+}
+
+/// Entries shown of the infinite address of a Misiurewicz point.
+const MAX_PREPERIODIC_ENTRIES: usize = 16;
+
+/// The angled internal address `1_{p₀/q₀} → S₁_{p₁/q₁} → … → period` of
+/// a hyperbolic component: the periods of the components on the way from
+/// the main cardioid, each with the limb the path takes off it. The path
+/// to a Misiurewicz point never ends; its address is cut short and shown
+/// ending in `→ …`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AngledInternalAddress {
+    entries: Vec<AddressEntry>,
+}
+
+impl AngledInternalAddress {
+    /// The address of the component whose root a periodic angle lands on,
+    /// or the first [`MAX_PREPERIODIC_ENTRIES`] entries of the address of
+    /// the Misiurewicz point a preperiodic angle lands on. `None` for
+    /// periodic angles not landing on a root.
+    pub fn of(angle: &ExternalAngle) -> Option<Self> {
+        let kneading = KneadingSequence::of(angle);
+        let preperiodic = !angle.preperiod().is_empty();
+        // An empty expansion is the angle 0 = 0.(0).
+        let period = minimal_period(angle.period()).max(1);
+        if !preperiodic && kneading.symbol(period) != Kneading::Star {
+            return None;
+        }
+        // ρ(r): the first k > r where ν disagrees with itself shifted by r.
+        // A preperiodic ν that agrees with its shift past both preperiods
+        // and over a whole period always does, and the address ends.
+        let horizon = kneading.preperiod.len() + kneading.period.len();
+        let rho = |r: usize| {
+            if preperiodic {
+                (r + 1..=r + horizon).find(|&k| kneading.symbol(k) != kneading.symbol(k - r))
+            } else {
+                (r + 1..period)
+                    .find(|&k| kneading.symbol(k) != kneading.symbol(k - r))
+                    .or(Some(period))
+            }
+        };
+
+        let mut periods = vec![1];
+        while let Some(&s) = periods.last() {
+            let done = if preperiodic {
+                periods.len() > MAX_PREPERIODIC_ENTRIES
+            } else {
+                s >= period
+            };
+            match rho(s).filter(|_| !done) {
+                Some(next) => periods.push(next),
+                None => break,
+            }
+        }
+        // The period after the last shown entry only gives its angle.
+        let shown = if preperiodic {
+            periods.len().min(MAX_PREPERIODIC_ENTRIES)
+        } else {
+            periods.len()
+        };
+
+        let len = comparison_len(angle);
+        let entries = periods[..shown]
+            .iter()
+            .enumerate()
+            .map(|(k, &s)| {
+                let angle = periods.get(k + 1).map(|&next| {
+                    // The denominator follows from the ρ-orbit of the
+                    // residue of the next period.
+                    let r = (next - 1) % s + 1;
+                    let mut orbit =
+                        std::iter::successors(Some(r), |&x| (x < s).then(|| rho(x)).flatten());
+                    let q = (next - r) / s + if orbit.any(|x| x == s) { 1 } else { 2 };
+                    // The numerator is the rotation number of the first q
+                    // images of θ under doubling s times.
+                    let image = |j: usize| move |i: usize| angle.bit(j * s + i);
+                    let rank = |j: usize| {
+                        (0..q)
+                            .filter(|&other| {
+                                cmp_digits(image(other), image(j), len) == Ordering::Less
+                            })
+                            .count()
+                    };
+                    let p = (rank(1) + q - rank(0)) % q;
+                    (p as u32, q as u32)
+                });
+                AddressEntry {
+                    period: s as u32,
+                    angle,
+                }
+            })
+            .collect();
+        Some(Self { entries })
+    }
+
+    pub fn entries(&self) -> &[AddressEntry] {
+        &self.entries
+    }
+
+    /// The period of the addressed component (of the last one shown for a
+    /// Misiurewicz point).
+    pub fn period(&self) -> u32 {
+        self.entries.last().map_or(1, |e| e.period)
+    }
+}
+
+impl fmt::Display for AngledInternalAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            write!(f, "{}", entry.period)?;
+            if let Some((p, q)) = entry.angle {
+                write!(f, "_{p}/{q} \u{2192} ")?;
+            }
+        }
+        if self.entries.last().is_some_and(|e| e.angle.is_some()) {
+            write!(f, "\u{2026}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Length of the shortest block that `bits` is a repetition of.
+fn minimal_period(bits: &[bool]) -> usize {
+    (1..bits.len())
+        .find(|&n| bits.len().is_multiple_of(n) && (n..bits.len()).all(|i| bits[i] == bits[i - n]))
+        .unwrap_or(bits.len())
+}
+
+/// A hyperbolic component with its external angles, angled internal
+/// address and kneading sequence.
+#[derive(Debug, Clone)]
+pub struct ComponentAddress {
+    pub component: Minibrot,
+    pub root: ComplexDD,
+    /// The two rays landing on the root, lower angle first (both `0` for
+    /// the main cardioid).
+    pub angles: (ExternalAngle, ExternalAngle),
+    pub address: AngledInternalAddress,
+    pub kneading: KneadingSequence,
+}
+
+impl ComponentAddress {
+    /// Describe `component`, measuring its root angles with orbits of up to
+    /// `max_iterations`. `None` if the root or its angles cannot be found.
+    pub fn of(component: Minibrot, max_iterations: u32) -> Option<Self> {
+        let root = minibrot::root(component.nucleus, component.period)?;
+        let angles =
+            external_ray::root_angles(root, component.period, component.size, max_iterations)?;
+        let address = AngledInternalAddress::of(&angles.0)?;
+        let kneading = KneadingSequence::of(&angles.0);
+        Some(Self {
+            component,
+            root,
+            angles,
+            address,
+            kneading,
+        })
+    }
+}
+
+/// A Misiurewicz point with the external angles of the rays landing on it,
+/// its angled internal address and kneading sequence.
+#[derive(Debug, Clone)]
+pub struct MisiurewiczAddress {
+    pub point: MisiurewiczPoint,
+    /// The rays landing on the point, in increasing order.
+    pub angles: Vec<ExternalAngle>,
+    pub address: AngledInternalAddress,
+    pub kneading: KneadingSequence,
+}
+
+impl MisiurewiczAddress {
+    /// Describe `point`, measuring its angles on circles of at most
+    /// `radius` with orbits of up to `max_iterations`. `None` if the angles
+    /// cannot be found.
+    pub fn of(point: MisiurewiczPoint, radius: f64, max_iterations: u32) -> Option<Self> {
+        let angles = external_ray::misiurewicz_angles(
+            point.c,
+            point.preperiod,
+            point.period,
+            radius,
+            max_iterations,
+        )?;
+        let address = AngledInternalAddress::of(&angles[0])?;
+        let kneading = KneadingSequence::of(&angles[0]);
+        Some(Self {
+            point,
+            angles,
+            address,
+            kneading,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complex::Complex;
+
+    fn address(angle: &str) -> String {
+        let angle = ExternalAngle::parse(angle).unwrap();
+        AngledInternalAddress::of(&angle).unwrap().to_string()
+    }
+
+    #[test]
+    fn kneading_sequences_of_periodic_angles() {
+        let kneading = |a: &str| KneadingSequence::of(&ExternalAngle::parse(a).unwrap());
+        assert_eq!(kneading("3/7").to_string(), "(10*)");
+        assert_eq!(kneading("1/5").to_string(), "(110*)");
+        assert_eq!(kneading("7/15").to_string(), "(100*)");
+        assert_eq!(kneading("0/1").to_string(), "(*)");
+        // Preperiodic: the ray 1/6 lands on the Misiurewicz point i.
+        assert_eq!(kneading("1/6").to_string(), "1(10)");
+    }
+
+    #[test]
+    fn known_angled_internal_addresses() {
+        assert_eq!(address("1/3"), "1_1/2 \u{2192} 2");
+        assert_eq!(address("1/7"), "1_1/3 \u{2192} 3");
+        assert_eq!(address("6/7"), "1_2/3 \u{2192} 3");
+        assert_eq!(address("3/7"), "1_1/2 \u{2192} 2_1/2 \u{2192} 3");
+        assert_eq!(address("1/15"), "1_1/4 \u{2192} 4");
+        assert_eq!(address("2/5"), "1_1/2 \u{2192} 2_1/2 \u{2192} 4");
+        assert_eq!(
+            address("7/15"),
+            "1_1/2 \u{2192} 2_1/2 \u{2192} 3_1/2 \u{2192} 4"
+        );
+        assert_eq!(address("1/5"), "1_1/3 \u{2192} 3_1/2 \u{2192} 4");
+        assert_eq!(address("0/1"), "1");
+    }
+
+    #[test]
+    fn misiurewicz_addresses_are_cut_short() {
+        let a = address("1/6");
+        assert!(a.starts_with("1_1/3 \u{2192} 3_1/2 \u{2192} 5"), "{a}");
+        assert!(a.ends_with("\u{2192} \u{2026}"), "{a}");
+        assert_eq!(address("5/12"), address("7/12"));
+    }
+
+    #[test]
+    fn describes_a_misiurewicz_point() {
+        let c = ComplexDD::from(Complex::new(-1.543_689_012_692_076, 0.0));
+        let point = MisiurewiczPoint::find(c, 1e-6, 1000).unwrap();
+        let described = MisiurewiczAddress::of(point, 1e-3, 10_000).unwrap();
+        let fractions: Vec<_> = described.angles.iter().map(|a| a.to_fraction()).collect();
+        assert_eq!(fractions, [Some((5, 12)), Some((7, 12))]);
+        assert_eq!(described.address.to_string(), address("5/12"));
+
+        let i = ComplexDD::from(Complex::new(0.0, 1.0));
+        let point = MisiurewiczPoint::find(i, 1e-6, 1000).unwrap();
+        let described = MisiurewiczAddress::of(point, 1e-3, 10_000).unwrap();
+        assert_eq!(described.angles, [ExternalAngle::parse("1/6").unwrap()]);
+        assert_eq!(described.kneading.to_string(), "1(10)");
+    }
+
+    #[test]
+    fn both_root_angles_give_the_same_address() {
+        let pairs = [
+            ("1/7", "2/7"),
+            ("3/15", "4/15"),
+            ("6/15", "9/15"),
+            ("11/31", "12/31"),
+            ("13/31", "18/31"),
+            ("14/31", "17/31"),
+            ("0.(001011)", "0.(001100)"),
+        ];
+        for (lo, hi) in pairs {
+            assert_eq!(address(lo), address(hi), "{lo} {hi}");
+        }
+    }
+
+    #[test]
+    fn describes_a_component_from_its_nucleus() {
+        let nucleus = ComplexDD::from(Complex::new(-0.122_561_166_876_654, 0.744_861_766_619_744));
+        let component = Minibrot::find(nucleus, 0.01, 100).unwrap();
+        let described = ComponentAddress::of(component, 10_000).unwrap();
+        assert_eq!(described.address.to_string(), "1_1/3 \u{2192} 3");
+        assert_eq!(described.angles.0.to_fraction(), Some((1, 7)));
+        assert_eq!(described.angles.1.to_fraction(), Some((2, 7)));
+        assert_eq!(described.kneading.to_string(), "(11*)");
+    }
+}
@@ -0,0 +1,924 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use crate::double_double::DoubleDouble;
+use crate::error::CoreError;
+use crate::float_exp::{ldexp, FloatExp};
+use crate::quad_double::QuadDouble;
+
+/// An arbitrary-precision binary floating-point number.
+///
+/// Stores `±mantissa · 2^exponent` where `mantissa` is an unsigned integer
+/// of at most `precision` bits (little-endian `u64` limbs). Every operation
+/// rounds its exact result to nearest-even at the larger of the operand
+/// precisions, so results are deterministic and independent of the platform.
+///
+/// The mantissa is kept odd (trailing zero bits move into the exponent), so
+/// two equal values always have the same representation.
+#[derive(Debug, Clone)]
+pub struct BigFloat {
+    negative: bool,
+    mantissa: Vec<u64>,
+    exponent: i64,
+    precision: u32,
+}
+
+/// Decimal exponent allowed in [`BigFloat::parse`] beyond the digits given
+/// and the decimal digits of the target precision. Scaling by `10^k` costs
+/// time quadratic in `k`, so larger exponents are rejected rather than
+/// computed.
+const EXP10_SLACK: u64 = 10_000;
+
+// ---------------------------------------------------------------------------
+// Unsigned limb arithmetic (little-endian `u64` limbs, no leading zeros)
+// ---------------------------------------------------------------------------
+
+fn trim(a: &mut Vec<u64>) {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+}
+
+fn bit_len(a: &[u64]) -> u64 {
+    match a.last() {
+        Some(&top) => (a.len() as u64 - 1) * 64 + (64 - top.leading_zeros() as u64),
+        None => 0,
+    }
+}
+
+fn bit(a: &[u64], i: u64) -> bool {
+    let limb = (i / 64) as usize;
+    limb < a.len() && (a[limb] >> (i % 64)) & 1 == 1
+}
+
+fn trailing_zeros(a: &[u64]) -> u64 {
+    let mut count = 0;
+    for &limb in a {
+        if limb == 0 {
+            count += 64;
+        } else {
+            return count + limb.trailing_zeros() as u64;
+        }
+    }
+    count
+}
+
+fn any_bits_below(a: &[u64], n: u64) -> bool {
+    let full = (n / 64) as usize;
+    if a[..full.min(a.len())].iter().any(|&l| l != 0) {
+        return true;
+    }
+    let rem = n % 64;
+    full < a.len() && rem > 0 && a[full] & ((1u64 << rem) - 1) != 0
+}
+
+fn shl(a: &[u64], n: u64) -> Vec<u64> {
+    if a.is_empty() {
+        return Vec::new();
+    }
+    let limbs = (n / 64) as usize;
+    let bits = n % 64;
+    let mut out = vec![0u64; limbs];
+    out.reserve(a.len() + 1);
+    if bits == 0 {
+        out.extend_from_slice(a);
+    } else {
+        let mut carry = 0u64;
+        for &l in a {
+            out.push((l << bits) | carry);
+            carry = l >> (64 - bits);
+        }
+        out.push(carry);
+    }
+    trim(&mut out);
+    out
+}
+
+fn shr(a: &[u64], n: u64) -> Vec<u64> {
+    let limbs = (n / 64) as usize;
+    if limbs >= a.len() {
+        return Vec::new();
+    }
+    let bits = n % 64;
+    let src = &a[limbs..];
+    let mut out = Vec::with_capacity(src.len());
+    if bits == 0 {
+        out.extend_from_slice(src);
+    } else {
+        for i in 0..src.len() {
+            let hi = src.get(i + 1).map_or(0, |&h| h << (64 - bits));
+            out.push((src[i] >> bits) | hi);
+        }
+    }
+    trim(&mut out);
+    out
+}
+
+fn cmp_mag(a: &[u64], b: &[u64]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(long.len() + 1);
+    let mut carry = false;
+    for (i, &l) in long.iter().enumerate() {
+        let (s, c1) = l.overflowing_add(short.get(i).copied().unwrap_or(0));
+        let (s, c2) = s.overflowing_add(carry as u64);
+        out.push(s);
+        carry = c1 || c2;
+    }
+    if carry {
+        out.push(1);
+    }
+    out
+}
+
+/// `a - b`, requires `a ≥ b`.
+fn sub_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut out = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, &l) in a.iter().enumerate() {
+        let (d, b1) = l.overflowing_sub(b.get(i).copied().unwrap_or(0));
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        out.push(d);
+        borrow = b1 || b2;
+    }
+    trim(&mut out);
+    out
+}
+
+fn mul_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut out = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u128 * y as u128 + out[i + j] as u128 + carry;
+            out[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        out[i + b.len()] = carry as u64;
+    }
+    trim(&mut out);
+    out
+}
+
+fn mul_small_add(a: &[u64], m: u64, add: u64) -> Vec<u64> {
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = add as u128;
+    for &l in a {
+        let t = l as u128 * m as u128 + carry;
+        out.push(t as u64);
+        carry = t >> 64;
+    }
+    out.push(carry as u64);
+    trim(&mut out);
+    out
+}
+
+fn div_small(a: &[u64], d: u64) -> (Vec<u64>, u64) {
+    let mut out = vec![0u64; a.len()];
+    let mut rem = 0u128;
+    for i in (0..a.len()).rev() {
+        let cur = (rem << 64) | a[i] as u128;
+        out[i] = (cur / d as u128) as u64;
+        rem = cur % d as u128;
+    }
+    trim(&mut out);
+    (out, rem as u64)
+}
+
+/// Binary long division: `(n / d, n % d)`. `d` must be non-zero.
+fn div_rem(n: &[u64], d: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    if cmp_mag(n, d) == Ordering::Less {
+        return (Vec::new(), n.to_vec());
+    }
+    let mut q = vec![0u64; n.len()];
+    let mut r: Vec<u64> = Vec::with_capacity(d.len() + 1);
+    for i in (0..bit_len(n)).rev() {
+        r = shl(&r, 1);
+        if bit(n, i) {
+            if r.is_empty() {
+                r.push(1);
+            } else {
+                r[0] |= 1;
+            }
+        }
+        if cmp_mag(&r, d) != Ordering::Less {
+            r = sub_mag(&r, d);
+            q[(i / 64) as usize] |= 1 << (i % 64);
+        }
+    }
+    trim(&mut q);
+    (q, r)
+}
+
+fn pow10(k: u64) -> Vec<u64> {
+    const TEN_19: u64 = 10_000_000_000_000_000_000;
+    let mut out = vec![1u64];
+    let mut rest = k;
+    while rest >= 19 {
+        out = mul_small_add(&out, TEN_19, 0);
+        rest -= 19;
+    }
+    mul_small_add(&out, 10u64.pow(rest as u32), 0)
+}
+
+fn to_decimal_digits(a: &[u64]) -> String {
+    if a.is_empty() {
+        return "0".to_string();
+    }
+    const TEN_19: u64 = 10_000_000_000_000_000_000;
+    let mut chunks = Vec::new();
+    let mut cur = a.to_vec();
+    while !cur.is_empty() {
+        let (q, r) = div_small(&cur, TEN_19);
+        chunks.push(r);
+        cur = q;
+    }
+    let mut s = chunks.pop().map(|c| c.to_string()).unwrap_or_default();
+    for c in chunks.iter().rev() {
+        s.push_str(&format!("{c:019}"));
+    }
+    s
+}
+
+/// Round `mantissa · 2^exponent` to `precision` bits (nearest, ties to even).
+/// `sticky` records non-zero bits already discarded below the mantissa.
+fn round(mantissa: Vec<u64>, exponent: i64, precision: u32, sticky: bool) -> (Vec<u64>, i64) {
+    let len = bit_len(&mantissa);
+    let (mut m, mut e) = if len > precision as u64 {
+        let shift = len - precision as u64;
+        let round_bit = bit(&mantissa, shift - 1);
+        let below = sticky || any_bits_below(&mantissa, shift - 1);
+        let mut m = shr(&mantissa, shift);
+        if round_bit && (below || bit(&m, 0)) {
+            m = add_mag(&m, &[1]);
+        }
+        (m, exponent + shift as i64)
+    } else {
+        (mantissa, exponent)
+    };
+    if m.is_empty() {
+        return (m, 0);
+    }
+    let tz = trailing_zeros(&m);
+    if tz > 0 {
+        m = shr(&m, tz);
+        e += tz as i64;
+    }
+    (m, e)
+}
+
+// ---------------------------------------------------------------------------
+// BigFloat
+// ---------------------------------------------------------------------------
+
+impl BigFloat {
+    /// Default working precision: 256 bits (~77 decimal digits).
+    pub const DEFAULT_PRECISION: u32 = 256;
+
+    /// Zero at the given precision.
+    pub fn zero(precision: u32) -> Self {
+        Self {
+            negative: false,
+            mantissa: Vec::new(),
+            exponent: 0,
+            precision: precision.max(1),
+        }
+    }
+
+    fn from_parts(negative: bool, mantissa: Vec<u64>, exponent: i64, precision: u32) -> Self {
+        Self::from_parts_sticky(negative, mantissa, exponent, precision, false)
+    }
+
+    fn from_parts_sticky(
+        negative: bool,
+        mantissa: Vec<u64>,
+        exponent: i64,
+        precision: u32,
+        sticky: bool,
+    ) -> Self {
+        let precision = precision.max(1);
+        let (mantissa, exponent) = round(mantissa, exponent, precision, sticky);
+        Self {
+            negative: negative && !mantissa.is_empty(),
+            mantissa,
+            exponent,
+            precision,
+        }
+    }
+
+    /// Exact conversion from `f64` (finite values only; non-finite input
+    /// yields zero). Precisions below 53 bits round the value.
+    pub fn from_f64(value: f64, precision: u32) -> Self {
+        if value == 0.0 || !value.is_finite() {
+            return Self::zero(precision);
+        }
+        let bits = value.to_bits();
+        let biased = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = bits & ((1u64 << 52) - 1);
+        let (mantissa, exponent) = if biased == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1u64 << 52), biased - 1075)
+        };
+        Self::from_parts(value < 0.0, vec![mantissa], exponent, precision)
+    }
+
+    /// Exact conversion from an extended-exponent float.
+    pub fn from_float_exp(value: FloatExp, precision: u32) -> Self {
+        Self::from_f64(value.mantissa(), precision).mul_pow2(value.exponent())
+    }
+
+    /// Sum of the two limbs, exact whenever `hi` and `lo` fit in `precision`
+    /// bits together (always the case for normalized double-doubles at
+    /// 107 bits or more, barring a gap of more than 53 zero bits).
+    pub fn from_dd(value: DoubleDouble, precision: u32) -> Self {
+        let wide = precision.max(2 * 53 + 1) + 64;
+        let sum = Self::from_f64(value.hi, wide) + Self::from_f64(value.lo, wide);
+        sum.with_precision(precision)
+    }
+
+    /// Sum of the four limbs of a quad-double, exact under the same
+    /// conditions as [`from_dd`](Self::from_dd).
+    pub fn from_qd(value: QuadDouble, precision: u32) -> Self {
+        let wide = precision.max(4 * 53 + 3) + 64;
+        let sum = value
+            .limbs
+            .iter()
+            .fold(Self::zero(wide), |acc, &x| acc + Self::from_f64(x, wide));
+        sum.with_precision(precision)
+    }
+
+    /// Round to `f64` (nearest-even). Values outside the `f64` range
+    /// saturate to `0` / `±∞`.
+    pub fn to_f64(&self) -> f64 {
+        if self.is_zero() {
+            return 0.0;
+        }
+        let (m, e) = round(self.mantissa.clone(), self.exponent, 53, false);
+        let v = ldexp(m[0] as f64, e);
+        if self.negative {
+            -v
+        } else {
+            v
+        }
+    }
+
+    /// Round to an extended-exponent float (nearest-even). Unlike
+    /// [`to_f64`](Self::to_f64), tiny and huge values keep their magnitude.
+    pub fn to_float_exp(&self) -> FloatExp {
+        if self.is_zero() {
+            return FloatExp::ZERO;
+        }
+        let (m, e) = round(self.mantissa.clone(), self.exponent, 53, false);
+        let m = m[0] as f64;
+        FloatExp::new(if self.negative { -m } else { m }, e)
+    }
+
+    /// Split into a normalized double-double (`hi = fl(x)`, `lo = fl(x - hi)`).
+    pub fn to_dd(&self) -> DoubleDouble {
+        let hi = self.to_f64();
+        let rest = self - &Self::from_f64(hi, self.precision.max(53));
+        DoubleDouble::new(hi, rest.to_f64())
+    }
+
+    /// Split into a normalized quad-double by peeling off one `f64` limb at
+    /// a time.
+    pub fn to_qd(&self) -> QuadDouble {
+        let mut rest = self.with_precision(self.precision.max(4 * 53));
+        let mut limbs = [0.0; 4];
+        for limb in &mut limbs {
+            *limb = rest.to_f64();
+            rest = &rest - &Self::from_f64(*limb, rest.precision);
+        }
+        QuadDouble::new(limbs[0], limbs[1], limbs[2], limbs[3])
+    }
+
+    /// Precision of this value in bits.
+    pub fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    /// Round (or widen) to a new precision.
+    pub fn with_precision(&self, precision: u32) -> Self {
+        Self::from_parts(
+            self.negative,
+            self.mantissa.clone(),
+            self.exponent,
+            precision,
+        )
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            negative: false,
+            ..self.clone()
+        }
+    }
+
+    /// `self²`.
+    pub fn square(&self) -> Self {
+        self * self
+    }
+
+    /// Multiply by `2^n` (exact).
+    pub fn mul_pow2(&self, n: i64) -> Self {
+        let mut out = self.clone();
+        if !out.is_zero() {
+            out.exponent += n;
+        }
+        out
+    }
+
+    /// Position of the most significant bit: `|x| ∈ [2^(k-1), 2^k)`.
+    fn top(&self) -> i64 {
+        self.exponent + bit_len(&self.mantissa) as i64
+    }
+
+    fn add_signed(&self, rhs: &Self, negate_rhs: bool) -> Self {
+        let precision = self.precision.max(rhs.precision);
+        let rhs_negative = rhs.negative != negate_rhs;
+        if rhs.is_zero() {
+            return self.with_precision(precision);
+        }
+        if self.is_zero() {
+            let mut out = rhs.with_precision(precision);
+            out.negative = rhs_negative;
+            return out;
+        }
+
+        // An operand entirely below the rounding position only contributes
+        // as a sticky bit; stand it in with a single bit just below that
+        // position so the aligned integers stay small.
+        let floor = self.top().max(rhs.top()) - precision as i64 - 3;
+        let clamp = |x: &Self| -> (Vec<u64>, i64) {
+            if x.top() < floor {
+                (vec![1], floor - 1)
+            } else {
+                (x.mantissa.clone(), x.exponent)
+            }
+        };
+        let (ma, ea) = clamp(self);
+        let (mb, eb) = clamp(rhs);
+        let e = ea.min(eb);
+        let ma = shl(&ma, (ea - e) as u64);
+        let mb = shl(&mb, (eb - e) as u64);
+
+        let (negative, mantissa) = if self.negative == rhs_negative {
+            (self.negative, add_mag(&ma, &mb))
+        } else {
+            match cmp_mag(&ma, &mb) {
+                Ordering::Less => (rhs_negative, sub_mag(&mb, &ma)),
+                _ => (self.negative, sub_mag(&ma, &mb)),
+            }
+        };
+        Self::from_parts(negative, mantissa, e, precision)
+    }
+
+    /// Parse a decimal string, correctly rounded to `precision` bits.
+    ///
+    /// Accepts an optional sign, digits with an optional decimal point, and
+    /// an optional `e`/`E` exponent (e.g. `"-1.25e-40"`). Exponents far
+    /// beyond the digits and the precision are rejected.
+    pub fn parse(s: &str, precision: u32) -> crate::Result<Self> {
+        let invalid = || CoreError::InvalidNumber {
+            input: s.to_string(),
+        };
+        let t = s.trim();
+        let (negative, t) = match t.as_bytes().first() {
+            Some(b'-') => (true, &t[1..]),
+            Some(b'+') => (false, &t[1..]),
+            _ => (false, t),
+        };
+        let (body, exp10) = match t.find(['e', 'E']) {
+            Some(pos) => {
+                let exp10: i64 = t[pos + 1..].parse().map_err(|_| invalid())?;
+                (&t[..pos], exp10)
+            }
+            None => (t, 0),
+        };
+        let (int_part, frac_part) = body.split_once('.').unwrap_or((body, ""));
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(invalid());
+        }
+        let digit_count = (int_part.len() + frac_part.len()) as u64;
+        let precision = precision.max(1);
+        let exp10 = exp10
+            .checked_sub(frac_part.len() as i64)
+            .filter(|e| {
+                // 0.31 > log10(2): the decimal digits of `precision` bits.
+                let limit = digit_count + u64::from(precision) * 31 / 100 + EXP10_SLACK;
+                e.unsigned_abs() <= limit
+            })
+            .ok_or_else(invalid)?;
+        let mut digits = Vec::new();
+        for ch in int_part.chars().chain(frac_part.chars()) {
+            let d = ch.to_digit(10).ok_or_else(invalid)?;
+            digits = mul_small_add(&digits, 10, d as u64);
+        }
+
+        if digits.is_empty() {
+            return Ok(Self::zero(precision));
+        }
+        if exp10 >= 0 {
+            let n = mul_mag(&digits, &pow10(exp10 as u64));
+            return Ok(Self::from_parts(negative, n, 0, precision));
+        }
+        // digits / 10^k: scale the numerator so the quotient carries at least
+        // `precision + 2` bits; the remainder feeds the sticky bit.
+        let den = pow10(exp10.unsigned_abs());
+        let shift = (precision as i64 + 2 + bit_len(&den) as i64 - bit_len(&digits) as i64).max(0);
+        let (q, r) = div_rem(&shl(&digits, shift as u64), &den);
+        Ok(Self::from_parts_sticky(
+            negative,
+            q,
+            -shift,
+            precision,
+            !r.is_empty(),
+        ))
+    }
+
+    /// Format with exactly `digits` significant decimal digits (rounded
+    /// half-up). Uses positional notation for moderate exponents and
+    /// scientific notation (`d.ddde±k`) otherwise.
+    pub fn to_string_digits(&self, digits: usize) -> String {
+        let digits = digits.max(1);
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        // Initial estimate of floor(log10 |x|), corrected below.
+        let mut k = ((self.top() - 1) as f64 * std::f64::consts::LOG10_2).floor() as i64;
+        let text = loop {
+            let p = digits as i64 - 1 - k;
+            let mut num = self.mantissa.clone();
+            let mut den = vec![1u64];
+            if p >= 0 {
+                num = mul_mag(&num, &pow10(p as u64));
+            } else {
+                den = pow10(p.unsigned_abs());
+            }
+            if self.exponent >= 0 {
+                num = shl(&num, self.exponent as u64);
+            } else {
+                den = shl(&den, self.exponent.unsigned_abs());
+            }
+            let (mut q, r) = div_rem(&num, &den);
+            if cmp_mag(&shl(&r, 1), &den) != Ordering::Less {
+                q = add_mag(&q, &[1]);
+            }
+            let text = to_decimal_digits(&q);
+            match text.len().cmp(&digits) {
+                Ordering::Greater => k += 1,
+                Ordering::Less => k -= 1,
+                Ordering::Equal => break text,
+            }
+        };
+
+        let mut out = String::with_capacity(digits + 8);
+        if self.negative {
+            out.push('-');
+        }
+        if (-7..21).contains(&k) {
+            if k >= 0 {
+                let int_len = k as usize + 1;
+                if int_len >= digits {
+                    out.push_str(&text);
+                    out.extend(std::iter::repeat_n('0', int_len - digits));
+                } else {
+                    out.push_str(&text[..int_len]);
+                    out.push('.');
+                    out.push_str(&text[int_len..]);
+                }
+            } else {
+                out.push_str("0.");
+                out.extend(std::iter::repeat_n('0', (-k - 1) as usize));
+                out.push_str(&text);
+            }
+        } else {
+            out.push_str(&text[..1]);
+            if digits > 1 {
+                out.push('.');
+                out.push_str(&text[1..]);
+            }
+            out.push_str(&format!("e{k}"));
+        }
+        out
+    }
+
+    /// Number of decimal digits that round-trip this value's precision.
+    pub fn significant_digits(&self) -> usize {
+        (self.precision as f64 * std::f64::consts::LOG10_2).ceil() as usize + 1
+    }
+}
+
+impl From<f64> for BigFloat {
+    fn from(value: f64) -> Self {
+        Self::from_f64(value, Self::DEFAULT_PRECISION)
+    }
+}
+
+impl From<DoubleDouble> for BigFloat {
+    fn from(value: DoubleDouble) -> Self {
+        Self::from_dd(value, Self::DEFAULT_PRECISION)
+    }
+}
+
+impl FromStr for BigFloat {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Self::DEFAULT_PRECISION)
+    }
+}
+
+// -- Arithmetic operators (by reference and by value) --
+
+impl Add for &BigFloat {
+    type Output = BigFloat;
+
+    fn add(self, rhs: Self) -> BigFloat {
+        self.add_signed(rhs, false)
+    }
+}
+
+impl Sub for &BigFloat {
+    type Output = BigFloat;
+
+    fn sub(self, rhs: Self) -> BigFloat {
+        self.add_signed(rhs, true)
+    }
+}
+
+impl Mul for &BigFloat {
+    type Output = BigFloat;
+
+    fn mul(self, rhs: Self) -> BigFloat {
+        BigFloat::from_parts(
+            self.negative != rhs.negative,
+            mul_mag(&self.mantissa, &rhs.mantissa),
+            self.exponent + rhs.exponent,
+            self.precision.max(rhs.precision),
+        )
+    }
+}
+
+impl Neg for &BigFloat {
+    type Output = BigFloat;
+
+    fn neg(self) -> BigFloat {
+        BigFloat {
+            negative: !self.negative && !self.is_zero(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Add for BigFloat {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        &self + &rhs
+    }
+}
+
+impl Sub for BigFloat {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        &self - &rhs
+    }
+}
+
+impl Mul for BigFloat {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        &self * &rhs
+    }
+}
+
+impl Neg for BigFloat {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        -&self
+    }
+}
+
+// -- Comparison --
+
+/// Compares values only; precision is not part of equality.
+impl PartialEq for BigFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.negative == other.negative
+            && self.exponent == other.exponent
+            && self.mantissa == other.mantissa
+    }
+}
+
+impl PartialOrd for BigFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let diff = self - other;
+        Some(if diff.is_zero() {
+            Ordering::Equal
+        } else if diff.negative {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        })
+    }
+}
+
+/// Prints all significant digits of the precision, trailing zeros trimmed.
+impl fmt::Display for BigFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.to_string_digits(f.precision().unwrap_or(self.significant_digits()));
+        let (body, exp) = match s.find('e') {
+            Some(pos) => s.split_at(pos),
+            None => (s.as_str(), ""),
+        };
+        let body = if body.contains('.') {
+            body.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            body
+        };
+        write!(f, "{body}{exp}")
+    }
+}
+
+/// Serialized as a decimal string carrying every significant digit, so a
+/// saved value reads back at (at least) its original precision.
+impl serde::Serialize for BigFloat {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BigFloat {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let digits = s
+            .bytes()
+            .take_while(|b| !matches!(b, b'e' | b'E'))
+            .filter(u8::is_ascii_digit)
+            .count();
+        let bits = (digits as f64 * std::f64::consts::LOG2_10).ceil() as u32;
+        let precision = bits
+            .div_ceil(64)
+            .saturating_mul(64)
+            .max(Self::DEFAULT_PRECISION);
+        Self::parse(&s, precision).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigFloat {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn f64_roundtrip() {
+        for &v in &[1.0, -0.75, 0.1, 1e-300, 5e-324, 1.7976931348623157e308, 0.0] {
+            assert_eq!(BigFloat::from(v).to_f64(), v);
+        }
+    }
+
+    #[test]
+    fn dd_roundtrip() {
+        let a = DoubleDouble::new(-0.743_643_887_037_151, 1.2e-17);
+        let b = BigFloat::from(a).to_dd();
+        assert_eq!(b.hi, a.hi);
+        assert_eq!(b.lo, a.lo);
+    }
+
+    #[test]
+    fn addition_and_cancellation() {
+        let a = big("1");
+        let tiny = big("1e-60");
+        let sum = &a + &tiny;
+        // 1e-60 sits ~200 bits below 1, so ~56 of its bits survive the sum.
+        let back = (&sum - &a).to_f64();
+        assert!((back / 1e-60 - 1.0).abs() < 1e-15);
+        assert_eq!((&sum - &sum).to_f64(), 0.0);
+        assert_eq!((big("0.25") + big("-1.5")).to_f64(), -1.25);
+    }
+
+    #[test]
+    fn multiplication_and_square() {
+        assert_eq!((big("1.5") * big("-2.5")).to_f64(), -3.75);
+        let x = big("1.000000000000000000000000000000000001");
+        let sq = x.square();
+        assert_eq!(
+            sq.to_string_digits(40),
+            "1.000000000000000000000000000000000002000"
+        );
+    }
+
+    #[test]
+    fn parse_is_correctly_rounded() {
+        // 0.1 parsed at 53 bits must match the f64 literal exactly.
+        assert_eq!(BigFloat::parse("0.1", 53).unwrap().to_f64(), 0.1);
+        assert_eq!(BigFloat::parse("-2.5e-3", 53).unwrap().to_f64(), -2.5e-3);
+        assert_eq!(
+            BigFloat::parse("123456789e10", 64).unwrap().to_f64(),
+            1.23456789e18
+        );
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert!("".parse::<BigFloat>().is_err());
+        assert!("1.2.3".parse::<BigFloat>().is_err());
+        assert!("1e".parse::<BigFloat>().is_err());
+        assert!("abc".parse::<BigFloat>().is_err());
+    }
+
+    #[test]
+    fn parse_rejects_huge_exponents_promptly() {
+        let start = std::time::Instant::now();
+        for s in ["1e999999999", "1e-999999999", "1e-9223372036854775808"] {
+            assert!(BigFloat::parse(s, 256).is_err(), "{s}");
+        }
+        assert!(start.elapsed() < std::time::Duration::from_millis(100));
+        // Exponents within reach still parse.
+        let tiny = BigFloat::parse("1e-400", 256).unwrap();
+        assert_eq!(tiny.to_string_digits(3), "1.00e-400");
+    }
+
+    #[test]
+    fn decimal_roundtrip_beyond_double_double() {
+        let s = "-0.74364388703715870475219150611477203719592305";
+        let x = BigFloat::parse(s, 200).unwrap();
+        assert_eq!(x.to_string_digits(44), s);
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(big("1234.5").to_string_digits(3), "1230");
+        assert_eq!(big("0.0001234").to_string_digits(2), "0.00012");
+        assert_eq!(big("9.999").to_string_digits(2), "10");
+        assert_eq!(big("2.5e-120").to_string_digits(3), "2.50e-120");
+        assert_eq!(format!("{}", big("-0.75")), "-0.75");
+        assert_eq!(format!("{:.4}", big("3.14159")), "3.142");
+    }
+
+    #[test]
+    fn precision_is_configurable() {
+        let third = |bits| {
+            let one = BigFloat::from_f64(1.0, bits);
+            let three =
+                BigFloat::parse("0.333333333333333333333333333333333333333333", bits).unwrap();
+            &one - &(&three * &BigFloat::from_f64(3.0, bits))
+        };
+        // More bits keep more of the residual 1e-42.
+        assert!(third(64).abs() > third(256).abs() || third(64).is_zero());
+        assert!((third(256).to_f64() - 1e-42).abs() < 1e-55);
+        assert_eq!(BigFloat::from_f64(1.0 / 3.0, 8).to_f64(), 0.333984375);
+    }
+
+    #[test]
+    fn float_exp_roundtrip_beyond_f64() {
+        let x = FloatExp::new(-1.25, -5000);
+        let b = BigFloat::from_float_exp(x, 64);
+        assert_eq!(b.to_f64(), 0.0);
+        assert_eq!(b.to_float_exp(), x);
+        assert_eq!(big("1e-1000").to_float_exp().log10().round(), -1000.0);
+    }
+
+    #[test]
+    fn serde_keeps_every_digit() {
+        let deep = &BigFloat::parse("-1.75", 4096).unwrap() + &big("3e-1000");
+        let json = serde_json::to_string(&deep).unwrap();
+        assert!(json.starts_with("\"-1.74999"));
+        let back: BigFloat = serde_json::from_str(&json).unwrap();
+        assert!(back.precision() >= deep.precision());
+        assert_eq!(back.with_precision(deep.precision()), deep);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(big("1e-100") > big("0"));
+        assert!(big("-3") < big("-2"));
+        assert_eq!(big("0.5"), BigFloat::from_f64(0.5, 64));
+    }
+}
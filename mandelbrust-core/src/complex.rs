@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 
 /// A complex number represented as two `f64` components.
 ///
@@ -31,6 +31,15 @@ impl Complex {
     pub fn norm(self) -> f64 {
         self.norm_sq().sqrt()
     }
+
+    /// Returns the complex conjugate `re − im·i`.
+    #[inline]
+    pub fn conj(self) -> Self {
+        Self {
+            re: self.re,
+            im: -self.im,
+        }
+    }
 }
 
 // -- Arithmetic operators --
@@ -119,6 +128,18 @@ impl Mul<f64> for Complex {
     }
 }
 
+/// Division by a nonzero complex number. Division by zero yields
+/// non-finite components, as with `f64`.
+impl Div for Complex {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        let inv = 1.0 / rhs.norm_sq();
+        (self * rhs.conj()) * inv
+    }
+}
+
 impl std::fmt::Display for Complex {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.im >= 0.0 {
@@ -182,6 +203,16 @@ mod tests {
         assert!(approx_eq(c.im, 12.0));
     }
 
+    #[test]
+    fn division_inverts_multiplication() {
+        let a = Complex::new(-5.0, 10.0);
+        let b = Complex::new(3.0, 4.0);
+        let c = a / b;
+        assert!(approx_eq(c.re, 1.0));
+        assert!(approx_eq(c.im, 2.0));
+        assert_eq!(a.conj(), Complex::new(-5.0, -10.0));
+    }
+
     #[test]
     fn negation() {
         let a = Complex::new(1.0, -2.0);
@@ -0,0 +1,240 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use crate::big_float::BigFloat;
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::complex_qd::ComplexQD;
+
+/// A complex number with arbitrary-precision [`BigFloat`] components.
+///
+/// Mirrors [`ComplexDD`] for positions deeper than ~31 digits. Values are
+/// heap-allocated, so this type is meant for per-frame work such as
+/// reference orbits and coordinate storage, not per-pixel iteration.
+///
+/// Serializes as `{"re": "<decimal>", "im": "<decimal>"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplexBig {
+    pub re: BigFloat,
+    pub im: BigFloat,
+}
+
+impl ComplexBig {
+    pub fn new(re: BigFloat, im: BigFloat) -> Self {
+        Self { re, im }
+    }
+
+    /// Zero at the given precision.
+    pub fn zero(precision: u32) -> Self {
+        Self {
+            re: BigFloat::zero(precision),
+            im: BigFloat::zero(precision),
+        }
+    }
+
+    /// Exact conversion from a double-double complex.
+    pub fn from_dd(c: ComplexDD, precision: u32) -> Self {
+        Self {
+            re: BigFloat::from_dd(c.re, precision),
+            im: BigFloat::from_dd(c.im, precision),
+        }
+    }
+
+    /// Exact conversion from a quad-double complex.
+    pub fn from_qd(c: ComplexQD, precision: u32) -> Self {
+        Self {
+            re: BigFloat::from_qd(c.re, precision),
+            im: BigFloat::from_qd(c.im, precision),
+        }
+    }
+
+    /// Round or widen both components to a new precision.
+    pub fn with_precision(&self, precision: u32) -> Self {
+        Self {
+            re: self.re.with_precision(precision),
+            im: self.im.with_precision(precision),
+        }
+    }
+
+    /// `self²`, using `(a + bi)² = a² − b² + 2ab·i`.
+    pub fn square(&self) -> Self {
+        let re = &self.re.square() - &self.im.square();
+        let im = (&self.re * &self.im).mul_pow2(1);
+        Self { re, im }
+    }
+
+    /// Returns `re² + im²` without taking the square root.
+    pub fn norm_sq(&self) -> BigFloat {
+        &self.re.square() + &self.im.square()
+    }
+
+    /// Round to `f64` complex.
+    pub fn to_complex(&self) -> Complex {
+        Complex::new(self.re.to_f64(), self.im.to_f64())
+    }
+
+    /// Round to double-double complex.
+    pub fn to_complex_dd(&self) -> ComplexDD {
+        ComplexDD::new(self.re.to_dd(), self.im.to_dd())
+    }
+
+    /// Round to quad-double complex.
+    pub fn to_complex_qd(&self) -> ComplexQD {
+        ComplexQD::new(self.re.to_qd(), self.im.to_qd())
+    }
+}
+
+impl From<Complex> for ComplexBig {
+    fn from(c: Complex) -> Self {
+        Self {
+            re: BigFloat::from(c.re),
+            im: BigFloat::from(c.im),
+        }
+    }
+}
+
+impl From<ComplexDD> for ComplexBig {
+    fn from(c: ComplexDD) -> Self {
+        Self::from_dd(c, BigFloat::DEFAULT_PRECISION)
+    }
+}
+
+// -- Arithmetic operators --
+
+impl Add for &ComplexBig {
+    type Output = ComplexBig;
+
+    fn add(self, rhs: Self) -> ComplexBig {
+        ComplexBig {
+            re: &self.re + &rhs.re,
+            im: &self.im + &rhs.im,
+        }
+    }
+}
+
+impl Sub for &ComplexBig {
+    type Output = ComplexBig;
+
+    fn sub(self, rhs: Self) -> ComplexBig {
+        ComplexBig {
+            re: &self.re - &rhs.re,
+            im: &self.im - &rhs.im,
+        }
+    }
+}
+
+impl Mul for &ComplexBig {
+    type Output = ComplexBig;
+
+    fn mul(self, rhs: Self) -> ComplexBig {
+        ComplexBig {
+            re: &(&self.re * &rhs.re) - &(&self.im * &rhs.im),
+            im: &(&self.re * &rhs.im) + &(&self.im * &rhs.re),
+        }
+    }
+}
+
+impl Neg for &ComplexBig {
+    type Output = ComplexBig;
+
+    fn neg(self) -> ComplexBig {
+        ComplexBig {
+            re: -&self.re,
+            im: -&self.im,
+        }
+    }
+}
+
+impl Add for ComplexBig {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        &self + &rhs
+    }
+}
+
+impl Sub for ComplexBig {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        &self - &rhs
+    }
+}
+
+impl Mul for ComplexBig {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        &self * &rhs
+    }
+}
+
+impl Neg for ComplexBig {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        -&self
+    }
+}
+
+impl std::fmt::Display for ComplexBig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} + {}·i", self.re, self.im)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::double_double::DoubleDouble;
+
+    fn cb(re: &str, im: &str) -> ComplexBig {
+        ComplexBig::new(re.parse().unwrap(), im.parse().unwrap())
+    }
+
+    #[test]
+    fn arithmetic_matches_f64() {
+        let a = cb("1.5", "-2");
+        let b = cb("0.25", "3");
+        assert_eq!((&a + &b).to_complex(), Complex::new(1.75, 1.0));
+        assert_eq!((&a - &b).to_complex(), Complex::new(1.25, -5.0));
+        assert_eq!((&a * &b).to_complex(), Complex::new(6.375, 4.0));
+        assert_eq!((-&a).to_complex(), Complex::new(-1.5, 2.0));
+    }
+
+    #[test]
+    fn square_matches_mul() {
+        let a = cb(
+            "-0.743643887037158704752191506114774",
+            "0.131825904205311970493132056385139",
+        );
+        assert_eq!(a.square(), &a * &a);
+        assert!((a.norm_sq().to_f64() - a.to_complex().norm_sq()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn complex_dd_roundtrip() {
+        let c = ComplexDD::new(
+            DoubleDouble::new(-0.75, 1e-17),
+            DoubleDouble::new(0.1, -3e-18),
+        );
+        let back = ComplexBig::from(c).to_complex_dd();
+        assert_eq!(back, c);
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let c = cb("-1.5e-1000", "0.25");
+        let json = serde_json::to_string(&c).unwrap();
+        assert!(json.starts_with(r#"{"re":"-1.49999"#) && json.ends_with(r#""im":"0.25"}"#));
+        let back: ComplexBig = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.with_precision(BigFloat::DEFAULT_PRECISION), c);
+    }
+
+    #[test]
+    fn complex_roundtrip() {
+        let c = Complex::new(0.3, -1e-200);
+        assert_eq!(ComplexBig::from(c).to_complex(), c);
+    }
+}
@@ -151,6 +151,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn to_complex_roundtrip() {
         let c = Complex::new(3.14, -2.71);
         let cdd = ComplexDD::from(c);
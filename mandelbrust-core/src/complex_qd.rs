@@ -0,0 +1,190 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::quad_double::QuadDouble;
+
+/// A complex number using quad-double components (~62 decimal digits per axis).
+///
+/// Mirrors [`ComplexDD`] one precision tier up. Used for viewport centers
+/// and the brute-force iteration paths past double-double depth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComplexQD {
+    pub re: QuadDouble,
+    pub im: QuadDouble,
+}
+
+impl ComplexQD {
+    pub const ZERO: Self = Self {
+        re: QuadDouble::ZERO,
+        im: QuadDouble::ZERO,
+    };
+
+    #[inline]
+    pub fn new(re: QuadDouble, im: QuadDouble) -> Self {
+        Self { re, im }
+    }
+
+    /// Returns `re² + im²` without taking the square root.
+    #[inline]
+    pub fn norm_sq(self) -> QuadDouble {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Downcast to `f64` complex.
+    #[inline]
+    pub fn to_complex(self) -> Complex {
+        Complex::new(self.re.to_f64(), self.im.to_f64())
+    }
+
+    /// Round to double-double complex.
+    #[inline]
+    pub fn to_complex_dd(self) -> ComplexDD {
+        ComplexDD::new(self.re.to_dd(), self.im.to_dd())
+    }
+}
+
+impl From<Complex> for ComplexQD {
+    #[inline]
+    fn from(c: Complex) -> Self {
+        Self {
+            re: QuadDouble::from(c.re),
+            im: QuadDouble::from(c.im),
+        }
+    }
+}
+
+impl From<ComplexDD> for ComplexQD {
+    #[inline]
+    fn from(c: ComplexDD) -> Self {
+        Self {
+            re: QuadDouble::from(c.re),
+            im: QuadDouble::from(c.im),
+        }
+    }
+}
+
+// -- Arithmetic operators --
+
+impl Add for ComplexQD {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            re: self.re + rhs.re,
+            im: self.im + rhs.im,
+        }
+    }
+}
+
+/// Offset by an `f64` delta, as used when reconstructing pixel coordinates.
+impl Add<Complex> for ComplexQD {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Complex) -> Self {
+        Self {
+            re: self.re + rhs.re,
+            im: self.im + rhs.im,
+        }
+    }
+}
+
+impl Sub for ComplexQD {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            re: self.re - rhs.re,
+            im: self.im - rhs.im,
+        }
+    }
+}
+
+impl Mul for ComplexQD {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            re: self.re * rhs.re - self.im * rhs.im,
+            im: self.re * rhs.im + self.im * rhs.re,
+        }
+    }
+}
+
+impl Neg for ComplexQD {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self {
+            re: -self.re,
+            im: -self.im,
+        }
+    }
+}
+
+/// Scalar multiplication: `ComplexQD * f64`.
+impl Mul<f64> for ComplexQD {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: f64) -> Self {
+        Self {
+            re: self.re * rhs,
+            im: self.im * rhs,
+        }
+    }
+}
+
+impl std::fmt::Display for ComplexQD {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} + {}·i", self.re, self.im)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::double_double::DoubleDouble;
+
+    fn cqd(re: f64, im: f64) -> ComplexQD {
+        ComplexQD::from(Complex::new(re, im))
+    }
+
+    #[test]
+    fn arithmetic_matches_f64() {
+        let a = cqd(1.5, -2.0);
+        let b = cqd(0.25, 3.0);
+        assert_eq!((a + b).to_complex(), Complex::new(1.75, 1.0));
+        assert_eq!((a - b).to_complex(), Complex::new(1.25, -5.0));
+        assert_eq!((a * b).to_complex(), Complex::new(6.375, 4.0));
+        assert_eq!((-a).to_complex(), Complex::new(-1.5, 2.0));
+        assert_eq!((a * 2.0).to_complex(), Complex::new(3.0, -4.0));
+        assert_eq!(a.norm_sq().to_f64(), 6.25);
+    }
+
+    #[test]
+    fn f64_offset_keeps_low_limbs() {
+        let c = ComplexQD::new(
+            QuadDouble::new(-0.75, 1e-17, 1e-34, 1e-51),
+            QuadDouble::new(0.1, 0.0, 0.0, 0.0),
+        );
+        let moved = c + Complex::new(1e-60, 0.0);
+        let back = moved - ComplexQD::from(Complex::new(1e-60, 0.0));
+        assert_eq!(back.re.limbs[..3], c.re.limbs[..3]);
+        assert!((moved.re - c.re).to_f64() > 0.0);
+    }
+
+    #[test]
+    fn complex_dd_roundtrip() {
+        let c = ComplexDD::new(
+            DoubleDouble::new(-0.75, 1e-17),
+            DoubleDouble::new(0.1, -3e-18),
+        );
+        assert_eq!(ComplexQD::from(c).to_complex_dd(), c);
+    }
+}
@@ -1,6 +1,11 @@
 use std::cmp::Ordering;
 use std::fmt;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::big_float::BigFloat;
+use crate::error::CoreError;
+use crate::float_exp::ldexp;
 
 /// A double-double floating-point number: ~31 significant decimal digits.
 ///
@@ -23,7 +28,7 @@ pub struct DoubleDouble {
 /// Knuth's TwoSum: error-free addition of two `f64` values.
 /// Returns `(s, e)` where `s + e = a + b` exactly.
 #[inline]
-fn two_sum(a: f64, b: f64) -> (f64, f64) {
+pub(crate) fn two_sum(a: f64, b: f64) -> (f64, f64) {
     let s = a + b;
     let v = s - a;
     let e = (a - (s - v)) + (b - v);
@@ -32,7 +37,7 @@ fn two_sum(a: f64, b: f64) -> (f64, f64) {
 
 /// Fast path for TwoSum when `|a| >= |b|`.
 #[inline]
-fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+pub(crate) fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
     let s = a + b;
     let e = b - (s - a);
     (s, e)
@@ -41,7 +46,7 @@ fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
 /// FMA-based TwoProd: error-free multiplication.
 /// Returns `(p, e)` where `p + e = a * b` exactly.
 #[inline]
-fn two_prod(a: f64, b: f64) -> (f64, f64) {
+pub(crate) fn two_prod(a: f64, b: f64) -> (f64, f64) {
     let p = a * b;
     let e = a.mul_add(b, -p);
     (p, e)
@@ -53,6 +58,36 @@ fn two_prod(a: f64, b: f64) -> (f64, f64) {
 
 impl DoubleDouble {
     pub const ZERO: Self = Self { hi: 0.0, lo: 0.0 };
+    pub const ONE: Self = Self { hi: 1.0, lo: 0.0 };
+
+    /// `ln 2` to full double-double precision.
+    pub const LN_2: Self = Self {
+        hi: std::f64::consts::LN_2,
+        lo: 2.319_046_813_846_299_6e-17,
+    };
+
+    /// `π` to full double-double precision.
+    pub const PI: Self = Self {
+        hi: std::f64::consts::PI,
+        lo: 1.224_646_799_147_353_2e-16,
+    };
+
+    /// `π/2` to full double-double precision.
+    pub const FRAC_PI_2: Self = Self {
+        hi: std::f64::consts::FRAC_PI_2,
+        lo: 6.123_233_995_736_766e-17,
+    };
+
+    /// Unit roundoff: `2^-104`, the relative spacing of double-double values.
+    pub const EPSILON: f64 = 4.930_380_657_631_324e-32;
+
+    /// Significant decimal digits a double-double resolves (`2^-106 ≈ 1.2e-32`).
+    pub const DIGITS: usize = 32;
+
+    const NAN: Self = Self {
+        hi: f64::NAN,
+        lo: f64::NAN,
+    };
 
     #[inline]
     pub fn new(hi: f64, lo: f64) -> Self {
@@ -85,6 +120,194 @@ impl DoubleDouble {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Elementary functions
+// ---------------------------------------------------------------------------
+
+impl DoubleDouble {
+    /// `1 / self`.
+    #[inline]
+    pub fn recip(self) -> Self {
+        Self::ONE / self
+    }
+
+    /// Square root via one Newton step on `1/√hi` (Karp's method).
+    /// Negative inputs yield NaN.
+    pub fn sqrt(self) -> Self {
+        if self.hi == 0.0 {
+            return Self::ZERO;
+        }
+        if self.is_negative() {
+            return Self::NAN;
+        }
+        let x = self.hi.sqrt().recip();
+        let ax = self.hi * x;
+        let (p, e) = two_prod(ax, ax);
+        let residual = (self - Self::new(p, e)).hi;
+        let (hi, lo) = two_sum(ax, residual * (x * 0.5));
+        Self { hi, lo }
+    }
+
+    /// `e^self`.
+    ///
+    /// Reduces the argument to `r = (x − k·ln 2) / 512`, sums the Taylor
+    /// series of `e^r − 1`, squares nine times and scales by `2^k`.
+    pub fn exp(self) -> Self {
+        /// log2 of the extra argument reduction factor.
+        const SQUARINGS: u32 = 9;
+
+        if self.hi.is_nan() {
+            return Self::NAN;
+        }
+        if self.hi > 709.79 {
+            return Self::from(f64::INFINITY);
+        }
+        if self.hi < -745.2 {
+            return Self::ZERO;
+        }
+        if self.hi == 0.0 {
+            return Self::ONE;
+        }
+
+        let k = (self.hi / Self::LN_2.hi).round();
+        let r = (self - Self::LN_2 * k) * (1.0 / (1u32 << SQUARINGS) as f64);
+
+        // e^r − 1, accumulated separately from the leading 1 so that the
+        // repeated squaring below does not cancel it away.
+        let mut s = r;
+        let mut term = r;
+        for n in 2..=20 {
+            term = term * r / n as f64;
+            s += term;
+            if term.hi.abs() <= Self::EPSILON * s.hi.abs() {
+                break;
+            }
+        }
+        for _ in 0..SQUARINGS {
+            s = s * 2.0 + s * s;
+        }
+        s += Self::ONE;
+
+        let k = k as i64;
+        Self::new(ldexp(s.hi, k), ldexp(s.lo, k))
+    }
+
+    /// Natural logarithm, by one Newton step `x + a·e^(−x) − 1` from the
+    /// `f64` estimate. Zero yields `−∞`, negative inputs NaN.
+    pub fn ln(self) -> Self {
+        if self.hi == 0.0 {
+            return Self::from(f64::NEG_INFINITY);
+        }
+        if self.is_negative() || self.hi.is_nan() {
+            return Self::NAN;
+        }
+        if self == Self::ONE {
+            return Self::ZERO;
+        }
+        let x = Self::from(self.hi.ln());
+        x + self * (-x).exp() - Self::ONE
+    }
+
+    /// `self^n` by binary exponentiation; negative powers take the
+    /// reciprocal at the end.
+    pub fn powi(self, n: i32) -> Self {
+        let mut result = Self::ONE;
+        let mut base = self;
+        let mut e = n.unsigned_abs();
+        while e > 0 {
+            if e & 1 == 1 {
+                result *= base;
+            }
+            e >>= 1;
+            if e > 0 {
+                base *= base;
+            }
+        }
+        if n < 0 {
+            result.recip()
+        } else {
+            result
+        }
+    }
+
+    /// `(sin self, cos self)`.
+    ///
+    /// Reduces the argument by the nearest multiple of `π/2`, divides it by
+    /// eight, sums both Taylor series and applies the double-angle formulas
+    /// three times. Accuracy degrades for `|self| ≫ 1e3`, where the
+    /// reduction by a double-double `π/2` loses digits.
+    pub fn sin_cos(self) -> (Self, Self) {
+        /// log2 of the extra argument reduction factor.
+        const HALVINGS: u32 = 3;
+
+        if !self.hi.is_finite() {
+            return (Self::NAN, Self::NAN);
+        }
+        if self.hi == 0.0 {
+            return (Self::ZERO, Self::ONE);
+        }
+
+        let k = (self.hi / Self::FRAC_PI_2.hi).round();
+        let r = (self - Self::FRAC_PI_2 * k) * (1.0 / (1u32 << HALVINGS) as f64);
+
+        // sin r = r − r³/3! + …, cos r = 1 − r²/2! + …
+        let r2 = r * r;
+        let mut sin = r;
+        let mut cos = Self::ONE;
+        let mut term = r;
+        for n in 1..=15 {
+            let m = 2 * n as u32;
+            term = -term * r2 / (m * (m + 1)) as f64;
+            sin += term;
+            if term.hi.abs() <= Self::EPSILON * sin.hi.abs() {
+                break;
+            }
+        }
+        let mut term = Self::ONE;
+        for n in 1..=15 {
+            let m = 2 * n as u32;
+            term = -term * r2 / ((m - 1) * m) as f64;
+            cos += term;
+            if term.hi.abs() <= Self::EPSILON {
+                break;
+            }
+        }
+        for _ in 0..HALVINGS {
+            (sin, cos) = (sin * cos * 2.0, (cos - sin) * (cos + sin));
+        }
+
+        match (k as i64).rem_euclid(4) {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        }
+    }
+
+    /// Four-quadrant arctangent of `y / x` (`y` is `self`), by one Newton
+    /// step from the `f64` estimate.
+    pub fn atan2(self, x: Self) -> Self {
+        let y = self;
+        if y.hi == 0.0 && x.hi == 0.0 {
+            return Self::ZERO;
+        }
+        let theta = Self::from(y.hi.atan2(x.hi));
+        let (sin, cos) = theta.sin_cos();
+        // tan(θ − θ₀) = (y·cos θ₀ − x·sin θ₀) / (x·cos θ₀ + y·sin θ₀); the
+        // correction is tiny, so its arctangent equals itself to full precision.
+        theta + (y * cos - x * sin) / (x * cos + y * sin)
+    }
+
+    /// Format with exactly `digits` correctly rounded significant decimal
+    /// digits (see [`BigFloat::to_string_digits`]).
+    pub fn to_string_digits(self, digits: usize) -> String {
+        if !self.hi.is_finite() {
+            return self.hi.to_string();
+        }
+        BigFloat::from_dd(self, BigFloat::DEFAULT_PRECISION).to_string_digits(digits)
+    }
+}
+
 impl From<f64> for DoubleDouble {
     #[inline]
     fn from(val: f64) -> Self {
@@ -174,6 +397,50 @@ impl Mul<f64> for DoubleDouble {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Arithmetic: DD / DD
+// ---------------------------------------------------------------------------
+
+impl Div for DoubleDouble {
+    type Output = Self;
+
+    /// Long division with three `f64` quotient digits, each correcting the
+    /// remainder of the previous one.
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        let q1 = self.hi / rhs.hi;
+        let r = self - rhs * q1;
+        let q2 = r.hi / rhs.hi;
+        let r = r - rhs * q2;
+        let q3 = r.hi / rhs.hi;
+        let (hi, lo) = quick_two_sum(q1, q2);
+        Self { hi, lo } + Self::from(q3)
+    }
+}
+
+impl DivAssign for DoubleDouble {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+/// Scalar division: `DoubleDouble / f64`.
+impl Div<f64> for DoubleDouble {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: f64) -> Self {
+        let q1 = self.hi / rhs;
+        let (p1, p2) = two_prod(q1, rhs);
+        let (s, e) = two_sum(self.hi, -p1);
+        let e = e + self.lo - p2;
+        let q2 = (s + e) / rhs;
+        let (hi, lo) = quick_two_sum(q1, q2);
+        Self { hi, lo }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Arithmetic: negation
 // ---------------------------------------------------------------------------
@@ -219,6 +486,21 @@ impl fmt::Display for DoubleDouble {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Parses a decimal string (e.g. `"-0.743643887037158704752191506114774"`
+/// or `"1.5e-20"`) to the nearest double-double, via an exact
+/// [`BigFloat`] intermediate.
+impl FromStr for DoubleDouble {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BigFloat::parse(s, BigFloat::DEFAULT_PRECISION).map(|x| x.to_dd())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -243,6 +525,7 @@ mod tests {
     // -- Construction --
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn from_f64() {
         let d = dd(3.14);
         assert_eq!(d.hi, 3.14);
@@ -257,6 +540,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn to_f64_roundtrip() {
         let d = dd(2.718281828);
         assert_eq!(d.to_f64(), 2.718281828);
@@ -431,6 +715,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn mul_one() {
         let a = DoubleDouble::new(3.14, 1e-17);
         let one = dd(1.0);
@@ -461,4 +746,189 @@ mod tests {
             "distributive property: {lhs} vs {rhs}"
         );
     }
+
+    // -- Division --
+
+    #[test]
+    fn division_simple() {
+        assert_eq!(dd(12.0) / dd(4.0), dd(3.0));
+        assert_eq!(dd(12.0) / 4.0, dd(3.0));
+        let mut a = dd(1.0);
+        a /= dd(8.0);
+        assert_eq!(a, dd(0.125));
+    }
+
+    #[test]
+    fn division_retains_precision() {
+        let third = dd(1.0) / dd(3.0);
+        assert!(approx_eq_dd(third * 3.0, dd(1.0), 1e-31));
+        let x = DoubleDouble::new(3.25, 1e-17);
+        let y = DoubleDouble::new(-0.7, 3e-18);
+        assert!(approx_eq_dd((x / y) * y, x, 1e-30));
+        assert!(approx_eq_dd(x / 7.0 * 7.0, x, 1e-30));
+    }
+
+    #[test]
+    fn recip_roundtrip() {
+        let x = DoubleDouble::new(1.7, -4e-17);
+        assert!(approx_eq_dd(x.recip().recip(), x, 1e-31));
+    }
+
+    // -- Elementary functions --
+
+    fn parsed(s: &str) -> DoubleDouble {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn sqrt_two() {
+        let r = dd(2.0).sqrt();
+        assert!(approx_eq_dd(
+            r,
+            parsed("1.41421356237309504880168872420969808"),
+            1e-31
+        ));
+        assert!(approx_eq_dd(r * r, dd(2.0), 2e-31));
+        assert_eq!(DoubleDouble::ZERO.sqrt(), DoubleDouble::ZERO);
+        assert!(dd(-1.0).sqrt().hi.is_nan());
+    }
+
+    #[test]
+    fn exp_and_ln() {
+        let e = dd(1.0).exp();
+        assert!(approx_eq_dd(
+            e,
+            parsed("2.71828182845904523536028747135266250"),
+            1e-30
+        ));
+        assert!(approx_eq_dd(dd(2.0).ln(), DoubleDouble::LN_2, 1e-31));
+        assert_eq!(DoubleDouble::ZERO.exp(), DoubleDouble::ONE);
+        assert_eq!(DoubleDouble::ONE.ln(), DoubleDouble::ZERO);
+        assert!(dd(-1.0).ln().hi.is_nan());
+        assert_eq!(DoubleDouble::ZERO.ln().hi, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn exp_ln_roundtrip() {
+        for x in [-30.5, -1e-3, 0.3, 5.0, 123.456] {
+            let x = DoubleDouble::new(x, x * 1e-18);
+            let back = x.exp().ln();
+            assert!(
+                approx_eq_dd(back, x, 1e-30 * x.hi.abs().max(1.0)),
+                "{x} -> {back}"
+            );
+        }
+    }
+
+    #[test]
+    fn exp_range_limits() {
+        assert_eq!(dd(800.0).exp().hi, f64::INFINITY);
+        assert_eq!(dd(-800.0).exp(), DoubleDouble::ZERO);
+    }
+
+    #[test]
+    fn powi_matches_repeated_multiplication() {
+        let x = DoubleDouble::new(1.1, 1e-17);
+        let mut expected = DoubleDouble::ONE;
+        for _ in 0..13 {
+            expected *= x;
+        }
+        assert!(approx_eq_dd(x.powi(13), expected, 1e-30));
+        assert!(approx_eq_dd(x.powi(-13) * expected, dd(1.0), 1e-30));
+        assert_eq!(x.powi(0), DoubleDouble::ONE);
+    }
+
+    #[test]
+    fn sin_cos_known_values() {
+        let (s, c) = dd(1.0).sin_cos();
+        assert!(approx_eq_dd(
+            s,
+            parsed("0.841470984807896506652502321630298999"),
+            1e-31
+        ));
+        assert!(approx_eq_dd(
+            c,
+            parsed("0.540302305868139717400936607442976603"),
+            1e-31
+        ));
+        let (s, c) = DoubleDouble::PI.sin_cos();
+        assert!(s.abs().hi < 1e-31);
+        assert!(approx_eq_dd(c, dd(-1.0), 1e-31));
+        assert_eq!(
+            DoubleDouble::ZERO.sin_cos(),
+            (DoubleDouble::ZERO, DoubleDouble::ONE)
+        );
+    }
+
+    #[test]
+    fn sin_cos_pythagorean_identity_in_every_quadrant() {
+        for x in [-7.3, -2.5, -0.4, 0.7, 2.2, 3.9, 5.1, 40.0] {
+            let x = DoubleDouble::new(x, x * 1e-18);
+            let (s, c) = x.sin_cos();
+            assert!(approx_eq_dd(s * s + c * c, dd(1.0), 1e-30), "x = {x}");
+            assert!((s.hi - x.hi.sin()).abs() < 1e-15, "x = {x}");
+            assert!((c.hi - x.hi.cos()).abs() < 1e-15, "x = {x}");
+        }
+    }
+
+    #[test]
+    fn atan2_inverts_sin_cos() {
+        for x in [-3.0, -1.7, -0.2, 0.5, 1.6, 3.1] {
+            let x = DoubleDouble::new(x, x * 1e-18);
+            let (s, c) = x.sin_cos();
+            let back = (s * 3.0).atan2(c * 3.0);
+            assert!(approx_eq_dd(back, x, 1e-30), "{x} -> {back}");
+        }
+        assert!(approx_eq_dd(
+            dd(1.0).atan2(dd(0.0)),
+            DoubleDouble::FRAC_PI_2,
+            1e-31
+        ));
+        assert_eq!(
+            DoubleDouble::ZERO.atan2(DoubleDouble::ZERO),
+            DoubleDouble::ZERO
+        );
+    }
+
+    // -- Decimal I/O --
+
+    #[test]
+    fn parse_keeps_digits_beyond_f64() {
+        let x = parsed("0.1");
+        assert_eq!(x.hi, 0.1);
+        // 0.1 − fl(0.1) ≈ −5.551115123125783e-18
+        assert!((x.lo + 5.551_115_123_125_783e-18).abs() < 1e-32);
+        let y = parsed("-0.743643887037158704752191506114774");
+        assert!(approx_eq_dd(
+            y,
+            DoubleDouble::new(-0.743_643_887_037_158_7, 0.0),
+            1e-16
+        ));
+        assert!(y.lo != 0.0);
+        assert!("0.1.2".parse::<DoubleDouble>().is_err());
+        assert!("".parse::<DoubleDouble>().is_err());
+    }
+
+    #[test]
+    fn format_significant_digits() {
+        let third = dd(1.0) / dd(3.0);
+        assert_eq!(
+            third.to_string_digits(DoubleDouble::DIGITS),
+            "0.33333333333333333333333333333333"
+        );
+        assert_eq!(dd(-2.5).to_string_digits(3), "-2.50");
+        assert_eq!(dd(1.5e-40).to_string_digits(2), "1.5e-40");
+        assert_eq!(dd(f64::INFINITY).to_string_digits(5), "inf");
+    }
+
+    #[test]
+    fn decimal_roundtrip() {
+        let text = "-0.7436438870371587047521915061147741";
+        let x = parsed(text);
+        let again = parsed(&x.to_string_digits(DoubleDouble::DIGITS));
+        assert!(approx_eq_dd(again, x, 1e-32));
+        // Two guard digits recover this value exactly.
+        let exact = parsed(&x.to_string_digits(DoubleDouble::DIGITS + 2));
+        assert_eq!(exact, x);
+    }
 }
@@ -9,6 +9,31 @@ pub enum CoreError {
     #[error("invalid escape radius: {0} (must be > 0.0)")]
     InvalidEscapeRadius(f64),
 
+    #[error("invalid exponent: {0} (must be > 1.0)")]
+    InvalidExponent(f64),
+
+    #[error("invalid relaxation: {0} (must be in (0.0, 2.0))")]
+    InvalidRelaxation(f64),
+
+    #[error("invalid polynomial: {reason}")]
+    InvalidPolynomial { reason: String },
+
+    #[error("invalid {part} at column {}: {message}", position + 1)]
+    InvalidFormula {
+        part: &'static str,
+        position: usize,
+        message: String,
+    },
+
+    #[error("invalid sequence: {reason}")]
+    InvalidSequence { reason: String },
+
     #[error("invalid viewport: {reason}")]
     InvalidViewport { reason: String },
+
+    #[error("invalid external angle: {reason}")]
+    InvalidAngle { reason: String },
+
+    #[error("invalid number: {input:?}")]
+    InvalidNumber { input: String },
 }
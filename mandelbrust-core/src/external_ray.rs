@@ -0,0 +1,570 @@
+//! External rays of the Mandelbrot set.
+//!
+//! A parameter ray of angle `t` is traced inward from a large circle: on
+//! level `k` the point `c` is moved, by Newton's method on `z_{k+1}(c)`, so
+//! that `z_{k+1}` has argument `2^k·t` and a radius that shrinks towards the
+//! escape circle, [`SHARPNESS`] steps per level. The reverse walks a point
+//! outward along its ray, reading one binary digit of its angle per level.
+//! Orbits run in double-double, so both keep working at DD zoom depths.
+
+use std::f64::consts::TAU;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::error::CoreError;
+
+/// Escape radius the ray levels are measured against.
+const ESCAPE_RADIUS: f64 = 65536.0;
+
+/// Ray points per level (per doubling of the angle).
+pub const SHARPNESS: u32 = 4;
+
+/// Newton steps allowed per ray point.
+const NEWTON_STEPS: usize = 64;
+
+/// Relative step size at which Newton has converged (DD resolution).
+const NEWTON_TOLERANCE: f64 = 1e-30;
+
+/// Trailing digits ignored when deciding whether two measured angles belong
+/// to the same ray.
+const ANGLE_SLACK_BITS: usize = 8;
+
+/// Points sampled on the circle around a picked point.
+const PICK_SAMPLES: usize = 8;
+
+/// First circle searched around a component root, in atom sizes, and how
+/// often it is shrunk before giving up.
+const ROOT_SEARCH_RADIUS: f64 = 0.25;
+const ROOT_SEARCH_ATTEMPTS: usize = 6;
+
+/// An external angle in turns, as a binary expansion with a preperiodic
+/// and a periodic part: `0.01(10)` is `0.01101010…`. An empty periodic
+/// part is a terminating (dyadic) expansion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExternalAngle {
+    preperiod: Vec<bool>,
+    period: Vec<bool>,
+}
+
+impl ExternalAngle {
+    /// Longest expansion accepted, preperiod and period together.
+    pub const MAX_BITS: usize = 4096;
+
+    pub fn new(preperiod: Vec<bool>, period: Vec<bool>) -> crate::Result<Self> {
+        if preperiod.len() + period.len() > Self::MAX_BITS {
+            return Err(CoreError::InvalidAngle {
+                reason: format!("longer than {} binary digits", Self::MAX_BITS),
+            });
+        }
+        Ok(Self { preperiod, period })
+    }
+
+    /// The angle `num/den` turns, reduced modulo 1.
+    pub fn from_fraction(num: u64, den: u64) -> crate::Result<Self> {
+        if den == 0 {
+            return Err(CoreError::InvalidAngle {
+                reason: "denominator is zero".into(),
+            });
+        }
+        let g = gcd(num % den, den);
+        let (mut x, den) = ((num % den / g) as u128, (den / g) as u128);
+        let digit = |x: &mut u128| {
+            *x *= 2;
+            let bit = *x >= den;
+            if bit {
+                *x -= den;
+            }
+            bit
+        };
+        // The factors of two in the denominator give the preperiod; after
+        // it the doubling map cycles through the odd part.
+        let preperiod: Vec<bool> = (0..den.trailing_zeros()).map(|_| digit(&mut x)).collect();
+        let start = x;
+        let mut period = Vec::new();
+        while start != 0 && (period.is_empty() || x != start) {
+            if preperiod.len() + period.len() >= Self::MAX_BITS {
+                return Err(CoreError::InvalidAngle {
+                    reason: format!("period longer than {} binary digits", Self::MAX_BITS),
+                });
+            }
+            period.push(digit(&mut x));
+        }
+        Self::new(preperiod, period)
+    }
+
+    /// Parse a fraction `p/q` or a binary expansion such as `0.01(10)`,
+    /// `.(011)` or `0.1`.
+    pub fn parse(text: &str) -> crate::Result<Self> {
+        let text = text.trim();
+        if let Some((num, den)) = text.split_once('/') {
+            let parse = |s: &str| {
+                s.trim()
+                    .parse::<u64>()
+                    .map_err(|_| CoreError::InvalidAngle {
+                        reason: format!("{:?} is not a whole number", s.trim()),
+                    })
+            };
+            return Self::from_fraction(parse(num)?, parse(den)?);
+        }
+
+        let digits = text
+            .strip_prefix("0.")
+            .or_else(|| text.strip_prefix('.'))
+            .unwrap_or(text);
+        let (pre, per) = match digits.split_once('(') {
+            Some((pre, rest)) => match rest.strip_suffix(')') {
+                Some(per) if !per.is_empty() => (pre, per),
+                _ => {
+                    return Err(CoreError::InvalidAngle {
+                        reason: "the periodic part must be non-empty digits in `( )`".into(),
+                    })
+                }
+            },
+            None => (digits, ""),
+        };
+        if pre.is_empty() && per.is_empty() {
+            return Err(CoreError::InvalidAngle {
+                reason: "angle is empty".into(),
+            });
+        }
+        let bits = |s: &str| {
+            s.chars()
+                .map(|ch| match ch {
+                    '0' => Ok(false),
+                    '1' => Ok(true),
+                    _ => Err(CoreError::InvalidAngle {
+                        reason: format!("unexpected {ch:?}, expected a fraction or binary digits"),
+                    }),
+                })
+                .collect::<crate::Result<Vec<_>>>()
+        };
+        Self::new(bits(pre)?, bits(per)?)
+    }
+
+    pub fn preperiod(&self) -> &[bool] {
+        &self.preperiod
+    }
+
+    pub fn period(&self) -> &[bool] {
+        &self.period
+    }
+
+    /// Binary digit `i` after the point (0-based).
+    pub fn bit(&self, i: usize) -> bool {
+        match i.checked_sub(self.preperiod.len()) {
+            None => self.preperiod[i],
+            Some(_) if self.period.is_empty() => false,
+            Some(j) => self.period[j % self.period.len()],
+        }
+    }
+
+    /// The angle in turns.
+    pub fn to_f64(&self) -> f64 {
+        self.doubled_f64(0)
+    }
+
+    /// `2^k·t mod 1`: the angle after `k` doublings, to `f64` precision.
+    fn doubled_f64(&self, k: usize) -> f64 {
+        (0..f64::MANTISSA_DIGITS as usize)
+            .rev()
+            .fold(0.0, |acc, i| (acc + f64::from(self.bit(k + i) as u8)) / 2.0)
+    }
+
+    /// The angle as a reduced fraction, or `None` if numerator or
+    /// denominator do not fit in a `u64`.
+    pub fn to_fraction(&self) -> Option<(u64, u64)> {
+        let (m, p) = (self.preperiod.len() as u32, self.period.len() as u32);
+        if m + p > 62 {
+            return None;
+        }
+        let value = |bits: &[bool]| bits.iter().fold(0u64, |acc, &b| acc * 2 + b as u64);
+        let (num, den) = if p == 0 {
+            (value(&self.preperiod), 1u64 << m)
+        } else {
+            let cycle = (1u64 << p) - 1;
+            (
+                value(&self.preperiod) * cycle + value(&self.period),
+                cycle << m,
+            )
+        };
+        let g = gcd(num, den);
+        Some((num / g, den / g))
+    }
+}
+
+impl fmt::Display for ExternalAngle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |bits: &[bool]| {
+            bits.iter()
+                .map(|&b| if b { '1' } else { '0' })
+                .collect::<String>()
+        };
+        write!(f, "0.{}", digits(&self.preperiod))?;
+        if self.period.is_empty() {
+            if self.preperiod.is_empty() {
+                f.write_str("0")?;
+            }
+        } else {
+            write!(f, "({})", digits(&self.period))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ExternalAngle {
+    type Err = CoreError;
+
+    fn from_str(text: &str) -> crate::Result<Self> {
+        Self::parse(text)
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a.max(1)
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// `|z_{k+1}|` aimed for on sub-step `j` of a level: from just inside
+/// [`ESCAPE_RADIUS`] down towards its square root.
+fn level_radius(j: u32) -> f64 {
+    ESCAPE_RADIUS.powf(0.5f64.powf((j as f64 + 0.5) / SHARPNESS as f64))
+}
+
+/// Newton's method for `z_n(c) = target` from `c`. The orbit runs in
+/// double-double; the derivative and step only need `f64`.
+fn solve(mut c: ComplexDD, n: usize, target: Complex) -> Option<ComplexDD> {
+    let target = ComplexDD::from(target);
+    for _ in 0..NEWTON_STEPS {
+        let mut z = ComplexDD::ZERO;
+        let mut dc = Complex::ZERO;
+        for _ in 0..n {
+            dc = z.to_complex() * dc * 2.0 + Complex::new(1.0, 0.0);
+            z = z * z + c;
+        }
+        let step = (z - target).to_complex() / dc;
+        if !step.re.is_finite() || !step.im.is_finite() {
+            return None;
+        }
+        c = c - ComplexDD::from(step);
+        if step.norm() <= NEWTON_TOLERANCE * c.to_complex().norm() {
+            break;
+        }
+    }
+    Some(c)
+}
+
+/// Traces the parameter ray of an angle inward, one point per
+/// [`next`](Iterator::next). The points approach the ray's landing point;
+/// the iterator ends only if Newton's method breaks down.
+#[derive(Debug, Clone)]
+pub struct RayTracer {
+    angle: ExternalAngle,
+    c: ComplexDD,
+    /// Points on this level target `z_{level+1}`.
+    level: usize,
+    substep: u32,
+}
+
+impl RayTracer {
+    pub fn new(angle: ExternalAngle) -> Self {
+        Self {
+            angle,
+            c: ComplexDD::ZERO,
+            level: 0,
+            substep: 0,
+        }
+    }
+
+    pub fn angle(&self) -> &ExternalAngle {
+        &self.angle
+    }
+
+    /// Levels completed so far: the number of angle doublings.
+    pub fn depth(&self) -> usize {
+        self.level
+    }
+}
+
+impl Iterator for RayTracer {
+    type Item = ComplexDD;
+
+    fn next(&mut self) -> Option<ComplexDD> {
+        let arg = TAU * self.angle.doubled_f64(self.level);
+        let r = level_radius(self.substep);
+        let target = Complex::new(r * arg.cos(), r * arg.sin());
+        self.c = solve(self.c, self.level + 1, target)?;
+        self.substep += 1;
+        if self.substep == SHARPNESS {
+            self.substep = 0;
+            self.level += 1;
+        }
+        Some(self.c)
+    }
+}
+
+/// Argument of `z_n(c)` in turns, in `[0, 1)`.
+fn orbit_turns(c: ComplexDD, n: usize) -> f64 {
+    let mut z = ComplexDD::ZERO;
+    for _ in 0..n {
+        z = z * z + c;
+    }
+    let z = z.to_complex();
+    (z.im.atan2(z.re) / TAU).rem_euclid(1.0)
+}
+
+/// The external angle of an exterior point `c`, to as many binary digits as
+/// its orbit takes to escape. `None` if `c` does not escape within
+/// `max_iterations`.
+pub fn external_angle(c: ComplexDD, max_iterations: u32) -> Option<ExternalAngle> {
+    let mut z = ComplexDD::ZERO;
+    let escape = (1..=max_iterations as usize).find(|_| {
+        z = z * z + c;
+        z.to_complex().norm_sq() > ESCAPE_RADIUS * ESCAPE_RADIUS
+    })?;
+
+    // Start one level inside the escape and walk out, one digit per level:
+    // halving the angle of the level below leaves two candidates, and the
+    // orbit's own argument there picks one.
+    let mut level = escape.saturating_sub(2);
+    let mut bits = vec![false; level];
+    let mut c = c;
+    let mut turns = orbit_turns(c, level + 1);
+    while level > 0 {
+        for j in (0..SHARPNESS).rev() {
+            let (r, arg) = (level_radius(j), TAU * turns);
+            c = solve(c, level + 1, Complex::new(r * arg.cos(), r * arg.sin()))?;
+        }
+        let below = orbit_turns(c, level);
+        let distance = |t: f64| {
+            let d = (t - below).rem_euclid(1.0);
+            d.min(1.0 - d)
+        };
+        let bit = distance(turns / 2.0 + 0.5) < distance(turns / 2.0);
+        turns = turns / 2.0 + if bit { 0.5 } else { 0.0 };
+        level -= 1;
+        bits[level] = bit;
+    }
+    ExternalAngle::new(bits, Vec::new()).ok()
+}
+
+/// Approximate external angles of the rays passing within `radius` of `c`:
+/// those of `c` and of points around it, one per distinct ray. Empty if no
+/// sampled point escapes within `max_iterations`.
+pub fn external_angles_near(c: ComplexDD, radius: f64, max_iterations: u32) -> Vec<ExternalAngle> {
+    let samples = std::iter::once(Complex::ZERO).chain((0..PICK_SAMPLES).map(|i| {
+        let arg = TAU * i as f64 / PICK_SAMPLES as f64;
+        Complex::new(radius * arg.cos(), radius * arg.sin())
+    }));
+    let mut angles: Vec<ExternalAngle> = Vec::new();
+    for offset in samples {
+        let Some(angle) = external_angle(c + ComplexDD::from(offset), max_iterations) else {
+            continue;
+        };
+        let same_ray = |other: &ExternalAngle| {
+            let (a, b) = (&angle.preperiod, &other.preperiod);
+            let n = a.len().min(b.len()).saturating_sub(ANGLE_SLACK_BITS);
+            a[..n] == b[..n]
+        };
+        // Of two measurements of one ray, keep the more precise.
+        match angles.iter_mut().find(|other| same_ray(other)) {
+            Some(other) if other.preperiod.len() < angle.preperiod.len() => *other = angle,
+            Some(_) => {}
+            None => angles.push(angle),
+        }
+    }
+    angles
+}
+
+/// The exact angles of the two rays landing on the root of a hyperbolic
+/// component of period `period` and atom size `size`, lower angle first.
+/// They are measured on shrinking circles around the root until both show
+/// their `period` binary digits repeating.
+pub fn root_angles(
+    root: ComplexDD,
+    period: u32,
+    size: f64,
+    max_iterations: u32,
+) -> Option<(ExternalAngle, ExternalAngle)> {
+    if period == 1 {
+        // Only the ray 0 lands on the cusp of the main cardioid.
+        let zero = ExternalAngle::new(Vec::new(), vec![false]).ok()?;
+        return Some((zero.clone(), zero));
+    }
+    let p = period as usize;
+    let mut radius = size * ROOT_SEARCH_RADIUS;
+    for _ in 0..ROOT_SEARCH_ATTEMPTS {
+        let mut exact: Vec<ExternalAngle> = Vec::new();
+        for measured in external_angles_near(root, radius, max_iterations) {
+            let bits = &measured.preperiod;
+            if bits.len() < 2 * p + ANGLE_SLACK_BITS || bits[..p] != bits[p..2 * p] {
+                continue;
+            }
+            let angle = ExternalAngle::new(Vec::new(), bits[..p].to_vec()).ok()?;
+            if !exact.contains(&angle) {
+                exact.push(angle);
+            }
+        }
+        if let [a, b] = &exact[..] {
+            // Same period length: digit order is numeric order.
+            return Some(if a.period < b.period {
+                (a.clone(), b.clone())
+            } else {
+                (b.clone(), a.clone())
+            });
+        }
+        radius /= 4.0;
+    }
+    None
+}
+
+/// The exact angles of the rays landing on the Misiurewicz point `c`
+/// whose critical orbit has the given preperiod and period (counted from
+/// `z₀ = 0` as by [`crate::MisiurewiczPoint`]), in increasing order. Their
+/// expansions have preperiod `preperiod − 1` and a multiple of `period` as
+/// period; they are measured on circles shrinking from `radius` until
+/// every ray seen shows that pattern.
+pub fn misiurewicz_angles(
+    c: ComplexDD,
+    preperiod: u32,
+    period: u32,
+    radius: f64,
+    max_iterations: u32,
+) -> Option<Vec<ExternalAngle>> {
+    let m = (preperiod as usize).saturating_sub(1);
+    let p = period.max(1) as usize;
+    let mut radius = radius;
+    for _ in 0..ROOT_SEARCH_ATTEMPTS {
+        let exact: Option<Vec<ExternalAngle>> = external_angles_near(c, radius, max_iterations)
+            .iter()
+            .map(|measured| preperiodic_angle(&measured.preperiod, m, p))
+            .collect();
+        if let Some(mut exact) = exact.filter(|e| !e.is_empty()) {
+            exact.sort_by(cmp_angles);
+            exact.dedup();
+            return Some(exact);
+        }
+        radius /= 4.0;
+    }
+    None
+}
+
+/// The angle with preperiod `m` and the shortest multiple of `p` as period
+/// that the measured digits `bits` show twice in a row.
+fn preperiodic_angle(bits: &[bool], m: usize, p: usize) -> Option<ExternalAngle> {
+    let len = bits.len().checked_sub(ANGLE_SLACK_BITS)?;
+    let q = (p..)
+        .step_by(p)
+        .take_while(|&q| m + 2 * q <= len)
+        .find(|&q| bits[m..m + q] == bits[m + q..m + 2 * q])?;
+    ExternalAngle::new(bits[..m].to_vec(), bits[m..m + q].to_vec()).ok()
+}
+
+/// Numeric order of two angles, digit by digit.
+fn cmp_angles(a: &ExternalAngle, b: &ExternalAngle) -> std::cmp::Ordering {
+    let len = a.preperiod.len() + a.period.len() + b.preperiod.len() + b.period.len();
+    (0..len)
+        .map(|i| a.bit(i).cmp(&b.bit(i)))
+        .find(|o| o.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn angle(text: &str) -> ExternalAngle {
+        ExternalAngle::parse(text).unwrap()
+    }
+
+    /// Ray points after `levels` doublings.
+    fn trace(angle: ExternalAngle, levels: usize) -> Vec<ComplexDD> {
+        RayTracer::new(angle)
+            .take(levels * SHARPNESS as usize)
+            .collect()
+    }
+
+    #[test]
+    fn fractions_and_binary_expansions_agree() {
+        assert_eq!(ExternalAngle::from_fraction(1, 3).unwrap(), angle(".(01)"));
+        assert_eq!(
+            ExternalAngle::from_fraction(1, 6).unwrap(),
+            angle("0.0(01)")
+        );
+        assert_eq!(ExternalAngle::from_fraction(3, 4).unwrap(), angle("0.11"));
+        assert_eq!(angle("9/7"), angle("0.(010)"));
+        assert_eq!(angle("0.0(01)").to_fraction(), Some((1, 6)));
+        assert_eq!(angle("0.(011)").to_fraction(), Some((3, 7)));
+        assert_eq!(angle("1/6").to_string(), "0.0(01)");
+        assert_eq!(angle("0/1").to_string(), "0.0");
+        assert!((angle("1/3").to_f64() - 1.0 / 3.0).abs() < 1e-16);
+        for bad in ["", "1/0", "0.2", "0.1()", "x/3", "0.(01"] {
+            assert!(ExternalAngle::parse(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn rays_land_where_expected() {
+        // 1/2 lands on the tip at −2 and 1/6 on the Misiurewicz point i.
+        let tip = trace(angle("1/2"), 40).pop().unwrap().to_complex();
+        assert!((tip - Complex::new(-2.0, 0.0)).norm() < 1e-6, "{tip}");
+        let m = trace(angle("1/6"), 60).pop().unwrap().to_complex();
+        assert!((m - Complex::new(0.0, 1.0)).norm() < 1e-6, "{m}");
+        // 1/3 lands on the root of the period-2 bulb, parabolically slowly.
+        let root = trace(angle("1/3"), 200).pop().unwrap().to_complex();
+        assert!((root - Complex::new(-0.75, 0.0)).norm() < 0.05, "{root}");
+        assert!(root.im > 0.0);
+    }
+
+    #[test]
+    fn tracing_continues_below_f64_resolution() {
+        let points = trace(angle("1/6"), 120);
+        let [.., a, b] = points[..] else {
+            unreachable!()
+        };
+        let step = (b - a).to_complex().norm();
+        assert!(step > 0.0 && step < 1e-20, "step {step}");
+    }
+
+    #[test]
+    fn outward_tracing_recovers_the_angle() {
+        let t = angle("1/6");
+        let c = trace(t.clone(), 20).pop().unwrap();
+        let measured = external_angle(c, 10_000).unwrap();
+        assert!(measured.preperiod().len() >= 16);
+        let n = measured.preperiod().len() - ANGLE_SLACK_BITS;
+        assert!((0..n).all(|i| measured.bit(i) == t.bit(i)), "{measured}");
+
+        // The rays 5/12 and 7/12 land together on the Misiurewicz point
+        // c ≈ −1.5437 on the real axis; points above and below it pick up
+        // one each.
+        let m = ComplexDD::from(Complex::new(-1.543_689_012_692_076, 0.0));
+        let angles = external_angles_near(m, 1e-3, 10_000);
+        for target in [5.0 / 12.0, 7.0 / 12.0] {
+            assert!(
+                angles.iter().any(|a| (a.to_f64() - target).abs() < 0.01),
+                "{angles:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn root_angles_are_exact() {
+        let roots = [
+            (-0.75, 0.0, 2, "1/3", "2/3"),
+            (-1.75, 0.0, 3, "3/7", "4/7"),
+            (-0.125, 0.649_519_052_838_329, 3, "1/7", "2/7"),
+        ];
+        for (re, im, period, lo, hi) in roots {
+            let root = ComplexDD::from(Complex::new(re, im));
+            let (a, b) = root_angles(root, period, 0.1, 10_000).unwrap();
+            assert_eq!((a, b), (angle(lo), angle(hi)), "period {period}");
+        }
+        let (a, b) = root_angles(ComplexDD::from(Complex::new(0.25, 0.0)), 1, 1.0, 100).unwrap();
+        assert_eq!((a.to_f64(), b.to_f64()), (0.0, 0.0));
+    }
+}
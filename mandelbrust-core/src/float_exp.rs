@@ -0,0 +1,498 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, DivAssign, Mul, MulAssign, Neg, Sub};
+use std::str::FromStr;
+
+use crate::error::CoreError;
+
+/// An extended-exponent float: an `f64` mantissa with a separate `i64`
+/// binary exponent.
+///
+/// Represents `mantissa · 2^exponent` with `1 ≤ |mantissa| < 2` (or exactly
+/// zero). Precision is that of an `f64` (~16 digits), but the range is
+/// effectively unbounded, so values such as `1e-1000` survive arithmetic
+/// and serialization. Used for the viewport scale, where only the
+/// magnitude — not extra digits — has to go beyond `f64`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatExp {
+    mantissa: f64,
+    exponent: i64,
+}
+
+const EXP_MASK: u64 = 0x7ff << 52;
+
+/// Multiply `x` by `2^e` without intermediate overflow or underflow.
+///
+/// Saturates to `0` / `±∞` when the result leaves the `f64` range.
+pub fn ldexp(mut x: f64, mut e: i64) -> f64 {
+    if x == 0.0 || !x.is_finite() {
+        return x;
+    }
+    while e > 1023 {
+        x *= f64::from_bits(2046 << 52); // 2^1023
+        e -= 1023;
+        if x.is_infinite() {
+            return x;
+        }
+    }
+    while e < -1022 {
+        x *= f64::from_bits(1 << 52); // 2^-1022
+        e += 1022;
+        if x == 0.0 {
+            return x;
+        }
+    }
+    x * f64::from_bits(((e + 1023) as u64) << 52)
+}
+
+/// Split a finite, non-zero `f64` into a mantissa in `[1, 2)` and a
+/// binary exponent.
+fn frexp(x: f64) -> (f64, i64) {
+    let bits = x.to_bits();
+    let biased = ((bits & EXP_MASK) >> 52) as i64;
+    if biased == 0 {
+        // Subnormal: renormalize first.
+        let (m, e) = frexp(x * f64::from_bits((1023 + 64) << 52));
+        return (m, e - 64);
+    }
+    let mantissa = f64::from_bits((bits & !EXP_MASK) | (1023 << 52));
+    (mantissa, biased - 1023)
+}
+
+impl FloatExp {
+    pub const ZERO: Self = Self {
+        mantissa: 0.0,
+        exponent: 0,
+    };
+    pub const ONE: Self = Self {
+        mantissa: 1.0,
+        exponent: 0,
+    };
+
+    /// Build `mantissa · 2^exponent`, normalizing the mantissa.
+    pub fn new(mantissa: f64, exponent: i64) -> Self {
+        if mantissa == 0.0 || !mantissa.is_finite() {
+            return Self {
+                mantissa,
+                exponent: 0,
+            };
+        }
+        let (m, e) = frexp(mantissa);
+        Self {
+            mantissa: m,
+            exponent: exponent.saturating_add(e),
+        }
+    }
+
+    /// The normalized mantissa, `1 ≤ |m| < 2` (or zero).
+    #[inline]
+    pub fn mantissa(self) -> f64 {
+        self.mantissa
+    }
+
+    /// The binary exponent.
+    #[inline]
+    pub fn exponent(self) -> i64 {
+        self.exponent
+    }
+
+    /// Convert to `f64`, flushing to `0` or `±∞` outside its range.
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        ldexp(self.mantissa, self.exponent)
+    }
+
+    pub fn abs(self) -> Self {
+        Self {
+            mantissa: self.mantissa.abs(),
+            exponent: self.exponent,
+        }
+    }
+
+    #[inline]
+    pub fn is_zero(self) -> bool {
+        self.mantissa == 0.0
+    }
+
+    #[inline]
+    pub fn is_positive(self) -> bool {
+        self.mantissa > 0.0
+    }
+
+    /// `true` unless the mantissa is infinite or NaN.
+    #[inline]
+    pub fn is_finite(self) -> bool {
+        self.mantissa.is_finite()
+    }
+
+    /// `1 / self`.
+    pub fn recip(self) -> Self {
+        Self::new(1.0 / self.mantissa, -self.exponent)
+    }
+
+    /// Base-2 logarithm of `|self|`.
+    pub fn log2(self) -> f64 {
+        self.mantissa.abs().log2() + self.exponent as f64
+    }
+
+    /// Base-10 logarithm of `|self|`.
+    pub fn log10(self) -> f64 {
+        self.mantissa.abs().log10() + self.exponent as f64 * std::f64::consts::LOG10_2
+    }
+
+    /// `10^k` for any integer `k`.
+    pub fn powi10(k: i64) -> Self {
+        // 1e300 keeps every chunk exact-ish and well inside the f64 range.
+        const CHUNK: i64 = 300;
+        let big = Self::from(10f64.powi(CHUNK as i32));
+        let mut result = Self::ONE;
+        let mut rest = k.abs();
+        while rest > CHUNK {
+            result *= big;
+            rest -= CHUNK;
+        }
+        result *= 10f64.powi(rest as i32);
+        if k < 0 {
+            result.recip()
+        } else {
+            result
+        }
+    }
+
+    /// Split into a decimal mantissa `1 ≤ |d| < 10` and a power of ten.
+    fn to_decimal(self) -> (f64, i64) {
+        if self.is_zero() || !self.is_finite() {
+            return (self.mantissa, 0);
+        }
+        let log = self.log10();
+        let mut exp10 = log.floor() as i64;
+        let mut digits = 10f64.powf(log - exp10 as f64).copysign(self.mantissa);
+        // Guard against `log10` rounding landing just outside [1, 10).
+        if digits.abs() >= 10.0 {
+            digits /= 10.0;
+            exp10 += 1;
+        } else if digits.abs() < 1.0 {
+            digits *= 10.0;
+            exp10 -= 1;
+        }
+        (digits, exp10)
+    }
+}
+
+impl From<f64> for FloatExp {
+    #[inline]
+    fn from(val: f64) -> Self {
+        Self::new(val, 0)
+    }
+}
+
+impl Mul for FloatExp {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.mantissa * rhs.mantissa,
+            self.exponent.saturating_add(rhs.exponent),
+        )
+    }
+}
+
+impl MulAssign for FloatExp {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<f64> for FloatExp {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.mantissa * rhs, self.exponent)
+    }
+}
+
+impl MulAssign<f64> for FloatExp {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for FloatExp {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        Self::new(
+            self.mantissa / rhs.mantissa,
+            self.exponent.saturating_sub(rhs.exponent),
+        )
+    }
+}
+
+impl Div<f64> for FloatExp {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.mantissa / rhs, self.exponent)
+    }
+}
+
+impl DivAssign<f64> for FloatExp {
+    #[inline]
+    fn div_assign(&mut self, rhs: f64) {
+        *self = *self / rhs;
+    }
+}
+
+impl Add for FloatExp {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        if self.is_zero() {
+            return rhs;
+        }
+        if rhs.is_zero() {
+            return self;
+        }
+        let (big, small) = if self.exponent >= rhs.exponent {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let shift = big.exponent - small.exponent;
+        if shift > 64 {
+            return big;
+        }
+        Self::new(big.mantissa + ldexp(small.mantissa, -shift), big.exponent)
+    }
+}
+
+impl Sub for FloatExp {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl Neg for FloatExp {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self {
+            mantissa: -self.mantissa,
+            exponent: self.exponent,
+        }
+    }
+}
+
+impl PartialOrd for FloatExp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (*self - *other).mantissa.partial_cmp(&0.0)
+    }
+}
+
+impl PartialEq<f64> for FloatExp {
+    fn eq(&self, other: &f64) -> bool {
+        *self == Self::from(*other)
+    }
+}
+
+impl PartialOrd<f64> for FloatExp {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        self.partial_cmp(&Self::from(*other))
+    }
+}
+
+impl fmt::LowerExp for FloatExp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (digits, exp10) = self.to_decimal();
+        let mut mantissa = match f.precision() {
+            Some(p) => format!("{digits:.p$}"),
+            None => format!("{digits}"),
+        };
+        // Rounding to the requested precision can carry into a new digit.
+        let mut exp10 = exp10;
+        if mantissa.trim_start_matches('-').starts_with("10") {
+            let rounded = digits / 10.0;
+            mantissa = match f.precision() {
+                Some(p) => format!("{rounded:.p$}"),
+                None => format!("{rounded}"),
+            };
+            exp10 += 1;
+        }
+        write!(f, "{mantissa}e{exp10}")
+    }
+}
+
+/// Plain `f64` formatting inside the `f64` range, scientific outside it.
+impl fmt::Display for FloatExp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v = self.to_f64();
+        if self.is_zero() || (v != 0.0 && v.is_finite()) {
+            fmt::Display::fmt(&v, f)
+        } else {
+            fmt::LowerExp::fmt(self, f)
+        }
+    }
+}
+
+/// Parses decimal notation with an arbitrarily large exponent
+/// (e.g. `"2.5e-1000"`).
+impl FromStr for FloatExp {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CoreError::InvalidNumber {
+            input: s.to_string(),
+        };
+        let trimmed = s.trim();
+        let (digits, exp10) = match trimmed.find(['e', 'E']) {
+            Some(pos) => {
+                let exp10: i64 = trimmed[pos + 1..].parse().map_err(|_| invalid())?;
+                (&trimmed[..pos], exp10)
+            }
+            None => (trimmed, 0),
+        };
+        let mantissa: f64 = digits.parse().map_err(|_| invalid())?;
+        if !mantissa.is_finite() {
+            return Err(invalid());
+        }
+        Ok(Self::from(mantissa) * Self::powi10(exp10))
+    }
+}
+
+/// Serialized as a JSON number while the value fits in an `f64` (so files
+/// stay readable by older builds), and as a decimal string beyond that.
+impl serde::Serialize for FloatExp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let v = self.to_f64();
+        if self.is_zero() || (v.is_normal() && v.is_finite()) {
+            serializer.serialize_f64(v)
+        } else {
+            serializer.serialize_str(&format!("{self:.17e}"))
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for FloatExp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(f64),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(v) => Ok(Self::from(v)),
+            Raw::Text(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * b.abs().max(1e-300)
+    }
+
+    #[test]
+    fn roundtrip_f64() {
+        for &v in &[1.0, -3.5, 1e-300, 2.2e-310, 1e300, 0.0] {
+            assert_eq!(FloatExp::from(v).to_f64(), v);
+        }
+    }
+
+    #[test]
+    fn normalized_mantissa() {
+        let x = FloatExp::from(12.0);
+        assert_eq!(x.mantissa(), 1.5);
+        assert_eq!(x.exponent(), 3);
+    }
+
+    #[test]
+    fn multiplication_beyond_f64_range() {
+        let tiny = FloatExp::from(1e-300);
+        let product = tiny * tiny * tiny;
+        assert_eq!(product.to_f64(), 0.0);
+        assert!(close(product.log10(), -900.0));
+        let back = product * FloatExp::powi10(900);
+        assert!(close(back.to_f64(), 1.0));
+    }
+
+    #[test]
+    fn scalar_ops() {
+        let mut x = FloatExp::powi10(-1000);
+        x *= 0.5;
+        x /= 0.25;
+        assert!(close(x.log10(), -1000.0 + 2f64.log10()));
+    }
+
+    #[test]
+    fn addition_aligns_exponents() {
+        let a = FloatExp::from(3.0);
+        let b = FloatExp::from(0.25);
+        assert_eq!((a + b).to_f64(), 3.25);
+        assert_eq!((a - b).to_f64(), 2.75);
+        // Negligible addend is dropped.
+        let huge = FloatExp::powi10(500);
+        assert_eq!(huge + a, huge);
+    }
+
+    #[test]
+    fn ordering() {
+        let a = FloatExp::powi10(-1000);
+        let b = FloatExp::powi10(-999);
+        assert!(a < b);
+        assert!(-b < -a);
+        assert!(a < 1e-13);
+        assert!(a > 0.0);
+    }
+
+    #[test]
+    fn ldexp_saturates() {
+        assert_eq!(ldexp(1.0, -2000), 0.0);
+        assert_eq!(ldexp(1.0, 2000), f64::INFINITY);
+        assert_eq!(ldexp(1.0, -1074), f64::from_bits(1));
+        assert_eq!(ldexp(1.5, 10), 1536.0);
+    }
+
+    #[test]
+    fn format_scientific() {
+        let x: FloatExp = "2.5e-1000".parse().unwrap();
+        assert_eq!(format!("{x:.2e}"), "2.50e-1000");
+        assert_eq!(format!("{:.1e}", FloatExp::from(9.96)), "1.0e1");
+        assert_eq!(format!("{}", FloatExp::from(0.5)), "0.5");
+        assert_eq!(format!("{:.3e}", x.recip()), "4.000e999");
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert!("abc".parse::<FloatExp>().is_err());
+        assert!("1e".parse::<FloatExp>().is_err());
+        assert!("inf".parse::<FloatExp>().is_err());
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let shallow = FloatExp::from(0.004);
+        let json = serde_json::to_string(&shallow).unwrap();
+        assert_eq!(json, "0.004");
+        let deep = FloatExp::powi10(-1000) * 3.0;
+        let json = serde_json::to_string(&deep).unwrap();
+        assert!(json.starts_with('"'));
+        let back: FloatExp = serde_json::from_str(&json).unwrap();
+        assert!(close((back / deep).to_f64(), 1.0));
+        let legacy: FloatExp = serde_json::from_str("1.5e-20").unwrap();
+        assert_eq!(legacy.to_f64(), 1.5e-20);
+    }
+}
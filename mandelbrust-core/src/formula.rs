@@ -0,0 +1,1348 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::error::CoreError;
+use crate::fractal::{
+    is_cycle, record_orbit, ExtrasOptions, Fractal, FractalParams, IterationExtras, IterationResult,
+};
+
+/// Deepest operand stack a compiled formula may need.
+const MAX_STACK: usize = 32;
+
+/// Deepest nesting of brackets, signs and powers the parser accepts, so
+/// hostile input is rejected instead of overflowing the thread's stack.
+const MAX_DEPTH: usize = 256;
+
+/// Most tokens in one expression. Bounds the tree depth of long operator
+/// chains, which later passes walk recursively.
+const MAX_TOKENS: usize = 1024;
+
+/// Identifiers with a fixed meaning; parameters may not reuse them.
+const RESERVED: [&str; 6] = ["z", "c", "pixel", "i", "pi", "e"];
+
+// ---------------------------------------------------------------------------
+// Definition
+// ---------------------------------------------------------------------------
+
+/// A named complex constant a formula can refer to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormulaParam {
+    pub name: String,
+    pub value: Complex,
+}
+
+/// A user-defined escape-time formula as typed, plus its settings.
+///
+/// The iteration step is an expression over `z`, `c` (the pixel),
+/// `pixel`, the constants `i`, `pi` and `e`, and the named [`params`].
+/// It supports `+ - * / ^`, `|x|` and the functions `sin cos sinh cosh
+/// exp log sqrt abs conj re im`. A leading `z =` is optional.
+///
+/// [`params`]: Self::params
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormulaDefinition {
+    /// The iteration step, e.g. `z = z^3 + c*z + 0.5`.
+    pub formula: String,
+    /// Starting value of `z`, evaluated once per pixel. May use everything
+    /// the step can except `z`.
+    #[serde(default = "default_initial_z")]
+    pub initial_z: String,
+    /// `|z|` beyond which the orbit counts as escaped.
+    #[serde(default = "default_bailout")]
+    pub bailout: f64,
+    #[serde(default)]
+    pub params: Vec<FormulaParam>,
+}
+
+fn default_initial_z() -> String {
+    "0".to_string()
+}
+
+fn default_bailout() -> f64 {
+    FractalParams::DEFAULT_ESCAPE_RADIUS
+}
+
+impl Default for FormulaDefinition {
+    fn default() -> Self {
+        Self {
+            formula: "z = z^2 + c".to_string(),
+            initial_z: default_initial_z(),
+            bailout: default_bailout(),
+            params: Vec::new(),
+        }
+    }
+}
+
+/// One line, e.g. `z = z^2 + k; z0 = 0; bailout = 2; k = 0.5+0i`, for
+/// metadata and clipboard text.
+impl fmt::Display for FormulaDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}; z0 = {}; bailout = {}",
+            self.formula.trim(),
+            self.initial_z.trim(),
+            self.bailout
+        )?;
+        for p in &self.params {
+            write!(f, "; {} = {}{:+}i", p.name, p.value.re, p.value.im)?;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Lexer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    /// A number with an `i` suffix, e.g. `2.5i`.
+    Imaginary(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LParen,
+    RParen,
+    Pipe,
+    Equals,
+    End,
+}
+
+/// Error message and the character column it points at (0-based).
+type SyntaxError = (usize, String);
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, SyntaxError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let ch = chars[pos];
+        let start = pos;
+        if ch.is_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if ch.is_ascii_digit() || ch == '.' {
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+            // Exponent: `1e-3`, but not the constant in `2e` or `2*e`.
+            if pos + 1 < chars.len()
+                && matches!(chars[pos], 'e' | 'E')
+                && (chars[pos + 1].is_ascii_digit()
+                    || (matches!(chars[pos + 1], '+' | '-')
+                        && chars.get(pos + 2).is_some_and(|c| c.is_ascii_digit())))
+            {
+                pos += 2;
+                while pos < chars.len() && chars[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+            }
+            let literal: String = chars[start..pos].iter().collect();
+            let value: f64 = literal
+                .parse()
+                .map_err(|_| (start, format!("invalid number '{literal}'")))?;
+            let imaginary = chars.get(pos) == Some(&'i')
+                && !chars
+                    .get(pos + 1)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_');
+            if imaginary {
+                pos += 1;
+                tokens.push((start, Token::Imaginary(value)));
+            } else {
+                tokens.push((start, Token::Number(value)));
+            }
+            continue;
+        }
+        if ch.is_ascii_alphabetic() || ch == '_' {
+            while pos < chars.len() && (chars[pos].is_ascii_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            tokens.push((start, Token::Ident(chars[start..pos].iter().collect())));
+            continue;
+        }
+        let token = match ch {
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '^' => Token::Caret,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '|' => Token::Pipe,
+            '=' => Token::Equals,
+            _ => return Err((start, format!("unexpected character '{ch}'"))),
+        };
+        tokens.push((start, token));
+        pos += 1;
+    }
+    if let Some(&(column, _)) = tokens.get(MAX_TOKENS) {
+        return Err((column, "formula is too long".to_string()));
+    }
+    tokens.push((chars.len(), Token::End));
+    Ok(tokens)
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Func {
+    Sin,
+    Cos,
+    Sinh,
+    Cosh,
+    Exp,
+    Log,
+    Sqrt,
+    Abs,
+    Conj,
+    Re,
+    Im,
+}
+
+impl Func {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sin" => Self::Sin,
+            "cos" => Self::Cos,
+            "sinh" => Self::Sinh,
+            "cosh" => Self::Cosh,
+            "exp" => Self::Exp,
+            "log" | "ln" => Self::Log,
+            "sqrt" => Self::Sqrt,
+            "abs" => Self::Abs,
+            "conj" => Self::Conj,
+            "re" => Self::Re,
+            "im" => Self::Im,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Const(Complex),
+    Z,
+    Pixel,
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Call(Func, Box<Expr>),
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    params: &'a [FormulaParam],
+    allow_z: bool,
+    /// Current recursion depth, checked against [`MAX_DEPTH`].
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].1
+    }
+
+    fn column(&self) -> usize {
+        self.tokens[self.pos].0
+    }
+
+    fn next(&mut self) -> (usize, Token) {
+        let token = self.tokens[self.pos].clone();
+        if token.1 != Token::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<(), SyntaxError> {
+        if *self.peek() == expected {
+            self.next();
+            Ok(())
+        } else {
+            Err((self.column(), format!("expected {what}")))
+        }
+    }
+
+    /// `[z =] expr`
+    fn program(&mut self) -> Result<Expr, SyntaxError> {
+        if self.allow_z
+            && matches!(self.peek(), Token::Ident(name) if name == "z")
+            && self.tokens.get(self.pos + 1).map(|t| &t.1) == Some(&Token::Equals)
+        {
+            self.pos += 2;
+        }
+        let expr = self.expr()?;
+        if *self.peek() != Token::End {
+            return Err((self.column(), "expected an operator".to_string()));
+        }
+        Ok(expr)
+    }
+
+    fn expr(&mut self) -> Result<Expr, SyntaxError> {
+        let mut lhs = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Token::Plus => BinOp::Add,
+                Token::Minus => BinOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.next();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, SyntaxError> {
+        let mut lhs = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Token::Star => BinOp::Mul,
+                Token::Slash => BinOp::Div,
+                _ => return Ok(lhs),
+            };
+            self.next();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+    }
+
+    /// Unary minus binds looser than `^`, so `-z^2` is `-(z^2)`.
+    ///
+    /// Every recursive path of the grammar passes through here, so this is
+    /// where nesting depth is counted.
+    fn unary(&mut self) -> Result<Expr, SyntaxError> {
+        if self.depth == MAX_DEPTH {
+            return Err((self.column(), "formula is nested too deeply".to_string()));
+        }
+        self.depth += 1;
+        let expr = match self.peek() {
+            Token::Minus => {
+                self.next();
+                self.unary().map(|inner| Expr::Neg(Box::new(inner)))
+            }
+            Token::Plus => {
+                self.next();
+                self.unary()
+            }
+            _ => self.power(),
+        };
+        self.depth -= 1;
+        expr
+    }
+
+    /// Right-associative: `2^3^2` is `2^9`.
+    fn power(&mut self) -> Result<Expr, SyntaxError> {
+        let base = self.primary()?;
+        if *self.peek() == Token::Caret {
+            self.next();
+            let exponent = self.unary()?;
+            return Ok(Expr::Binary(BinOp::Pow, Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Expr, SyntaxError> {
+        let (column, token) = self.next();
+        match token {
+            Token::Number(v) => Ok(Expr::Const(Complex::new(v, 0.0))),
+            Token::Imaginary(v) => Ok(Expr::Const(Complex::new(0.0, v))),
+            Token::LParen => {
+                let inner = self.expr()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(inner)
+            }
+            Token::Pipe => {
+                let inner = self.expr()?;
+                self.expect(Token::Pipe, "closing '|'")?;
+                Ok(Expr::Call(Func::Abs, Box::new(inner)))
+            }
+            Token::Ident(name) => self.identifier(column, &name),
+            Token::End => Err((column, "unexpected end of formula".to_string())),
+            _ => Err((column, "expected a value".to_string())),
+        }
+    }
+
+    fn identifier(&mut self, column: usize, name: &str) -> Result<Expr, SyntaxError> {
+        if let Some(func) = Func::from_name(name) {
+            if *self.peek() != Token::LParen {
+                return Err((self.column(), format!("expected '(' after {name}")));
+            }
+            self.next();
+            let arg = self.expr()?;
+            self.expect(Token::RParen, "')'")?;
+            return Ok(Expr::Call(func, Box::new(arg)));
+        }
+        match name {
+            "z" if self.allow_z => Ok(Expr::Z),
+            "z" => Err((column, "z is not defined in the initial value".to_string())),
+            "c" | "pixel" => Ok(Expr::Pixel),
+            "i" => Ok(Expr::Const(Complex::new(0.0, 1.0))),
+            "pi" => Ok(Expr::Const(Complex::new(std::f64::consts::PI, 0.0))),
+            "e" => Ok(Expr::Const(Complex::new(std::f64::consts::E, 0.0))),
+            _ => self
+                .params
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| Expr::Const(p.value))
+                .ok_or_else(|| (column, format!("unknown name '{name}'"))),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Constant folding and degree analysis
+// ---------------------------------------------------------------------------
+
+fn fold(expr: Expr) -> Expr {
+    match expr {
+        Expr::Neg(a) => match fold(*a) {
+            Expr::Const(v) => Expr::Const(-v),
+            a => Expr::Neg(Box::new(a)),
+        },
+        Expr::Binary(op, a, b) => match (fold(*a), fold(*b)) {
+            (Expr::Const(x), Expr::Const(y)) => Expr::Const(binary(op, x, y)),
+            (a, b) => Expr::Binary(op, Box::new(a), Box::new(b)),
+        },
+        Expr::Call(func, a) => match fold(*a) {
+            Expr::Const(v) => Expr::Const(call(func, v)),
+            a => Expr::Call(func, Box::new(a)),
+        },
+        leaf => leaf,
+    }
+}
+
+/// Growth rate of `|z|` per step as a power of `|z|`, when the step is
+/// polynomial-like in `z`. `None` for anything else.
+fn degree(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Const(_) | Expr::Pixel => Some(0.0),
+        Expr::Z => Some(1.0),
+        Expr::Neg(a) => degree(a),
+        Expr::Binary(BinOp::Add | BinOp::Sub, a, b) => Some(degree(a)?.max(degree(b)?)),
+        Expr::Binary(BinOp::Mul, a, b) => Some(degree(a)? + degree(b)?),
+        Expr::Binary(BinOp::Div, a, b) => {
+            let (num, den) = (degree(a)?, degree(b)?);
+            (den == 0.0).then_some(num)
+        }
+        Expr::Binary(BinOp::Pow, a, b) => match **b {
+            Expr::Const(k) if k.im == 0.0 && k.re > 0.0 => Some(degree(a)? * k.re),
+            _ => None,
+        },
+        Expr::Call(Func::Abs | Func::Conj | Func::Re | Func::Im, a) => degree(a),
+        Expr::Call(..) => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Bytecode
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Const(Complex),
+    Z,
+    Pixel,
+    Neg,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    /// Power by a constant integer, via repeated squaring.
+    PowInt(i32),
+    Pow,
+    Call(Func),
+}
+
+/// A compiled expression: postfix ops for a small operand stack.
+#[derive(Debug, Clone)]
+struct Program {
+    ops: Vec<Op>,
+}
+
+impl Program {
+    fn compile(expr: &Expr) -> Result<Self, SyntaxError> {
+        let mut ops = Vec::new();
+        let depth = emit(expr, &mut ops);
+        if depth > MAX_STACK {
+            return Err((0, "formula is nested too deeply".to_string()));
+        }
+        Ok(Self { ops })
+    }
+
+    #[inline]
+    fn eval<V: Operand>(&self, stack: &mut [V; MAX_STACK], z: V, pixel: V) -> V {
+        let mut sp = 0;
+        for &op in &self.ops {
+            match op {
+                Op::Const(v) => {
+                    stack[sp] = V::constant(v);
+                    sp += 1;
+                }
+                Op::Z => {
+                    stack[sp] = z;
+                    sp += 1;
+                }
+                Op::Pixel => {
+                    stack[sp] = pixel;
+                    sp += 1;
+                }
+                Op::Neg => stack[sp - 1] = -stack[sp - 1],
+                Op::PowInt(n) => stack[sp - 1] = stack[sp - 1].powi(n),
+                Op::Call(func) => stack[sp - 1] = stack[sp - 1].call(func),
+                Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow => {
+                    sp -= 1;
+                    let (a, b) = (stack[sp - 1], stack[sp]);
+                    stack[sp - 1] = match op {
+                        Op::Add => a + b,
+                        Op::Sub => a - b,
+                        Op::Mul => a * b,
+                        Op::Div => a / b,
+                        _ => a.pow(b),
+                    };
+                }
+            }
+        }
+        stack[0]
+    }
+}
+
+/// Append the ops for `expr` and return the stack depth they need.
+fn emit(expr: &Expr, ops: &mut Vec<Op>) -> usize {
+    match expr {
+        Expr::Const(v) => {
+            ops.push(Op::Const(*v));
+            1
+        }
+        Expr::Z => {
+            ops.push(Op::Z);
+            1
+        }
+        Expr::Pixel => {
+            ops.push(Op::Pixel);
+            1
+        }
+        Expr::Neg(a) => {
+            let depth = emit(a, ops);
+            ops.push(Op::Neg);
+            depth
+        }
+        Expr::Call(func, a) => {
+            let depth = emit(a, ops);
+            ops.push(Op::Call(*func));
+            depth
+        }
+        Expr::Binary(BinOp::Pow, a, b) if as_small_int(b).is_some() => {
+            let depth = emit(a, ops);
+            ops.push(Op::PowInt(as_small_int(b).unwrap_or(1)));
+            depth
+        }
+        Expr::Binary(op, a, b) => {
+            let left = emit(a, ops);
+            let right = emit(b, ops);
+            ops.push(match op {
+                BinOp::Add => Op::Add,
+                BinOp::Sub => Op::Sub,
+                BinOp::Mul => Op::Mul,
+                BinOp::Div => Op::Div,
+                BinOp::Pow => Op::Pow,
+            });
+            left.max(right + 1)
+        }
+    }
+}
+
+fn as_small_int(expr: &Expr) -> Option<i32> {
+    match expr {
+        Expr::Const(k) if k.im == 0.0 && k.re.fract() == 0.0 && k.re.abs() <= 64.0 => {
+            Some(k.re as i32)
+        }
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Complex functions
+// ---------------------------------------------------------------------------
+
+fn exp(z: Complex) -> Complex {
+    let (sin, cos) = z.im.sin_cos();
+    let r = z.re.exp();
+    Complex::new(r * cos, r * sin)
+}
+
+/// Principal branch.
+fn ln(z: Complex) -> Complex {
+    Complex::new(z.norm().ln(), z.im.atan2(z.re))
+}
+
+/// Principal branch.
+fn sqrt(z: Complex) -> Complex {
+    let r = z.norm();
+    let re = ((r + z.re) * 0.5).sqrt();
+    let im = ((r - z.re) * 0.5).sqrt();
+    let im = if z.im < 0.0 { -im } else { im };
+    Complex::new(re, im)
+}
+
+fn sin(z: Complex) -> Complex {
+    let (s, c) = z.re.sin_cos();
+    Complex::new(s * z.im.cosh(), c * z.im.sinh())
+}
+
+fn cos(z: Complex) -> Complex {
+    let (s, c) = z.re.sin_cos();
+    Complex::new(c * z.im.cosh(), -s * z.im.sinh())
+}
+
+fn sinh(z: Complex) -> Complex {
+    let (s, c) = z.im.sin_cos();
+    Complex::new(z.re.sinh() * c, z.re.cosh() * s)
+}
+
+fn cosh(z: Complex) -> Complex {
+    let (s, c) = z.im.sin_cos();
+    Complex::new(z.re.cosh() * c, z.re.sinh() * s)
+}
+
+/// `a^b` on the principal branch; `0^b = 0`.
+fn pow(a: Complex, b: Complex) -> Complex {
+    if a == Complex::ZERO {
+        return Complex::ZERO;
+    }
+    exp(ln(a) * b)
+}
+
+fn powi(z: Complex, n: i32) -> Complex {
+    let mut result = Complex::new(1.0, 0.0);
+    let mut base = z;
+    let mut k = n.unsigned_abs();
+    while k > 0 {
+        if k & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        k >>= 1;
+    }
+    if n < 0 {
+        Complex::new(1.0, 0.0) / result
+    } else {
+        result
+    }
+}
+
+fn call(func: Func, z: Complex) -> Complex {
+    match func {
+        Func::Sin => sin(z),
+        Func::Cos => cos(z),
+        Func::Sinh => sinh(z),
+        Func::Cosh => cosh(z),
+        Func::Exp => exp(z),
+        Func::Log => ln(z),
+        Func::Sqrt => sqrt(z),
+        Func::Abs => Complex::new(z.norm(), 0.0),
+        Func::Conj => z.conj(),
+        Func::Re => Complex::new(z.re, 0.0),
+        Func::Im => Complex::new(z.im, 0.0),
+    }
+}
+
+fn binary(op: BinOp, a: Complex, b: Complex) -> Complex {
+    match op {
+        BinOp::Add => a + b,
+        BinOp::Sub => a - b,
+        BinOp::Mul => a * b,
+        BinOp::Div => a / b,
+        BinOp::Pow => match as_small_int(&Expr::Const(b)) {
+            Some(n) => powi(a, n),
+            None => pow(a, b),
+        },
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Operands: plain values, and dual numbers for the pixel derivative
+// ---------------------------------------------------------------------------
+
+trait Operand:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    fn constant(v: Complex) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn pow(self, b: Self) -> Self;
+    fn call(self, func: Func) -> Self;
+}
+
+impl Operand for Complex {
+    const ZERO: Self = Complex::ZERO;
+
+    fn constant(v: Complex) -> Self {
+        v
+    }
+
+    fn powi(self, n: i32) -> Self {
+        powi(self, n)
+    }
+
+    fn pow(self, b: Self) -> Self {
+        pow(self, b)
+    }
+
+    fn call(self, func: Func) -> Self {
+        call(func, self)
+    }
+}
+
+/// A value with its derivative with respect to the pixel. Non-holomorphic
+/// functions (`conj`, `abs`, `re`, `im`) use the derivative along the
+/// direction of `d`, which keeps its magnitude right for distance estimates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Dual {
+    v: Complex,
+    d: Complex,
+}
+
+impl Add for Dual {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            v: self.v + rhs.v,
+            d: self.d + rhs.d,
+        }
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            v: self.v - rhs.v,
+            d: self.d - rhs.d,
+        }
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            v: self.v * rhs.v,
+            d: self.d * rhs.v + self.v * rhs.d,
+        }
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Self {
+            v: self.v / rhs.v,
+            d: (self.d * rhs.v - self.v * rhs.d) / (rhs.v * rhs.v),
+        }
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            v: -self.v,
+            d: -self.d,
+        }
+    }
+}
+
+impl Operand for Dual {
+    const ZERO: Self = Self {
+        v: Complex::ZERO,
+        d: Complex::ZERO,
+    };
+
+    fn constant(v: Complex) -> Self {
+        Self {
+            v,
+            d: Complex::ZERO,
+        }
+    }
+
+    fn powi(self, n: i32) -> Self {
+        Self {
+            v: powi(self.v, n),
+            d: powi(self.v, n - 1) * self.d * n as f64,
+        }
+    }
+
+    fn pow(self, b: Self) -> Self {
+        let w = pow(self.v, b.v);
+        Self {
+            v: w,
+            d: w * (b.d * ln(self.v) + b.v * self.d / self.v),
+        }
+    }
+
+    fn call(self, func: Func) -> Self {
+        let (v, d) = (self.v, self.d);
+        let (value, slope) = match func {
+            Func::Sin => (sin(v), cos(v)),
+            Func::Cos => (cos(v), -sin(v)),
+            Func::Sinh => (sinh(v), cosh(v)),
+            Func::Cosh => (cosh(v), sinh(v)),
+            Func::Exp => (exp(v), exp(v)),
+            Func::Log => (ln(v), Complex::new(1.0, 0.0) / v),
+            Func::Sqrt => {
+                let s = sqrt(v);
+                (s, Complex::new(0.5, 0.0) / s)
+            }
+            Func::Abs => {
+                let r = v.norm();
+                let dr = if r > 0.0 { (v.conj() * d).re / r } else { 0.0 };
+                return Self {
+                    v: Complex::new(r, 0.0),
+                    d: Complex::new(dr, 0.0),
+                };
+            }
+            Func::Conj => {
+                return Self {
+                    v: v.conj(),
+                    d: d.conj(),
+                }
+            }
+            Func::Re => {
+                return Self {
+                    v: Complex::new(v.re, 0.0),
+                    d: Complex::new(d.re, 0.0),
+                }
+            }
+            Func::Im => {
+                return Self {
+                    v: Complex::new(v.im, 0.0),
+                    d: Complex::new(d.im, 0.0),
+                }
+            }
+        };
+        Self {
+            v: value,
+            d: slope * d,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Formula fractal
+// ---------------------------------------------------------------------------
+
+/// A compiled [`FormulaDefinition`].
+///
+/// The step and the initial value are parsed, constant-folded (parameters
+/// are constants) and compiled to postfix bytecode run on a fixed-size
+/// stack. Extras evaluate the same bytecode on dual numbers, which gives
+/// the pixel derivative for distance estimation without a separate formula.
+#[derive(Debug, Clone)]
+pub struct Formula {
+    params: FractalParams,
+    definition: FormulaDefinition,
+    step: Program,
+    initial: Program,
+    degree: Option<f64>,
+}
+
+impl Formula {
+    /// Compile `definition`. Its bailout replaces `params.escape_radius`.
+    pub fn compile(definition: &FormulaDefinition, params: FractalParams) -> crate::Result<Self> {
+        if !(definition.bailout > 0.0 && definition.bailout.is_finite()) {
+            return Err(CoreError::InvalidEscapeRadius(definition.bailout));
+        }
+        for (i, p) in definition.params.iter().enumerate() {
+            let valid_name = p
+                .name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic())
+                && p.name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            let message = if !valid_name {
+                format!("'{}' is not a valid parameter name", p.name)
+            } else if RESERVED.contains(&p.name.as_str()) || Func::from_name(&p.name).is_some() {
+                format!("'{}' is a reserved name", p.name)
+            } else if definition.params[..i].iter().any(|q| q.name == p.name) {
+                format!("parameter '{}' is defined twice", p.name)
+            } else {
+                continue;
+            };
+            return Err(CoreError::InvalidFormula {
+                part: "parameter",
+                position: 0,
+                message,
+            });
+        }
+
+        let parse = |text: &str, allow_z: bool, part: &'static str| {
+            let syntax = |(position, message)| CoreError::InvalidFormula {
+                part,
+                position,
+                message,
+            };
+            let mut parser = Parser {
+                tokens: tokenize(text).map_err(syntax)?,
+                pos: 0,
+                params: &definition.params,
+                allow_z,
+                depth: 0,
+            };
+            parser.program().map(fold).map_err(syntax)
+        };
+        let step = parse(&definition.formula, true, "formula")?;
+        let initial = parse(&definition.initial_z, false, "initial z")?;
+
+        let to_program = |expr: &Expr, part| {
+            Program::compile(expr).map_err(|(position, message)| CoreError::InvalidFormula {
+                part,
+                position,
+                message,
+            })
+        };
+        let mut params = params;
+        params.set_escape_radius(definition.bailout);
+        Ok(Self {
+            params,
+            definition: definition.clone(),
+            step: to_program(&step, "formula")?,
+            initial: to_program(&initial, "initial z")?,
+            degree: degree(&step),
+        })
+    }
+
+    pub fn definition(&self) -> &FormulaDefinition {
+        &self.definition
+    }
+
+    /// Evaluate one step at `z` for `pixel`.
+    pub fn step(&self, z: Complex, pixel: Complex) -> Complex {
+        self.step.eval(&mut [Complex::ZERO; MAX_STACK], z, pixel)
+    }
+
+    /// Escape test that also catches NaN and infinite orbits.
+    #[inline]
+    fn escaped(&self, norm_sq: f64) -> Option<f64> {
+        let bailout_sq = self.params.escape_radius_sq();
+        if norm_sq <= bailout_sq {
+            None
+        } else if norm_sq.is_finite() {
+            Some(norm_sq)
+        } else {
+            Some(bailout_sq)
+        }
+    }
+}
+
+impl Fractal for Formula {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let mut stack = [Complex::ZERO; MAX_STACK];
+        let mut z = self.initial.eval(&mut stack, Complex::ZERO, point);
+
+        // Brent's cycle detection state.
+        let mut old_z = z;
+        let mut period: u32 = 0;
+        let mut check: u32 = 3;
+
+        for n in 0..self.params.max_iterations {
+            z = self.step.eval(&mut stack, z, point);
+
+            if let Some(norm_sq) = self.escaped(z.norm_sq()) {
+                return IterationResult::Escaped {
+                    iterations: n,
+                    norm_sq,
+                };
+            }
+
+            if n >= 32 && n & 3 == 0 {
+                if is_cycle(z, old_z) {
+                    return IterationResult::Interior;
+                }
+                period += 1;
+                if period > check {
+                    old_z = z;
+                    period = 0;
+                    check = check.saturating_mul(2);
+                }
+            }
+        }
+
+        IterationResult::Interior
+    }
+
+    fn iterate_with_extras(
+        &self,
+        point: Complex,
+        opts: &ExtrasOptions,
+    ) -> (IterationResult, IterationExtras) {
+        let stripe_density = opts.stripe_density;
+        let mut stack = [Dual::ZERO; MAX_STACK];
+        let pixel = Dual {
+            v: point,
+            d: Complex::new(1.0, 0.0),
+        };
+        let mut z = self.initial.eval(&mut stack, Dual::ZERO, pixel);
+        let mut stripe_sum = 0.0f64;
+        let max_iter = self.params.max_iterations;
+
+        let mut old_z = z.v;
+        let mut period: u32 = 0;
+        let mut check: u32 = 3;
+
+        for n in 0..max_iter {
+            z = self.step.eval(&mut stack, z, pixel);
+            stripe_sum += 0.5 * (stripe_density * z.v.im.atan2(z.v.re)).sin() + 0.5;
+
+            if let Some(norm_sq) = self.escaped(z.v.norm_sq()) {
+                let z_norm = norm_sq.sqrt();
+                let dz_norm = z.d.norm();
+                let distance = if dz_norm > 0.0 && dz_norm.is_finite() {
+                    z_norm * z_norm.ln() / dz_norm
+                } else {
+                    0.0
+                };
+                return (
+                    IterationResult::Escaped {
+                        iterations: n,
+                        norm_sq,
+                    },
+                    IterationExtras {
+                        distance,
+                        stripe_avg: 0.0,
+                        final_arg: z.v.im.atan2(z.v.re),
+                        ..IterationExtras::default()
+                    },
+                );
+            }
+
+            if n >= 32 && n & 3 == 0 {
+                if is_cycle(z.v, old_z) {
+                    let stripe_avg = if n > 0 { stripe_sum / n as f64 } else { 0.0 };
+                    return (
+                        IterationResult::Interior,
+                        IterationExtras {
+                            distance: 0.0,
+                            stripe_avg,
+                            ..IterationExtras::default()
+                        },
+                    );
+                }
+                period += 1;
+                if period > check {
+                    old_z = z.v;
+                    period = 0;
+                    check = check.saturating_mul(2);
+                }
+            }
+        }
+
+        let stripe_avg = if max_iter > 0 {
+            stripe_sum / max_iter as f64
+        } else {
+            0.0
+        };
+        (
+            IterationResult::Interior,
+            IterationExtras {
+                distance: 0.0,
+                stripe_avg,
+                ..IterationExtras::default()
+            },
+        )
+    }
+
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let mut stack = [Complex::ZERO; MAX_STACK];
+        let z0 = self.initial.eval(&mut stack, Complex::ZERO, point);
+        let escape_radius_sq = self.params.escape_radius_sq();
+        Some(record_orbit(z0, limit, escape_radius_sq, |z| {
+            self.step.eval(&mut stack, z, point)
+        }))
+    }
+
+    fn params(&self) -> &FractalParams {
+        &self.params
+    }
+
+    /// The degree of the step in `z` when it is polynomial-like (`z^3 + c*z`
+    /// gives 3), otherwise 2.
+    fn degree(&self) -> f64 {
+        self.degree.filter(|&d| d > 1.0).unwrap_or(2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mandelbrot::Mandelbrot;
+
+    fn definition(formula: &str) -> FormulaDefinition {
+        FormulaDefinition {
+            formula: formula.to_string(),
+            ..FormulaDefinition::default()
+        }
+    }
+
+    fn compile(formula: &str) -> crate::Result<Formula> {
+        Formula::compile(&definition(formula), FractalParams::default())
+    }
+
+    /// Value of a formula without `z` or the pixel.
+    fn value(formula: &str) -> Complex {
+        compile(formula).unwrap().step(Complex::ZERO, Complex::ZERO)
+    }
+
+    fn close(a: Complex, b: Complex) -> bool {
+        (a - b).norm() < 1e-12
+    }
+
+    fn error_column(formula: &str) -> usize {
+        match compile(formula) {
+            Err(CoreError::InvalidFormula { position, .. }) => position,
+            other => panic!("expected a formula error for {formula:?}, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(value("2 + 3*4"), Complex::new(14.0, 0.0));
+        assert_eq!(value("-2^2"), Complex::new(-4.0, 0.0));
+        assert_eq!(value("2^3^2"), Complex::new(512.0, 0.0));
+        assert_eq!(value("(1 + 2i)*(3 - i)"), Complex::new(5.0, 5.0));
+        assert_eq!(value("8/4/2"), Complex::new(1.0, 0.0));
+        assert_eq!(value("2^-1"), Complex::new(0.5, 0.0));
+        assert_eq!(value("1e-3 * 1E+3"), Complex::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn functions_and_constants() {
+        assert!(close(value("exp(i*pi)"), Complex::new(-1.0, 0.0)));
+        assert!(close(value("sqrt(-4)"), Complex::new(0.0, 2.0)));
+        assert!(close(value("log(e)"), Complex::new(1.0, 0.0)));
+        assert!(close(value("|3 + 4i|"), Complex::new(5.0, 0.0)));
+        assert!(close(
+            value("conj(1 + 2i) + re(3i) + im(3i)"),
+            Complex::new(4.0, -2.0)
+        ));
+        assert!(close(
+            value("sin(0.5 + 0.2i)^2 + cos(0.5 + 0.2i)^2"),
+            Complex::new(1.0, 0.0)
+        ));
+        assert!(close(
+            value("cosh(0.3 - i)^2 - sinh(0.3 - i)^2"),
+            Complex::new(1.0, 0.0)
+        ));
+        assert!(close(value("(2i)^(0.5)"), Complex::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn named_params_and_pixel() {
+        let def = FormulaDefinition {
+            formula: "z = k*z + pixel - c".to_string(),
+            initial_z: "2 * k".to_string(),
+            params: vec![FormulaParam {
+                name: "k".to_string(),
+                value: Complex::new(0.0, 1.0),
+            }],
+            ..FormulaDefinition::default()
+        };
+        let f = Formula::compile(&def, FractalParams::default()).unwrap();
+        assert_eq!(
+            f.step(Complex::new(2.0, 0.0), Complex::new(7.0, 1.0)),
+            Complex::new(0.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_column() {
+        assert_eq!(error_column("z^2 + q"), 6);
+        assert_eq!(error_column("z^2 + "), 6);
+        assert_eq!(error_column("(z^2 + c"), 8);
+        assert_eq!(error_column("z^2 $ c"), 4);
+        assert_eq!(error_column("z z"), 2);
+        assert_eq!(error_column("sin z"), 4);
+
+        let initial = FormulaDefinition {
+            initial_z: "z + 1".to_string(),
+            ..FormulaDefinition::default()
+        };
+        assert!(matches!(
+            Formula::compile(&initial, FractalParams::default()),
+            Err(CoreError::InvalidFormula {
+                part: "initial z",
+                position: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        let with_param = |name: &str| FormulaDefinition {
+            params: vec![FormulaParam {
+                name: name.to_string(),
+                value: Complex::ZERO,
+            }],
+            ..FormulaDefinition::default()
+        };
+        for name in ["z", "sin", "2k", "", "a b"] {
+            assert!(Formula::compile(&with_param(name), FractalParams::default()).is_err());
+        }
+        let bailout = FormulaDefinition {
+            bailout: 0.0,
+            ..FormulaDefinition::default()
+        };
+        assert!(Formula::compile(&bailout, FractalParams::default()).is_err());
+
+        let deep = format!("{}z{}", "(".repeat(40), " + 1)".repeat(40));
+        assert!(compile(&deep).is_ok(), "left-leaning sums stay shallow");
+        let nested = format!("{}z + 1{}", "sin(".repeat(40), ")".repeat(40));
+        assert!(compile(&nested).is_ok());
+        let right = format!("{}z{}", "z*(1 + ".repeat(40), ")".repeat(40));
+        assert!(compile(&right).is_err());
+    }
+
+    #[test]
+    fn hostile_nesting_is_a_syntax_error() {
+        let parens = format!("{}z{}", "(".repeat(100_000), ")".repeat(100_000));
+        let signs = format!("{}z", "-".repeat(100_000));
+        let powers = format!("z{}", "^z".repeat(100_000));
+        let chain = format!("z{}", " + z".repeat(100_000));
+        for formula in [parens, signs, powers, chain] {
+            assert!(matches!(
+                compile(&formula),
+                Err(CoreError::InvalidFormula {
+                    part: "formula",
+                    ..
+                })
+            ));
+        }
+        // Short enough to tokenize, too deep to parse.
+        let deep = format!("{}z", "-".repeat(300));
+        assert!(matches!(
+            compile(&deep),
+            Err(CoreError::InvalidFormula { message, .. }) if message.contains("nested")
+        ));
+        let limit = format!("{}z{}", "(".repeat(200), ")".repeat(200));
+        assert!(compile(&limit).is_ok());
+    }
+
+    #[test]
+    fn constants_are_folded() {
+        let f = compile("z^2 + (1 + 2) * sin(0)").unwrap();
+        assert_eq!(
+            f.step.ops,
+            vec![Op::Z, Op::PowInt(2), Op::Const(Complex::ZERO), Op::Add]
+        );
+    }
+
+    #[test]
+    fn quadratic_formula_matches_mandelbrot() {
+        let params = FractalParams::default();
+        let f = compile("z^2 + c").unwrap();
+        let m = Mandelbrot::new(params);
+        let mut mismatches = 0;
+        for i in 0..=30 {
+            for k in 0..=20 {
+                let c = Complex::new(-2.2 + i as f64 * 0.1, -1.2 + k as f64 * 0.12);
+                let (r, e) = f.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(1.0));
+                assert_eq!(r, f.iterate(c), "extras path at {c}");
+                let (r_m, e_m) = m.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(1.0));
+                if r.class() != r_m.class() {
+                    mismatches += 1;
+                } else if e_m.distance > 0.0 {
+                    assert!((e.distance - e_m.distance).abs() <= 1e-9 * e_m.distance);
+                }
+            }
+        }
+        assert!(mismatches <= 2, "{mismatches} mismatches");
+    }
+
+    #[test]
+    fn degree_analysis() {
+        assert_eq!(compile("z = z^3 + c*z + 0.5").unwrap().degree(), 3.0);
+        assert_eq!(compile("z*z*z*z/2 + c").unwrap().degree(), 4.0);
+        assert_eq!(compile("|z|^2.5 + c").unwrap().degree(), 2.5);
+        assert_eq!(compile("sin(z) + c").unwrap().degree(), 2.0);
+        assert_eq!(compile("1/z + c").unwrap().degree(), 2.0);
+    }
+
+    #[test]
+    fn nan_orbits_escape() {
+        let f = compile("z/0 + c").unwrap();
+        assert!(matches!(
+            f.iterate(Complex::new(0.1, 0.1)),
+            IterationResult::Escaped { iterations: 0, .. }
+        ));
+        let orbit = f.orbit(Complex::new(0.1, 0.1), 100).unwrap();
+        assert_eq!(orbit.len(), 2, "the orbit ends at the first NaN");
+    }
+
+    #[test]
+    fn orbit_starts_at_the_initial_z() {
+        let f = compile("z^2 + c").unwrap();
+        let orbit = f.orbit(Complex::new(-1.0, 0.0), 4).unwrap();
+        let re: Vec<f64> = orbit.iter().map(|z| z.to_complex().re).collect();
+        assert_eq!(re, [0.0, -1.0, 0.0, -1.0, 0.0]);
+
+        let def = FormulaDefinition {
+            initial_z: "pixel".to_string(),
+            ..FormulaDefinition::default()
+        };
+        let f = Formula::compile(&def, FractalParams::default()).unwrap();
+        let orbit = f.orbit(Complex::new(1.0, 0.0), 100).unwrap();
+        assert_eq!(orbit[0].to_complex(), Complex::new(1.0, 0.0));
+        assert_eq!(orbit.last().unwrap().to_complex(), Complex::new(5.0, 0.0));
+    }
+
+    #[test]
+    fn definition_round_trips_and_displays() {
+        let def = FormulaDefinition {
+            formula: "z = z^3 + k".to_string(),
+            initial_z: "pixel".to_string(),
+            bailout: 4.0,
+            params: vec![FormulaParam {
+                name: "k".to_string(),
+                value: Complex::new(0.5, -0.25),
+            }],
+        };
+        let json = serde_json::to_string(&def).unwrap();
+        assert_eq!(
+            serde_json::from_str::<FormulaDefinition>(&json).unwrap(),
+            def
+        );
+        assert_eq!(
+            def.to_string(),
+            "z = z^3 + k; z0 = pixel; bailout = 4; k = 0.5-0.25i"
+        );
+        let minimal: FormulaDefinition = serde_json::from_str(r#"{"formula":"z^2+c"}"#).unwrap();
+        assert_eq!(minimal.initial_z, "0");
+        assert_eq!(minimal.bailout, 2.0);
+    }
+}
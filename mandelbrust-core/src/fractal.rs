@@ -1,5 +1,9 @@
 use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
 use crate::error::CoreError;
+use crate::numeric::{ComplexNum, Real};
+use crate::orbit_average::OrbitAverage;
+use crate::orbit_trap::OrbitTrap;
 
 /// The result of iterating a single point.
 ///
@@ -15,6 +19,15 @@ pub enum IterationResult {
     /// The point is (likely) inside the set — it did not escape within
     /// `max_iterations`, or was detected as periodic.
     Interior,
+
+    /// Root-finding fractals: the orbit settled on root `root_index`
+    /// after `iterations` steps.
+    Converged { root_index: u32, iterations: u32 },
+
+    /// Fractals that measure a signed real quantity per point instead of
+    /// counting iterations, e.g. a Lyapunov exponent. Negative values mean
+    /// stability and positive ones chaos; `+∞` marks a diverged orbit.
+    Measured { value: f64 },
 }
 
 /// Extra per-pixel data computed alongside the main iteration when advanced
@@ -28,6 +41,21 @@ pub struct IterationExtras {
     /// Stripe average for interior points.
     /// Zero for escaped points.
     pub stripe_avg: f64,
+    /// Closest approach of the orbit to [`ExtrasOptions::orbit_trap`].
+    /// Infinite when no trap was tracked.
+    pub trap_distance: f64,
+    /// Smoothed [`ExtrasOptions::exterior_average`] for escaped points, in
+    /// `[0, 1]`. `NaN` when none was measured.
+    pub exterior_avg: f64,
+    /// Argument of the final `z` for escaped points, in `[−π, π]`. `NaN`
+    /// for interior points.
+    pub final_arg: f64,
+    /// Length of the attracting cycle of an interior point; `0` when unknown.
+    pub period: u32,
+    /// `|λ|` of that cycle; `NaN` when unknown.
+    pub multiplier: f64,
+    /// Interior distance estimate to the boundary; `NaN` when unknown.
+    pub interior_distance: f64,
 }
 
 impl Default for IterationExtras {
@@ -35,6 +63,48 @@ impl Default for IterationExtras {
         Self {
             distance: 0.0,
             stripe_avg: 0.0,
+            trap_distance: f64::INFINITY,
+            exterior_avg: f64::NAN,
+            final_arg: f64::NAN,
+            period: 0,
+            multiplier: f64::NAN,
+            interior_distance: f64::NAN,
+        }
+    }
+}
+
+/// What [`Fractal::iterate_with_extras`] computes besides the result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExtrasOptions {
+    /// Frequency of the stripe pattern for stripe averages.
+    pub stripe_density: f64,
+    /// Shape to measure orbits against, if any.
+    pub orbit_trap: Option<OrbitTrap>,
+    /// Average to measure along escaping orbits, if any.
+    pub exterior_average: Option<OrbitAverage>,
+    /// Blend the exterior average across the last iteration.
+    pub smooth_average: bool,
+    /// Measure the attracting cycle of interior points.
+    pub analyze_interior: bool,
+}
+
+impl ExtrasOptions {
+    pub fn with_stripe_density(stripe_density: f64) -> Self {
+        Self {
+            stripe_density,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ExtrasOptions {
+    fn default() -> Self {
+        Self {
+            stripe_density: 1.0,
+            orbit_trap: None,
+            exterior_average: None,
+            smooth_average: true,
+            analyze_interior: false,
         }
     }
 }
@@ -49,10 +119,49 @@ impl IterationResult {
         match self {
             Self::Escaped { iterations, .. } => *iterations as u64,
             Self::Interior => u64::MAX,
+            // Offset past every escape count so basins never match escapes.
+            Self::Converged {
+                root_index,
+                iterations,
+            } => ((*root_index as u64 + 1) << 32) | *iterations as u64,
+            // Only ever compared with other measurements of the same
+            // fractal, so the raw bits suffice.
+            Self::Measured { value } => value.to_bits(),
         }
     }
 }
 
+/// Symmetries of the plane a fractal is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symmetry {
+    /// The set is its own mirror image across the real axis, so a renderer
+    /// may compute one half of a view straddling it and reflect the other.
+    pub conjugate: bool,
+
+    /// Order of rotational symmetry about the origin (`1` for none).
+    pub rotational_order: u32,
+
+    /// The familiar picture is the real-axis mirror of the textbook
+    /// formula. Such fractals iterate the conjugated formula instead, so
+    /// they appear upright with the imaginary axis pointing up.
+    pub flipped: bool,
+}
+
+impl Symmetry {
+    pub const NONE: Self = Self {
+        conjugate: false,
+        rotational_order: 1,
+        flipped: false,
+    };
+
+    /// Mirror symmetry across the real axis only.
+    pub const CONJUGATE: Self = Self {
+        conjugate: true,
+        rotational_order: 1,
+        flipped: false,
+    };
+}
+
 /// Parameters controlling fractal iteration.
 ///
 /// The cached `escape_radius_sq` field is automatically recomputed on
@@ -140,9 +249,10 @@ impl Default for FractalParams {
 
 /// Trait implemented by all fractal types.
 ///
-/// Designed for **static dispatch** — renderers should be generic over
-/// `F: Fractal` rather than using `dyn Fractal`, so the compiler can
-/// inline and optimize the hot iteration loop.
+/// Designed for **static dispatch** — renderers are generic over
+/// `F: Fractal + ?Sized`, so each type's iteration loop is compiled on its
+/// own. The [registry](crate::registry) hands out boxed trait objects; that
+/// costs one indirect call per pixel, not per iteration.
 pub trait Fractal {
     /// Iterate a single point and return the result.
     ///
@@ -153,23 +263,32 @@ pub trait Fractal {
     /// `point` is the **delta from the stored center** (from [`Viewport::pixel_to_delta`]).
     fn iterate(&self, point: Complex) -> IterationResult;
 
-    /// Iterate a single point, also computing extras (distance estimate and
-    /// stripe average) for advanced coloring modes.
-    ///
-    /// `stripe_density` controls the frequency of the stripe pattern for
-    /// interior coloring.
+    /// Iterate a single point, also computing extras (distance estimate,
+    /// stripe average, orbit trap distance) for advanced coloring modes.
     ///
     /// The default implementation delegates to [`iterate`](Self::iterate) and
-    /// returns zero extras.
+    /// returns default extras.
     fn iterate_with_extras(
         &self,
         point: Complex,
-        stripe_density: f64,
+        opts: &ExtrasOptions,
     ) -> (IterationResult, IterationExtras) {
-        let _ = stripe_density;
+        let _ = opts;
         (self.iterate(point), IterationExtras::default())
     }
 
+    /// The orbit `z₀, z₁, …` of `point` (the same coordinate as for
+    /// [`iterate`](Self::iterate)), in absolute coordinates on the plane it
+    /// moves in. It stops after `limit` steps or at the first point past
+    /// the escape radius.
+    ///
+    /// Returns `None` for fractals without a single escape-time orbit to
+    /// show (the default).
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let _ = (point, limit);
+        None
+    }
+
     /// Access the iteration parameters.
     fn params(&self) -> &FractalParams;
 
@@ -179,6 +298,64 @@ pub trait Fractal {
     fn uses_delta_coordinates(&self) -> bool {
         false
     }
+
+    /// Binary exponent of the deltas passed to [`iterate`](Self::iterate):
+    /// the true delta is `point · 2^exponent`. Only meaningful when
+    /// [`uses_delta_coordinates`](Self::uses_delta_coordinates) is `true`;
+    /// the renderer passes it to [`Viewport::pixel_to_delta_scaled`].
+    fn delta_exponent(&self) -> i64 {
+        0
+    }
+
+    /// Iterations every pixel skips before [`iterate`](Self::iterate) does
+    /// any per-pixel work (series approximation at deep zoom). Reported
+    /// results still count from iteration zero.
+    fn skipped_iterations(&self) -> u32 {
+        0
+    }
+
+    /// Symmetries of the fractal's plane. Renderers only mirror across the
+    /// real axis when [`Symmetry::conjugate`] is set.
+    fn symmetry(&self) -> Symmetry {
+        Symmetry::NONE
+    }
+
+    /// Growth rate of `|z|` per iteration near escape, as a power of `|z|`.
+    /// Smooth coloring uses it as the base of its double logarithm.
+    fn degree(&self) -> f64 {
+        2.0
+    }
+}
+
+/// Follow `z₀` under `step` for [`Fractal::orbit`]: up to `limit` steps,
+/// ending with the first point whose squared norm exceeds `escape_radius_sq`
+/// or is not finite.
+pub(crate) fn record_orbit<C: ComplexNum>(
+    z0: C,
+    limit: u32,
+    escape_radius_sq: f64,
+    mut step: impl FnMut(C) -> C,
+) -> Vec<ComplexDD> {
+    let mut z = z0;
+    let mut orbit = vec![z.to_complex_dd()];
+    for _ in 0..limit {
+        z = step(z);
+        orbit.push(z.to_complex_dd());
+        let norm_sq = z.norm_sq().to_f64();
+        if norm_sq > escape_radius_sq || !norm_sq.is_finite() {
+            break;
+        }
+    }
+    orbit
+}
+
+/// Brent periodicity test shared by the escape-time formulas: `true` if the
+/// orbit point `z` lies within [`Real::PERIOD_TOLERANCE`] of the saved `old_z`.
+#[inline]
+pub(crate) fn is_cycle<C: ComplexNum>(z: C, old_z: C) -> bool {
+    let tolerance = C::Real::PERIOD_TOLERANCE;
+    (z.re() - old_z.re()).abs().to_f64() < tolerance
+        && (z.im() - old_z.im()).abs().to_f64() < tolerance
 }
 
 #[cfg(test)]
@@ -211,4 +388,20 @@ mod tests {
         assert!(FractalParams::new(256, f64::NAN).is_err());
         assert!(FractalParams::new(256, f64::INFINITY).is_err());
     }
+
+    #[test]
+    fn converged_classes_are_distinct() {
+        let converged = |root_index, iterations| IterationResult::Converged {
+            root_index,
+            iterations,
+        };
+        let escaped = IterationResult::Escaped {
+            iterations: 5,
+            norm_sq: 9.0,
+        };
+        assert_ne!(converged(0, 5).class(), escaped.class());
+        assert_ne!(converged(0, 5).class(), converged(1, 5).class());
+        assert_ne!(converged(0, 5).class(), IterationResult::Interior.class());
+        assert_eq!(converged(2, 7).class(), converged(2, 7).class());
+    }
 }
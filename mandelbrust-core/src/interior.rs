@@ -0,0 +1,187 @@
+//! Interior analysis of the Mandelbrot set: the attracting cycle an interior
+//! orbit settles on, its multiplier and an interior distance estimate.
+//!
+//! The periodic point is refined in the precision the orbit was iterated
+//! in, so deep-zoom pixels sharing an `f64` coordinate still get their own
+//! cycle; derivatives are products of orbit points and stay in `f64`.
+
+use crate::complex::Complex;
+use crate::numeric::{ComplexNum, Real};
+
+/// Longest cycle [`analyze`] searches for without a known cycle length.
+pub const MAX_PERIOD: u32 = 4096;
+
+/// How close the orbit must return to its start to count as one period
+/// when no cycle length is known.
+const RETURN_TOLERANCE_SQ: f64 = 1e-12;
+
+/// How much farther than over the whole known cycle the orbit may return
+/// after one of its divisors and still count as one period.
+const DIVISOR_SLACK: f64 = 4.0;
+
+/// Newton steps refining the periodic point.
+const NEWTON_STEPS: usize = 16;
+
+/// The attracting cycle of an interior point `c`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InteriorInfo {
+    /// Length of the cycle.
+    pub period: u32,
+    /// `|λ|`, where `λ = ∏ 2·zₖ` over the cycle: `0` at the nucleus of the
+    /// hyperbolic component, `1` on its boundary.
+    pub multiplier: f64,
+    /// Estimated distance from `c` to the boundary of the set.
+    pub distance: f64,
+}
+
+/// `f^p(z)` and its derivative `∏ 2·zₖ` for `f(z) = z² + c`.
+fn cycle<C: ComplexNum>(z: C, c: C, period: u32) -> (C, Complex) {
+    let mut z = z;
+    let mut dz = Complex::new(1.0, 0.0);
+    for _ in 0..period {
+        dz = z.to_complex() * dz * 2.0;
+        z = z.square_add(c);
+    }
+    (z, dz)
+}
+
+/// The period of the cycle `z` has settled on: the shortest divisor of the
+/// known `cycle_len` the orbit returns about as close after, or else the
+/// first close return within [`MAX_PERIOD`] steps.
+fn period<C: ComplexNum>(z: C, c: C, cycle_len: Option<u32>) -> Option<u32> {
+    let distance = |w: C| (w - z).to_complex().norm();
+    let mut w = z;
+    let Some(cycle_len) = cycle_len.filter(|&n| n > 0) else {
+        return (1..=MAX_PERIOD).find(|_| {
+            w = w.square_add(c);
+            distance(w).powi(2) < RETURN_TOLERANCE_SQ
+        });
+    };
+    let returns: Vec<(u32, f64)> = (1..=cycle_len)
+        .filter_map(|k| {
+            w = w.square_add(c);
+            cycle_len.is_multiple_of(k).then(|| (k, distance(w)))
+        })
+        .collect();
+    let tolerance = DIVISOR_SLACK * returns[returns.len() - 1].1.max(C::Real::PERIOD_TOLERANCE);
+    returns
+        .iter()
+        .find(|&&(_, d)| d <= tolerance)
+        .map(|&(k, _)| k)
+}
+
+/// Analyze the cycle of `c` from an orbit point `z` that has already
+/// settled near it, in the precision of `C`. `cycle_len` is a length after
+/// which the orbit was seen to repeat, such as Brent's check finds (any
+/// multiple of the period); without it the period is searched for up to
+/// [`MAX_PERIOD`]. Returns `None` when no attracting cycle is found.
+pub fn analyze<C: ComplexNum>(z: C, c: C, cycle_len: Option<u32>) -> Option<InteriorInfo> {
+    let period = period(z, c, cycle_len)?;
+
+    // Newton on f^p(w) − w = 0 for the periodic point itself. The residual
+    // needs the full precision; the step is small enough for `f64`.
+    let one = Complex::new(1.0, 0.0);
+    let tolerance_sq = C::Real::PERIOD_TOLERANCE.powi(2);
+    let mut w = z;
+    for _ in 0..NEWTON_STEPS {
+        let (fw, dw) = cycle(w, c, period);
+        let step = (fw - w).to_complex() / (dw - one);
+        w = w - C::from_complex(step);
+        if step.norm_sq() < tolerance_sq {
+            break;
+        }
+    }
+
+    // Derivatives over one cycle for the interior distance estimate:
+    // ∂/∂z, ∂/∂c, ∂²/∂z² and ∂²/∂c∂z of f^p at the periodic point.
+    let (mut dz, mut dc) = (one, Complex::ZERO);
+    let (mut dzdz, mut dcdz) = (Complex::ZERO, Complex::ZERO);
+    for _ in 0..period {
+        let z = w.to_complex();
+        dcdz = (z * dcdz + dz * dc) * 2.0;
+        dc = z * dc * 2.0 + one;
+        dzdz = (dz * dz + z * dzdz) * 2.0;
+        dz = z * dz * 2.0;
+        w = w.square_add(c);
+    }
+
+    let multiplier = dz.norm();
+    let distance = (1.0 - dz.norm_sq()) / (dcdz + dzdz * dc / (one - dz)).norm();
+    (multiplier < 1.0 && distance.is_finite()).then_some(InteriorInfo {
+        period,
+        multiplier,
+        distance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complex_dd::ComplexDD;
+    use crate::double_double::DoubleDouble;
+
+    #[test]
+    fn nucleus_of_main_cardioid() {
+        // c = 0: the fixed point 0 is superattracting, and the nearest
+        // boundary point is the cusp-facing 1/4. The estimate `b` bounds
+        // the true distance `d` by b/4 ≤ d ≤ b.
+        let info = analyze(Complex::ZERO, Complex::ZERO, None).unwrap();
+        assert_eq!(info.period, 1);
+        assert_eq!(info.multiplier, 0.0);
+        assert!((0.25..=1.0).contains(&info.distance));
+    }
+
+    #[test]
+    fn period_two_bulb() {
+        // c = −1 cycles 0, −1, 0, … with λ = 0.
+        let c = Complex::new(-1.0, 0.0);
+        let info = analyze(Complex::ZERO, c, None).unwrap();
+        assert_eq!(info.period, 2);
+        assert!(info.multiplier < 1e-12);
+
+        // Off the nucleus |λ| = 4·|c + 1| inside the bulb of radius 1/4.
+        let c = Complex::new(-1.1, 0.05);
+        let mut z = Complex::ZERO;
+        for _ in 0..1000 {
+            z = z * z + c;
+        }
+        let info = analyze(z, c, None).unwrap();
+        assert_eq!(info.period, 2);
+        let expected = 4.0 * (c + Complex::new(1.0, 0.0)).norm();
+        assert!((info.multiplier - expected).abs() < 1e-9);
+        // The bulb edge is 0.25 − |c + 1| away.
+        let edge = 0.25 - (c + Complex::new(1.0, 0.0)).norm();
+        assert!(info.distance >= edge * (1.0 - 1e-9) && info.distance <= 4.0 * edge);
+    }
+
+    #[test]
+    fn escaping_orbit_has_no_cycle() {
+        assert!(analyze(Complex::new(3.0, 0.0), Complex::new(1.0, 0.0), None).is_none());
+    }
+
+    #[test]
+    fn known_cycle_lengths_give_the_period() {
+        // Brent may see the period-2 cycle of c = −1 repeat only after 6.
+        let c = Complex::new(-1.0, 0.0);
+        let info = analyze(Complex::ZERO, c, Some(6)).unwrap();
+        assert_eq!(info.period, 2);
+    }
+
+    #[test]
+    fn cycles_are_resolved_below_f64() {
+        // |λ| = 4·|c + 1| in the period-2 bulb; `f64` would round the
+        // offset away and put every such `c` on the nucleus.
+        let c = ComplexDD::new(DoubleDouble::new(-1.0, 1e-20), DoubleDouble::ZERO);
+        let mut z = ComplexDD::ZERO;
+        for _ in 0..64 {
+            z = z.square_add(c);
+        }
+        let info = analyze(z, c, Some(2)).unwrap();
+        assert_eq!(info.period, 2);
+        assert!(
+            (info.multiplier / 4e-20 - 1.0).abs() < 1e-9,
+            "{}",
+            info.multiplier
+        );
+    }
+}
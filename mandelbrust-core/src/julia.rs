@@ -1,30 +1,68 @@
 use crate::complex::Complex;
-use crate::fractal::{Fractal, FractalParams, IterationExtras, IterationResult};
+use crate::complex_dd::ComplexDD;
+use crate::double_double::DoubleDouble;
+use crate::fractal::{
+    is_cycle, record_orbit, ExtrasOptions, Fractal, FractalParams, IterationExtras,
+    IterationResult, Symmetry,
+};
+use crate::numeric::{ComplexNum, Real};
+use crate::orbit_average::OrbitAverager;
+use crate::quad_double::QuadDouble;
 
 /// A Julia set: `z_{n+1} = z_n² + c`, where `c` is a fixed constant
 /// and `z₀` is the point on the complex plane.
+///
+/// Generic over the [`Real`] type the orbit is iterated in. Extended
+/// precisions store the viewport `center` and reconstruct
+/// `z₀ = center + delta` from the `f64` delta they receive.
 #[derive(Debug, Clone)]
-pub struct Julia {
+pub struct Julia<T: Real = f64> {
     params: FractalParams,
 
     /// The fixed constant `c` that defines this Julia set.
-    c: Complex,
+    c: T::Complex,
+
+    center: T::Complex,
 }
 
+/// Double-double Julia set (~31 digits).
+pub type JuliaDD = Julia<DoubleDouble>;
+
+/// Quad-double Julia set (~62 digits).
+pub type JuliaQD = Julia<QuadDouble>;
+
 impl Julia {
     pub fn new(c: Complex, params: FractalParams) -> Self {
-        Self { params, c }
+        Self::with_center(c, params, Complex::ZERO)
     }
 
     /// A visually interesting default: `c = -0.7 + 0.27015i`.
     pub fn default_c() -> Complex {
         Complex::new(-0.7, 0.27015)
     }
+}
+
+impl<T: Real> Julia<T> {
+    /// Construct with a viewport center. Ignored for `f64`, whose points
+    /// are absolute.
+    pub fn with_center(c: T::Complex, params: FractalParams, center: T::Complex) -> Self {
+        Self { params, c, center }
+    }
 
     /// The constant `c` defining this Julia set.
-    pub fn c(&self) -> Complex {
+    pub fn c(&self) -> T::Complex {
         self.c
     }
+
+    /// The starting point `z₀` for an incoming pixel coordinate.
+    #[inline]
+    fn start(&self, point: Complex) -> T::Complex {
+        if T::USES_DELTA_COORDINATES {
+            self.center + T::Complex::from_complex(point)
+        } else {
+            T::Complex::from_complex(point)
+        }
+    }
 }
 
 impl Default for Julia {
@@ -33,12 +71,12 @@ impl Default for Julia {
     }
 }
 
-impl Fractal for Julia {
+impl<T: Real> Fractal for Julia<T> {
     fn iterate(&self, point: Complex) -> IterationResult {
-        let escape_radius_sq = self.params.escape_radius_sq();
+        let escape_radius_sq = T::from_f64(self.params.escape_radius_sq());
         let max_iter = self.params.max_iterations;
 
-        let mut z = point;
+        let mut z = self.start(point);
 
         // Brent's cycle detection state.
         let mut old_z = z;
@@ -47,23 +85,20 @@ impl Fractal for Julia {
 
         for n in 0..max_iter {
             // z = z² + c
-            z = Complex::new(
-                z.re * z.re - z.im * z.im + self.c.re,
-                2.0 * z.re * z.im + self.c.im,
-            );
+            z = z.square_add(self.c);
 
             let norm_sq = z.norm_sq();
             if norm_sq > escape_radius_sq {
                 return IterationResult::Escaped {
                     iterations: n,
-                    norm_sq,
+                    norm_sq: norm_sq.to_f64(),
                 };
             }
 
             // Periodicity detection (Brent's algorithm).
             // Skip the first 32 iterations and only check every 4th.
             if n >= 32 && n & 3 == 0 {
-                if (z.re - old_z.re).abs() < 1e-13 && (z.im - old_z.im).abs() < 1e-13 {
+                if is_cycle(z, old_z) {
                     return IterationResult::Interior;
                 }
 
@@ -82,15 +117,19 @@ impl Fractal for Julia {
     fn iterate_with_extras(
         &self,
         point: Complex,
-        stripe_density: f64,
+        opts: &ExtrasOptions,
     ) -> (IterationResult, IterationExtras) {
-        let escape_radius_sq = self.params.escape_radius_sq();
+        let stripe_density = opts.stripe_density;
+        let escape_radius_sq = T::from_f64(self.params.escape_radius_sq());
         let max_iter = self.params.max_iterations;
 
-        let mut z = point;
-        // Derivative: dz/dz₀ for Julia (no +1 term, dz₀ = 1)
+        let mut z = self.start(point);
+        let c_f64 = self.c.to_complex();
+        // Derivative: dz/dz₀ for Julia (no +1 term, dz₀ = 1), kept in f64
         let mut dz = Complex::new(1.0, 0.0);
         let mut stripe_sum = 0.0f64;
+        let mut trap_distance = f64::INFINITY;
+        let mut averager = OrbitAverager::new(opts.exterior_average, stripe_density);
 
         let mut old_z = z;
         let mut period: u32 = 0;
@@ -98,21 +137,25 @@ impl Fractal for Julia {
 
         for n in 0..max_iter {
             // Derivative: dz = 2·z·dz  (d(z_n)/dz₀ for Julia)
+            let z_old = z.to_complex();
             dz = Complex::new(
-                2.0 * (z.re * dz.re - z.im * dz.im),
-                2.0 * (z.re * dz.im + z.im * dz.re),
+                2.0 * (z_old.re * dz.re - z_old.im * dz.im),
+                2.0 * (z_old.re * dz.im + z_old.im * dz.re),
             );
 
-            z = Complex::new(
-                z.re * z.re - z.im * z.im + self.c.re,
-                2.0 * z.re * z.im + self.c.im,
-            );
+            z = z.square_add(self.c);
 
             let norm_sq = z.norm_sq();
+            let z_f64 = z.to_complex();
 
-            stripe_sum += 0.5 * (stripe_density * z.im.atan2(z.re)).sin() + 0.5;
+            stripe_sum += 0.5 * (stripe_density * z_f64.im.atan2(z_f64.re)).sin() + 0.5;
+            if let Some(trap) = &opts.orbit_trap {
+                trap_distance = trap_distance.min(trap.distance(z_f64));
+            }
+            averager.push(z_old, z_f64, c_f64);
 
             if norm_sq > escape_radius_sq {
+                let norm_sq = norm_sq.to_f64();
                 let z_norm = norm_sq.sqrt();
                 let dz_norm = dz.norm_sq().sqrt();
                 let distance = if dz_norm > 0.0 {
@@ -128,18 +171,28 @@ impl Fractal for Julia {
                     IterationExtras {
                         distance,
                         stripe_avg: 0.0,
+                        final_arg: z_f64.im.atan2(z_f64.re),
+                        trap_distance,
+                        exterior_avg: averager.finish(
+                            norm_sq,
+                            self.params.escape_radius_sq(),
+                            opts.smooth_average,
+                        ),
+                        ..IterationExtras::default()
                     },
                 );
             }
 
             if n >= 32 && n & 3 == 0 {
-                if (z.re - old_z.re).abs() < 1e-13 && (z.im - old_z.im).abs() < 1e-13 {
+                if is_cycle(z, old_z) {
                     let stripe_avg = if n > 0 { stripe_sum / n as f64 } else { 0.0 };
                     return (
                         IterationResult::Interior,
                         IterationExtras {
                             distance: 0.0,
                             stripe_avg,
+                            trap_distance,
+                            ..IterationExtras::default()
                         },
                     );
                 }
@@ -162,18 +215,43 @@ impl Fractal for Julia {
             IterationExtras {
                 distance: 0.0,
                 stripe_avg,
+                trap_distance,
+                ..IterationExtras::default()
             },
         )
     }
 
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let escape_radius_sq = self.params.escape_radius_sq();
+        let z0 = self.start(point);
+        Some(record_orbit(z0, limit, escape_radius_sq, |z| {
+            z.square_add(self.c)
+        }))
+    }
+
     fn params(&self) -> &FractalParams {
         &self.params
     }
+
+    fn uses_delta_coordinates(&self) -> bool {
+        T::USES_DELTA_COORDINATES
+    }
+
+    /// `(−z)² = z²`: every quadratic Julia set is symmetric under a half
+    /// turn. Only real `c` would add a mirror, which is not exploited.
+    fn symmetry(&self) -> Symmetry {
+        Symmetry {
+            rotational_order: 2,
+            ..Symmetry::NONE
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::complex_qd::ComplexQD;
+    use crate::orbit_trap::{OrbitTrap, TrapShape};
 
     fn julia() -> Julia {
         Julia::default()
@@ -224,4 +302,147 @@ mod tests {
         let run2: Vec<_> = points.iter().map(|&p| j.iterate(p)).collect();
         assert_eq!(run1, run2, "iteration results must be deterministic");
     }
+
+    fn julia_dd() -> JuliaDD {
+        JuliaDD::with_center(
+            ComplexDD::from(Julia::default_c()),
+            FractalParams::default(),
+            ComplexDD::ZERO,
+        )
+    }
+
+    fn julia_qd() -> JuliaQD {
+        JuliaQD::with_center(
+            ComplexQD::from(Julia::default_c()),
+            FractalParams::default(),
+            ComplexQD::ZERO,
+        )
+    }
+
+    #[test]
+    fn precisions_agree_on_grid() {
+        let (j, j_dd, j_qd) = (julia(), julia_dd(), julia_qd());
+        for i in 0..=12 {
+            for k in 0..=8 {
+                let p = Complex::new(-1.6 + i as f64 * 0.27, -1.1 + k as f64 * 0.29);
+                let (r, e) = j.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0));
+                assert_eq!(r, j.iterate(p), "extras path must match iterate at p = {p}");
+                for (r_ext, e_ext) in [
+                    j_dd.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0)),
+                    j_qd.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0)),
+                ] {
+                    assert_eq!(r.class(), r_ext.class(), "class mismatch at p = {p}");
+                    // Rounding differences grow with every iteration, so
+                    // distances are only compared for short orbits.
+                    if matches!(r, IterationResult::Escaped { iterations, .. } if iterations < 20) {
+                        assert!((e.distance - e_ext.distance).abs() <= 1e-9 * e.distance);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn orbit_trap_follows_the_orbit() {
+        // With c = 0 the orbit of 1/2 is 1/4, 1/16, … towards the origin.
+        let j = Julia::new(Complex::ZERO, FractalParams::default());
+        let trap = |shape, center| ExtrasOptions {
+            orbit_trap: Some(OrbitTrap {
+                shape,
+                center,
+                ..OrbitTrap::default()
+            }),
+            ..ExtrasOptions::default()
+        };
+        let p = Complex::new(0.5, 0.0);
+        let (_, e) = j.iterate_with_extras(p, &trap(TrapShape::Point, Complex::new(0.25, 0.0)));
+        assert_eq!(e.trap_distance, 0.0);
+        let (_, e) = j.iterate_with_extras(p, &trap(TrapShape::Circle, Complex::ZERO));
+        assert_eq!(e.trap_distance, 0.75);
+
+        let j_dd = JuliaDD::with_center(ComplexDD::ZERO, FractalParams::default(), ComplexDD::ZERO);
+        let opts = trap(TrapShape::Line, Complex::new(0.0, 0.1));
+        let q = Complex::new(0.3, 0.6);
+        let (_, e) = j.iterate_with_extras(q, &opts);
+        let (_, e_dd) = j_dd.iterate_with_extras(q, &opts);
+        assert!((e.trap_distance - e_dd.trap_distance).abs() < 1e-12);
+    }
+
+    #[test]
+    fn extended_c_zero_origin_is_interior() {
+        let j = JuliaDD::with_center(ComplexDD::ZERO, FractalParams::default(), ComplexDD::ZERO);
+        assert_eq!(j.iterate(Complex::ZERO), IterationResult::Interior);
+        let j = JuliaQD::with_center(ComplexQD::ZERO, FractalParams::default(), ComplexQD::ZERO);
+        assert_eq!(j.iterate(Complex::ZERO), IterationResult::Interior);
+    }
+
+    #[test]
+    fn extended_far_point_escapes() {
+        let far = Complex::new(10.0, 0.0);
+        assert!(matches!(
+            julia_dd().iterate(far),
+            IterationResult::Escaped { .. }
+        ));
+        assert!(matches!(
+            julia_qd().iterate(far),
+            IterationResult::Escaped { .. }
+        ));
+    }
+
+    #[test]
+    fn extended_deterministic_results() {
+        let points = [
+            Complex::new(0.0, 0.0),
+            Complex::new(0.5, 0.5),
+            Complex::new(-1.0, 0.3),
+        ];
+        let (j_dd, j_qd) = (julia_dd(), julia_qd());
+        for &p in &points {
+            assert_eq!(j_dd.iterate(p), j_dd.iterate(p));
+            assert_eq!(j_qd.iterate(p), j_qd.iterate(p));
+        }
+    }
+
+    #[test]
+    fn double_double_deep_zoom_center_offset() {
+        let center = ComplexDD::new(
+            DoubleDouble::new(0.3, 1e-18),
+            DoubleDouble::new(0.5, -2e-19),
+        );
+        let j = JuliaDD::with_center(
+            ComplexDD::from(Julia::default_c()),
+            FractalParams::default(),
+            center,
+        );
+        let result = j.iterate(Complex::new(0.0, 0.0));
+        assert!(
+            matches!(
+                result,
+                IterationResult::Interior | IterationResult::Escaped { .. }
+            ),
+            "should produce a valid result at deep zoom"
+        );
+    }
+
+    #[test]
+    fn quad_double_matches_double_double_within_dd_range() {
+        let center_dd = ComplexDD::new(
+            DoubleDouble::new(0.3, 1e-18),
+            DoubleDouble::new(0.5, -2e-19),
+        );
+        let c = ComplexDD::from(Julia::default_c());
+        let p = FractalParams::new(1000, 2.0).unwrap();
+        let dd = JuliaDD::with_center(c, p, center_dd);
+        let qd = JuliaQD::with_center(ComplexQD::from(c), p, ComplexQD::from(center_dd));
+        let mut mismatches = 0;
+        for i in -6..=6 {
+            for j in -6..=6 {
+                let delta = Complex::new(i as f64 * 3e-20, j as f64 * 2e-20);
+                if dd.iterate(delta).class() != qd.iterate(delta).class() {
+                    mismatches += 1;
+                }
+            }
+        }
+        assert!(mismatches <= 3, "{mismatches} of 169 pixels differ");
+    }
 }
@@ -1,25 +1,71 @@
+pub mod abs_variant;
+pub mod address;
+pub mod big_float;
 pub mod complex;
+pub mod complex_big;
 pub mod complex_dd;
+pub mod complex_qd;
 pub mod double_double;
 pub mod error;
+pub mod external_ray;
+pub mod float_exp;
+pub mod formula;
 pub mod fractal;
+pub mod interior;
 pub mod julia;
-pub mod julia_dd;
+pub mod lyapunov;
 pub mod mandelbrot;
-pub mod mandelbrot_dd;
+pub mod mandelbrot_perturb;
+pub mod minibrot;
+pub mod misiurewicz;
+pub mod multibrot;
+pub mod multijulia;
+pub mod newton;
+pub mod numeric;
+pub mod orbit_average;
+pub mod orbit_trap;
 pub mod palette_data;
+pub mod quad_double;
+pub mod registry;
 pub mod viewport;
 
+#[cfg(test)]
+mod test_util;
+
 // Re-export primary types for convenience.
+pub use abs_variant::{AbsJulia, AbsJuliaDD, AbsMandelbrot, AbsMandelbrotDD, AbsVariant};
+pub use address::{AngledInternalAddress, ComponentAddress, KneadingSequence, MisiurewiczAddress};
+pub use big_float::BigFloat;
 pub use complex::Complex;
+pub use complex_big::ComplexBig;
 pub use complex_dd::ComplexDD;
+pub use complex_qd::ComplexQD;
 pub use double_double::DoubleDouble;
 pub use error::CoreError;
-pub use fractal::{Fractal, FractalParams, IterationExtras, IterationResult};
-pub use julia::Julia;
-pub use julia_dd::JuliaDD;
-pub use mandelbrot::Mandelbrot;
-pub use mandelbrot_dd::MandelbrotDD;
+pub use external_ray::{ExternalAngle, RayTracer};
+pub use float_exp::FloatExp;
+pub use formula::{Formula, FormulaDefinition, FormulaParam};
+pub use fractal::{
+    ExtrasOptions, Fractal, FractalParams, IterationExtras, IterationResult, Symmetry,
+};
+pub use interior::InteriorInfo;
+pub use julia::{Julia, JuliaDD, JuliaQD};
+pub use lyapunov::{Lyapunov, LyapunovSequence};
+pub use mandelbrot::{Mandelbrot, MandelbrotDD, MandelbrotQD};
+pub use mandelbrot_perturb::{MandelbrotPerturb, ReferenceOrbit, SeriesApproximation};
+pub use minibrot::Minibrot;
+pub use misiurewicz::MisiurewiczPoint;
+pub use multibrot::{Exponent, Multibrot, MultibrotDD};
+pub use multijulia::{Multijulia, MultijuliaDD};
+pub use newton::{Newton, NewtonVariant, Polynomial};
+pub use numeric::{ComplexNum, Real, RealMath};
+pub use orbit_average::OrbitAverage;
+pub use orbit_trap::{OrbitTrap, TrapShape};
+pub use quad_double::QuadDouble;
+pub use registry::{
+    BoxedFractal, DefaultView, ExtraInput, FractalDescriptor, FractalInputs, ParamMap, ParamSpec,
+    Precision,
+};
 pub use viewport::Viewport;
 
 /// Convenience result type for the core crate.
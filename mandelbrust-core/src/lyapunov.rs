@@ -0,0 +1,279 @@
+//! Lyapunov fractals of the logistic map `x ← r·x·(1 − x)`.
+//!
+//! The rate `r` alternates between the pixel's real part `a` and imaginary
+//! part `b` following a periodic A/B sequence. Each pixel reports the
+//! Lyapunov exponent `λ = (1/N) Σ ln|r·(1 − 2x)|` of its orbit: negative
+//! where the orbit settles into a stable cycle, positive where it is chaotic.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::complex::Complex;
+use crate::error::CoreError;
+use crate::fractal::{Fractal, FractalParams, IterationResult};
+
+/// Starting point of every orbit; the critical point of the logistic map.
+const X0: f64 = 0.5;
+
+/// `|x|` beyond which an orbit has left for infinity (possible once `r` is
+/// outside `[0, 4]`).
+const DIVERGENCE: f64 = 1e10;
+
+/// Running products of `|r·(1 − 2x)|` are folded into the logarithm sum
+/// once they leave `[1/RENORMALIZE, RENORMALIZE]`.
+const RENORMALIZE: f64 = 1e100;
+
+#[inline]
+fn diverged(x: f64) -> bool {
+    x.is_nan() || x.abs() > DIVERGENCE
+}
+
+/// The periodic pattern of rates, e.g. `AB` or `AABAB`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LyapunovSequence {
+    /// `true` where the step uses `b`.
+    steps: Vec<bool>,
+}
+
+impl LyapunovSequence {
+    pub const MAX_LEN: usize = 64;
+
+    /// Parse a string of `A` and `B` (case-insensitive).
+    pub fn parse(text: &str) -> crate::Result<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(CoreError::InvalidSequence {
+                reason: "sequence is empty".into(),
+            });
+        }
+        if text.len() > Self::MAX_LEN {
+            return Err(CoreError::InvalidSequence {
+                reason: format!("longer than {} steps", Self::MAX_LEN),
+            });
+        }
+        let steps = text
+            .chars()
+            .map(|ch| match ch.to_ascii_uppercase() {
+                'A' => Ok(false),
+                'B' => Ok(true),
+                _ => Err(CoreError::InvalidSequence {
+                    reason: format!("unexpected {ch:?}, only A and B are allowed"),
+                }),
+            })
+            .collect::<crate::Result<_>>()?;
+        Ok(Self { steps })
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The rate for step `n`.
+    #[inline]
+    fn rate(&self, n: usize, a: f64, b: f64) -> f64 {
+        if self.steps[n % self.steps.len()] {
+            b
+        } else {
+            a
+        }
+    }
+}
+
+impl Default for LyapunovSequence {
+    /// `AB`, the classic "Zircon Zity" sequence.
+    fn default() -> Self {
+        Self {
+            steps: vec![false, true],
+        }
+    }
+}
+
+impl fmt::Display for LyapunovSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &b in &self.steps {
+            f.write_str(if b { "B" } else { "A" })?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for LyapunovSequence {
+    type Error = CoreError;
+
+    fn try_from(text: String) -> crate::Result<Self> {
+        Self::parse(&text)
+    }
+}
+
+impl From<LyapunovSequence> for String {
+    fn from(sequence: LyapunovSequence) -> Self {
+        sequence.to_string()
+    }
+}
+
+/// The Lyapunov fractal over the `(a, b)` rate plane.
+///
+/// The first fifth of `max_iterations` lets the orbit settle; the rest is
+/// averaged. Pixels report [`IterationResult::Measured`], with `+∞` for an
+/// orbit that diverged.
+#[derive(Debug, Clone)]
+pub struct Lyapunov {
+    params: FractalParams,
+    sequence: LyapunovSequence,
+}
+
+impl Lyapunov {
+    pub fn new(sequence: LyapunovSequence, params: FractalParams) -> Self {
+        Self { params, sequence }
+    }
+
+    pub fn sequence(&self) -> &LyapunovSequence {
+        &self.sequence
+    }
+
+    /// Iterations discarded before the exponent is measured.
+    fn warm_up(&self) -> u32 {
+        self.params.max_iterations / 5
+    }
+}
+
+impl Fractal for Lyapunov {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let (a, b) = (point.re, point.im);
+        let warm_up = self.warm_up() as usize;
+        let total = self.params.max_iterations as usize;
+        let mut x = X0;
+
+        for n in 0..warm_up {
+            x *= self.sequence.rate(n, a, b) * (1.0 - x);
+            if diverged(x) {
+                return IterationResult::Measured {
+                    value: f64::INFINITY,
+                };
+            }
+        }
+
+        // Multiply the derivatives and take one logarithm per run of
+        // products instead of one per step.
+        let mut log_sum = 0.0;
+        let mut product = 1.0_f64;
+        for n in warm_up..total {
+            let r = self.sequence.rate(n, a, b);
+            product *= (r * (1.0 - 2.0 * x)).abs();
+            x *= r * (1.0 - x);
+            if diverged(x) {
+                return IterationResult::Measured {
+                    value: f64::INFINITY,
+                };
+            }
+            if product == 0.0 {
+                // Super-stable: the orbit passed through the critical point.
+                return IterationResult::Measured {
+                    value: f64::NEG_INFINITY,
+                };
+            }
+            if !(1.0 / RENORMALIZE..=RENORMALIZE).contains(&product) {
+                log_sum += product.ln();
+                product = 1.0;
+            }
+        }
+        log_sum += product.ln();
+
+        IterationResult::Measured {
+            value: log_sum / (total - warm_up) as f64,
+        }
+    }
+
+    fn params(&self) -> &FractalParams {
+        &self.params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exponent(fractal: &Lyapunov, a: f64, b: f64) -> f64 {
+        match fractal.iterate(Complex::new(a, b)) {
+            IterationResult::Measured { value } => value,
+            other => panic!("expected a measurement, got {other:?}"),
+        }
+    }
+
+    fn single_rate() -> Lyapunov {
+        Lyapunov::new(
+            LyapunovSequence::parse("A").unwrap(),
+            FractalParams::default().with_max_iterations(5000),
+        )
+    }
+
+    #[test]
+    fn sequence_parsing() {
+        let seq = LyapunovSequence::parse(" aAbB ").unwrap();
+        assert_eq!(seq.to_string(), "AABB");
+        assert_eq!(seq.len(), 4);
+        assert!(LyapunovSequence::parse("").is_err());
+        assert!(LyapunovSequence::parse("ABC").is_err());
+        assert!(LyapunovSequence::parse(&"A".repeat(65)).is_err());
+        assert_eq!(LyapunovSequence::default().to_string(), "AB");
+    }
+
+    #[test]
+    fn sequence_serializes_as_text() {
+        let seq = LyapunovSequence::parse("BBBBBBAAAAAA").unwrap();
+        let json = serde_json::to_string(&seq).unwrap();
+        assert_eq!(json, "\"BBBBBBAAAAAA\"");
+        assert_eq!(
+            serde_json::from_str::<LyapunovSequence>(&json).unwrap(),
+            seq
+        );
+        assert!(serde_json::from_str::<LyapunovSequence>("\"AXB\"").is_err());
+    }
+
+    #[test]
+    fn stable_and_chaotic_rates() {
+        let f = single_rate();
+        // Period 2 at r = 3.2, period 4 at r = 3.5: both attracting.
+        assert!(exponent(&f, 3.2, 0.0) < 0.0);
+        assert!(exponent(&f, 3.5, 0.0) < 0.0);
+        // Fully developed chaos.
+        assert!(exponent(&f, 3.9, 0.0) > 0.3);
+        // x ≡ 1/2 at r = 2 is super-stable.
+        assert_eq!(exponent(&f, 2.0, 0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn fixed_point_exponent_matches_closed_form() {
+        // For 1 < r < 3 the orbit settles on x* = 1 − 1/r, where
+        // |r·(1 − 2x*)| = |2 − r|.
+        let f = single_rate();
+        let r = 2.5;
+        assert!((exponent(&f, r, 0.0) - (2.0 - r).abs().ln()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn sequence_picks_the_rate_per_step() {
+        // With only A steps the b coordinate is never used.
+        let f = single_rate();
+        assert_eq!(exponent(&f, 3.3, 0.1), exponent(&f, 3.3, 3.9));
+        // With only B steps the roles swap.
+        let g = Lyapunov::new(
+            LyapunovSequence::parse("BB").unwrap(),
+            FractalParams::default().with_max_iterations(5000),
+        );
+        assert_eq!(exponent(&g, 0.1, 3.3), exponent(&f, 3.3, 0.0));
+    }
+
+    #[test]
+    fn divergent_orbits_are_infinitely_chaotic() {
+        let f = single_rate();
+        assert_eq!(exponent(&f, 4.5, 0.0), f64::INFINITY);
+        assert_eq!(exponent(&f, -3.0, 0.0), f64::INFINITY);
+    }
+}
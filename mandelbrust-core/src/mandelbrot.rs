@@ -1,17 +1,54 @@
 use crate::complex::Complex;
-use crate::fractal::{Fractal, FractalParams, IterationExtras, IterationResult};
+use crate::complex_dd::ComplexDD;
+use crate::double_double::DoubleDouble;
+use crate::fractal::{
+    is_cycle, record_orbit, ExtrasOptions, Fractal, FractalParams, IterationExtras,
+    IterationResult, Symmetry,
+};
+use crate::interior;
+use crate::numeric::{ComplexNum, Real};
+use crate::orbit_average::OrbitAverager;
+use crate::quad_double::QuadDouble;
 
 /// The Mandelbrot set: `z_{n+1} = z_n² + c`, starting from `z₀ = 0`.
 ///
-/// The point `c` is the coordinate on the complex plane.
+/// Generic over the [`Real`] type the orbit is iterated in. With `f64` the
+/// point `c` is the coordinate on the complex plane. Wider types store the
+/// viewport `center` at full precision, receive a **delta** from it (small
+/// enough for `f64`) and reconstruct `c = center + delta` in `T`.
 #[derive(Debug, Clone)]
-pub struct Mandelbrot {
+pub struct Mandelbrot<T: Real = f64> {
     params: FractalParams,
+    center: T::Complex,
 }
 
+/// Double-double Mandelbrot (~31 digits).
+pub type MandelbrotDD = Mandelbrot<DoubleDouble>;
+
+/// Quad-double Mandelbrot (~62 digits).
+pub type MandelbrotQD = Mandelbrot<QuadDouble>;
+
 impl Mandelbrot {
     pub fn new(params: FractalParams) -> Self {
-        Self { params }
+        Self::with_center(params, Complex::ZERO)
+    }
+}
+
+impl<T: Real> Mandelbrot<T> {
+    /// Construct with a viewport center. Ignored for `f64`, whose points
+    /// are absolute.
+    pub fn with_center(params: FractalParams, center: T::Complex) -> Self {
+        Self { params, center }
+    }
+
+    /// The point `c` on the complex plane for an incoming pixel coordinate.
+    #[inline]
+    fn point(&self, point: Complex) -> T::Complex {
+        if T::USES_DELTA_COORDINATES {
+            self.center + T::Complex::from_complex(point)
+        } else {
+            T::Complex::from_complex(point)
+        }
     }
 }
 
@@ -24,7 +61,8 @@ impl Default for Mandelbrot {
 /// Returns `true` if `c` lies inside the main cardioid.
 ///
 /// This is a closed-form check that avoids iterating ~30–40% of visible
-/// points at the default zoom level.
+/// points at the default zoom level. It runs in `f64` at every precision:
+/// a false negative near the boundary only costs a full iteration.
 #[inline]
 fn in_cardioid(re: f64, im: f64) -> bool {
     let im2 = im * im;
@@ -38,17 +76,44 @@ fn in_period2_bulb(re: f64, im: f64) -> bool {
     (re + 1.0) * (re + 1.0) + im * im <= 0.0625
 }
 
-impl Fractal for Mandelbrot {
-    fn iterate(&self, c: Complex) -> IterationResult {
+/// Extras describing the attracting cycle near `z`, which the orbit was
+/// seen to repeat after `cycle_len` steps if known, when
+/// [`ExtrasOptions::analyze_interior`] asks for them.
+fn cycle_extras<C: ComplexNum>(
+    z: C,
+    c: C,
+    cycle_len: Option<u32>,
+    opts: &ExtrasOptions,
+) -> IterationExtras {
+    match opts
+        .analyze_interior
+        .then(|| interior::analyze(z, c, cycle_len))
+        .flatten()
+    {
+        Some(info) => IterationExtras {
+            period: info.period,
+            multiplier: info.multiplier,
+            interior_distance: info.distance,
+            ..IterationExtras::default()
+        },
+        None => IterationExtras::default(),
+    }
+}
+
+impl<T: Real> Fractal for Mandelbrot<T> {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let c = self.point(point);
+        let c_f64 = c.to_complex();
+
         // Fast rejection: skip iteration for points known to be interior.
-        if in_cardioid(c.re, c.im) || in_period2_bulb(c.re, c.im) {
+        if in_cardioid(c_f64.re, c_f64.im) || in_period2_bulb(c_f64.re, c_f64.im) {
             return IterationResult::Interior;
         }
 
-        let escape_radius_sq = self.params.escape_radius_sq();
+        let escape_radius_sq = T::from_f64(self.params.escape_radius_sq());
         let max_iter = self.params.max_iterations;
 
-        let mut z = Complex::ZERO;
+        let mut z = T::Complex::ZERO;
 
         // Brent's cycle detection state.
         let mut old_z = z;
@@ -57,13 +122,13 @@ impl Fractal for Mandelbrot {
 
         for n in 0..max_iter {
             // z = z² + c
-            z = Complex::new(z.re * z.re - z.im * z.im + c.re, 2.0 * z.re * z.im + c.im);
+            z = z.square_add(c);
 
             let norm_sq = z.norm_sq();
             if norm_sq > escape_radius_sq {
                 return IterationResult::Escaped {
                     iterations: n,
-                    norm_sq,
+                    norm_sq: norm_sq.to_f64(),
                 };
             }
 
@@ -71,7 +136,7 @@ impl Fractal for Mandelbrot {
             // Skip the first 32 iterations (orbits rarely converge early)
             // and only check every 4th iteration to reduce branch overhead.
             if n >= 32 && n & 3 == 0 {
-                if (z.re - old_z.re).abs() < 1e-13 && (z.im - old_z.im).abs() < 1e-13 {
+                if is_cycle(z, old_z) {
                     return IterationResult::Interior;
                 }
 
@@ -89,38 +154,59 @@ impl Fractal for Mandelbrot {
 
     fn iterate_with_extras(
         &self,
-        c: Complex,
-        stripe_density: f64,
+        point: Complex,
+        opts: &ExtrasOptions,
     ) -> (IterationResult, IterationExtras) {
-        if in_cardioid(c.re, c.im) || in_period2_bulb(c.re, c.im) {
+        let stripe_density = opts.stripe_density;
+        let c = self.point(point);
+        let c_f64 = c.to_complex();
+
+        // Trapped and analyzed orbits must be followed even where the set
+        // is known.
+        if opts.orbit_trap.is_none()
+            && !opts.analyze_interior
+            && (in_cardioid(c_f64.re, c_f64.im) || in_period2_bulb(c_f64.re, c_f64.im))
+        {
             return (IterationResult::Interior, IterationExtras::default());
         }
 
-        let escape_radius_sq = self.params.escape_radius_sq();
+        let escape_radius_sq = T::from_f64(self.params.escape_radius_sq());
         let max_iter = self.params.max_iterations;
 
-        let mut z = Complex::ZERO;
+        let mut z = T::Complex::ZERO;
+        // f64-precision derivative (sufficient for coloring at every tier)
         let mut dz = Complex::ZERO;
         let mut stripe_sum = 0.0f64;
+        let mut trap_distance = f64::INFINITY;
+        let mut averager = OrbitAverager::new(opts.exterior_average, stripe_density);
 
+        // `old_z` is z_{old_n}; after step `n`, `z` is z_{n+1}.
         let mut old_z = z;
+        let mut old_n: u32 = 0;
         let mut period: u32 = 0;
         let mut check: u32 = 3;
 
         for n in 0..max_iter {
             // Derivative: dz = 2·z·dz + 1  (d(z_n)/dc for Mandelbrot)
+            let z_old = z.to_complex();
             dz = Complex::new(
-                2.0 * (z.re * dz.re - z.im * dz.im) + 1.0,
-                2.0 * (z.re * dz.im + z.im * dz.re),
+                2.0 * (z_old.re * dz.re - z_old.im * dz.im) + 1.0,
+                2.0 * (z_old.re * dz.im + z_old.im * dz.re),
             );
 
-            z = Complex::new(z.re * z.re - z.im * z.im + c.re, 2.0 * z.re * z.im + c.im);
+            z = z.square_add(c);
 
             let norm_sq = z.norm_sq();
+            let z_f64 = z.to_complex();
 
-            stripe_sum += 0.5 * (stripe_density * z.im.atan2(z.re)).sin() + 0.5;
+            stripe_sum += 0.5 * (stripe_density * z_f64.im.atan2(z_f64.re)).sin() + 0.5;
+            if let Some(trap) = &opts.orbit_trap {
+                trap_distance = trap_distance.min(trap.distance(z_f64));
+            }
+            averager.push(z_old, z_f64, c_f64);
 
             if norm_sq > escape_radius_sq {
+                let norm_sq = norm_sq.to_f64();
                 let z_norm = norm_sq.sqrt();
                 let dz_norm = dz.norm_sq().sqrt();
                 let distance = if dz_norm > 0.0 {
@@ -136,24 +222,35 @@ impl Fractal for Mandelbrot {
                     IterationExtras {
                         distance,
                         stripe_avg: 0.0,
+                        final_arg: z_f64.im.atan2(z_f64.re),
+                        trap_distance,
+                        exterior_avg: averager.finish(
+                            norm_sq,
+                            self.params.escape_radius_sq(),
+                            opts.smooth_average,
+                        ),
+                        ..IterationExtras::default()
                     },
                 );
             }
 
             if n >= 32 && n & 3 == 0 {
-                if (z.re - old_z.re).abs() < 1e-13 && (z.im - old_z.im).abs() < 1e-13 {
+                if is_cycle(z, old_z) {
                     let stripe_avg = if n > 0 { stripe_sum / n as f64 } else { 0.0 };
                     return (
                         IterationResult::Interior,
                         IterationExtras {
                             distance: 0.0,
                             stripe_avg,
+                            trap_distance,
+                            ..cycle_extras(z, c, Some(n + 1 - old_n), opts)
                         },
                     );
                 }
                 period += 1;
                 if period > check {
                     old_z = z;
+                    old_n = n + 1;
                     period = 0;
                     check = check.saturating_mul(2);
                 }
@@ -170,23 +267,59 @@ impl Fractal for Mandelbrot {
             IterationExtras {
                 distance: 0.0,
                 stripe_avg,
+                trap_distance,
+                ..cycle_extras(z, c, None, opts)
             },
         )
     }
 
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let c = self.point(point);
+        let escape_radius_sq = self.params.escape_radius_sq();
+        Some(record_orbit(
+            T::Complex::ZERO,
+            limit,
+            escape_radius_sq,
+            |z| z.square_add(c),
+        ))
+    }
+
     fn params(&self) -> &FractalParams {
         &self.params
     }
+
+    fn uses_delta_coordinates(&self) -> bool {
+        T::USES_DELTA_COORDINATES
+    }
+
+    fn symmetry(&self) -> Symmetry {
+        Symmetry::CONJUGATE
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::complex_qd::ComplexQD;
+    use crate::mandelbrot_perturb::{MandelbrotPerturb, ReferenceOrbit};
+    use crate::orbit_average::OrbitAverage;
+    use crate::orbit_trap::OrbitTrap;
+    use crate::test_util::grid;
 
     fn mb() -> Mandelbrot {
         Mandelbrot::default()
     }
 
+    /// With a zero center the delta IS the absolute coordinate, so the
+    /// extended tiers see exactly the same `c` as `f64`.
+    fn mb_dd() -> MandelbrotDD {
+        MandelbrotDD::with_center(FractalParams::default(), ComplexDD::ZERO)
+    }
+
+    fn mb_qd() -> MandelbrotQD {
+        MandelbrotQD::with_center(FractalParams::default(), ComplexQD::ZERO)
+    }
+
     #[test]
     fn origin_is_interior() {
         assert_eq!(
@@ -202,7 +335,7 @@ mod tests {
             IterationResult::Escaped { iterations, .. } => {
                 assert_eq!(iterations, 0, "should escape on the very first iteration");
             }
-            IterationResult::Interior => panic!("far point should escape"),
+            other => panic!("far point should escape, got {other:?}"),
         }
     }
 
@@ -270,4 +403,262 @@ mod tests {
         let run2: Vec<_> = points.iter().map(|&c| m.iterate(c)).collect();
         assert_eq!(run1, run2, "iteration results must be deterministic");
     }
+
+    #[test]
+    fn extended_tiers_report_delta_coordinates() {
+        assert!(!mb().uses_delta_coordinates());
+        assert!(mb_dd().uses_delta_coordinates());
+        assert!(mb_qd().uses_delta_coordinates());
+    }
+
+    #[test]
+    fn extended_known_escape_count() {
+        for result in [
+            mb_dd().iterate(Complex::new(1.0, 0.0)),
+            mb_qd().iterate(Complex::new(1.0, 0.0)),
+        ] {
+            match result {
+                IterationResult::Escaped { iterations, .. } => assert_eq!(iterations, 2),
+                _ => panic!("c=1.0 should escape"),
+            }
+        }
+        assert_eq!(mb_dd().iterate(Complex::ZERO), IterationResult::Interior);
+        assert_eq!(mb_qd().iterate(Complex::ZERO), IterationResult::Interior);
+    }
+
+    #[test]
+    fn extended_far_point_escapes_immediately() {
+        for result in [
+            mb_dd().iterate(Complex::new(10.0, 0.0)),
+            mb_qd().iterate(Complex::new(10.0, 0.0)),
+        ] {
+            assert!(matches!(
+                result,
+                IterationResult::Escaped { iterations: 0, .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn extended_deterministic_results() {
+        let (m_dd, m_qd) = (mb_dd(), mb_qd());
+        for c in grid((-2.1, 0.0), (0.17, 0.145), (16, 8)) {
+            assert_eq!(m_dd.iterate(c), m_dd.iterate(c));
+            assert_eq!(m_qd.iterate(c), m_qd.iterate(c));
+        }
+    }
+
+    /// Rounding differences grow with every iteration, so smooth-coloring
+    /// inputs are only compared for orbits that escape quickly.
+    const SHORT_ORBIT: u32 = 20;
+
+    #[test]
+    fn precisions_agree_on_grid() {
+        // Iteration counts (which determine coloring) must match everywhere.
+        let (m, m_dd, m_qd) = (mb(), mb_dd(), mb_qd());
+        for c in grid((-2.1, 0.0), (0.17, 0.145), (16, 8)) {
+            let (r, e) = m.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(5.0));
+            assert_eq!(r, m.iterate(c), "extras path must match iterate at c = {c}");
+            for (r_ext, e_ext) in [
+                m_dd.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(5.0)),
+                m_qd.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(5.0)),
+            ] {
+                assert_eq!(r.class(), r_ext.class(), "class mismatch at c = {c}");
+                if let (
+                    IterationResult::Escaped {
+                        iterations,
+                        norm_sq,
+                    },
+                    IterationResult::Escaped { norm_sq: ext, .. },
+                ) = (r, r_ext)
+                {
+                    if iterations < SHORT_ORBIT {
+                        assert!(
+                            (norm_sq - ext).abs() <= 1e-9 * norm_sq,
+                            "norm_sq at c = {c}"
+                        );
+                        assert!((e.distance - e_ext.distance).abs() <= 1e-9 * e.distance);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn orbit_trap_tracks_closest_approach() {
+        let m = mb();
+        let untrapped = m.iterate_with_extras(Complex::new(0.3, 0.5), &ExtrasOptions::default());
+        assert_eq!(untrapped.1.trap_distance, f64::INFINITY);
+
+        let opts = ExtrasOptions {
+            orbit_trap: Some(OrbitTrap::default()),
+            ..ExtrasOptions::default()
+        };
+        // Inside the cardioid and the period-2 bulb the orbit is still
+        // followed: 0, 0, … and −1, 0, −1, … both touch the origin.
+        for c in [Complex::ZERO, Complex::new(-1.0, 0.0)] {
+            let (r, e) = m.iterate_with_extras(c, &opts);
+            assert_eq!(r, IterationResult::Interior);
+            assert_eq!(e.trap_distance, 0.0, "c = {c}");
+        }
+        // 1, 2, 5, … escapes; its closest point is the first.
+        let (r, e) = m.iterate_with_extras(Complex::new(1.0, 0.0), &opts);
+        assert!(matches!(r, IterationResult::Escaped { .. }));
+        assert_eq!(e.trap_distance, 1.0);
+
+        // Extended tiers measure absolute orbit points too.
+        let c = Complex::new(-0.12, 0.75);
+        let m_dd = MandelbrotDD::with_center(FractalParams::default(), ComplexDD::from(c));
+        let (_, e) = m.iterate_with_extras(c, &opts);
+        let (_, e_dd) = m_dd.iterate_with_extras(Complex::ZERO, &opts);
+        assert!((e.trap_distance - e_dd.trap_distance).abs() < 1e-12);
+    }
+
+    #[test]
+    fn exterior_averages_measure_escaping_orbits() {
+        let params = FractalParams::new(200, 100.0).unwrap();
+        let m = Mandelbrot::new(params);
+        let c = Complex::new(-0.12, 0.75);
+        let m_dd = MandelbrotDD::with_center(params, ComplexDD::from(c));
+        let outside = Complex::new(0.5, 0.5);
+        for kind in [
+            OrbitAverage::Stripe,
+            OrbitAverage::TriangleInequality,
+            OrbitAverage::Curvature,
+        ] {
+            let opts = ExtrasOptions {
+                exterior_average: Some(kind),
+                ..ExtrasOptions::default()
+            };
+            let (r, e) = m.iterate_with_extras(outside, &opts);
+            assert!(matches!(r, IterationResult::Escaped { .. }));
+            assert!((0.0..=1.0).contains(&e.exterior_avg), "{kind:?}");
+            // Both precisions agree on a short escaping orbit.
+            let (_, e) = m.iterate_with_extras(c + Complex::new(0.0, 0.25), &opts);
+            let (_, e_dd) = m_dd.iterate_with_extras(Complex::new(0.0, 0.25), &opts);
+            assert!(
+                (e.exterior_avg - e_dd.exterior_avg).abs() < 1e-9,
+                "{kind:?}"
+            );
+        }
+        let (_, e) = m.iterate_with_extras(outside, &ExtrasOptions::default());
+        assert!(e.exterior_avg.is_nan());
+    }
+
+    #[test]
+    fn final_arg_records_escape_direction() {
+        let m = mb();
+        let opts = ExtrasOptions::default();
+        // 3i and −3 escape on the first step.
+        let (_, e) = m.iterate_with_extras(Complex::new(0.0, 3.0), &opts);
+        assert!((e.final_arg - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        let (_, e) = m.iterate_with_extras(Complex::new(-3.0, 0.0), &opts);
+        assert!((e.final_arg - std::f64::consts::PI).abs() < 1e-12);
+        let (_, e) = m.iterate_with_extras(Complex::new(-1.0, 0.0), &opts);
+        assert!(e.final_arg.is_nan());
+    }
+
+    #[test]
+    fn interior_analysis_measures_cycles() {
+        let m = Mandelbrot::new(FractalParams::new(2000, 2.0).unwrap());
+        let opts = ExtrasOptions {
+            analyze_interior: true,
+            ..ExtrasOptions::default()
+        };
+        // Inside the cardioid, the period-2 bulb and the period-3 bulb on top.
+        for (c, period) in [
+            (Complex::new(0.1, 0.1), 1),
+            (Complex::new(-1.1, 0.05), 2),
+            (Complex::new(-0.12, 0.74), 3),
+        ] {
+            let (r, e) = m.iterate_with_extras(c, &opts);
+            assert_eq!(r, IterationResult::Interior);
+            assert_eq!(e.period, period, "c = {c}");
+            assert!(e.multiplier < 1.0 && e.interior_distance > 0.0, "c = {c}");
+        }
+        let (_, e) = m.iterate_with_extras(Complex::new(0.1, 0.1), &ExtrasOptions::default());
+        assert_eq!(e.period, 0);
+        assert!(e.multiplier.is_nan());
+    }
+
+    #[test]
+    fn delta_from_center_matches_absolute() {
+        // Dyadic center and deltas: center + delta is exact in every tier.
+        let center = Complex::new(-0.75, 0.125);
+        let params = FractalParams::new(500, 2.0).unwrap();
+        let m = Mandelbrot::new(params);
+        let m_dd = MandelbrotDD::with_center(params, ComplexDD::from(center));
+        let m_qd = MandelbrotQD::with_center(params, ComplexQD::from(center));
+        for i in -4..=4 {
+            for j in -4..=4 {
+                let delta = Complex::new(i as f64 / 64.0, j as f64 / 128.0);
+                let expected = m.iterate(center + delta).class();
+                assert_eq!(m_dd.iterate(delta).class(), expected);
+                assert_eq!(m_qd.iterate(delta).class(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn double_double_deep_zoom_center_offset() {
+        // Simulate a deep zoom: center is far from origin, delta is tiny.
+        // This wouldn't work with f64 absolute coordinates.
+        let center = ComplexDD::new(
+            DoubleDouble::new(-0.75, 1e-17),
+            DoubleDouble::new(0.1, 2e-18),
+        );
+        let m = MandelbrotDD::with_center(FractalParams::default(), center);
+        let result = m.iterate(Complex::new(0.0, 0.0));
+        assert!(
+            matches!(
+                result,
+                IterationResult::Interior | IterationResult::Escaped { .. }
+            ),
+            "should produce a valid result at deep zoom"
+        );
+    }
+
+    #[test]
+    fn quad_double_matches_perturbation_beyond_double_double() {
+        // ~1e-40 per pixel: the center needs all four limbs.
+        let center = ComplexQD::new(
+            QuadDouble::new(-0.743_643_887_037_151, 1.2e-17, -3.1e-34, 4.4e-51),
+            QuadDouble::new(0.131_825_904_205_33, -3.4e-18, 2.7e-35, -1.9e-52),
+        );
+        let p = FractalParams::new(3000, 2.0).unwrap();
+        let qd = MandelbrotQD::with_center(p, center);
+        let pert = MandelbrotPerturb::with_reference(p, ReferenceOrbit::compute_qd(center, &p));
+        let scale = 1e-40;
+        let mut mismatches = 0;
+        for i in -6..=6 {
+            for j in -6..=6 {
+                let delta = Complex::new(i as f64 * 29.0 * scale, j as f64 * 17.0 * scale);
+                if qd.iterate(delta).class() != pert.iterate(delta).class() {
+                    mismatches += 1;
+                }
+            }
+        }
+        assert!(mismatches <= 3, "{mismatches} of 169 pixels differ");
+    }
+
+    #[test]
+    fn orbit_follows_the_iteration_until_escape() {
+        let orbit = mb().orbit(Complex::new(-1.0, 0.0), 6).unwrap();
+        let re: Vec<f64> = orbit.iter().map(|z| z.to_complex().re).collect();
+        assert_eq!(re, [0.0, -1.0, 0.0, -1.0, 0.0, -1.0, 0.0]);
+
+        // 0, 1, 2, 5: the orbit stops at the first point past the radius.
+        let orbit = mb().orbit(Complex::new(1.0, 0.0), 100).unwrap();
+        assert_eq!(orbit.len(), 4);
+        assert_eq!(orbit[3].to_complex(), Complex::new(5.0, 0.0));
+    }
+
+    #[test]
+    fn deep_orbits_keep_double_double_precision() {
+        let center = ComplexDD::new(DoubleDouble::new(-1.0, 1e-20), DoubleDouble::new(0.0, 0.0));
+        let dd = MandelbrotDD::with_center(FractalParams::default(), center);
+        let orbit = dd.orbit(Complex::ZERO, 2).unwrap();
+        // z₁ = c keeps the offset f64 alone would round away.
+        assert_eq!(orbit[1].re, DoubleDouble::new(-1.0, 1e-20));
+    }
 }
@@ -0,0 +1,979 @@
+use std::sync::OnceLock;
+
+use crate::complex::Complex;
+use crate::complex_big::ComplexBig;
+use crate::complex_dd::ComplexDD;
+use crate::complex_qd::ComplexQD;
+use crate::float_exp::{ldexp, FloatExp};
+use crate::fractal::{
+    ExtrasOptions, Fractal, FractalParams, IterationExtras, IterationResult, Symmetry,
+};
+use crate::numeric::{ComplexNum, Real};
+use crate::viewport::Viewport;
+
+/// Pauldelbrot glitch tolerance: a pixel is considered glitched when
+/// `|Z_m + δ|² < GLITCH_TOLERANCE · |Z_m|²`, i.e. when the full orbit
+/// passes much closer to zero than the reference does and the `f64` delta
+/// no longer carries enough significant bits relative to the reference.
+const GLITCH_TOLERANCE: f64 = 1e-6;
+
+/// While iterating rescaled deltas, switch to plain `f64` deltas once the
+/// shared exponent rises above this (the delta is then a normal `f64`).
+const RESCALE_EXIT_EXPONENT: i64 = -900;
+
+/// Renormalize rescaled deltas when their mantissa grows past `2^64`.
+const RESCALE_LIMIT: f64 = 18_446_744_073_709_551_616.0;
+
+/// Series approximation stays valid while the cubic coefficient is this
+/// small relative to the linear one (both normalized to the viewport
+/// radius, so the bound holds for every pixel).
+const SERIES_TOLERANCE: f64 = 1e-12;
+
+/// Stop the series before its coefficients can overflow the rescaled
+/// prefix that picks up where it leaves off.
+const SERIES_COEFFICIENT_LIMIT: f64 = 1e300;
+
+/// Down to this scale exponent the quad-double rounding of the center is
+/// far below a pixel, so the cheaper QD reference orbit is used; deeper
+/// views iterate the reference at the center's full precision.
+const QD_REFERENCE_EXPONENT: i64 = -150;
+
+/// High-precision orbit of a single reference point, rounded to `f64`.
+///
+/// The orbit is computed once per frame in extended precision and
+/// shared (read-only) by every pixel. Entry `0` is always `Z₀ = 0`; the
+/// orbit stops after the reference escapes or after `max_iterations` steps.
+#[derive(Debug, Clone)]
+pub struct ReferenceOrbit {
+    /// The reference point `c_ref`.
+    center: ComplexDD,
+    /// `Z₀ … Z_k`, rounded to `f64`.
+    points: Vec<Complex>,
+}
+
+impl ReferenceOrbit {
+    /// Iterate `z → z² + c_ref` in double-double precision.
+    pub fn compute(center: ComplexDD, params: &FractalParams) -> Self {
+        Self {
+            center,
+            points: reference_points(center, params),
+        }
+    }
+
+    /// Iterate `z → z² + c_ref` in quad-double precision, for reference
+    /// points placed with ~62 digits.
+    pub fn compute_qd(center: ComplexQD, params: &FractalParams) -> Self {
+        Self {
+            center: center.to_complex_dd(),
+            points: reference_points(center, params),
+        }
+    }
+
+    /// Iterate `z → z² + c_ref` at the precision of `center`, for reference
+    /// points that need more than double-double's ~31 digits.
+    pub fn compute_big(center: &ComplexBig, params: &FractalParams) -> Self {
+        let escape_radius_sq = params.escape_radius_sq();
+        let max_iter = params.max_iterations as usize;
+
+        let mut points = Vec::with_capacity(max_iter + 1);
+        let mut z = ComplexBig::zero(center.re.precision());
+        points.push(Complex::ZERO);
+
+        for _ in 0..max_iter {
+            z = &z.square() + center;
+            let z_f64 = z.to_complex();
+            points.push(z_f64);
+            if z_f64.norm_sq() > escape_radius_sq {
+                break;
+            }
+        }
+
+        Self {
+            center: center.to_complex_dd(),
+            points,
+        }
+    }
+
+    /// The reference point this orbit was computed for.
+    pub fn center(&self) -> ComplexDD {
+        self.center
+    }
+
+    /// Number of stored orbit points, including `Z₀`.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Always `false` — the orbit contains at least `Z₀`.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// `true` if the reference point itself escaped before `max_iterations`.
+    ///
+    /// Pixels that outlive an escaped reference are re-referenced onto the
+    /// start of the orbit, so this only affects performance, not correctness.
+    pub fn escaped(&self, params: &FractalParams) -> bool {
+        self.points
+            .last()
+            .is_some_and(|z| z.norm_sq() > params.escape_radius_sq())
+    }
+}
+
+/// `Z₀ … Z_k` for `c_ref = center`, iterated at the precision of `C` and
+/// rounded to `f64`.
+fn reference_points<C: ComplexNum>(center: C, params: &FractalParams) -> Vec<Complex> {
+    let escape_radius_sq = C::Real::from_f64(params.escape_radius_sq());
+    let max_iter = params.max_iterations as usize;
+
+    let mut points = Vec::with_capacity(max_iter + 1);
+    let mut z = C::ZERO;
+    points.push(Complex::ZERO);
+
+    for _ in 0..max_iter {
+        z = z.square_add(center);
+        points.push(z.to_complex());
+        if z.norm_sq() > escape_radius_sq {
+            break;
+        }
+    }
+
+    points
+}
+
+/// Truncated Taylor series of the perturbed orbit in `δc`, used to skip the
+/// first iterations of every pixel at once.
+///
+/// For `u = δc / radius`, the delta after `skipped` iterations is
+///
+/// `δ_N ≈ a·u + b·u² + c·u³`
+///
+/// with coefficients obtained from the reference orbit by
+/// `a' = 2·Z·a + radius`, `b' = 2·Z·b + a²`, `c' = 2·Z·c + 2·a·b`.
+/// Normalizing to the radius keeps the coefficients at the magnitude of the
+/// deltas themselves, and `|u| ≤ 1` turns the validity check into a single
+/// comparison for the whole viewport. Deltas and coefficients are in units
+/// of `2^delta_exponent`, like the points passed to
+/// [`MandelbrotPerturb::iterate`](Fractal::iterate).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeriesApproximation {
+    skipped: u32,
+    radius: f64,
+    a: Complex,
+    b: Complex,
+    c: Complex,
+}
+
+impl SeriesApproximation {
+    /// No skipping: every pixel starts at iteration zero.
+    pub const NONE: Self = Self {
+        skipped: 0,
+        radius: 0.0,
+        a: Complex::ZERO,
+        b: Complex::ZERO,
+        c: Complex::ZERO,
+    };
+
+    /// Advance the series along `reference` for as long as it stays valid
+    /// for every delta with `|δc| ≤ radius` (in units of `2^delta_exponent`).
+    ///
+    /// The series stops when the cubic term stops being negligible, when a
+    /// pixel could have escaped, or one step before the reference ends.
+    pub fn compute(
+        reference: &ReferenceOrbit,
+        params: &FractalParams,
+        radius: f64,
+        delta_exponent: i64,
+    ) -> Self {
+        if !(radius.is_finite() && radius > 0.0) {
+            return Self::NONE;
+        }
+
+        let orbit = &reference.points;
+        let escape_radius = params.escape_radius_sq().sqrt();
+        let limit = orbit
+            .len()
+            .saturating_sub(2)
+            .min(params.max_iterations as usize);
+
+        let mut series = Self {
+            radius,
+            ..Self::NONE
+        };
+        for n in 0..limit {
+            let z2 = orbit[n] * 2.0;
+            let (a, b, c) = (series.a, series.b, series.c);
+            // The quadratic terms carry a factor 2^delta_exponent; applying
+            // it before the product flushes them to zero exactly where the
+            // rescaled iteration would.
+            let a_next = z2 * a + Complex::new(radius, 0.0);
+            let b_next = z2 * b + a * ldexp_complex(a, delta_exponent);
+            let c_next = z2 * c + a * ldexp_complex(b, delta_exponent) * 2.0;
+
+            let a_norm = a_next.norm();
+            let bound = ldexp(a_norm + b_next.norm() + c_next.norm(), delta_exponent);
+            let valid = a_norm < SERIES_COEFFICIENT_LIMIT
+                && c_next.norm() <= SERIES_TOLERANCE * a_norm
+                && orbit[n + 1].norm() + bound <= escape_radius;
+            if !valid {
+                break;
+            }
+
+            series.a = a_next;
+            series.b = b_next;
+            series.c = c_next;
+            series.skipped = n as u32 + 1;
+        }
+        series
+    }
+
+    /// Number of iterations every pixel skips.
+    pub fn skipped(&self) -> u32 {
+        self.skipped
+    }
+
+    /// The delta after [`skipped`](Self::skipped) iterations for `dc`.
+    #[inline]
+    pub fn evaluate(&self, dc: Complex) -> Complex {
+        if self.skipped == 0 {
+            return Complex::ZERO;
+        }
+        let u = dc * (1.0 / self.radius);
+        ((self.c * u + self.b) * u + self.a) * u
+    }
+
+    /// `dδ_N / dδc`: the orbit derivative with respect to `c` at the
+    /// skipped iteration.
+    #[inline]
+    pub fn derivative(&self, dc: Complex) -> Complex {
+        if self.skipped == 0 {
+            return Complex::ZERO;
+        }
+        let u = dc * (1.0 / self.radius);
+        ((self.c * u * 3.0 + self.b * 2.0) * u + self.a) * (1.0 / self.radius)
+    }
+}
+
+/// Perturbation-theory Mandelbrot for deep zooms.
+///
+/// A single [`ReferenceOrbit`] is computed at the viewport center in
+/// extended precision. [`iterate`](Fractal::iterate) receives a
+/// **delta** `δc` from this center and iterates only the deviation from
+/// the reference orbit in `f64`:
+///
+/// `δ_{n+1} = 2·Z_n·δ_n + δ_n² + δc`
+///
+/// The deltas stay representable in `f64` long after absolute coordinates
+/// would collapse, so the per-pixel cost is that of plain `f64` iteration.
+///
+/// Glitches are detected with Pauldelbrot's criterion (see
+/// `GLITCH_TOLERANCE`). A glitched pixel, or one that outlives the
+/// reference orbit, is re-referenced onto `Z₀ = 0` by taking the full value
+/// `Z_m + δ` as its new delta. This is exact because every entry of the
+/// orbit shares the same `c_ref`.
+///
+/// Below the `f64` exponent range the incoming deltas are expressed relative
+/// to `2^delta_exponent` (see [`Viewport::delta_exponent`](crate::Viewport::delta_exponent)).
+/// The first iterations then run on rescaled deltas that share one exponent,
+/// until the delta has grown back into the normal `f64` range.
+///
+/// An optional [`SeriesApproximation`] lets every pixel start at a later
+/// iteration instead of zero.
+#[derive(Debug, Clone)]
+pub struct MandelbrotPerturb {
+    params: FractalParams,
+    reference: ReferenceOrbit,
+    /// `f64` approximation of the reference point, used for the cheap
+    /// interior pre-checks.
+    center_f64: Complex,
+    /// Binary exponent of the deltas passed to `iterate`.
+    delta_exponent: i64,
+    series: SeriesApproximation,
+    /// Stripe sum over the skipped iterations, approximated along the
+    /// reference orbit, keyed by stripe density.
+    skipped_stripe: OnceLock<(f64, f64)>,
+}
+
+/// Pixel state after the rescaled prefix of the iteration.
+enum Start {
+    /// Continue with plain `f64` deltas from iteration `n`.
+    Continue {
+        delta: Complex,
+        dc: Complex,
+        m: usize,
+        n: u32,
+    },
+    /// The pixel was decided before the delta reached the `f64` range.
+    Done(IterationResult),
+}
+
+#[inline]
+fn ldexp_complex(c: Complex, e: i64) -> Complex {
+    Complex::new(ldexp(c.re, e), ldexp(c.im, e))
+}
+
+#[inline]
+fn escape_distance(norm_sq: f64, dz: Complex) -> f64 {
+    let z_norm = norm_sq.sqrt();
+    let dz_norm = dz.norm_sq().sqrt();
+    if dz_norm > 0.0 {
+        z_norm * z_norm.ln() / dz_norm
+    } else {
+        0.0
+    }
+}
+
+impl MandelbrotPerturb {
+    /// Compute the reference orbit at `center` and build the fractal.
+    pub fn new(params: FractalParams, center: ComplexDD) -> Self {
+        Self::with_reference(params, ReferenceOrbit::compute(center, &params))
+    }
+
+    /// Build the fractal from a precomputed reference orbit.
+    pub fn with_reference(params: FractalParams, reference: ReferenceOrbit) -> Self {
+        let center_f64 = reference.center().to_complex();
+        Self {
+            params,
+            reference,
+            center_f64,
+            delta_exponent: 0,
+            series: SeriesApproximation::NONE,
+            skipped_stripe: OnceLock::new(),
+        }
+    }
+
+    /// Build the fractal for rendering `viewport`: reference orbit at its
+    /// full-precision center, matching delta exponent, and a series
+    /// approximation covering every pixel.
+    ///
+    /// The reference is iterated in quad-double while that resolves the
+    /// center well below a pixel, and at the viewport's
+    /// [`center_precision`](Viewport::center_precision) beyond.
+    pub fn for_viewport(params: FractalParams, viewport: &Viewport) -> Self {
+        let exponent = viewport.delta_exponent();
+        let reference = if viewport.scale.exponent() > QD_REFERENCE_EXPONENT {
+            ReferenceOrbit::compute_qd(viewport.center_qd, &params)
+        } else {
+            let center = viewport
+                .center_big
+                .with_precision(viewport.center_precision());
+            ReferenceOrbit::compute_big(&center, &params)
+        };
+        Self::with_reference(params, reference)
+            .with_delta_exponent(exponent)
+            .with_series_approximation(viewport.delta_radius_scaled(exponent))
+    }
+
+    /// Interpret incoming deltas as multiples of `2^exponent`.
+    ///
+    /// Drops any series approximation, which depends on the exponent.
+    pub fn with_delta_exponent(self, exponent: i64) -> Self {
+        Self {
+            delta_exponent: exponent,
+            series: SeriesApproximation::NONE,
+            skipped_stripe: OnceLock::new(),
+            ..self
+        }
+    }
+
+    /// Skip iterations with a series approximation valid for all deltas
+    /// up to `radius` (in units of `2^delta_exponent`).
+    pub fn with_series_approximation(self, radius: f64) -> Self {
+        let series = SeriesApproximation::compute(
+            &self.reference,
+            &self.params,
+            radius,
+            self.delta_exponent,
+        );
+        Self {
+            series,
+            skipped_stripe: OnceLock::new(),
+            ..self
+        }
+    }
+
+    /// The reference orbit shared by all pixels.
+    pub fn reference(&self) -> &ReferenceOrbit {
+        &self.reference
+    }
+
+    /// The series approximation in use ([`SeriesApproximation::NONE`] if
+    /// none was requested).
+    pub fn series(&self) -> &SeriesApproximation {
+        &self.series
+    }
+
+    /// Stripe sum over the skipped iterations. The pixel orbits shadow the
+    /// reference there, so its values stand in for theirs.
+    fn skipped_stripe_sum(&self, stripe_density: f64) -> f64 {
+        let sum = |density: f64| -> f64 {
+            self.reference.points[1..=self.series.skipped as usize]
+                .iter()
+                .map(|z| 0.5 * (density * z.im.atan2(z.re)).sin() + 0.5)
+                .sum()
+        };
+        let &(density, cached) = self
+            .skipped_stripe
+            .get_or_init(|| (stripe_density, sum(stripe_density)));
+        if density == stripe_density {
+            cached
+        } else {
+            sum(stripe_density)
+        }
+    }
+
+    /// Advance one perturbed step and handle re-referencing.
+    ///
+    /// Returns the full orbit value `z = Z_m + δ` after the step.
+    #[inline]
+    fn step(&self, delta: &mut Complex, m: &mut usize, dc: Complex) -> Complex {
+        let orbit = &self.reference.points;
+        let zr = orbit[*m];
+        let d = *delta;
+        // δ' = 2·Z·δ + δ² + δc
+        *delta = Complex::new(
+            2.0 * (zr.re * d.re - zr.im * d.im) + d.re * d.re - d.im * d.im + dc.re,
+            2.0 * (zr.re * d.im + zr.im * d.re) + 2.0 * d.re * d.im + dc.im,
+        );
+        *m += 1;
+
+        let zr = orbit[*m];
+        let z = zr + *delta;
+
+        // Pauldelbrot glitch detection, or end of the reference orbit:
+        // rebase onto Z₀ = 0 with the full value as the new delta.
+        if z.norm_sq() < GLITCH_TOLERANCE * zr.norm_sq() || *m + 1 >= orbit.len() {
+            *delta = z;
+            *m = 0;
+        }
+        z
+    }
+
+    /// Evaluate the series approximation, then run the iterations whose
+    /// delta is too small for `f64`.
+    ///
+    /// The delta is kept as `δ̃ · 2^s` with a shared exponent `s`; the
+    /// quadratic term `δ̃ · (δ̃ · 2^s)` flushes to zero while it is negligible.
+    /// No glitch check is needed here: `Z_m + δ` rounds to `Z_m`.
+    /// `on_step` receives the full orbit value after every step.
+    fn start(&self, point: Complex, mut on_step: impl FnMut(Complex)) -> Start {
+        let skipped = self.series.skipped();
+        let mut d = self.series.evaluate(point);
+        let mut m = skipped as usize;
+
+        if self.delta_exponent == 0 {
+            return Start::Continue {
+                delta: d,
+                dc: point,
+                m,
+                n: skipped,
+            };
+        }
+
+        let orbit = &self.reference.points;
+        let escape_radius_sq = self.params.escape_radius_sq();
+        let mut dc = point;
+        let mut s = self.delta_exponent;
+
+        for n in skipped..self.params.max_iterations {
+            let mag = d.re.abs().max(d.im.abs());
+            if mag > RESCALE_LIMIT {
+                let e = FloatExp::from(mag).exponent();
+                d = ldexp_complex(d, -e);
+                dc = ldexp_complex(dc, -e);
+                s += e;
+            }
+            if s > RESCALE_EXIT_EXPONENT {
+                return Start::Continue {
+                    delta: ldexp_complex(d, s),
+                    dc: ldexp_complex(dc, s),
+                    m,
+                    n,
+                };
+            }
+
+            let zr = orbit[m];
+            let dk = ldexp_complex(d, s);
+            d = zr * d * 2.0 + d * dk + dc;
+            m += 1;
+
+            let z = orbit[m] + ldexp_complex(d, s);
+            on_step(z);
+
+            let norm_sq = z.norm_sq();
+            if norm_sq > escape_radius_sq {
+                return Start::Done(IterationResult::Escaped {
+                    iterations: n,
+                    norm_sq,
+                });
+            }
+            if m + 1 >= orbit.len() {
+                return Start::Continue {
+                    delta: z,
+                    dc: ldexp_complex(dc, s),
+                    m: 0,
+                    n: n + 1,
+                };
+            }
+        }
+
+        Start::Done(IterationResult::Interior)
+    }
+}
+
+/// Cardioid check in f64 (rough filter — false negatives are fine).
+#[inline]
+fn in_cardioid(re: f64, im: f64) -> bool {
+    let im2 = im * im;
+    let q = (re - 0.25) * (re - 0.25) + im2;
+    q * (q + (re - 0.25)) <= 0.25 * im2
+}
+
+/// Period-2 bulb check in f64 (rough filter).
+#[inline]
+fn in_period2_bulb(re: f64, im: f64) -> bool {
+    (re + 1.0) * (re + 1.0) + im * im <= 0.0625
+}
+
+// Periodicity detection is intentionally absent: at perturbation depths two
+// orbit points closer than any fixed `f64` tolerance do not prove the orbit
+// is periodic, and a false positive would paint escaping pixels as interior.
+
+impl Fractal for MandelbrotPerturb {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let c_f64 = self.center_f64 + ldexp_complex(point, self.delta_exponent);
+        if in_cardioid(c_f64.re, c_f64.im) || in_period2_bulb(c_f64.re, c_f64.im) {
+            return IterationResult::Interior;
+        }
+
+        let escape_radius_sq = self.params.escape_radius_sq();
+        let max_iter = self.params.max_iterations;
+
+        let (mut delta, dc, mut m, first_n) = match self.start(point, |_| {}) {
+            Start::Continue { delta, dc, m, n } => (delta, dc, m, n),
+            Start::Done(result) => return result,
+        };
+
+        for n in first_n..max_iter {
+            let z = self.step(&mut delta, &mut m, dc);
+
+            let norm_sq = z.norm_sq();
+            if norm_sq > escape_radius_sq {
+                return IterationResult::Escaped {
+                    iterations: n,
+                    norm_sq,
+                };
+            }
+        }
+
+        IterationResult::Interior
+    }
+
+    fn iterate_with_extras(
+        &self,
+        point: Complex,
+        opts: &ExtrasOptions,
+    ) -> (IterationResult, IterationExtras) {
+        let stripe_density = opts.stripe_density;
+        let c_f64 = self.center_f64 + ldexp_complex(point, self.delta_exponent);
+        if in_cardioid(c_f64.re, c_f64.im) || in_period2_bulb(c_f64.re, c_f64.im) {
+            return (IterationResult::Interior, IterationExtras::default());
+        }
+
+        let escape_radius_sq = self.params.escape_radius_sq();
+        let max_iter = self.params.max_iterations;
+
+        // Resume the derivative and stripe sum where the series leaves off.
+        let skipped = self.series.skipped();
+        let mut z = self.reference.points[skipped as usize]
+            + ldexp_complex(self.series.evaluate(point), self.delta_exponent);
+        let mut dz = self.series.derivative(point);
+        let mut stripe_sum = if skipped > 0 {
+            self.skipped_stripe_sum(stripe_density)
+        } else {
+            0.0
+        };
+
+        // Derivative: dz = 2·z·dz + 1, using the full orbit value.
+        let mut advance = |z_new: Complex| {
+            dz = Complex::new(
+                2.0 * (z.re * dz.re - z.im * dz.im) + 1.0,
+                2.0 * (z.re * dz.im + z.im * dz.re),
+            );
+            z = z_new;
+            stripe_sum += 0.5 * (stripe_density * z.im.atan2(z.re)).sin() + 0.5;
+        };
+
+        let started = self.start(point, &mut advance);
+        let (mut delta, dc, mut m, first_n) = match started {
+            Start::Continue { delta, dc, m, n } => (delta, dc, m, n),
+            Start::Done(IterationResult::Escaped {
+                iterations,
+                norm_sq,
+            }) => {
+                return (
+                    IterationResult::Escaped {
+                        iterations,
+                        norm_sq,
+                    },
+                    IterationExtras {
+                        distance: escape_distance(norm_sq, dz),
+                        stripe_avg: 0.0,
+                        final_arg: z.im.atan2(z.re),
+                        ..IterationExtras::default()
+                    },
+                );
+            }
+            // Escape-time orbits never report `Converged` or `Measured`.
+            Start::Done(
+                IterationResult::Interior
+                | IterationResult::Converged { .. }
+                | IterationResult::Measured { .. },
+            ) => (Complex::ZERO, point, 0, max_iter),
+        };
+
+        for n in first_n..max_iter {
+            let z_new = self.step(&mut delta, &mut m, dc);
+            advance(z_new);
+
+            let norm_sq = z_new.norm_sq();
+            if norm_sq > escape_radius_sq {
+                return (
+                    IterationResult::Escaped {
+                        iterations: n,
+                        norm_sq,
+                    },
+                    IterationExtras {
+                        distance: escape_distance(norm_sq, dz),
+                        stripe_avg: 0.0,
+                        final_arg: z_new.im.atan2(z_new.re),
+                        ..IterationExtras::default()
+                    },
+                );
+            }
+        }
+
+        let stripe_avg = if max_iter > 0 {
+            stripe_sum / max_iter as f64
+        } else {
+            0.0
+        };
+        (
+            IterationResult::Interior,
+            IterationExtras {
+                distance: 0.0,
+                stripe_avg,
+                ..IterationExtras::default()
+            },
+        )
+    }
+
+    fn params(&self) -> &FractalParams {
+        &self.params
+    }
+
+    fn uses_delta_coordinates(&self) -> bool {
+        true
+    }
+
+    fn delta_exponent(&self) -> i64 {
+        self.delta_exponent
+    }
+
+    fn skipped_iterations(&self) -> u32 {
+        self.series.skipped()
+    }
+
+    fn symmetry(&self) -> Symmetry {
+        Symmetry::CONJUGATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::big_float::BigFloat;
+    use crate::double_double::DoubleDouble;
+    use crate::mandelbrot::{Mandelbrot, MandelbrotDD};
+
+    fn params(max_iterations: u32) -> FractalParams {
+        FractalParams::new(max_iterations, 2.0).unwrap()
+    }
+
+    #[test]
+    fn reference_orbit_starts_at_zero() {
+        let orbit = ReferenceOrbit::compute(ComplexDD::from(Complex::new(-0.5, 0.0)), &params(64));
+        assert_eq!(orbit.points[0], Complex::ZERO);
+        assert_eq!(orbit.len(), 65);
+        assert!(!orbit.escaped(&params(64)));
+    }
+
+    #[test]
+    fn big_reference_matches_dd_reference() {
+        let center = ComplexDD::new(
+            DoubleDouble::new(-0.743_643_887_037_151, 1.2e-17),
+            DoubleDouble::new(0.131_825_904_205_33, -3.4e-18),
+        );
+        let dd = ReferenceOrbit::compute(center, &params(200));
+        let big = ReferenceOrbit::compute_big(&ComplexBig::from(center), &params(200));
+        assert_eq!(dd.len(), big.len());
+        // The orbits agree while double-double still carries enough digits.
+        for (a, b) in dd.points.iter().zip(&big.points).take(60) {
+            assert!((*a - *b).norm_sq() <= 1e-24 * a.norm_sq().max(1e-300));
+        }
+    }
+
+    #[test]
+    fn escaping_reference_is_truncated() {
+        // c = 1.0 escapes at the third step: 0 → 1 → 2 → 5.
+        let orbit = ReferenceOrbit::compute(ComplexDD::from(Complex::new(1.0, 0.0)), &params(64));
+        assert_eq!(orbit.len(), 4);
+        assert!(orbit.escaped(&params(64)));
+    }
+
+    #[test]
+    fn matches_f64_at_shallow_zoom() {
+        // With the reference at the origin, deltas are absolute coordinates.
+        let m = Mandelbrot::new(params(500));
+        let p = MandelbrotPerturb::new(params(500), ComplexDD::ZERO);
+        let points = [
+            Complex::new(-0.75, 0.1),
+            Complex::new(0.3, 0.5),
+            Complex::new(-2.0, 0.0),
+            Complex::new(1.0, 1.0),
+            Complex::new(0.5, 0.0),
+            Complex::new(-1.0, 0.0),
+            Complex::new(-0.1, 0.9),
+        ];
+        for &c in &points {
+            let r_f64 = m.iterate(c);
+            let r_p = p.iterate(c);
+            assert_eq!(
+                r_f64.class(),
+                r_p.class(),
+                "iteration class mismatch at c = {c}: f64={r_f64:?}, perturb={r_p:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_double_double_at_deep_zoom() {
+        // Seahorse valley at ~1e-20 per pixel: beyond f64, within DD.
+        let center = ComplexDD::new(
+            DoubleDouble::new(-0.743_643_887_037_151, 1.2e-17),
+            DoubleDouble::new(0.131_825_904_205_33, -3.4e-18),
+        );
+        let p = params(2000);
+        let dd = MandelbrotDD::with_center(p, center);
+        let pert = MandelbrotPerturb::new(p, center);
+        let scale = 1e-20;
+        let mut mismatches = 0;
+        for i in -8..=8 {
+            for j in -8..=8 {
+                let delta = Complex::new(i as f64 * 37.0 * scale, j as f64 * 23.0 * scale);
+                let a = dd.iterate(delta);
+                let b = pert.iterate(delta);
+                if a.class() != b.class() {
+                    mismatches += 1;
+                }
+            }
+        }
+        // Isolated ±1 differences right at iteration-band edges are
+        // acceptable; systematic mismatches indicate a broken engine.
+        assert!(mismatches <= 3, "{mismatches} of 289 pixels differ from DD");
+    }
+
+    #[test]
+    fn re_references_past_escaped_reference() {
+        // The reference (c = 0.5) escapes quickly, but c = -0.5 is interior.
+        // Pixels must keep iterating by re-referencing onto Z₀.
+        let p = MandelbrotPerturb::new(params(300), ComplexDD::from(Complex::new(0.5, 0.0)));
+        assert!(p.reference().escaped(&params(300)));
+        assert_eq!(
+            p.iterate(Complex::new(-1.0, 0.0)),
+            IterationResult::Interior
+        );
+        // c = -0.5 + 0.7i escapes after several dozen iterations.
+        let m = Mandelbrot::new(params(300));
+        let c = Complex::new(-0.5, 0.7);
+        assert_eq!(
+            p.iterate(c - Complex::new(0.5, 0.0)).class(),
+            m.iterate(c).class()
+        );
+    }
+
+    #[test]
+    fn glitch_detection_triggers_rebase() {
+        // Reference c = -1 has orbit 0, -1, 0, -1, … — every other step sits
+        // on zero, where nearby pixels would glitch without rebasing.
+        let p = MandelbrotPerturb::new(params(500), ComplexDD::from(Complex::new(-1.0, 0.0)));
+        let m = Mandelbrot::new(params(500));
+        for &d in &[
+            Complex::new(0.3, 0.05),
+            Complex::new(-0.3, 0.2),
+            Complex::new(0.05, 0.3),
+        ] {
+            let c = Complex::new(-1.0, 0.0) + d;
+            assert_eq!(p.iterate(d).class(), m.iterate(c).class(), "at c = {c}");
+        }
+    }
+
+    #[test]
+    fn rescaled_deltas_match_plain_deltas() {
+        let center = ComplexDD::new(
+            DoubleDouble::new(-0.743_643_887_037_151, 1.2e-17),
+            DoubleDouble::new(0.131_825_904_205_33, -3.4e-18),
+        );
+        let plain = MandelbrotPerturb::new(params(3000), center);
+        let scaled = plain.clone().with_delta_exponent(-1100);
+        for i in -3..=3 {
+            for j in -3..=3 {
+                let delta = Complex::new(i as f64 * 1e-290, j as f64 * 0.7e-290);
+                let a = plain.iterate(delta);
+                let b = scaled.iterate(ldexp_complex(delta, 1100));
+                assert_eq!(a.class(), b.class(), "at delta = {delta}");
+            }
+        }
+    }
+
+    #[test]
+    fn scale_beyond_f64_range() {
+        // At 1e-1000 every pixel shadows the reference, which escapes.
+        let center = ComplexDD::from(Complex::new(-0.75, 0.05));
+        let reference = Mandelbrot::new(params(1000)).iterate(Complex::new(-0.75, 0.05));
+        let vp = crate::Viewport::new_dd(center, FloatExp::powi10(-1000), 64, 64).unwrap();
+        let p =
+            MandelbrotPerturb::new(params(1000), center).with_delta_exponent(vp.delta_exponent());
+        let (r, _) = p.iterate_with_extras(
+            vp.pixel_to_delta_scaled(3, 60, p.delta_exponent()),
+            &ExtrasOptions::default(),
+        );
+        assert!(matches!(r, IterationResult::Escaped { .. }));
+        assert_eq!(r.class(), reference.class());
+    }
+
+    fn seahorse() -> ComplexDD {
+        ComplexDD::new(
+            DoubleDouble::new(-0.743_643_887_037_151, 1.2e-17),
+            DoubleDouble::new(0.131_825_904_205_33, -3.4e-18),
+        )
+    }
+
+    #[test]
+    fn series_skips_nothing_at_shallow_zoom() {
+        let center = ComplexDD::from(Complex::new(-0.5, 0.5));
+        let p = MandelbrotPerturb::new(params(500), center).with_series_approximation(2.0);
+        assert_eq!(p.skipped_iterations(), 0);
+        assert_eq!(p.series().evaluate(Complex::new(0.5, 0.5)), Complex::ZERO);
+    }
+
+    #[test]
+    fn series_skip_matches_full_iteration() {
+        let scale = 1e-20;
+        let radius = Complex::new(8.0 * 37.0 * scale, 8.0 * 23.0 * scale).norm();
+        let plain = MandelbrotPerturb::new(params(2000), seahorse());
+        let sa = plain.clone().with_series_approximation(radius);
+        assert!(
+            sa.skipped_iterations() > 10,
+            "skipped {}",
+            sa.skipped_iterations()
+        );
+
+        let mut mismatches = 0;
+        for i in -8..=8 {
+            for j in -8..=8 {
+                let delta = Complex::new(i as f64 * 37.0 * scale, j as f64 * 23.0 * scale);
+                let a = plain.iterate(delta);
+                let b = sa.iterate(delta);
+                assert!(
+                    !matches!(b, IterationResult::Escaped { iterations, .. }
+                        if iterations < sa.skipped_iterations()),
+                    "escape inside the skipped range at {delta}"
+                );
+                if a.class() != b.class() {
+                    mismatches += 1;
+                }
+            }
+        }
+        assert!(mismatches <= 3, "{mismatches} of 289 pixels differ");
+    }
+
+    #[test]
+    fn series_derivative_matches_full_iteration() {
+        let radius = 1e-16;
+        let plain = MandelbrotPerturb::new(params(2000), seahorse());
+        let sa = plain.clone().with_series_approximation(radius);
+        assert!(sa.skipped_iterations() > 0);
+        let delta = Complex::new(0.3 * radius, -0.6 * radius);
+        let (ra, ea) = plain.iterate_with_extras(delta, &ExtrasOptions::with_stripe_density(1.0));
+        let (rb, eb) = sa.iterate_with_extras(delta, &ExtrasOptions::with_stripe_density(1.0));
+        assert_eq!(ra.class(), rb.class());
+        if matches!(ra, IterationResult::Escaped { .. }) {
+            assert!((ea.distance - eb.distance).abs() <= 1e-3 * ea.distance);
+        } else {
+            assert!((ea.stripe_avg - eb.stripe_avg).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn series_with_rescaled_deltas() {
+        let plain = MandelbrotPerturb::new(params(3000), seahorse());
+        let scaled = plain
+            .clone()
+            .with_delta_exponent(-1100)
+            .with_series_approximation(ldexp(4e-290, 1100));
+        assert!(scaled.skipped_iterations() > 0);
+        for i in -3..=3 {
+            for j in -3..=3 {
+                let delta = Complex::new(i as f64 * 1e-290, j as f64 * 0.7e-290);
+                let a = plain.iterate(delta);
+                let b = scaled.iterate(ldexp_complex(delta, 1100));
+                assert_eq!(a.class(), b.class(), "at delta = {delta}");
+            }
+        }
+    }
+
+    #[test]
+    fn for_viewport_covers_every_pixel() {
+        let vp = crate::Viewport::new_dd(seahorse(), FloatExp::from(1e-30), 64, 48).unwrap();
+        let p = MandelbrotPerturb::for_viewport(params(1000), &vp);
+        assert_eq!(p.delta_exponent(), 0);
+        assert!(p.skipped_iterations() > 0);
+        let corner = vp.pixel_to_delta(0, 0);
+        assert!(corner.norm() <= vp.delta_radius_scaled(0));
+    }
+
+    #[test]
+    fn extras_match_plain_iteration() {
+        let p = MandelbrotPerturb::new(params(256), ComplexDD::ZERO);
+        let delta = Complex::new(0.5, 0.0);
+        let (r, extras) = p.iterate_with_extras(delta, &ExtrasOptions::with_stripe_density(1.0));
+        assert_eq!(r, p.iterate(delta));
+        assert!(matches!(r, IterationResult::Escaped { .. }));
+        assert!(extras.distance > 0.0);
+    }
+
+    #[test]
+    fn deep_viewports_reference_the_full_precision_center() {
+        // Centers 1e-90 apart round to the same quad-double, but at 1e-100
+        // per pixel they are 1e10 pixels apart.
+        let p = FractalParams::new(1000, 2.0).unwrap();
+        let scale = FloatExp::powi10(-100);
+        let a = ComplexBig::new(BigFloat::parse("-1.9", 512).unwrap(), BigFloat::zero(512));
+        let b = ComplexBig::new(
+            &a.re + &BigFloat::parse("1e-90", 512).unwrap(),
+            BigFloat::zero(512),
+        );
+        let va = Viewport::new_big(a, scale, 64, 64).unwrap();
+        let vb = Viewport::new_big(b, scale, 64, 64).unwrap();
+        assert_eq!(va.center_qd, vb.center_qd);
+
+        let ra = MandelbrotPerturb::for_viewport(p, &va);
+        let rb = MandelbrotPerturb::for_viewport(p, &vb);
+        assert_ne!(ra.reference().points, rb.reference().points);
+        let exact = ReferenceOrbit::compute_big(&vb.center_big, &p);
+        // Chaotic along the real axis: agreement is only expected until
+        // rounding at the two precisions has grown.
+        assert_eq!(rb.reference().points[..200], exact.points[..200]);
+    }
+}
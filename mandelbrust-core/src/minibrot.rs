@@ -0,0 +1,298 @@
+//! Locating minibrots: the box-period finder picks the lowest period whose
+//! orbit of a small region surrounds the origin, and Newton's method then
+//! solves for the nucleus of that period. Both iterate in double-double, so
+//! they keep working down to the depth the DD tier renders.
+
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+
+/// Corners escaping past this squared radius end the box-period search.
+const BOX_ESCAPE_SQ: f64 = 1e8;
+
+/// Newton steps allowed before the nucleus solver gives up.
+const NEWTON_STEPS: usize = 64;
+
+/// Relative step size at which Newton has converged (DD resolution).
+const NEWTON_TOLERANCE: f64 = 1e-30;
+
+/// Multipliers visited on the way from a nucleus to its root.
+const ROOT_RAY_POINTS: u32 = 8;
+
+/// A minibrot near a search point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Minibrot {
+    /// The nucleus: the center of its main cardioid, where `c` is periodic
+    /// with period [`period`](Self::period) through `0`.
+    pub nucleus: ComplexDD,
+    pub period: u32,
+    /// Atom size estimate: roughly the radius of the main cardioid.
+    pub size: f64,
+}
+
+impl Minibrot {
+    /// Find the minibrot of lowest period inside the square of half-width
+    /// `radius` around `center`, searching periods up to `max_period`.
+    pub fn find(center: ComplexDD, radius: f64, max_period: u32) -> Option<Self> {
+        let period = box_period(center, radius, max_period)?;
+        let nucleus = nucleus(center, period)?;
+        Some(Self {
+            nucleus,
+            period,
+            size: atom_size(nucleus, period),
+        })
+    }
+}
+
+/// The lowest period `p ≤ max_period` for which the image of the square of
+/// half-width `radius` around `center` under `z ↦ z² + c`, iterated `p`
+/// times from `z = 0`, surrounds the origin. `None` if the square escapes
+/// first or no such period exists.
+pub fn box_period(center: ComplexDD, radius: f64, max_period: u32) -> Option<u32> {
+    let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+        .map(|(re, im)| center + ComplexDD::from(Complex::new(re * radius, im * radius)));
+    let mut z = [ComplexDD::ZERO; 4];
+    for period in 1..=max_period {
+        for (z, &c) in z.iter_mut().zip(&corners) {
+            *z = *z * *z + c;
+        }
+        let points = z.map(ComplexDD::to_complex);
+        if points.iter().any(|p| p.norm_sq() > BOX_ESCAPE_SQ) {
+            return None;
+        }
+        if surrounds_origin(&points) {
+            return Some(period);
+        }
+    }
+    None
+}
+
+/// Whether the closed polygon through `points` winds around the origin,
+/// counting crossings of the positive real axis.
+fn surrounds_origin(points: &[Complex]) -> bool {
+    let mut inside = false;
+    for (i, &a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        if (a.im > 0.0) != (b.im > 0.0) {
+            let x = a.re - a.im * (b.re - a.re) / (b.im - a.im);
+            if x > 0.0 {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Newton's method for the nucleus of period `period` nearest `guess`:
+/// the root of `z_p(c) = 0`. The orbit runs in double-double; the derivative
+/// and step only need `f64`. `None` if Newton does not converge.
+pub fn nucleus(guess: ComplexDD, period: u32) -> Option<ComplexDD> {
+    let mut c = guess;
+    for _ in 0..NEWTON_STEPS {
+        let mut z = ComplexDD::ZERO;
+        let mut dz = Complex::ZERO;
+        for _ in 0..period {
+            let z_f64 = z.to_complex();
+            dz = z_f64 * dz * 2.0 + Complex::new(1.0, 0.0);
+            z = z * z + c;
+        }
+        let step = z.to_complex() / dz;
+        if !step.re.is_finite() || !step.im.is_finite() {
+            return None;
+        }
+        c = c - ComplexDD::from(step);
+        if step.norm() <= NEWTON_TOLERANCE * c.to_complex().norm().max(1.0) {
+            return Some(c);
+        }
+    }
+    None
+}
+
+/// Root of the hyperbolic component of period `period` at `nucleus`: the
+/// cusp or bulb attachment point where the cycle's multiplier reaches `1`.
+/// Newton's method on the periodic point `z` and `c` together follows the
+/// internal ray of angle `0` out from the nucleus. `None` if it diverges.
+pub fn root(nucleus: ComplexDD, period: u32) -> Option<ComplexDD> {
+    follow_internal_ray(nucleus, period).map(|(_, c)| c)
+}
+
+/// Points on the boundary of the hyperbolic component of period `period`
+/// at `nucleus`, through the multiplier map: the `c` whose cycle has the
+/// multiplier `e^{2πik/points}`, for `k` in `0..points`, starting at the
+/// root. `None` if Newton's method diverges on the way.
+pub fn boundary(nucleus: ComplexDD, period: u32, points: usize) -> Option<Vec<ComplexDD>> {
+    let (mut z, mut c) = follow_internal_ray(nucleus, period)?;
+    let mut boundary = vec![c];
+    for k in 1..points {
+        let turn = std::f64::consts::TAU * k as f64 / points as f64;
+        let multiplier = Complex::new(turn.cos(), turn.sin());
+        (z, c) = solve_multiplier(z, c, period, multiplier)?;
+        boundary.push(c);
+    }
+    Some(boundary)
+}
+
+/// The periodic point and parameter at the root, reached by stepping the
+/// multiplier from `0` at the nucleus to `1`.
+fn follow_internal_ray(nucleus: ComplexDD, period: u32) -> Option<(ComplexDD, ComplexDD)> {
+    let (mut z, mut c) = (ComplexDD::ZERO, nucleus);
+    for k in 1..=ROOT_RAY_POINTS {
+        let multiplier = Complex::new(k as f64 / ROOT_RAY_POINTS as f64, 0.0);
+        (z, c) = solve_multiplier(z, c, period, multiplier)?;
+    }
+    Some((z, c))
+}
+
+/// Newton's method from `(z, c)` for the periodic point `z` and parameter
+/// `c` at which the `period`-cycle through `z` has the given multiplier.
+fn solve_multiplier(
+    mut z: ComplexDD,
+    mut c: ComplexDD,
+    period: u32,
+    multiplier: Complex,
+) -> Option<(ComplexDD, ComplexDD)> {
+    let one = Complex::new(1.0, 0.0);
+    for _ in 0..NEWTON_STEPS {
+        // f^p(z) and its derivatives by z, c, z² and c·z.
+        let (mut w, mut dz, mut dc) = (z, one, Complex::ZERO);
+        let (mut dzdz, mut dcdz) = (Complex::ZERO, Complex::ZERO);
+        for _ in 0..period {
+            let wf = w.to_complex();
+            dcdz = (wf * dcdz + dz * dc) * 2.0;
+            dc = wf * dc * 2.0 + one;
+            dzdz = (dz * dz + wf * dzdz) * 2.0;
+            dz = wf * dz * 2.0;
+            w = w * w + c;
+        }
+        // Solve for the step taking f^p(z) − z and (f^p)'(z) − λ to 0.
+        let f = (w - z).to_complex();
+        let g = dz - multiplier;
+        let a = dz - one;
+        let det = a * dcdz - dc * dzdz;
+        let step_z = (dc * g - dcdz * f) / det;
+        let step_c = (dzdz * f - a * g) / det;
+        if ![step_z.re, step_z.im, step_c.re, step_c.im]
+            .iter()
+            .all(|x| x.is_finite())
+        {
+            return None;
+        }
+        z = z + ComplexDD::from(step_z);
+        c = c + ComplexDD::from(step_c);
+        // The step in `z` matters too: at a cusp `c` can be right while
+        // `z` still has to move.
+        let size = z.to_complex().norm() + c.to_complex().norm();
+        if step_z.norm() + step_c.norm() <= NEWTON_TOLERANCE * size {
+            break;
+        }
+    }
+    Some((z, c))
+}
+
+/// Size estimate of the atom of period `period` at `nucleus`, comparable to
+/// the radius of its main cardioid.
+pub fn atom_size(nucleus: ComplexDD, period: u32) -> f64 {
+    let one = Complex::new(1.0, 0.0);
+    let mut z = ComplexDD::ZERO;
+    let mut l = one;
+    let mut b = one;
+    for _ in 1..period {
+        z = z * z + nucleus;
+        l = z.to_complex() * l * 2.0;
+        b += one / l;
+    }
+    1.0 / (b * l * l).norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dd(re: f64, im: f64) -> ComplexDD {
+        ComplexDD::from(Complex::new(re, im))
+    }
+
+    #[test]
+    fn box_period_finds_the_bulbs_around_a_point() {
+        // The main cardioid's nucleus, the period-2 bulb and the period-3
+        // minibrot on the real axis.
+        assert_eq!(box_period(dd(0.0, 0.0), 0.01, 100), Some(1));
+        assert_eq!(box_period(dd(-1.0, 0.0), 0.01, 100), Some(2));
+        assert_eq!(box_period(dd(-1.754, 0.0), 0.001, 100), Some(3));
+        // Far outside the set the square escapes.
+        assert_eq!(box_period(dd(2.0, 2.0), 0.01, 100), None);
+    }
+
+    #[test]
+    fn newton_converges_to_known_nuclei() {
+        let c = nucleus(dd(-1.1, 0.1), 2).unwrap();
+        assert!((c.to_complex() - Complex::new(-1.0, 0.0)).norm() < 1e-15);
+
+        // The period-3 "airplane" minibrot at c ≈ −1.754877666.
+        let c = nucleus(dd(-1.75, 0.0), 3).unwrap();
+        assert!((c.re.to_f64() + 1.754_877_666_246_693).abs() < 1e-14);
+        // z_3(c) vanishes to DD resolution.
+        let mut z = ComplexDD::ZERO;
+        for _ in 0..3 {
+            z = z * z + c;
+        }
+        assert!(z.to_complex().norm() < 1e-28);
+    }
+
+    #[test]
+    fn roots_of_known_components() {
+        let cases = [
+            ((-1.0, 0.0), 2, (-0.75, 0.0)),
+            ((-1.754_877_666_246_693, 0.0), 3, (-1.75, 0.0)),
+            // The 1/3 bulb touches the main cardioid at e^{2πi/3}/2 − e^{4πi/3}/4.
+            (
+                (-0.122_561_166_876_654, 0.744_861_766_619_744),
+                3,
+                (-0.125, 0.649_519_052_838_329),
+            ),
+            ((0.0, 0.0), 1, (0.25, 0.0)),
+        ];
+        for ((re, im), period, expected) in cases {
+            let c = root(dd(re, im), period).unwrap().to_complex();
+            let expected = Complex::new(expected.0, expected.1);
+            assert!((c - expected).norm() < 1e-12, "period {period}: {c}");
+        }
+    }
+
+    #[test]
+    fn boundaries_follow_the_multiplier_map() {
+        // The main cardioid is c = λ/2 − λ²/4 and the period-2 bulb the
+        // circle |c + 1| = 1/4.
+        let points = boundary(dd(0.0, 0.0), 1, 16).unwrap();
+        assert_eq!(points.len(), 16);
+        for (k, c) in points.iter().enumerate() {
+            let turn = std::f64::consts::TAU * k as f64 / 16.0;
+            let l = Complex::new(turn.cos(), turn.sin());
+            let expected = l * 0.5 - l * l * 0.25;
+            assert!((c.to_complex() - expected).norm() < 1e-12, "k = {k}: {c:?}");
+        }
+        for c in boundary(dd(-1.0, 0.0), 2, 16).unwrap() {
+            let r = (c.to_complex() + Complex::new(1.0, 0.0)).norm();
+            assert!((r - 0.25).abs() < 1e-12, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn find_frames_a_minibrot_from_off_center() {
+        let m = Minibrot::find(dd(-1.7548, 0.0001), 0.001, 1000).unwrap();
+        assert_eq!(m.period, 3);
+        assert!((m.nucleus.to_complex() - Complex::new(-1.754_877_666, 0.0)).norm() < 1e-8);
+        // The airplane's cardioid spans roughly 0.02 along the axis.
+        assert!(m.size > 1e-3 && m.size < 1e-1, "size {}", m.size);
+    }
+
+    #[test]
+    fn find_resolves_offsets_below_f64() {
+        let airplane = nucleus(dd(-1.75, 0.0), 3).unwrap();
+        // 1e-25 away: the same f64 point, a different DD one.
+        let offset = ComplexDD::from(Complex::new(1e-25, -1e-25));
+        let m = Minibrot::find(airplane + offset, 1e-24, 100).unwrap();
+        assert_eq!(m.period, 3);
+        let d = m.nucleus - airplane;
+        assert!(d.re.to_f64().abs() < 1e-29 && d.im.to_f64().abs() < 1e-29);
+    }
+}
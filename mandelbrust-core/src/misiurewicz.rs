@@ -0,0 +1,224 @@
+//! Locating Misiurewicz points: parameters whose critical orbit is strictly
+//! preperiodic, `z_{k+p} = z_k` with `k ≥ 2` minimal and `p` minimal
+//! (counting from the critical point `z₀ = 0`, so `c = i` is `M₂,₂` and
+//! `c = −2` is `M₂,₁`). They sit at the centers of the spirals and
+//! branch points of the boundary, so zooming towards one keeps the same
+//! picture repeating.
+//!
+//! Newton's method on `z_{k+p} − z_k` alone tends to fall into nuclei and
+//! points of lower preperiod or period, which are roots too; those are
+//! divided out ("deflated") so the iteration only converges to the point
+//! asked for.
+
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+
+/// Longest orbit (preperiod plus period) searched by [`MisiurewiczPoint::find`].
+const MAX_ORBIT: usize = 1024;
+
+/// Orbits escaping past this squared radius end the search.
+const ESCAPE_SQ: f64 = 1e8;
+
+/// Newton solves tried before the search gives up.
+const MAX_CANDIDATES: usize = 64;
+
+/// Newton steps allowed before the solver gives up.
+const NEWTON_STEPS: usize = 64;
+
+/// Relative step size at which Newton has converged (DD resolution).
+const NEWTON_TOLERANCE: f64 = 1e-30;
+
+/// Relative distance below which a lower preperiod or period is taken to
+/// hold as well.
+const DEGENERATE_TOLERANCE: f64 = 1e-27;
+
+/// A Misiurewicz point near a search point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MisiurewiczPoint {
+    pub c: ComplexDD,
+    /// Steps from `z₀ = 0` until the orbit lands on its cycle.
+    pub preperiod: u32,
+    pub period: u32,
+}
+
+impl MisiurewiczPoint {
+    /// Find the Misiurewicz point of lowest preperiod plus period within
+    /// `radius` of `center`, with orbits of up to `max_iterations` steps.
+    ///
+    /// Candidates are the `(k, p)` for which one Newton step from `center`
+    /// stays within `radius` but not for `(k − 1, p)`. They are solved in
+    /// order until one converges nearby with exactly that preperiod and
+    /// period.
+    pub fn find(center: ComplexDD, radius: f64, max_iterations: u32) -> Option<Self> {
+        let (z, dz) = orbit(center, (max_iterations as usize).min(MAX_ORBIT));
+        let len = z.len();
+        let mut tried = 0;
+        // Distance to the nearest root of z_{k+p} − z_k, from one Newton step.
+        let estimate =
+            |k: usize, p: usize| ((z[k + p] - z[k]).to_complex() / (dz[k + p] - dz[k])).norm();
+        for total in 3..len {
+            for preperiod in 2..total {
+                let period = total - preperiod;
+                // Once the orbit comes back close, every later step does
+                // too; only the first is worth solving for.
+                if estimate(preperiod, period) > radius || estimate(preperiod - 1, period) <= radius
+                {
+                    continue;
+                }
+                tried += 1;
+                if tried > MAX_CANDIDATES {
+                    return None;
+                }
+                let Some(c) = misiurewicz(center, preperiod as u32, period as u32) else {
+                    continue;
+                };
+                if (c - center).to_complex().norm() <= radius
+                    && is_exact(c, preperiod as u32, period as u32)
+                {
+                    return Some(Self {
+                        c,
+                        preperiod: preperiod as u32,
+                        period: period as u32,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The critical orbit `z₀ … z_n` of `c` in double-double with its
+/// derivatives by `c` in `f64`, cut short if it escapes.
+fn orbit(c: ComplexDD, n: usize) -> (Vec<ComplexDD>, Vec<Complex>) {
+    let mut z = vec![ComplexDD::ZERO];
+    let mut dz = vec![Complex::ZERO];
+    for _ in 0..n {
+        let (last, d) = (z[z.len() - 1], dz[dz.len() - 1]);
+        let next = last * last + c;
+        if next.to_complex().norm_sq() > ESCAPE_SQ {
+            break;
+        }
+        dz.push(last.to_complex() * d * 2.0 + Complex::new(1.0, 0.0));
+        z.push(next);
+    }
+    (z, dz)
+}
+
+/// Proper divisors of `n`.
+fn proper_divisors(n: u32) -> impl Iterator<Item = u32> {
+    (1..n).filter(move |d| n.is_multiple_of(*d))
+}
+
+/// The `(i, j)` pairs whose `z_i = z_j` would make a root of
+/// `z_{k+p} − z_k` degenerate: a lower preperiod or a proper divisor of the
+/// period.
+fn lower_pairs(preperiod: u32, period: u32) -> impl Iterator<Item = (usize, usize)> {
+    let k = preperiod as usize;
+    let p = period as usize;
+    (0..k)
+        .map(move |i| (i + p, i))
+        .chain(proper_divisors(period).map(move |q| (k + q as usize, k)))
+}
+
+/// Newton's method for the Misiurewicz point of the given preperiod and
+/// period nearest `guess`, deflated by the lower preperiods and periods.
+/// `None` if Newton does not converge.
+pub fn misiurewicz(guess: ComplexDD, preperiod: u32, period: u32) -> Option<ComplexDD> {
+    let total = (preperiod + period) as usize;
+    let mut c = guess;
+    for _ in 0..NEWTON_STEPS {
+        let (z, dz) = orbit(c, total);
+        if z.len() <= total {
+            return None;
+        }
+        let k = preperiod as usize;
+        if (z[total] - z[k]).to_complex().norm() == 0.0 {
+            return Some(c);
+        }
+        // (log h)' for h = (z_{k+p} − z_k) / Π (z_i − z_j).
+        let log_derivative = |(i, j): (usize, usize)| (dz[i] - dz[j]) / (z[i] - z[j]).to_complex();
+        let ratio = lower_pairs(preperiod, period).fold(log_derivative((total, k)), |r, pair| {
+            r - log_derivative(pair)
+        });
+        let step = Complex::new(1.0, 0.0) / ratio;
+        if !step.re.is_finite() || !step.im.is_finite() {
+            return None;
+        }
+        c = c - ComplexDD::from(step);
+        if step.norm() <= NEWTON_TOLERANCE * c.to_complex().norm().max(1.0) {
+            return Some(c);
+        }
+    }
+    None
+}
+
+/// Whether `c` has exactly the given preperiod and period rather than a
+/// lower one: no degenerate pair has a root within DD resolution.
+fn is_exact(c: ComplexDD, preperiod: u32, period: u32) -> bool {
+    let (z, dz) = orbit(c, (preperiod + period) as usize);
+    let scale = DEGENERATE_TOLERANCE * c.to_complex().norm().max(1.0);
+    z.len() > (preperiod + period) as usize
+        && lower_pairs(preperiod, period).all(|(i, j)| {
+            let distance = ((z[i] - z[j]).to_complex() / (dz[i] - dz[j])).norm();
+            distance > scale
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dd(re: f64, im: f64) -> ComplexDD {
+        ComplexDD::from(Complex::new(re, im))
+    }
+
+    #[test]
+    fn newton_converges_to_known_points() {
+        let c = misiurewicz(dd(0.1, 0.9), 2, 2).unwrap();
+        assert!((c.to_complex() - Complex::new(0.0, 1.0)).norm() < 1e-15);
+
+        let c = misiurewicz(dd(-1.9, 0.0), 2, 1).unwrap();
+        assert!((c.to_complex() - Complex::new(-2.0, 0.0)).norm() < 1e-15);
+
+        // Lands on the fixed point after three steps; z_4 = z_3 to DD
+        // resolution.
+        let c = misiurewicz(dd(-1.54, 0.0), 3, 1).unwrap();
+        assert!((c.re.to_f64() + 1.543_689_012_692_076).abs() < 1e-14);
+        let (z, _) = orbit(c, 4);
+        assert!((z[4] - z[3]).to_complex().norm() < 1e-28);
+    }
+
+    #[test]
+    fn deflation_avoids_lower_roots() {
+        // Next to the period-2 nucleus, plain Newton on z_4 − z_2 would
+        // fall into it.
+        let c = misiurewicz(dd(-1.001, 0.001), 2, 2);
+        assert!(c.is_none_or(|c| (c.to_complex() - Complex::new(-1.0, 0.0)).norm() > 1e-6));
+        assert!(!is_exact(dd(-2.0, 0.0), 2, 2));
+        assert!(is_exact(dd(-2.0, 0.0), 2, 1));
+    }
+
+    #[test]
+    fn find_picks_the_simplest_point_nearby() {
+        let m = MisiurewiczPoint::find(dd(0.01, 0.99), 0.05, 1000).unwrap();
+        assert_eq!((m.preperiod, m.period), (2, 2));
+        assert!((m.c.to_complex() - Complex::new(0.0, 1.0)).norm() < 1e-15);
+
+        let m = MisiurewiczPoint::find(dd(-1.99, 0.0), 0.05, 1000).unwrap();
+        assert_eq!((m.preperiod, m.period), (2, 1));
+
+        // Inside the main cardioid no orbit comes close to repeating
+        // non-trivially within a small radius.
+        assert!(MisiurewiczPoint::find(dd(0.0, 0.0), 1e-3, 1000).is_none());
+    }
+
+    #[test]
+    fn find_resolves_offsets_below_f64() {
+        let i = misiurewicz(dd(0.0, 1.0), 2, 2).unwrap();
+        let offset = dd(1e-25, -1e-25);
+        let m = MisiurewiczPoint::find(i + offset, 1e-24, 1000).unwrap();
+        assert_eq!((m.preperiod, m.period), (2, 2));
+        let d = m.c - i;
+        assert!(d.re.to_f64().abs() < 1e-29 && d.im.to_f64().abs() < 1e-29);
+    }
+}
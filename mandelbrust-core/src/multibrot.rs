@@ -0,0 +1,464 @@
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::double_double::DoubleDouble;
+use crate::error::CoreError;
+use crate::fractal::{
+    is_cycle, record_orbit, ExtrasOptions, Fractal, FractalParams, IterationExtras,
+    IterationResult, Symmetry,
+};
+use crate::numeric::{ComplexNum, Real, RealMath};
+
+/// The exponent `d` of a `z^d + c` family.
+///
+/// Whole numbers take a fast path through repeated complex multiplication.
+/// Any other value goes through the polar form `|z|^d · e^(i·d·arg z)` on
+/// the principal branch, which needs [`RealMath`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exponent {
+    Integer(u32),
+    Real(f64),
+}
+
+impl Exponent {
+    /// The classic quadratic exponent.
+    pub const SQUARE: Self = Self::Integer(2);
+
+    /// Validate `d` and pick the integer fast path when it is a whole number.
+    pub fn new(d: f64) -> crate::Result<Self> {
+        if !d.is_finite() || d <= 1.0 {
+            return Err(CoreError::InvalidExponent(d));
+        }
+        if d.fract() == 0.0 && d <= u32::MAX as f64 {
+            Ok(Self::Integer(d as u32))
+        } else {
+            Ok(Self::Real(d))
+        }
+    }
+
+    /// The exponent as a float.
+    pub fn value(self) -> f64 {
+        match self {
+            Self::Integer(d) => d as f64,
+            Self::Real(d) => d,
+        }
+    }
+
+    /// `z^d`.
+    #[inline]
+    pub fn pow<C>(self, z: C) -> C
+    where
+        C: ComplexNum,
+        C::Real: RealMath,
+    {
+        match self {
+            Self::Integer(0) => C::from_complex(Complex::new(1.0, 0.0)),
+            Self::Integer(d) => {
+                // Left-to-right binary exponentiation: d = 2 is a single
+                // squaring, bit-identical to the quadratic formula.
+                let mut result = z;
+                for bit in (0..d.ilog2()).rev() {
+                    result = result * result;
+                    if (d >> bit) & 1 == 1 {
+                        result = result * z;
+                    }
+                }
+                result
+            }
+            Self::Real(d) => {
+                let (re, im) = (z.re(), z.im());
+                if re.to_f64() == 0.0 && im.to_f64() == 0.0 {
+                    return C::ZERO;
+                }
+                // |z|^d = e^(d/2 · ln|z|²)
+                let modulus = (z.norm_sq().ln() * (d * 0.5)).exp();
+                let (sin, cos) = (im.atan2(re) * d).sin_cos();
+                C::new(modulus * cos, modulus * sin)
+            }
+        }
+    }
+
+    /// The derivative `d·z^(d−1)`, in `f64` for distance estimation.
+    #[inline]
+    pub fn derivative(self, z: Complex) -> Complex {
+        let power = match self {
+            // Constant and linear powers (reachable only by constructing
+            // `Exponent::Integer` directly); `0·1` is the derivative of 1.
+            Self::Integer(0 | 1) => Complex::new(1.0, 0.0),
+            Self::Integer(d) => Self::Integer(d - 1).pow(z),
+            Self::Real(d) => Self::Real(d - 1.0).pow(z),
+        };
+        power * self.value()
+    }
+}
+
+impl Default for Exponent {
+    fn default() -> Self {
+        Self::SQUARE
+    }
+}
+
+/// The Multibrot set: `z_{n+1} = z_n^d + c`, starting from `z₀ = 0`.
+///
+/// `d = 2` is the Mandelbrot set. Like [`Mandelbrot`](crate::Mandelbrot),
+/// extended precisions store the viewport `center` and receive deltas.
+/// Smooth coloring must use `ln d` as the logarithm base, since the orbit
+/// magnitude grows as `|z|^(dⁿ)` after escape.
+#[derive(Debug, Clone)]
+pub struct Multibrot<T: RealMath = f64> {
+    params: FractalParams,
+    exponent: Exponent,
+    center: T::Complex,
+}
+
+/// Double-double Multibrot (~31 digits).
+pub type MultibrotDD = Multibrot<DoubleDouble>;
+
+impl Multibrot {
+    pub fn new(exponent: Exponent, params: FractalParams) -> Self {
+        Self::with_center(exponent, params, Complex::ZERO)
+    }
+}
+
+impl<T: RealMath> Multibrot<T> {
+    /// Construct with a viewport center. Ignored for `f64`, whose points
+    /// are absolute.
+    pub fn with_center(exponent: Exponent, params: FractalParams, center: T::Complex) -> Self {
+        Self {
+            params,
+            exponent,
+            center,
+        }
+    }
+
+    pub fn exponent(&self) -> Exponent {
+        self.exponent
+    }
+
+    /// The point `c` on the complex plane for an incoming pixel coordinate.
+    #[inline]
+    fn point(&self, point: Complex) -> T::Complex {
+        if T::USES_DELTA_COORDINATES {
+            self.center + T::Complex::from_complex(point)
+        } else {
+            T::Complex::from_complex(point)
+        }
+    }
+}
+
+impl<T: RealMath> Fractal for Multibrot<T> {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let c = self.point(point);
+        let escape_radius_sq = T::from_f64(self.params.escape_radius_sq());
+        let max_iter = self.params.max_iterations;
+
+        let mut z = T::Complex::ZERO;
+
+        // Brent's cycle detection state.
+        let mut old_z = z;
+        let mut period: u32 = 0;
+        let mut check: u32 = 3;
+
+        for n in 0..max_iter {
+            // z = z^d + c
+            z = self.exponent.pow(z) + c;
+
+            let norm_sq = z.norm_sq();
+            if norm_sq > escape_radius_sq {
+                return IterationResult::Escaped {
+                    iterations: n,
+                    norm_sq: norm_sq.to_f64(),
+                };
+            }
+
+            if n >= 32 && n & 3 == 0 {
+                if is_cycle(z, old_z) {
+                    return IterationResult::Interior;
+                }
+
+                period += 1;
+                if period > check {
+                    old_z = z;
+                    period = 0;
+                    check = check.saturating_mul(2);
+                }
+            }
+        }
+
+        IterationResult::Interior
+    }
+
+    fn iterate_with_extras(
+        &self,
+        point: Complex,
+        opts: &ExtrasOptions,
+    ) -> (IterationResult, IterationExtras) {
+        let stripe_density = opts.stripe_density;
+        let c = self.point(point);
+        let escape_radius_sq = T::from_f64(self.params.escape_radius_sq());
+        let max_iter = self.params.max_iterations;
+
+        let mut z = T::Complex::ZERO;
+        let mut dz = Complex::ZERO;
+        let mut stripe_sum = 0.0f64;
+
+        let mut old_z = z;
+        let mut period: u32 = 0;
+        let mut check: u32 = 3;
+
+        for n in 0..max_iter {
+            // Derivative: dz = d·z^(d−1)·dz + 1
+            dz = self.exponent.derivative(z.to_complex()) * dz + Complex::new(1.0, 0.0);
+
+            z = self.exponent.pow(z) + c;
+
+            let norm_sq = z.norm_sq();
+            let z_f64 = z.to_complex();
+
+            stripe_sum += 0.5 * (stripe_density * z_f64.im.atan2(z_f64.re)).sin() + 0.5;
+
+            if norm_sq > escape_radius_sq {
+                let norm_sq = norm_sq.to_f64();
+                let z_norm = norm_sq.sqrt();
+                let dz_norm = dz.norm_sq().sqrt();
+                let distance = if dz_norm > 0.0 {
+                    z_norm * z_norm.ln() / dz_norm
+                } else {
+                    0.0
+                };
+                return (
+                    IterationResult::Escaped {
+                        iterations: n,
+                        norm_sq,
+                    },
+                    IterationExtras {
+                        distance,
+                        stripe_avg: 0.0,
+                        final_arg: z_f64.im.atan2(z_f64.re),
+                        ..IterationExtras::default()
+                    },
+                );
+            }
+
+            if n >= 32 && n & 3 == 0 {
+                if is_cycle(z, old_z) {
+                    let stripe_avg = if n > 0 { stripe_sum / n as f64 } else { 0.0 };
+                    return (
+                        IterationResult::Interior,
+                        IterationExtras {
+                            distance: 0.0,
+                            stripe_avg,
+                            ..IterationExtras::default()
+                        },
+                    );
+                }
+                period += 1;
+                if period > check {
+                    old_z = z;
+                    period = 0;
+                    check = check.saturating_mul(2);
+                }
+            }
+        }
+
+        let stripe_avg = if max_iter > 0 {
+            stripe_sum / max_iter as f64
+        } else {
+            0.0
+        };
+        (
+            IterationResult::Interior,
+            IterationExtras {
+                distance: 0.0,
+                stripe_avg,
+                ..IterationExtras::default()
+            },
+        )
+    }
+
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let c = self.point(point);
+        let escape_radius_sq = self.params.escape_radius_sq();
+        Some(record_orbit(
+            T::Complex::ZERO,
+            limit,
+            escape_radius_sq,
+            |z| self.exponent.pow(z) + c,
+        ))
+    }
+
+    fn params(&self) -> &FractalParams {
+        &self.params
+    }
+
+    fn uses_delta_coordinates(&self) -> bool {
+        T::USES_DELTA_COORDINATES
+    }
+
+    fn degree(&self) -> f64 {
+        self.exponent.value()
+    }
+
+    /// Mirror symmetric for any `d`; whole exponents add `(d−1)`-fold
+    /// rotational symmetry.
+    fn symmetry(&self) -> Symmetry {
+        Symmetry {
+            conjugate: true,
+            rotational_order: match self.exponent {
+                Exponent::Integer(d) => d.saturating_sub(1).max(1),
+                Exponent::Real(_) => 1,
+            },
+            flipped: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mandelbrot::Mandelbrot;
+    use crate::test_util::grid;
+
+    #[test]
+    fn exponent_validation() {
+        assert_eq!(Exponent::new(3.0).unwrap(), Exponent::Integer(3));
+        assert_eq!(Exponent::new(2.5).unwrap(), Exponent::Real(2.5));
+        assert_eq!(Exponent::new(2.5).unwrap().value(), 2.5);
+        assert!(Exponent::new(1.0).is_err());
+        assert!(Exponent::new(-3.0).is_err());
+        assert!(Exponent::new(f64::NAN).is_err());
+        assert!(Exponent::new(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn degenerate_integer_exponents_do_not_panic() {
+        let z = Complex::new(0.3, -0.4);
+        assert_eq!(Exponent::Integer(0).pow(z), Complex::new(1.0, 0.0));
+        assert_eq!(Exponent::Integer(1).pow(z), z);
+        assert_eq!(Exponent::Integer(0).derivative(z), Complex::ZERO);
+        assert_eq!(Exponent::Integer(1).derivative(z), Complex::new(1.0, 0.0));
+        for d in [0, 1] {
+            let mb = Multibrot::new(Exponent::Integer(d), FractalParams::default());
+            mb.iterate(Complex::new(0.1, 0.2));
+            mb.iterate_with_extras(Complex::new(0.1, 0.2), &ExtrasOptions::default());
+            assert_eq!(mb.symmetry().rotational_order, 1);
+        }
+    }
+
+    #[test]
+    fn real_pow_matches_integer_pow() {
+        for z in [
+            Complex::new(0.3, -1.2),
+            Complex::new(-1.5, 0.25),
+            Complex::new(0.0, 0.9),
+        ] {
+            for d in [2, 3, 5, 8] {
+                let exact = Exponent::Integer(d).pow(z);
+                let polar = Exponent::Real(d as f64).pow(z);
+                let tol = 1e-13 * exact.norm_sq().sqrt();
+                assert!((exact - polar).norm_sq().sqrt() < tol, "z = {z}, d = {d}");
+
+                let exact = Exponent::Integer(d).pow(ComplexDD::from(z));
+                let polar = Exponent::Real(d as f64).pow(ComplexDD::from(z));
+                let diff = (exact - polar).norm_sq().to_f64().sqrt();
+                assert!(
+                    diff < 1e-29 * exact.norm_sq().to_f64().sqrt(),
+                    "z = {z}, d = {d}"
+                );
+            }
+        }
+        assert_eq!(Exponent::Real(2.5).pow(Complex::ZERO), Complex::ZERO);
+    }
+
+    #[test]
+    fn derivative_matches_power_rule() {
+        let z = Complex::new(0.7, -0.4);
+        let d3 = Exponent::Integer(3).derivative(z);
+        let expected = z * z * 3.0;
+        assert!((d3 - expected).norm_sq() < 1e-28);
+        let d_real = Exponent::Real(3.0).derivative(z);
+        assert!((d_real - expected).norm_sq() < 1e-26);
+    }
+
+    #[test]
+    fn degree_two_matches_mandelbrot() {
+        let params = FractalParams::default();
+        let m = Mandelbrot::new(params);
+        let mb = Multibrot::new(Exponent::SQUARE, params);
+        for c in grid((-2.1, 0.0), (0.17, 0.145), (16, 8)) {
+            assert_eq!(mb.iterate(c), m.iterate(c), "c = {c}");
+            let (r, e) = mb.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(5.0));
+            let (r_m, e_m) = m.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(5.0));
+            if let IterationResult::Escaped { .. } = r_m {
+                assert_eq!(r, r_m);
+                assert!((e.distance - e_m.distance).abs() <= 1e-12 * e_m.distance);
+            }
+        }
+    }
+
+    #[test]
+    fn cubic_known_escape_count() {
+        // c = 2: z₁ = 2 (|z|² = 4, not > 4), z₂ = 8 + 2 = 10 → escapes at n = 1.
+        let mb = Multibrot::new(Exponent::Integer(3), FractalParams::default());
+        assert_eq!(
+            mb.iterate(Complex::new(2.0, 0.0)),
+            IterationResult::Escaped {
+                iterations: 1,
+                norm_sq: 100.0
+            }
+        );
+        assert_eq!(mb.iterate(Complex::ZERO), IterationResult::Interior);
+    }
+
+    #[test]
+    fn real_exponent_origin_interior_far_point_escapes() {
+        let mb = Multibrot::new(Exponent::new(2.5).unwrap(), FractalParams::default());
+        assert_eq!(mb.iterate(Complex::ZERO), IterationResult::Interior);
+        assert!(matches!(
+            mb.iterate(Complex::new(10.0, 0.0)),
+            IterationResult::Escaped { iterations: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn precisions_agree_on_grid() {
+        let params = FractalParams::default();
+        for exponent in [Exponent::Integer(4), Exponent::Real(3.5)] {
+            let mb = Multibrot::new(exponent, params);
+            let mb_dd = MultibrotDD::with_center(exponent, params, ComplexDD::ZERO);
+            assert!(mb_dd.uses_delta_coordinates());
+            let mut mismatches = 0;
+            for c in grid((-2.1, 0.0), (0.17, 0.145), (16, 8)) {
+                let (r, e) = mb.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(5.0));
+                assert_eq!(
+                    r,
+                    mb.iterate(c),
+                    "extras path must match iterate at c = {c}"
+                );
+                let (r_dd, e_dd) =
+                    mb_dd.iterate_with_extras(c, &ExtrasOptions::with_stripe_density(5.0));
+                if r.class() != r_dd.class() {
+                    mismatches += 1;
+                }
+                if matches!(r, IterationResult::Escaped { iterations, .. } if iterations < 10) {
+                    assert_eq!(r.class(), r_dd.class(), "c = {c}");
+                    assert!((e.distance - e_dd.distance).abs() <= 1e-9 * e.distance);
+                }
+            }
+            assert!(mismatches <= 2, "{exponent:?}: {mismatches} mismatches");
+        }
+    }
+
+    #[test]
+    fn double_double_center_offset() {
+        let exponent = Exponent::Integer(3);
+        let center = ComplexDD::new(
+            DoubleDouble::new(0.4, 1e-18),
+            DoubleDouble::new(0.6, -3e-19),
+        );
+        let mb_dd = MultibrotDD::with_center(exponent, FractalParams::default(), center);
+        let mb = Multibrot::new(exponent, FractalParams::default());
+        let delta = Complex::new(1e-3, -2e-3);
+        let absolute = (center + ComplexDD::from(delta)).to_complex();
+        assert_eq!(mb_dd.iterate(delta).class(), mb.iterate(absolute).class());
+    }
+}
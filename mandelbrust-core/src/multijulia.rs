@@ -0,0 +1,294 @@
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::double_double::DoubleDouble;
+use crate::fractal::{
+    is_cycle, record_orbit, ExtrasOptions, Fractal, FractalParams, IterationExtras,
+    IterationResult, Symmetry,
+};
+use crate::multibrot::Exponent;
+use crate::numeric::{ComplexNum, RealMath};
+
+/// The Julia sets of the Multibrot family: `z_{n+1} = z_n^d + c` with a
+/// fixed `c` and `z₀` the point on the complex plane.
+///
+/// `d = 2` is the ordinary [`Julia`](crate::Julia) set.
+#[derive(Debug, Clone)]
+pub struct Multijulia<T: RealMath = f64> {
+    params: FractalParams,
+    exponent: Exponent,
+
+    /// The fixed constant `c` that defines this Julia set.
+    c: T::Complex,
+
+    center: T::Complex,
+}
+
+/// Double-double Multijulia (~31 digits).
+pub type MultijuliaDD = Multijulia<DoubleDouble>;
+
+impl Multijulia {
+    pub fn new(c: Complex, exponent: Exponent, params: FractalParams) -> Self {
+        Self::with_center(c, exponent, params, Complex::ZERO)
+    }
+}
+
+impl<T: RealMath> Multijulia<T> {
+    /// Construct with a viewport center. Ignored for `f64`, whose points
+    /// are absolute.
+    pub fn with_center(
+        c: T::Complex,
+        exponent: Exponent,
+        params: FractalParams,
+        center: T::Complex,
+    ) -> Self {
+        Self {
+            params,
+            exponent,
+            c,
+            center,
+        }
+    }
+
+    /// The constant `c` defining this Julia set.
+    pub fn c(&self) -> T::Complex {
+        self.c
+    }
+
+    pub fn exponent(&self) -> Exponent {
+        self.exponent
+    }
+
+    /// The starting point `z₀` for an incoming pixel coordinate.
+    #[inline]
+    fn start(&self, point: Complex) -> T::Complex {
+        if T::USES_DELTA_COORDINATES {
+            self.center + T::Complex::from_complex(point)
+        } else {
+            T::Complex::from_complex(point)
+        }
+    }
+}
+
+impl<T: RealMath> Fractal for Multijulia<T> {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let escape_radius_sq = T::from_f64(self.params.escape_radius_sq());
+        let max_iter = self.params.max_iterations;
+
+        let mut z = self.start(point);
+
+        // Brent's cycle detection state.
+        let mut old_z = z;
+        let mut period: u32 = 0;
+        let mut check: u32 = 3;
+
+        for n in 0..max_iter {
+            // z = z^d + c
+            z = self.exponent.pow(z) + self.c;
+
+            let norm_sq = z.norm_sq();
+            if norm_sq > escape_radius_sq {
+                return IterationResult::Escaped {
+                    iterations: n,
+                    norm_sq: norm_sq.to_f64(),
+                };
+            }
+
+            if n >= 32 && n & 3 == 0 {
+                if is_cycle(z, old_z) {
+                    return IterationResult::Interior;
+                }
+
+                period += 1;
+                if period > check {
+                    old_z = z;
+                    period = 0;
+                    check = check.saturating_mul(2);
+                }
+            }
+        }
+
+        IterationResult::Interior
+    }
+
+    fn iterate_with_extras(
+        &self,
+        point: Complex,
+        opts: &ExtrasOptions,
+    ) -> (IterationResult, IterationExtras) {
+        let stripe_density = opts.stripe_density;
+        let escape_radius_sq = T::from_f64(self.params.escape_radius_sq());
+        let max_iter = self.params.max_iterations;
+
+        let mut z = self.start(point);
+        let mut dz = Complex::new(1.0, 0.0);
+        let mut stripe_sum = 0.0f64;
+
+        let mut old_z = z;
+        let mut period: u32 = 0;
+        let mut check: u32 = 3;
+
+        for n in 0..max_iter {
+            // Derivative: dz = d·z^(d−1)·dz  (d(z_n)/dz₀)
+            dz = self.exponent.derivative(z.to_complex()) * dz;
+
+            z = self.exponent.pow(z) + self.c;
+
+            let norm_sq = z.norm_sq();
+            let z_f64 = z.to_complex();
+
+            stripe_sum += 0.5 * (stripe_density * z_f64.im.atan2(z_f64.re)).sin() + 0.5;
+
+            if norm_sq > escape_radius_sq {
+                let norm_sq = norm_sq.to_f64();
+                let z_norm = norm_sq.sqrt();
+                let dz_norm = dz.norm_sq().sqrt();
+                let distance = if dz_norm > 0.0 {
+                    z_norm * z_norm.ln() / dz_norm
+                } else {
+                    0.0
+                };
+                return (
+                    IterationResult::Escaped {
+                        iterations: n,
+                        norm_sq,
+                    },
+                    IterationExtras {
+                        distance,
+                        stripe_avg: 0.0,
+                        final_arg: z_f64.im.atan2(z_f64.re),
+                        ..IterationExtras::default()
+                    },
+                );
+            }
+
+            if n >= 32 && n & 3 == 0 {
+                if is_cycle(z, old_z) {
+                    let stripe_avg = if n > 0 { stripe_sum / n as f64 } else { 0.0 };
+                    return (
+                        IterationResult::Interior,
+                        IterationExtras {
+                            distance: 0.0,
+                            stripe_avg,
+                            ..IterationExtras::default()
+                        },
+                    );
+                }
+                period += 1;
+                if period > check {
+                    old_z = z;
+                    period = 0;
+                    check = check.saturating_mul(2);
+                }
+            }
+        }
+
+        let stripe_avg = if max_iter > 0 {
+            stripe_sum / max_iter as f64
+        } else {
+            0.0
+        };
+        (
+            IterationResult::Interior,
+            IterationExtras {
+                distance: 0.0,
+                stripe_avg,
+                ..IterationExtras::default()
+            },
+        )
+    }
+
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let escape_radius_sq = self.params.escape_radius_sq();
+        let z0 = self.start(point);
+        Some(record_orbit(z0, limit, escape_radius_sq, |z| {
+            self.exponent.pow(z) + self.c
+        }))
+    }
+
+    fn params(&self) -> &FractalParams {
+        &self.params
+    }
+
+    fn uses_delta_coordinates(&self) -> bool {
+        T::USES_DELTA_COORDINATES
+    }
+
+    fn degree(&self) -> f64 {
+        self.exponent.value()
+    }
+
+    /// `(ωz)^d = z^d` for every `d`-th root of unity `ω` when `d` is whole.
+    fn symmetry(&self) -> Symmetry {
+        Symmetry {
+            rotational_order: match self.exponent {
+                Exponent::Integer(d) => d.max(1),
+                Exponent::Real(_) => 1,
+            },
+            ..Symmetry::NONE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::julia::Julia;
+    use crate::test_util::grid;
+
+    #[test]
+    fn degree_two_matches_julia() {
+        let params = FractalParams::default();
+        let j = Julia::new(Julia::default_c(), params);
+        let mj = Multijulia::new(Julia::default_c(), Exponent::SQUARE, params);
+        for p in grid((-1.6, -1.1), (0.27, 0.29), (12, 8)) {
+            assert_eq!(mj.iterate(p), j.iterate(p), "p = {p}");
+            let (r, e) = mj.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0));
+            let (r_j, e_j) = j.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0));
+            assert_eq!(r, r_j);
+            assert!((e.distance - e_j.distance).abs() <= 1e-12 * e_j.distance);
+        }
+    }
+
+    #[test]
+    fn c_zero_origin_is_interior() {
+        for exponent in [Exponent::Integer(5), Exponent::Real(2.5)] {
+            let mj = Multijulia::new(Complex::ZERO, exponent, FractalParams::default());
+            assert_eq!(mj.iterate(Complex::ZERO), IterationResult::Interior);
+            assert!(matches!(
+                mj.iterate(Complex::new(3.0, 0.0)),
+                IterationResult::Escaped { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn precisions_agree_on_grid() {
+        let params = FractalParams::default();
+        let c = Complex::new(-0.5, 0.55);
+        for exponent in [Exponent::Integer(3), Exponent::Real(2.5)] {
+            let mj = Multijulia::new(c, exponent, params);
+            let mj_dd =
+                MultijuliaDD::with_center(ComplexDD::from(c), exponent, params, ComplexDD::ZERO);
+            assert!(mj_dd.uses_delta_coordinates());
+            let mut mismatches = 0;
+            for p in grid((-1.6, -1.1), (0.27, 0.29), (12, 8)) {
+                let (r, e) = mj.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0));
+                assert_eq!(
+                    r,
+                    mj.iterate(p),
+                    "extras path must match iterate at p = {p}"
+                );
+                let (r_dd, e_dd) =
+                    mj_dd.iterate_with_extras(p, &ExtrasOptions::with_stripe_density(5.0));
+                if r.class() != r_dd.class() {
+                    mismatches += 1;
+                }
+                if matches!(r, IterationResult::Escaped { iterations, .. } if iterations < 10) {
+                    assert_eq!(r.class(), r_dd.class(), "p = {p}");
+                    assert!((e.distance - e_dd.distance).abs() <= 1e-9 * e.distance);
+                }
+            }
+            assert!(mismatches <= 2, "{exponent:?}: {mismatches} mismatches");
+        }
+    }
+}
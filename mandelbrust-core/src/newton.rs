@@ -0,0 +1,424 @@
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::error::CoreError;
+use crate::fractal::{
+    record_orbit, ExtrasOptions, Fractal, FractalParams, IterationExtras, IterationResult, Symmetry,
+};
+
+/// `|Δz|²` below which an orbit counts as converged.
+const CONVERGENCE_EPSILON_SQ: f64 = 1e-12;
+
+/// `|z|²` above which a Nova orbit is treated as escaping. Newton orbits
+/// thrown far out by a near-critical point always come back, so only Nova
+/// bails out.
+const BAILOUT_SQ: f64 = 1e12;
+
+/// A monic polynomial `p(z) = Π (z − rᵢ)` given by its roots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    roots: Vec<Complex>,
+    /// Coefficients from the leading term down, `coeffs[0] = 1`.
+    coeffs: Vec<Complex>,
+}
+
+impl Polynomial {
+    /// Build from at least two finite roots.
+    pub fn from_roots(roots: Vec<Complex>) -> crate::Result<Self> {
+        if roots.len() < 2 {
+            return Err(CoreError::InvalidPolynomial {
+                reason: format!("need at least 2 roots, got {}", roots.len()),
+            });
+        }
+        if let Some(r) = roots
+            .iter()
+            .find(|r| !(r.re.is_finite() && r.im.is_finite()))
+        {
+            return Err(CoreError::InvalidPolynomial {
+                reason: format!("root {r} is not finite"),
+            });
+        }
+
+        // Multiply out (z − r₀)(z − r₁)… one factor at a time.
+        let mut coeffs = vec![Complex::new(1.0, 0.0)];
+        for &r in &roots {
+            coeffs.push(Complex::ZERO);
+            for k in (1..coeffs.len()).rev() {
+                coeffs[k] = coeffs[k] - coeffs[k - 1] * r;
+            }
+        }
+        Ok(Self { roots, coeffs })
+    }
+
+    /// `z³ − 1`, the classic three-basin Newton fractal.
+    pub fn cube_roots_of_unity() -> Self {
+        let (s, c) = (2.0 * std::f64::consts::PI / 3.0).sin_cos();
+        Self::from_roots(vec![
+            Complex::new(1.0, 0.0),
+            Complex::new(c, s),
+            Complex::new(c, -s),
+        ])
+        .expect("three finite roots")
+    }
+
+    pub fn roots(&self) -> &[Complex] {
+        &self.roots
+    }
+
+    pub fn degree(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// `(p(z), p'(z))` by Horner's scheme.
+    #[inline]
+    pub fn eval_with_derivative(&self, z: Complex) -> (Complex, Complex) {
+        let mut p = self.coeffs[0];
+        let mut dp = Complex::ZERO;
+        for &a in &self.coeffs[1..] {
+            dp = dp * z + p;
+            p = p * z + a;
+        }
+        (p, dp)
+    }
+
+    /// Index of the root closest to `z`.
+    fn nearest_root(&self, z: Complex) -> u32 {
+        let mut best = 0;
+        let mut best_dist = f64::INFINITY;
+        for (i, &r) in self.roots.iter().enumerate() {
+            let d = (z - r).norm_sq();
+            if d < best_dist {
+                best = i;
+                best_dist = d;
+            }
+        }
+        best as u32
+    }
+}
+
+impl Default for Polynomial {
+    fn default() -> Self {
+        Self::cube_roots_of_unity()
+    }
+}
+
+/// Which plane the Newton map is drawn over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NewtonVariant {
+    /// `z ← z − a·p(z)/p'(z)` with the pixel as `z₀`; each pixel is
+    /// colored by the root its orbit converges to.
+    #[default]
+    Newton,
+    /// `z ← z − a·p(z)/p'(z) + c` with the pixel as `c` and `z₀` the first
+    /// root, so `z³ − 1` with `a = 1` is the classic Nova fractal.
+    Nova,
+}
+
+/// Newton's root-finding method as a fractal.
+///
+/// The relaxation `a` damps (`a < 1`) or overshoots (`a > 1`) each step;
+/// `a = 1` is plain Newton.
+#[derive(Debug, Clone)]
+pub struct Newton {
+    params: FractalParams,
+    polynomial: Polynomial,
+    relaxation: f64,
+    variant: NewtonVariant,
+}
+
+impl Newton {
+    pub const DEFAULT_RELAXATION: f64 = 1.0;
+
+    pub fn new(polynomial: Polynomial, params: FractalParams) -> Self {
+        Self {
+            params,
+            polynomial,
+            relaxation: Self::DEFAULT_RELAXATION,
+            variant: NewtonVariant::Newton,
+        }
+    }
+
+    /// The Nova variant of [`new`](Self::new).
+    pub fn nova(polynomial: Polynomial, params: FractalParams) -> Self {
+        Self {
+            variant: NewtonVariant::Nova,
+            ..Self::new(polynomial, params)
+        }
+    }
+
+    /// Set the relaxation factor. Roots stay attracting only for `0 < a < 2`.
+    pub fn with_relaxation(mut self, relaxation: f64) -> crate::Result<Self> {
+        if !(relaxation > 0.0 && relaxation < 2.0) {
+            return Err(CoreError::InvalidRelaxation(relaxation));
+        }
+        self.relaxation = relaxation;
+        Ok(self)
+    }
+
+    pub fn polynomial(&self) -> &Polynomial {
+        &self.polynomial
+    }
+
+    pub fn relaxation(&self) -> f64 {
+        self.relaxation
+    }
+
+    pub fn variant(&self) -> NewtonVariant {
+        self.variant
+    }
+}
+
+impl Fractal for Newton {
+    fn iterate(&self, point: Complex) -> IterationResult {
+        let (mut z, c) = match self.variant {
+            NewtonVariant::Newton => (point, Complex::ZERO),
+            NewtonVariant::Nova => (self.polynomial.roots[0], point),
+        };
+
+        for n in 0..self.params.max_iterations {
+            let (p, dp) = self.polynomial.eval_with_derivative(z);
+            let next = z - p / dp * self.relaxation + c;
+
+            let norm_sq = next.norm_sq();
+            if !norm_sq.is_finite() {
+                // Landed on a critical point of p.
+                return IterationResult::Interior;
+            }
+            if norm_sq > BAILOUT_SQ && self.variant == NewtonVariant::Nova {
+                return IterationResult::Escaped {
+                    iterations: n,
+                    norm_sq,
+                };
+            }
+
+            // Nova orbits settle on fixed points shifted away from the
+            // roots, so both variants test the step size and report the
+            // nearest root.
+            if (next - z).norm_sq() < CONVERGENCE_EPSILON_SQ {
+                return IterationResult::Converged {
+                    root_index: self.polynomial.nearest_root(next),
+                    iterations: n,
+                };
+            }
+            z = next;
+        }
+
+        IterationResult::Interior
+    }
+
+    /// Newton maps have no escape-time distance estimate or stripe
+    /// average; extras are always zero.
+    fn iterate_with_extras(
+        &self,
+        point: Complex,
+        _opts: &ExtrasOptions,
+    ) -> (IterationResult, IterationExtras) {
+        (self.iterate(point), IterationExtras::default())
+    }
+
+    /// Only Nova orbits bail out; Newton orbits run to `limit` unless they
+    /// land on a critical point of `p`.
+    fn orbit(&self, point: Complex, limit: u32) -> Option<Vec<ComplexDD>> {
+        let (z0, c) = match self.variant {
+            NewtonVariant::Newton => (point, Complex::ZERO),
+            NewtonVariant::Nova => (self.polynomial.roots[0], point),
+        };
+        let bailout_sq = match self.variant {
+            NewtonVariant::Newton => f64::INFINITY,
+            NewtonVariant::Nova => BAILOUT_SQ,
+        };
+        Some(record_orbit(z0, limit, bailout_sq, |z| {
+            let (p, dp) = self.polynomial.eval_with_derivative(z);
+            z - p / dp * self.relaxation + c
+        }))
+    }
+
+    fn params(&self) -> &FractalParams {
+        &self.params
+    }
+
+    /// Conjugate pairs of roots make the basins mirror images, but a
+    /// mirrored pixel would keep the wrong root index, so only all-real
+    /// roots report the symmetry.
+    fn symmetry(&self) -> Symmetry {
+        if self.polynomial.roots.iter().all(|r| r.im == 0.0) {
+            Symmetry::CONJUGATE
+        } else {
+            Symmetry::NONE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::grid;
+
+    #[test]
+    fn polynomial_validation() {
+        assert!(Polynomial::from_roots(vec![Complex::ZERO]).is_err());
+        assert!(Polynomial::from_roots(vec![Complex::ZERO, Complex::new(f64::NAN, 0.0)]).is_err());
+        assert!(Polynomial::from_roots(vec![Complex::ZERO, Complex::new(1.0, 1.0)]).is_ok());
+    }
+
+    #[test]
+    fn expansion_vanishes_at_roots_with_matching_derivative() {
+        let roots = vec![
+            Complex::new(1.0, 0.5),
+            Complex::new(-0.3, 0.0),
+            Complex::new(0.2, -1.1),
+            Complex::new(2.0, 0.0),
+        ];
+        let poly = Polynomial::from_roots(roots.clone()).unwrap();
+        for &r in &roots {
+            assert!(poly.eval_with_derivative(r).0.norm() < 1e-12);
+        }
+        // p'(z) = Σᵢ Π_{j≠i} (z − r_j)
+        let z = Complex::new(0.7, 0.4);
+        let mut expected = Complex::ZERO;
+        for i in 0..roots.len() {
+            let mut term = Complex::new(1.0, 0.0);
+            for (j, &r) in roots.iter().enumerate() {
+                if i != j {
+                    term *= z - r;
+                }
+            }
+            expected += term;
+        }
+        let (_, dp) = poly.eval_with_derivative(z);
+        assert!((dp - expected).norm() < 1e-12);
+    }
+
+    #[test]
+    fn relaxation_validation() {
+        let n = || Newton::new(Polynomial::default(), FractalParams::default());
+        assert!(n().with_relaxation(0.0).is_err());
+        assert!(n().with_relaxation(2.0).is_err());
+        assert!(n().with_relaxation(f64::NAN).is_err());
+        assert_eq!(n().with_relaxation(0.5).unwrap().relaxation(), 0.5);
+    }
+
+    #[test]
+    fn points_near_roots_converge_to_them() {
+        let newton = Newton::new(Polynomial::default(), FractalParams::default());
+        for (i, &r) in newton.polynomial().roots().iter().enumerate() {
+            match newton.iterate(r * 1.1) {
+                IterationResult::Converged {
+                    root_index,
+                    iterations,
+                } => {
+                    assert_eq!(root_index, i as u32);
+                    assert!(iterations < 10);
+                }
+                other => panic!("expected convergence near root {i}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn critical_point_is_interior() {
+        // p'(0) = 0 exactly for z² − 1.
+        let poly =
+            Polynomial::from_roots(vec![Complex::new(1.0, 0.0), Complex::new(-1.0, 0.0)]).unwrap();
+        let newton = Newton::new(poly, FractalParams::default());
+        assert_eq!(newton.iterate(Complex::ZERO), IterationResult::Interior);
+    }
+
+    #[test]
+    fn cube_roots_basins_have_threefold_symmetry() {
+        let newton = Newton::new(Polynomial::default(), FractalParams::default());
+        let (s, c) = (2.0 * std::f64::consts::PI / 3.0).sin_cos();
+        let omega = Complex::new(c, s);
+        let mut checked = 0;
+        for z in grid((-1.5, -1.5), (0.15, 0.15), (20, 20)) {
+            if let (
+                IterationResult::Converged { root_index: a, .. },
+                IterationResult::Converged { root_index: b, .. },
+            ) = (newton.iterate(z), newton.iterate(z * omega))
+            {
+                // Rotating z by ω rotates its root by ω, i.e. shifts the index.
+                let rotated = newton
+                    .polynomial()
+                    .nearest_root(newton.polynomial().roots()[a as usize] * omega);
+                if rotated == b {
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 400, "only {checked} points matched");
+    }
+
+    #[test]
+    fn relaxation_slows_convergence() {
+        let params = FractalParams::default();
+        let plain = Newton::new(Polynomial::default(), params);
+        let damped = Newton::new(Polynomial::default(), params)
+            .with_relaxation(0.5)
+            .unwrap();
+        let z = Complex::new(1.4, 0.3);
+        let count = |r| match r {
+            IterationResult::Converged { iterations, .. } => iterations,
+            other => panic!("expected convergence, got {other:?}"),
+        };
+        assert!(count(damped.iterate(z)) > count(plain.iterate(z)));
+    }
+
+    #[test]
+    fn orbits_follow_the_newton_step() {
+        let newton = Newton::new(Polynomial::default(), FractalParams::default());
+        let orbit = newton.orbit(Complex::new(2.0, 0.0), 50).unwrap();
+        assert_eq!(orbit.len(), 51, "Newton orbits never bail out");
+        // 2 − (8 − 1)/12 = 17/12.
+        assert_eq!(orbit[1].to_complex(), Complex::new(17.0 / 12.0, 0.0));
+        assert!((orbit[50].to_complex() - Complex::new(1.0, 0.0)).norm() < 1e-12);
+
+        // Nova starts on the first root and is shifted by the pixel.
+        let nova = Newton::nova(Polynomial::default(), FractalParams::default());
+        let orbit = nova.orbit(Complex::new(0.5, 0.0), 1).unwrap();
+        assert_eq!(orbit[0].to_complex(), Complex::new(1.0, 0.0));
+        assert_eq!(orbit[1].to_complex(), Complex::new(1.5, 0.0));
+    }
+
+    #[test]
+    fn nova_converges_at_origin_and_far_out() {
+        // With c = 0 the orbit starts on a root and converges at once.
+        let nova = Newton::nova(Polynomial::default(), FractalParams::default());
+        assert_eq!(
+            nova.iterate(Complex::ZERO),
+            IterationResult::Converged {
+                root_index: 0,
+                iterations: 0
+            }
+        );
+        // Far out the map is ≈ 2z/3 + c, which settles on z ≈ 3c.
+        assert!(matches!(
+            nova.iterate(Complex::new(50.0, 0.0)),
+            IterationResult::Converged { root_index: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn real_roots_mirror_across_the_real_axis() {
+        let params = FractalParams::default();
+        assert!(
+            !Newton::new(Polynomial::default(), params)
+                .symmetry()
+                .conjugate
+        );
+
+        let real = Polynomial::from_roots(vec![
+            Complex::new(-1.0, 0.0),
+            Complex::new(0.5, 0.0),
+            Complex::new(1.2, 0.0),
+        ])
+        .unwrap();
+        for newton in [
+            Newton::new(real.clone(), params),
+            Newton::nova(real, params),
+        ] {
+            assert!(newton.symmetry().conjugate);
+            for z in grid((-1.5, -1.5), (0.15, 0.15), (20, 20)) {
+                assert_eq!(newton.iterate(z), newton.iterate(z.conj()), "z = {z}");
+            }
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::complex_qd::ComplexQD;
+use crate::double_double::DoubleDouble;
+use crate::quad_double::QuadDouble;
+
+/// A real scalar type the escape-time formulas can iterate in.
+///
+/// Implemented by `f64`, [`DoubleDouble`] and [`QuadDouble`]. Fractals such
+/// as [`Mandelbrot<T>`](crate::Mandelbrot) are written once against this
+/// trait and monomorphized per precision tier, so a new precision only needs
+/// an implementation here.
+pub trait Real:
+    Copy
+    + Debug
+    + PartialOrd
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Mul<f64, Output = Self>
+    + Neg<Output = Self>
+    + 'static
+{
+    /// The complex type with components of this precision.
+    type Complex: ComplexNum<Real = Self>;
+
+    /// Whether fractals at this precision take pixel coordinates as deltas
+    /// from a stored center (see [`Fractal::uses_delta_coordinates`]).
+    ///
+    /// `f64` addresses the plane directly; wider types exist precisely to
+    /// hold a center that `f64` cannot represent.
+    ///
+    /// [`Fractal::uses_delta_coordinates`]: crate::Fractal::uses_delta_coordinates
+    const USES_DELTA_COORDINATES: bool;
+
+    /// Brent periodicity tolerance: two orbit points closer than this on
+    /// both axes are treated as a cycle.
+    const PERIOD_TOLERANCE: f64;
+
+    fn from_f64(x: f64) -> Self;
+
+    /// Round to the nearest `f64`.
+    fn to_f64(self) -> f64;
+
+    fn abs(self) -> Self;
+}
+
+/// Transcendental functions needed by formulas beyond polynomials, such as
+/// the polar form of `z^d` for real `d`.
+///
+/// Implemented by `f64` and [`DoubleDouble`]; [`QuadDouble`] has no
+/// elementary functions, so formulas that need them stop at double-double.
+pub trait RealMath: Real {
+    fn exp(self) -> Self;
+
+    /// Natural logarithm.
+    fn ln(self) -> Self;
+
+    /// `(sin self, cos self)`.
+    fn sin_cos(self) -> (Self, Self);
+
+    /// Four-quadrant arctangent of `self / x`.
+    fn atan2(self, x: Self) -> Self;
+}
+
+/// A complex number whose components are a [`Real`].
+pub trait ComplexNum:
+    Copy
+    + Debug
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + 'static
+{
+    type Real: Real<Complex = Self>;
+
+    const ZERO: Self;
+
+    fn new(re: Self::Real, im: Self::Real) -> Self;
+
+    fn re(self) -> Self::Real;
+
+    fn im(self) -> Self::Real;
+
+    /// Widen an `f64` complex (exact).
+    fn from_complex(c: Complex) -> Self;
+
+    /// Round to `f64` complex.
+    fn to_complex(self) -> Complex;
+
+    /// Round to double-double complex (exact for `f64` and double-double).
+    fn to_complex_dd(self) -> ComplexDD;
+
+    /// Returns `re² + im²` without taking the square root.
+    #[inline]
+    fn norm_sq(self) -> Self::Real {
+        self.re() * self.re() + self.im() * self.im()
+    }
+
+    /// `self² + c`, the quadratic step shared by Mandelbrot and Julia.
+    #[inline]
+    fn square_add(self, c: Self) -> Self {
+        let (re, im) = (self.re(), self.im());
+        Self::new(re * re - im * im + c.re(), re * 2.0 * im + c.im())
+    }
+}
+
+// -- f64 --
+
+impl Real for f64 {
+    type Complex = Complex;
+
+    const USES_DELTA_COORDINATES: bool = false;
+    const PERIOD_TOLERANCE: f64 = 1e-13;
+
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+}
+
+impl RealMath for f64 {
+    #[inline]
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    #[inline]
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) {
+        f64::sin_cos(self)
+    }
+
+    #[inline]
+    fn atan2(self, x: Self) -> Self {
+        f64::atan2(self, x)
+    }
+}
+
+impl ComplexNum for Complex {
+    type Real = f64;
+
+    const ZERO: Self = Complex::ZERO;
+
+    #[inline]
+    fn new(re: f64, im: f64) -> Self {
+        Complex::new(re, im)
+    }
+
+    #[inline]
+    fn re(self) -> f64 {
+        self.re
+    }
+
+    #[inline]
+    fn im(self) -> f64 {
+        self.im
+    }
+
+    #[inline]
+    fn from_complex(c: Complex) -> Self {
+        c
+    }
+
+    #[inline]
+    fn to_complex(self) -> Complex {
+        self
+    }
+
+    #[inline]
+    fn to_complex_dd(self) -> ComplexDD {
+        ComplexDD::from(self)
+    }
+}
+
+// -- Double-double --
+
+impl Real for DoubleDouble {
+    type Complex = ComplexDD;
+
+    const USES_DELTA_COORDINATES: bool = true;
+    const PERIOD_TOLERANCE: f64 = 1e-28;
+
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        DoubleDouble::from(x)
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        DoubleDouble::to_f64(self)
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        DoubleDouble::abs(self)
+    }
+}
+
+impl RealMath for DoubleDouble {
+    #[inline]
+    fn exp(self) -> Self {
+        DoubleDouble::exp(self)
+    }
+
+    #[inline]
+    fn ln(self) -> Self {
+        DoubleDouble::ln(self)
+    }
+
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) {
+        DoubleDouble::sin_cos(self)
+    }
+
+    #[inline]
+    fn atan2(self, x: Self) -> Self {
+        DoubleDouble::atan2(self, x)
+    }
+}
+
+impl ComplexNum for ComplexDD {
+    type Real = DoubleDouble;
+
+    const ZERO: Self = ComplexDD::ZERO;
+
+    #[inline]
+    fn new(re: DoubleDouble, im: DoubleDouble) -> Self {
+        ComplexDD::new(re, im)
+    }
+
+    #[inline]
+    fn re(self) -> DoubleDouble {
+        self.re
+    }
+
+    #[inline]
+    fn im(self) -> DoubleDouble {
+        self.im
+    }
+
+    #[inline]
+    fn from_complex(c: Complex) -> Self {
+        ComplexDD::from(c)
+    }
+
+    #[inline]
+    fn to_complex(self) -> Complex {
+        ComplexDD::to_complex(self)
+    }
+
+    #[inline]
+    fn to_complex_dd(self) -> ComplexDD {
+        self
+    }
+}
+
+// -- Quad-double --
+
+impl Real for QuadDouble {
+    type Complex = ComplexQD;
+
+    const USES_DELTA_COORDINATES: bool = true;
+    const PERIOD_TOLERANCE: f64 = 1e-58;
+
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        QuadDouble::from(x)
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        QuadDouble::to_f64(self)
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        QuadDouble::abs(self)
+    }
+}
+
+impl ComplexNum for ComplexQD {
+    type Real = QuadDouble;
+
+    const ZERO: Self = ComplexQD::ZERO;
+
+    #[inline]
+    fn new(re: QuadDouble, im: QuadDouble) -> Self {
+        ComplexQD::new(re, im)
+    }
+
+    #[inline]
+    fn re(self) -> QuadDouble {
+        self.re
+    }
+
+    #[inline]
+    fn im(self) -> QuadDouble {
+        self.im
+    }
+
+    #[inline]
+    fn from_complex(c: Complex) -> Self {
+        ComplexQD::from(c)
+    }
+
+    #[inline]
+    fn to_complex(self) -> Complex {
+        ComplexQD::to_complex(self)
+    }
+
+    #[inline]
+    fn to_complex_dd(self) -> ComplexDD {
+        ComplexQD::to_complex_dd(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_add_all(z: Complex, c: Complex) -> (Complex, Complex, Complex) {
+        (
+            z.square_add(c),
+            ComplexDD::from(z)
+                .square_add(ComplexDD::from(c))
+                .to_complex(),
+            ComplexQD::from(z)
+                .square_add(ComplexQD::from(c))
+                .to_complex(),
+        )
+    }
+
+    #[test]
+    fn square_add_agrees_across_precisions() {
+        // Small dyadic inputs are exact in every tier.
+        let (f, dd, qd) = square_add_all(Complex::new(1.5, -0.25), Complex::new(-0.75, 0.125));
+        assert_eq!(f, Complex::new(1.4375, -0.625));
+        assert_eq!(dd, f);
+        assert_eq!(qd, f);
+    }
+
+    #[test]
+    fn norm_sq_matches_inherent() {
+        let z = Complex::new(0.3, -1.7);
+        assert_eq!(ComplexNum::norm_sq(z), z.norm_sq());
+        let zd = ComplexDD::from(z);
+        assert_eq!(ComplexNum::norm_sq(zd), zd.norm_sq());
+        let zq = ComplexQD::from(z);
+        assert_eq!(ComplexNum::norm_sq(zq), zq.norm_sq());
+    }
+}
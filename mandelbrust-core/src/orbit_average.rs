@@ -0,0 +1,149 @@
+//! Orbit averages: per-iteration quantities averaged along an escaping orbit
+//! and blended across the last iteration so the result varies smoothly
+//! between escape-time bands.
+
+use crate::complex::Complex;
+
+/// The quantity an [`OrbitAverager`] averages. Every term lies in `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrbitAverage {
+    /// `½·sin(density·arg z) + ½`: stripes radiating from the set.
+    Stripe,
+    /// Triangle inequality average: where `|z² + c|` falls between its
+    /// bounds `||z|² − |c||` and `|z|² + |c|`.
+    TriangleInequality,
+    /// Turning angle `|arg((zₙ − zₙ₋₁) / (zₙ₋₁ − zₙ₋₂))| / π` of the orbit.
+    Curvature,
+}
+
+/// Running sum of [`OrbitAverage`] terms along an orbit.
+#[derive(Debug, Clone)]
+pub(crate) struct OrbitAverager {
+    kind: Option<OrbitAverage>,
+    density: f64,
+    sum: f64,
+    last: f64,
+    count: u32,
+    prev_step: Complex,
+}
+
+impl OrbitAverager {
+    /// An averager measuring `kind`; `None` makes every call a no-op.
+    pub(crate) fn new(kind: Option<OrbitAverage>, density: f64) -> Self {
+        Self {
+            kind,
+            density,
+            sum: 0.0,
+            last: 0.0,
+            count: 0,
+            prev_step: Complex::ZERO,
+        }
+    }
+
+    /// Record the step `z_old → z`, where `z = z_old² + c`.
+    #[inline]
+    pub(crate) fn push(&mut self, z_old: Complex, z: Complex, c: Complex) {
+        let term = match self.kind {
+            None => return,
+            Some(OrbitAverage::Stripe) => Some(0.5 * (self.density * z.im.atan2(z.re)).sin() + 0.5),
+            Some(OrbitAverage::TriangleInequality) => {
+                let z_sq = z_old.norm_sq();
+                let c_norm = c.norm();
+                let low = (z_sq - c_norm).abs();
+                let high = z_sq + c_norm;
+                (high > low).then(|| (z.norm() - low) / (high - low))
+            }
+            Some(OrbitAverage::Curvature) => {
+                let step = z - z_old;
+                let prev = self.prev_step;
+                self.prev_step = step;
+                (prev != Complex::ZERO).then(|| {
+                    let dot = step.re * prev.re + step.im * prev.im;
+                    let cross = step.im * prev.re - step.re * prev.im;
+                    cross.atan2(dot).abs() / std::f64::consts::PI
+                })
+            }
+        };
+        if let Some(term) = term {
+            self.sum += term;
+            self.last = term;
+            self.count += 1;
+        }
+    }
+
+    /// The average for an orbit that escaped with `|z|² = norm_sq`. With
+    /// `smooth`, the averages with and without the last term are blended by
+    /// how far past the escape radius the orbit landed. `NaN` when nothing
+    /// was measured.
+    pub(crate) fn finish(&self, norm_sq: f64, escape_radius_sq: f64, smooth: bool) -> f64 {
+        if self.kind.is_none() || self.count == 0 {
+            return f64::NAN;
+        }
+        let avg = self.sum / self.count as f64;
+        if !smooth || self.count < 2 || escape_radius_sq <= 1.0 {
+            return avg;
+        }
+        let prev = (self.sum - self.last) / (self.count - 1) as f64;
+        // 1 just past the escape radius, 0 at its square.
+        let weight = (1.0 - (norm_sq.ln() / escape_radius_sq.ln()).log2()).clamp(0.0, 1.0);
+        prev + (avg - prev) * weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(kind: OrbitAverage, orbit: &[Complex], c: Complex) -> OrbitAverager {
+        let mut avg = OrbitAverager::new(Some(kind), 1.0);
+        for pair in orbit.windows(2) {
+            avg.push(pair[0], pair[1], c);
+        }
+        avg
+    }
+
+    #[test]
+    fn terms_measure_each_quantity() {
+        // A straight orbit along the positive real axis.
+        let line = [
+            Complex::ZERO,
+            Complex::new(1.0, 0.0),
+            Complex::new(2.0, 0.0),
+        ];
+        let stripe = run(OrbitAverage::Stripe, &line, Complex::ZERO);
+        assert!((stripe.finish(4.0, 4.0, false) - 0.5).abs() < 1e-12);
+        // No turn between the two steps.
+        let curvature = run(OrbitAverage::Curvature, &line, Complex::ZERO);
+        assert_eq!(curvature.count, 1);
+        assert_eq!(curvature.finish(4.0, 4.0, false), 0.0);
+
+        // z² + c lands on the upper bound when z² and c point the same way.
+        let c = Complex::new(0.5, 0.0);
+        let tia = run(
+            OrbitAverage::TriangleInequality,
+            &[Complex::new(1.0, 0.0), Complex::new(1.5, 0.0)],
+            c,
+        );
+        assert!((tia.finish(4.0, 4.0, false) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn smoothing_blends_toward_the_previous_average() {
+        let mut avg = OrbitAverager::new(Some(OrbitAverage::Stripe), 1.0);
+        avg.sum = 1.0;
+        avg.last = 1.0;
+        avg.count = 2;
+        // Just past the radius keeps the full average, at its square the
+        // average without the last term.
+        assert!((avg.finish(100.0, 100.0, true) - 0.5).abs() < 1e-12);
+        assert!(avg.finish(10_000.0, 100.0, true).abs() < 1e-12);
+        assert!((avg.finish(10_000.0, 100.0, false) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn disabled_averager_measures_nothing() {
+        let mut avg = OrbitAverager::new(None, 1.0);
+        avg.push(Complex::ZERO, Complex::new(1.0, 1.0), Complex::ZERO);
+        assert!(avg.finish(4.0, 4.0, true).is_nan());
+    }
+}
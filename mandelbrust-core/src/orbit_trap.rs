@@ -0,0 +1,164 @@
+//! Orbit traps: shapes in the plane whose closest approach by an orbit
+//! colors the pixel the orbit started from.
+
+use serde::{Deserialize, Serialize};
+
+use crate::complex::Complex;
+
+/// The shape of an [`OrbitTrap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrapShape {
+    /// A single point.
+    #[default]
+    Point,
+    /// An infinite line through the center.
+    Line,
+    /// Two perpendicular segments crossing at the center.
+    Cross,
+    /// A circle around the center.
+    Circle,
+    /// Pickover stalks: both infinite axes through the center.
+    Stalks,
+}
+
+impl TrapShape {
+    pub const ALL: [Self; 5] = [
+        Self::Point,
+        Self::Line,
+        Self::Cross,
+        Self::Circle,
+        Self::Stalks,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Point => "Point",
+            Self::Line => "Line",
+            Self::Cross => "Cross",
+            Self::Circle => "Circle",
+            Self::Stalks => "Pickover stalks",
+        }
+    }
+
+    /// Whether [`OrbitTrap::size`] changes the shape.
+    pub fn has_size(self) -> bool {
+        matches!(self, Self::Cross | Self::Circle)
+    }
+
+    /// Whether [`OrbitTrap::angle`] changes the shape.
+    pub fn has_angle(self) -> bool {
+        matches!(self, Self::Line | Self::Cross | Self::Stalks)
+    }
+}
+
+/// A trap shape placed in the plane. Fractals that support traps report the
+/// smallest [`distance`](Self::distance) reached by any orbit point.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrbitTrap {
+    pub shape: TrapShape,
+    pub center: Complex,
+    /// Radius of a circle, half the arm length of a cross.
+    pub size: f64,
+    /// Rotation of a line, cross or stalks in radians; `0` is horizontal.
+    pub angle: f64,
+}
+
+impl Default for OrbitTrap {
+    fn default() -> Self {
+        Self {
+            shape: TrapShape::Point,
+            center: Complex::ZERO,
+            size: 1.0,
+            angle: 0.0,
+        }
+    }
+}
+
+impl OrbitTrap {
+    /// Distance from `z` to the trap.
+    #[inline]
+    pub fn distance(&self, z: Complex) -> f64 {
+        let d = z - self.center;
+        match self.shape {
+            TrapShape::Point => d.norm(),
+            TrapShape::Circle => (d.norm() - self.size).abs(),
+            TrapShape::Line | TrapShape::Cross | TrapShape::Stalks => {
+                // Coordinates along (u) and across (v) the rotated axes.
+                let (sin, cos) = self.angle.sin_cos();
+                let u = d.re * cos + d.im * sin;
+                let v = d.im * cos - d.re * sin;
+                match self.shape {
+                    TrapShape::Line => v.abs(),
+                    TrapShape::Stalks => u.abs().min(v.abs()),
+                    _ => {
+                        let arm = |along: f64, across: f64| {
+                            across.hypot((along.abs() - self.size).max(0.0))
+                        };
+                        arm(u, v).min(arm(v, u))
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trap(shape: TrapShape) -> OrbitTrap {
+        OrbitTrap {
+            shape,
+            center: Complex::new(1.0, 1.0),
+            size: 0.5,
+            angle: 0.0,
+        }
+    }
+
+    #[test]
+    fn distances_to_each_shape() {
+        let z = Complex::new(2.0, 1.5);
+        let eps = 1e-12;
+        assert!((trap(TrapShape::Point).distance(z) - 1.25f64.sqrt()).abs() < eps);
+        assert!((trap(TrapShape::Circle).distance(z) - (1.25f64.sqrt() - 0.5)).abs() < eps);
+        assert!((trap(TrapShape::Line).distance(z) - 0.5).abs() < eps);
+        assert!((trap(TrapShape::Stalks).distance(z) - 0.5).abs() < eps);
+        // Past the end of the horizontal arm at (1.5, 1).
+        assert!((trap(TrapShape::Cross).distance(z) - 0.5f64.hypot(0.5)).abs() < eps);
+        // On an arm.
+        assert!(trap(TrapShape::Cross).distance(Complex::new(1.0, 1.3)) < eps);
+    }
+
+    #[test]
+    fn rotation_turns_lines_and_crosses() {
+        let vertical = OrbitTrap {
+            angle: std::f64::consts::FRAC_PI_2,
+            ..trap(TrapShape::Line)
+        };
+        assert!(vertical.distance(Complex::new(1.0, 9.0)) < 1e-12);
+        assert!((vertical.distance(Complex::new(3.0, 1.0)) - 2.0).abs() < 1e-12);
+
+        let diagonal = OrbitTrap {
+            angle: std::f64::consts::FRAC_PI_4,
+            ..trap(TrapShape::Cross)
+        };
+        let on_arm = Complex::new(1.0 + 0.3, 1.0 - 0.3);
+        assert!(diagonal.distance(on_arm) < 1e-12);
+    }
+
+    #[test]
+    fn missing_fields_take_defaults() {
+        let trap: OrbitTrap = serde_json::from_str(r#"{"shape":"circle"}"#).unwrap();
+        assert_eq!(
+            trap,
+            OrbitTrap {
+                shape: TrapShape::Circle,
+                ..OrbitTrap::default()
+            }
+        );
+        let json = serde_json::to_string(&trap).unwrap();
+        assert_eq!(serde_json::from_str::<OrbitTrap>(&json).unwrap(), trap);
+    }
+}
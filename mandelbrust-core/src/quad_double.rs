@@ -0,0 +1,469 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::double_double::{quick_two_sum, two_prod, two_sum, DoubleDouble};
+
+/// A quad-double floating-point number: ~62 significant decimal digits.
+///
+/// Stores a value as the unevaluated sum `x₀ + x₁ + x₂ + x₃` of four
+/// non-overlapping `f64` limbs, ordered by decreasing magnitude. Sits
+/// between [`DoubleDouble`] and [`BigFloat`](crate::BigFloat): still
+/// `Copy` and allocation-free, so it can be used per pixel.
+///
+/// Reference: Hida, Li, Bailey — "Library for Double-Double and Quad-Double
+/// Arithmetic" (2001). Addition and multiplication are the library's
+/// "sloppy" variants, which are accurate to a few ulps of the last limb.
+#[derive(Debug, Clone, Copy)]
+pub struct QuadDouble {
+    pub limbs: [f64; 4],
+}
+
+// ---------------------------------------------------------------------------
+// Error-free building blocks
+// ---------------------------------------------------------------------------
+
+/// Error-free sum of three values: afterwards `a` holds the rounded sum and
+/// `b`, `c` the two error terms.
+#[inline]
+fn three_sum(a: &mut f64, b: &mut f64, c: &mut f64) {
+    let (t1, t2) = two_sum(*a, *b);
+    let (s, t3) = two_sum(*c, t1);
+    *a = s;
+    let (e1, e2) = two_sum(t2, t3);
+    *b = e1;
+    *c = e2;
+}
+
+/// Like [`three_sum`] but only keeps the first error term (in `b`).
+#[inline]
+fn three_sum2(a: &mut f64, b: &mut f64, c: f64) {
+    let (t1, t2) = two_sum(*a, *b);
+    let (s, t3) = two_sum(c, t1);
+    *a = s;
+    *b = t2 + t3;
+}
+
+/// Renormalize five overlapping components into four non-overlapping limbs.
+#[inline]
+fn renorm(c0: f64, c1: f64, c2: f64, c3: f64, c4: f64) -> [f64; 4] {
+    if c0.is_infinite() {
+        return [c0, c1, c2, c3];
+    }
+
+    let (s, c4) = quick_two_sum(c3, c4);
+    let (s, c3) = quick_two_sum(c2, s);
+    let (s, c2) = quick_two_sum(c1, s);
+    let (c0, c1) = quick_two_sum(c0, s);
+
+    let (mut s0, mut s1) = (c0, c1);
+    let (mut s2, mut s3) = (0.0, 0.0);
+
+    if s1 != 0.0 {
+        (s1, s2) = quick_two_sum(s1, c2);
+        if s2 != 0.0 {
+            (s2, s3) = quick_two_sum(s2, c3);
+            if s3 != 0.0 {
+                s3 += c4;
+            } else {
+                s2 += c4;
+            }
+        } else {
+            (s1, s2) = quick_two_sum(s1, c3);
+            if s2 != 0.0 {
+                (s2, s3) = quick_two_sum(s2, c4);
+            } else {
+                (s1, s2) = quick_two_sum(s1, c4);
+            }
+        }
+    } else {
+        (s0, s1) = quick_two_sum(s0, c2);
+        if s1 != 0.0 {
+            (s1, s2) = quick_two_sum(s1, c3);
+            if s2 != 0.0 {
+                (s2, s3) = quick_two_sum(s2, c4);
+            } else {
+                (s1, s2) = quick_two_sum(s1, c4);
+            }
+        } else {
+            (s0, s1) = quick_two_sum(s0, c3);
+            if s1 != 0.0 {
+                (s1, s2) = quick_two_sum(s1, c4);
+            } else {
+                (s0, s1) = quick_two_sum(s0, c4);
+            }
+        }
+    }
+
+    [s0, s1, s2, s3]
+}
+
+// ---------------------------------------------------------------------------
+// Construction
+// ---------------------------------------------------------------------------
+
+impl QuadDouble {
+    pub const ZERO: Self = Self { limbs: [0.0; 4] };
+
+    /// Build from four limbs ordered by decreasing magnitude, renormalizing
+    /// them so they no longer overlap.
+    #[inline]
+    pub fn new(x0: f64, x1: f64, x2: f64, x3: f64) -> Self {
+        Self {
+            limbs: renorm(x0, x1, x2, x3, 0.0),
+        }
+    }
+
+    /// The combined value as a single `f64` (loses the low-order limbs).
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.limbs[0] + self.limbs[1]
+    }
+
+    /// Round to double-double (keeps ~31 of the ~62 digits).
+    #[inline]
+    pub fn to_dd(self) -> DoubleDouble {
+        let [x0, x1, x2, x3] = self.limbs;
+        let (hi, lo) = quick_two_sum(x0, x1 + (x2 + x3));
+        DoubleDouble::new(hi, lo)
+    }
+
+    #[inline]
+    pub fn abs(self) -> Self {
+        if self.is_negative() {
+            -self
+        } else {
+            self
+        }
+    }
+
+    #[inline]
+    pub fn is_positive(self) -> bool {
+        self.limbs
+            .iter()
+            .find(|&&x| x != 0.0)
+            .is_some_and(|&x| x > 0.0)
+    }
+
+    #[inline]
+    pub fn is_negative(self) -> bool {
+        self.limbs
+            .iter()
+            .find(|&&x| x != 0.0)
+            .is_some_and(|&x| x < 0.0)
+    }
+}
+
+impl From<f64> for QuadDouble {
+    #[inline]
+    fn from(val: f64) -> Self {
+        Self {
+            limbs: [val, 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+impl From<DoubleDouble> for QuadDouble {
+    #[inline]
+    fn from(val: DoubleDouble) -> Self {
+        Self {
+            limbs: [val.hi, val.lo, 0.0, 0.0],
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Arithmetic: QD + QD, QD + f64
+// ---------------------------------------------------------------------------
+
+impl Add for QuadDouble {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        let (a, b) = (self.limbs, rhs.limbs);
+        let (s0, mut t0) = two_sum(a[0], b[0]);
+        let (mut s1, mut t1) = two_sum(a[1], b[1]);
+        let (mut s2, mut t2) = two_sum(a[2], b[2]);
+        let (mut s3, t3) = two_sum(a[3], b[3]);
+
+        (s1, t0) = two_sum(s1, t0);
+        three_sum(&mut s2, &mut t0, &mut t1);
+        three_sum2(&mut s3, &mut t0, t2);
+        t2 = t0 + t1 + t3;
+
+        Self {
+            limbs: renorm(s0, s1, s2, s3, t2),
+        }
+    }
+}
+
+impl Add<f64> for QuadDouble {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: f64) -> Self {
+        let a = self.limbs;
+        let (c0, e) = two_sum(a[0], rhs);
+        let (c1, e) = two_sum(a[1], e);
+        let (c2, e) = two_sum(a[2], e);
+        let (c3, e) = two_sum(a[3], e);
+        Self {
+            limbs: renorm(c0, c1, c2, c3, e),
+        }
+    }
+}
+
+impl AddAssign for QuadDouble {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Arithmetic: QD - QD
+// ---------------------------------------------------------------------------
+
+impl Sub for QuadDouble {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl SubAssign for QuadDouble {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Arithmetic: QD * QD, QD * f64
+// ---------------------------------------------------------------------------
+
+impl Mul for QuadDouble {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.limbs, rhs.limbs);
+
+        // O(1), O(ε) and O(ε²) partial products, each with its error.
+        let (p0, mut q0) = two_prod(a[0], b[0]);
+        let (mut p1, mut q1) = two_prod(a[0], b[1]);
+        let (mut p2, mut q2) = two_prod(a[1], b[0]);
+        let (mut p3, q3) = two_prod(a[0], b[2]);
+        let (mut p4, q4) = two_prod(a[1], b[1]);
+        let (mut p5, q5) = two_prod(a[2], b[0]);
+
+        // O(ε) terms.
+        three_sum(&mut p1, &mut p2, &mut q0);
+
+        // O(ε²) terms: (p2, q1, q2) + (p3, p4, p5).
+        three_sum(&mut p2, &mut q1, &mut q2);
+        three_sum(&mut p3, &mut p4, &mut p5);
+        let (s0, t0) = two_sum(p2, p3);
+        let (s1, t1) = two_sum(q1, p4);
+        let mut s2 = q2 + p5;
+        let (s1, t0) = two_sum(s1, t0);
+        s2 += t0 + t1;
+
+        // O(ε³) terms need no error tracking.
+        let s1 = s1 + (a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0]) + (q0 + q3 + q4 + q5);
+
+        Self {
+            limbs: renorm(p0, p1, s0, s1, s2),
+        }
+    }
+}
+
+impl MulAssign for QuadDouble {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+/// Scalar multiplication: `QuadDouble * f64`.
+impl Mul<f64> for QuadDouble {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: f64) -> Self {
+        let a = self.limbs;
+        let (p0, q0) = two_prod(a[0], rhs);
+        let (p1, mut q1) = two_prod(a[1], rhs);
+        let (mut p2, mut q2) = two_prod(a[2], rhs);
+        let p3 = a[3] * rhs;
+
+        let (s1, mut s2) = two_sum(q0, p1);
+        three_sum(&mut s2, &mut q1, &mut p2);
+        three_sum2(&mut q1, &mut q2, p3);
+        let s3 = q1;
+        let s4 = q2 + p2;
+
+        Self {
+            limbs: renorm(p0, s1, s2, s3, s4),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Arithmetic: negation
+// ---------------------------------------------------------------------------
+
+impl Neg for QuadDouble {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self {
+            limbs: self.limbs.map(|x| -x),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Comparison
+// ---------------------------------------------------------------------------
+
+impl PartialEq for QuadDouble {
+    fn eq(&self, other: &Self) -> bool {
+        self.limbs == other.limbs
+    }
+}
+
+impl PartialOrd for QuadDouble {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        for (a, b) in self.limbs.iter().zip(&other.limbs) {
+            match a.partial_cmp(b) {
+                Some(Ordering::Equal) => continue,
+                ord => return ord,
+            }
+        }
+        Some(Ordering::Equal)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Display
+// ---------------------------------------------------------------------------
+
+impl fmt::Display for QuadDouble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x0, x1, x2, x3] = self.limbs;
+        write!(f, "({x0:+.17e} + {x1:+.17e} + {x2:+.17e} + {x3:+.17e})")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::big_float::BigFloat;
+
+    fn qd(val: f64) -> QuadDouble {
+        QuadDouble::from(val)
+    }
+
+    /// Exact value of a quad-double as a big float.
+    fn big(x: QuadDouble) -> BigFloat {
+        BigFloat::from_qd(x, 512)
+    }
+
+    /// `|a − b| ≤ 2^-200 · |b|`, i.e. agreement to ~60 digits.
+    fn close(a: &BigFloat, b: &BigFloat) -> bool {
+        let diff = (a - b).abs();
+        let tol = b.abs().mul_pow2(-200);
+        diff <= tol
+    }
+
+    /// A value that needs all four limbs: 1/3 to ~64 digits.
+    fn third() -> QuadDouble {
+        // Each correction step on 3x = 1 adds one limb of accuracy.
+        let mut x = qd(1.0 / 3.0);
+        for _ in 0..3 {
+            x += (qd(1.0) - x * 3.0) * (1.0 / 3.0);
+        }
+        x
+    }
+
+    #[test]
+    fn from_f64_and_dd() {
+        assert_eq!(qd(2.5).limbs, [2.5, 0.0, 0.0, 0.0]);
+        let d = DoubleDouble::new(1.0, 1e-20);
+        assert_eq!(QuadDouble::from(d).to_dd(), d);
+        assert_eq!(qd(2.5).to_f64(), 2.5);
+    }
+
+    #[test]
+    fn new_renormalizes() {
+        let x = QuadDouble::new(1.0, 1.0, 1e-20, 0.0);
+        assert_eq!(x.limbs, [2.0, 1e-20, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn addition_is_exact_for_separated_values() {
+        let x = qd(1.0) + qd(1e-40) + qd(1e-60);
+        let expected = &(&BigFloat::from_f64(1.0, 512) + &BigFloat::from_f64(1e-40, 512))
+            + &BigFloat::from_f64(1e-60, 512);
+        assert!(close(&big(x), &expected));
+        assert_eq!((x - qd(1.0) - qd(1e-40)).to_f64(), 1e-60);
+    }
+
+    #[test]
+    fn add_f64_matches_add_qd() {
+        let x = third();
+        assert!(close(&big(x + 0.25), &big(x + qd(0.25))));
+    }
+
+    #[test]
+    fn multiplication_precision() {
+        let x = third();
+        let expected = &big(x) * &big(x);
+        assert!(close(&big(x * x), &expected));
+        let three = big(x * 3.0);
+        assert!(close(&three, &(&big(x) * &BigFloat::from_f64(3.0, 512))));
+    }
+
+    #[test]
+    fn third_is_accurate() {
+        let residual = (qd(1.0) - third() * 3.0).abs();
+        assert!(residual.to_f64() < 1e-62, "residual {}", residual);
+    }
+
+    #[test]
+    fn catastrophic_cancellation() {
+        let a = qd(1.0) + qd(1e-50);
+        let b = qd(1.0);
+        assert_eq!((a - b).to_f64(), 1e-50);
+    }
+
+    #[test]
+    fn big_float_roundtrip() {
+        let x = third();
+        assert_eq!(big(x).to_qd(), x);
+        let pi: BigFloat = "3.14159265358979323846264338327950288419716939937510582097494459"
+            .parse()
+            .unwrap();
+        assert!(close(&big(pi.to_qd()), &pi));
+    }
+
+    #[test]
+    fn sign_and_ordering() {
+        let a = QuadDouble::new(0.0, 0.0, -1e-70, 0.0);
+        assert!(a.is_negative());
+        assert!(!a.is_positive());
+        assert_eq!(a.abs().limbs[0], 1e-70);
+        assert!(qd(1.0) + qd(1e-50) > qd(1.0));
+        assert!(-qd(1.0) < QuadDouble::ZERO);
+        assert_eq!(third(), third());
+    }
+}
@@ -0,0 +1,753 @@
+//! Registry of every fractal family the engine can draw.
+//!
+//! Each [`FractalDescriptor`] declares what a front end needs to offer a
+//! family without knowing its formula: a stable id for files, a display
+//! name, scalar parameters with their ranges, the default view, symmetry,
+//! the precision tiers it has formulas for, its Julia counterpart, and a
+//! builder for the fractal itself. Adding a family means adding one
+//! descriptor to [`FRACTALS`].
+
+use std::collections::BTreeMap;
+
+use crate::abs_variant::{AbsJulia, AbsJuliaDD, AbsMandelbrot, AbsMandelbrotDD, AbsVariant};
+use crate::complex::Complex;
+use crate::complex_dd::ComplexDD;
+use crate::complex_qd::ComplexQD;
+use crate::formula::{Formula, FormulaDefinition};
+use crate::fractal::{Fractal, FractalParams, Symmetry};
+use crate::julia::{Julia, JuliaDD, JuliaQD};
+use crate::lyapunov::{Lyapunov, LyapunovSequence};
+use crate::mandelbrot::{Mandelbrot, MandelbrotDD, MandelbrotQD};
+use crate::mandelbrot_perturb::MandelbrotPerturb;
+use crate::multibrot::{Exponent, Multibrot, MultibrotDD};
+use crate::multijulia::{Multijulia, MultijuliaDD};
+use crate::newton::{Newton, Polynomial};
+use crate::viewport::Viewport;
+
+/// A fractal ready to render, whatever its family and precision.
+pub type BoxedFractal = Box<dyn Fractal + Send + Sync>;
+
+/// Scalar parameter values by [`ParamSpec::key`]. Families read the keys
+/// they declare and ignore the rest, so one map can serve them all.
+pub type ParamMap = BTreeMap<String, f64>;
+
+// ---------------------------------------------------------------------------
+// Descriptor parts
+// ---------------------------------------------------------------------------
+
+/// Arithmetic a fractal is iterated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Precision {
+    /// Plain `f64` absolute coordinates.
+    F64,
+    /// Double-double brute force.
+    DoubleDouble,
+    /// Quad-double brute force.
+    QuadDouble,
+    /// Perturbation around one high-precision reference orbit.
+    Perturbation,
+}
+
+impl Precision {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::F64 => "f64",
+            Self::DoubleDouble => "f64\u{00d7}2",
+            Self::QuadDouble => "f64\u{00d7}4",
+            Self::Perturbation => "perturbation",
+        }
+    }
+}
+
+/// A real parameter of a family, e.g. the exponent `d` of `z^d + c`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSpec {
+    /// Key in a [`ParamMap`] and in saved files.
+    pub key: &'static str,
+    pub label: &'static str,
+    /// Short math name, e.g. `d`.
+    pub symbol: &'static str,
+    pub default: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Suggested drag speed for a UI slider.
+    pub speed: f64,
+    /// One-line explanation for tooltips.
+    pub hint: &'static str,
+}
+
+impl ParamSpec {
+    /// The value in `values`, clamped to the range; the default when it is
+    /// missing or not a number.
+    pub fn value(&self, values: &ParamMap) -> f64 {
+        match values.get(self.key) {
+            Some(v) if !v.is_nan() => v.clamp(self.min, self.max),
+            _ => self.default,
+        }
+    }
+}
+
+/// An input beyond scalar parameters that a family reads from
+/// [`FractalInputs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtraInput {
+    None,
+    /// The fixed constant `c` of a Julia set.
+    JuliaConstant,
+    /// The polynomial roots of Newton's method.
+    Roots,
+    /// A user-defined formula.
+    Formula,
+    /// The A/B rate sequence of a Lyapunov fractal.
+    Sequence,
+}
+
+/// The region shown before the user zooms anywhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DefaultView {
+    pub center: Complex,
+    /// Real and imaginary extent that must be visible.
+    pub span: (f64, f64),
+}
+
+const MANDELBROT_VIEW: DefaultView = DefaultView {
+    center: Complex { re: -0.75, im: 0.0 },
+    span: (3.6, 2.6),
+};
+
+const CENTERED_VIEW: DefaultView = DefaultView {
+    center: Complex::ZERO,
+    span: (4.2, 4.2),
+};
+
+/// The rate square `[2, 4]²`, where the logistic map turns chaotic.
+const LYAPUNOV_VIEW: DefaultView = DefaultView {
+    center: Complex { re: 3.0, im: 3.0 },
+    span: (2.0, 2.0),
+};
+
+/// Everything a builder may read besides the iteration parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractalInputs {
+    pub values: ParamMap,
+    pub julia_c: ComplexDD,
+    /// Newton roots; an invalid list falls back to the cube roots of unity.
+    pub roots: Vec<Complex>,
+    /// A definition that does not compile falls back to `z^2 + c`.
+    pub formula: FormulaDefinition,
+    pub sequence: LyapunovSequence,
+}
+
+impl Default for FractalInputs {
+    fn default() -> Self {
+        Self {
+            values: ParamMap::new(),
+            julia_c: ComplexDD::from(Julia::default_c()),
+            roots: Polynomial::default().roots().to_vec(),
+            formula: FormulaDefinition::default(),
+            sequence: LyapunovSequence::default(),
+        }
+    }
+}
+
+type Builder = fn(&FractalInputs, FractalParams, Precision, &Viewport) -> BoxedFractal;
+
+// ---------------------------------------------------------------------------
+// Descriptor
+// ---------------------------------------------------------------------------
+
+/// Everything a front end needs to know about one fractal family.
+#[derive(Debug)]
+pub struct FractalDescriptor {
+    /// Stable identifier for files, e.g. `burning_ship_julia`.
+    pub id: &'static str,
+    pub name: &'static str,
+    pub params: &'static [ParamSpec],
+    pub extra_input: ExtraInput,
+    pub default_view: DefaultView,
+    /// Symmetry at the default parameters. Built fractals report their own,
+    /// which the renderer uses.
+    pub symmetry: Symmetry,
+    /// Tiers with formulas, shallowest first. Always starts with `F64`.
+    pub precisions: &'static [Precision],
+    /// Id of the family that swaps the roles of `c` and the plane.
+    pub julia_counterpart: Option<&'static str>,
+    build: Builder,
+}
+
+impl PartialEq for FractalDescriptor {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for FractalDescriptor {}
+
+impl FractalDescriptor {
+    /// Whether the family iterates the plane with a fixed constant `c`.
+    pub fn is_julia(&self) -> bool {
+        self.extra_input == ExtraInput::JuliaConstant
+    }
+
+    pub fn counterpart(&self) -> Option<&'static FractalDescriptor> {
+        self.julia_counterpart.and_then(by_id)
+    }
+
+    pub fn supports(&self, precision: Precision) -> bool {
+        self.precisions.contains(&precision)
+    }
+
+    pub fn param(&self, key: &str) -> Option<&'static ParamSpec> {
+        self.params.iter().find(|p| p.key == key)
+    }
+
+    /// The default value of every parameter.
+    pub fn default_values(&self) -> ParamMap {
+        self.params
+            .iter()
+            .map(|p| (p.key.to_string(), p.default))
+            .collect()
+    }
+
+    pub fn default_viewport(&self, width: u32, height: u32) -> Viewport {
+        let DefaultView { center, span } = self.default_view;
+        Viewport::framing(center, span.0, span.1, width, height)
+    }
+
+    /// Build the fractal for `viewport`. A tier the family lacks falls back
+    /// to the deepest one it has.
+    pub fn build(
+        &self,
+        inputs: &FractalInputs,
+        params: FractalParams,
+        precision: Precision,
+        viewport: &Viewport,
+    ) -> BoxedFractal {
+        let precision = if self.supports(precision) {
+            precision
+        } else {
+            self.precisions.last().copied().unwrap_or(Precision::F64)
+        };
+        (self.build)(inputs, params, precision, viewport)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parameters
+// ---------------------------------------------------------------------------
+
+const EXPONENT: ParamSpec = ParamSpec {
+    key: "exponent",
+    label: "Exponent",
+    symbol: "d",
+    default: 3.0,
+    min: 1.1,
+    max: 16.0,
+    speed: 0.01,
+    hint: "Whole numbers use exact multiplication; others the principal branch of z^d",
+};
+
+const RELAXATION: ParamSpec = ParamSpec {
+    key: "relaxation",
+    label: "Relaxation",
+    symbol: "a",
+    default: Newton::DEFAULT_RELAXATION,
+    min: 0.05,
+    max: 1.95,
+    speed: 0.005,
+    hint: "Scales each Newton step; 1 is plain Newton",
+};
+
+fn exponent(inputs: &FractalInputs) -> Exponent {
+    Exponent::new(EXPONENT.value(&inputs.values)).unwrap_or_default()
+}
+
+fn newton(inputs: &FractalInputs, params: FractalParams, nova: bool) -> BoxedFractal {
+    let polynomial = Polynomial::from_roots(inputs.roots.clone()).unwrap_or_default();
+    let newton = if nova {
+        Newton::nova(polynomial, params)
+    } else {
+        Newton::new(polynomial, params)
+    };
+    let relaxed = newton
+        .clone()
+        .with_relaxation(RELAXATION.value(&inputs.values));
+    Box::new(relaxed.unwrap_or(newton))
+}
+
+// ---------------------------------------------------------------------------
+// Builders shared by several families
+// ---------------------------------------------------------------------------
+
+const F64_ONLY: &[Precision] = &[Precision::F64];
+const UP_TO_DD: &[Precision] = &[Precision::F64, Precision::DoubleDouble];
+
+fn abs_mandelbrot(
+    variant: AbsVariant,
+    params: FractalParams,
+    precision: Precision,
+    viewport: &Viewport,
+) -> BoxedFractal {
+    match precision {
+        Precision::F64 => Box::new(AbsMandelbrot::new(variant, params)),
+        _ => Box::new(AbsMandelbrotDD::with_center(
+            variant,
+            params,
+            viewport.center_dd,
+        )),
+    }
+}
+
+fn abs_julia(
+    variant: AbsVariant,
+    inputs: &FractalInputs,
+    params: FractalParams,
+    precision: Precision,
+    viewport: &Viewport,
+) -> BoxedFractal {
+    match precision {
+        Precision::F64 => Box::new(AbsJulia::new(variant, inputs.julia_c.to_complex(), params)),
+        _ => Box::new(AbsJuliaDD::with_center(
+            variant,
+            inputs.julia_c,
+            params,
+            viewport.center_dd,
+        )),
+    }
+}
+
+/// A Burning Ship family member over the parameter plane.
+const fn abs_plane(
+    id: &'static str,
+    name: &'static str,
+    variant: AbsVariant,
+    julia_counterpart: &'static str,
+    build: Builder,
+) -> FractalDescriptor {
+    FractalDescriptor {
+        id,
+        name,
+        params: &[],
+        extra_input: ExtraInput::None,
+        default_view: CENTERED_VIEW,
+        symmetry: variant.symmetry(),
+        precisions: UP_TO_DD,
+        julia_counterpart: Some(julia_counterpart),
+        build,
+    }
+}
+
+/// The Julia sets of a Burning Ship family member.
+const fn abs_julia_plane(
+    id: &'static str,
+    name: &'static str,
+    parameter_plane: &'static str,
+    build: Builder,
+) -> FractalDescriptor {
+    FractalDescriptor {
+        id,
+        name,
+        params: &[],
+        extra_input: ExtraInput::JuliaConstant,
+        default_view: CENTERED_VIEW,
+        symmetry: Symmetry {
+            rotational_order: 2,
+            ..Symmetry::NONE
+        },
+        precisions: UP_TO_DD,
+        julia_counterpart: Some(parameter_plane),
+        build,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Descriptors
+// ---------------------------------------------------------------------------
+
+pub static MANDELBROT: FractalDescriptor = FractalDescriptor {
+    id: "mandelbrot",
+    name: "Mandelbrot",
+    params: &[],
+    extra_input: ExtraInput::None,
+    default_view: MANDELBROT_VIEW,
+    symmetry: Symmetry::CONJUGATE,
+    precisions: &[
+        Precision::F64,
+        Precision::DoubleDouble,
+        Precision::QuadDouble,
+        Precision::Perturbation,
+    ],
+    julia_counterpart: Some("julia"),
+    build: |_, params, precision, viewport| match precision {
+        Precision::F64 => Box::new(Mandelbrot::new(params)),
+        Precision::DoubleDouble => Box::new(MandelbrotDD::with_center(params, viewport.center_dd)),
+        Precision::QuadDouble => Box::new(MandelbrotQD::with_center(params, viewport.center_qd)),
+        // One reference orbit at the center (QD, or the center's full
+        // precision at deep zooms), f64 deltas per pixel, and a series
+        // approximation skipping the iterations all pixels share.
+        Precision::Perturbation => Box::new(MandelbrotPerturb::for_viewport(params, viewport)),
+    },
+};
+
+pub static JULIA: FractalDescriptor = FractalDescriptor {
+    id: "julia",
+    name: "Julia",
+    params: &[],
+    extra_input: ExtraInput::JuliaConstant,
+    default_view: CENTERED_VIEW,
+    symmetry: Symmetry {
+        rotational_order: 2,
+        ..Symmetry::NONE
+    },
+    // No perturbation engine: quad-double brute force at any depth.
+    precisions: &[
+        Precision::F64,
+        Precision::DoubleDouble,
+        Precision::QuadDouble,
+    ],
+    julia_counterpart: Some("mandelbrot"),
+    build: |inputs, params, precision, viewport| match precision {
+        Precision::F64 => Box::new(Julia::new(inputs.julia_c.to_complex(), params)),
+        Precision::DoubleDouble => Box::new(JuliaDD::with_center(
+            inputs.julia_c,
+            params,
+            viewport.center_dd,
+        )),
+        _ => Box::new(JuliaQD::with_center(
+            ComplexQD::from(inputs.julia_c),
+            params,
+            viewport.center_qd,
+        )),
+    },
+};
+
+pub static MULTIBROT: FractalDescriptor = FractalDescriptor {
+    id: "multibrot",
+    name: "Multibrot",
+    params: &[EXPONENT],
+    extra_input: ExtraInput::None,
+    default_view: CENTERED_VIEW,
+    symmetry: Symmetry {
+        conjugate: true,
+        rotational_order: 2,
+        flipped: false,
+    },
+    precisions: UP_TO_DD,
+    julia_counterpart: Some("multijulia"),
+    build: |inputs, params, precision, viewport| match precision {
+        Precision::F64 => Box::new(Multibrot::new(exponent(inputs), params)),
+        _ => Box::new(MultibrotDD::with_center(
+            exponent(inputs),
+            params,
+            viewport.center_dd,
+        )),
+    },
+};
+
+pub static MULTIJULIA: FractalDescriptor = FractalDescriptor {
+    id: "multijulia",
+    name: "Multijulia",
+    params: &[EXPONENT],
+    extra_input: ExtraInput::JuliaConstant,
+    default_view: CENTERED_VIEW,
+    symmetry: Symmetry {
+        rotational_order: 3,
+        ..Symmetry::NONE
+    },
+    precisions: UP_TO_DD,
+    julia_counterpart: Some("multibrot"),
+    build: |inputs, params, precision, viewport| match precision {
+        Precision::F64 => Box::new(Multijulia::new(
+            inputs.julia_c.to_complex(),
+            exponent(inputs),
+            params,
+        )),
+        _ => Box::new(MultijuliaDD::with_center(
+            inputs.julia_c,
+            exponent(inputs),
+            params,
+            viewport.center_dd,
+        )),
+    },
+};
+
+pub static BURNING_SHIP: FractalDescriptor = abs_plane(
+    "burning_ship",
+    "Burning Ship",
+    AbsVariant::BurningShip,
+    "burning_ship_julia",
+    |_, params, precision, viewport| {
+        abs_mandelbrot(AbsVariant::BurningShip, params, precision, viewport)
+    },
+);
+
+pub static BURNING_SHIP_JULIA: FractalDescriptor = abs_julia_plane(
+    "burning_ship_julia",
+    "Burning Ship Julia",
+    "burning_ship",
+    |inputs, params, precision, viewport| {
+        abs_julia(AbsVariant::BurningShip, inputs, params, precision, viewport)
+    },
+);
+
+pub static TRICORN: FractalDescriptor = abs_plane(
+    "tricorn",
+    "Tricorn",
+    AbsVariant::Tricorn,
+    "tricorn_julia",
+    |_, params, precision, viewport| {
+        abs_mandelbrot(AbsVariant::Tricorn, params, precision, viewport)
+    },
+);
+
+pub static TRICORN_JULIA: FractalDescriptor = abs_julia_plane(
+    "tricorn_julia",
+    "Tricorn Julia",
+    "tricorn",
+    |inputs, params, precision, viewport| {
+        abs_julia(AbsVariant::Tricorn, inputs, params, precision, viewport)
+    },
+);
+
+pub static CELTIC: FractalDescriptor = abs_plane(
+    "celtic",
+    "Celtic",
+    AbsVariant::Celtic,
+    "celtic_julia",
+    |_, params, precision, viewport| {
+        abs_mandelbrot(AbsVariant::Celtic, params, precision, viewport)
+    },
+);
+
+pub static CELTIC_JULIA: FractalDescriptor = abs_julia_plane(
+    "celtic_julia",
+    "Celtic Julia",
+    "celtic",
+    |inputs, params, precision, viewport| {
+        abs_julia(AbsVariant::Celtic, inputs, params, precision, viewport)
+    },
+);
+
+pub static BUFFALO: FractalDescriptor = abs_plane(
+    "buffalo",
+    "Buffalo",
+    AbsVariant::Buffalo,
+    "buffalo_julia",
+    |_, params, precision, viewport| {
+        abs_mandelbrot(AbsVariant::Buffalo, params, precision, viewport)
+    },
+);
+
+pub static BUFFALO_JULIA: FractalDescriptor = abs_julia_plane(
+    "buffalo_julia",
+    "Buffalo Julia",
+    "buffalo",
+    |inputs, params, precision, viewport| {
+        abs_julia(AbsVariant::Buffalo, inputs, params, precision, viewport)
+    },
+);
+
+pub static NEWTON: FractalDescriptor = FractalDescriptor {
+    id: "newton",
+    name: "Newton",
+    params: &[RELAXATION],
+    extra_input: ExtraInput::Roots,
+    default_view: CENTERED_VIEW,
+    // Only mirrored when every root is real; see `Newton::symmetry`.
+    symmetry: Symmetry::NONE,
+    precisions: F64_ONLY,
+    julia_counterpart: None,
+    build: |inputs, params, _, _| newton(inputs, params, false),
+};
+
+pub static NOVA: FractalDescriptor = FractalDescriptor {
+    id: "nova",
+    name: "Nova",
+    params: &[RELAXATION],
+    extra_input: ExtraInput::Roots,
+    default_view: CENTERED_VIEW,
+    symmetry: Symmetry::NONE,
+    precisions: F64_ONLY,
+    julia_counterpart: None,
+    build: |inputs, params, _, _| newton(inputs, params, true),
+};
+
+pub static FORMULA: FractalDescriptor = FractalDescriptor {
+    id: "formula",
+    name: "Formula",
+    params: &[],
+    extra_input: ExtraInput::Formula,
+    default_view: MANDELBROT_VIEW,
+    symmetry: Symmetry::NONE,
+    precisions: F64_ONLY,
+    julia_counterpart: None,
+    build: |inputs, params, _, _| {
+        let formula = Formula::compile(&inputs.formula, params).unwrap_or_else(|_| {
+            Formula::compile(&FormulaDefinition::default(), params)
+                .expect("default formula compiles")
+        });
+        Box::new(formula)
+    },
+};
+
+/// The plane is the rate pair `(a, b)`, not a complex number, so neither
+/// symmetry nor deeper tiers apply.
+pub static LYAPUNOV: FractalDescriptor = FractalDescriptor {
+    id: "lyapunov",
+    name: "Lyapunov",
+    params: &[],
+    extra_input: ExtraInput::Sequence,
+    default_view: LYAPUNOV_VIEW,
+    symmetry: Symmetry::NONE,
+    precisions: F64_ONLY,
+    julia_counterpart: None,
+    build: |inputs, params, _, _| Box::new(Lyapunov::new(inputs.sequence.clone(), params)),
+};
+
+/// Every registered family, in menu order.
+pub static FRACTALS: [&FractalDescriptor; 16] = [
+    &MANDELBROT,
+    &JULIA,
+    &MULTIBROT,
+    &MULTIJULIA,
+    &BURNING_SHIP,
+    &BURNING_SHIP_JULIA,
+    &TRICORN,
+    &TRICORN_JULIA,
+    &CELTIC,
+    &CELTIC_JULIA,
+    &BUFFALO,
+    &BUFFALO_JULIA,
+    &NEWTON,
+    &NOVA,
+    &FORMULA,
+    &LYAPUNOV,
+];
+
+pub fn by_id(id: &str) -> Option<&'static FractalDescriptor> {
+    FRACTALS.iter().copied().find(|d| d.id == id)
+}
+
+/// Lookup by display name, for files written before ids existed.
+pub fn by_name(name: &str) -> Option<&'static FractalDescriptor> {
+    FRACTALS.iter().copied().find(|d| d.name == name)
+}
+
+/// The default value of every parameter of every family.
+pub fn default_values() -> ParamMap {
+    FRACTALS.iter().flat_map(|d| d.default_values()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractal::IterationResult;
+
+    #[test]
+    fn ids_and_names_are_unique() {
+        for (i, a) in FRACTALS.iter().enumerate() {
+            for b in &FRACTALS[i + 1..] {
+                assert_ne!(a.id, b.id);
+                assert_ne!(a.name, b.name);
+            }
+            assert_eq!(by_id(a.id), Some(*a));
+            assert_eq!(by_name(a.name), Some(*a));
+        }
+        assert_eq!(by_id("nope"), None);
+    }
+
+    #[test]
+    fn counterparts_are_mutual() {
+        for d in FRACTALS {
+            if let Some(other) = d.julia_counterpart {
+                let other = by_id(other).expect("counterpart is registered");
+                assert_eq!(other.counterpart(), Some(d), "{}", d.id);
+                assert_ne!(d.is_julia(), other.is_julia(), "{}", d.id);
+            }
+        }
+    }
+
+    #[test]
+    fn declarations_match_built_fractals() {
+        let inputs = FractalInputs {
+            values: default_values(),
+            ..FractalInputs::default()
+        };
+        let params = FractalParams::default().with_max_iterations(64);
+        for d in FRACTALS {
+            assert_eq!(d.precisions.first(), Some(&Precision::F64), "{}", d.id);
+            for p in d.params {
+                assert!(p.min <= p.default && p.default <= p.max, "{}", p.key);
+            }
+            let viewport = d.default_viewport(64, 48);
+            for &precision in d.precisions {
+                let fractal = d.build(&inputs, params, precision, &viewport);
+                assert_eq!(fractal.symmetry(), d.symmetry, "{} {precision:?}", d.id);
+            }
+        }
+    }
+
+    #[test]
+    fn builds_agree_across_precisions() {
+        let inputs = FractalInputs::default();
+        let params = FractalParams::default().with_max_iterations(200);
+        let point = Complex::new(0.11, 0.37);
+        for d in FRACTALS {
+            let viewport = Viewport::framing(point, 1e-3, 1e-3, 8, 8);
+            let reference = d
+                .build(&inputs, params, Precision::F64, &viewport)
+                .iterate(point);
+            for &precision in &d.precisions[1..] {
+                let f = d.build(&inputs, params, precision, &viewport);
+                let at = if f.uses_delta_coordinates() {
+                    Complex::ZERO
+                } else {
+                    point
+                };
+                assert_eq!(
+                    f.iterate(at).class(),
+                    reference.class(),
+                    "{} {precision:?}",
+                    d.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn params_are_clamped_and_defaulted() {
+        let mut values = ParamMap::new();
+        assert_eq!(EXPONENT.value(&values), 3.0);
+        values.insert("exponent".into(), 100.0);
+        assert_eq!(EXPONENT.value(&values), 16.0);
+        values.insert("exponent".into(), f64::NAN);
+        assert_eq!(EXPONENT.value(&values), 3.0);
+
+        let inputs = FractalInputs {
+            values: ParamMap::from([("exponent".into(), 2.0)]),
+            ..FractalInputs::default()
+        };
+        let viewport = MULTIBROT.default_viewport(8, 8);
+        let quadratic =
+            MULTIBROT.build(&inputs, FractalParams::default(), Precision::F64, &viewport);
+        let mandelbrot = Mandelbrot::new(FractalParams::default());
+        let c = Complex::new(-0.4, 0.6);
+        assert_eq!(quadratic.iterate(c), mandelbrot.iterate(c));
+        assert_eq!(quadratic.degree(), 2.0);
+    }
+
+    #[test]
+    fn unsupported_tier_falls_back() {
+        let viewport = NEWTON.default_viewport(8, 8);
+        let f = NEWTON.build(
+            &FractalInputs::default(),
+            FractalParams::default(),
+            Precision::Perturbation,
+            &viewport,
+        );
+        assert!(matches!(
+            f.iterate(Complex::new(0.9, 0.1)),
+            IterationResult::Converged { .. }
+        ));
+    }
+}
@@ -0,0 +1,15 @@
+//! Fixtures shared by the fractal families' tests.
+
+use crate::complex::Complex;
+
+/// The lattice `origin + (i·step.0, j·step.1)` for `i ≤ steps.0` and
+/// `j ≤ steps.1`, row by row.
+pub(crate) fn grid(origin: (f64, f64), step: (f64, f64), steps: (u32, u32)) -> Vec<Complex> {
+    (0..=steps.0)
+        .flat_map(|i| {
+            (0..=steps.1).map(move |j| {
+                Complex::new(origin.0 + i as f64 * step.0, origin.1 + j as f64 * step.1)
+            })
+        })
+        .collect()
+}
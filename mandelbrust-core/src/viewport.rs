@@ -1,7 +1,20 @@
+use crate::big_float::BigFloat;
 use crate::complex::Complex;
+use crate::complex_big::ComplexBig;
 use crate::complex_dd::ComplexDD;
-use crate::double_double::DoubleDouble;
+use crate::complex_qd::ComplexQD;
 use crate::error::CoreError;
+use crate::float_exp::{ldexp, FloatExp};
+
+/// Scales with a binary exponent at or below this value make the renderer
+/// express pixel deltas relative to a power of two (see
+/// [`Viewport::delta_exponent`]) because the plain deltas would leave the
+/// normal `f64` range.
+const DELTA_RESCALE_EXPONENT: i64 = -900;
+
+/// Bits of center precision kept below the pixel size, so long runs of pans
+/// and zooms never round the center.
+const CENTER_GUARD_BITS: i64 = 64;
 
 /// Defines the visible region of the complex plane.
 ///
@@ -9,19 +22,30 @@ use crate::error::CoreError;
 /// The viewport is centred on `center`, with `scale` defining how many
 /// complex-plane units each pixel spans.
 ///
-/// `center_dd` is the authoritative high-precision center (~31 digits).
-/// `center` is always the `f64` approximation of `center_dd` and is kept
-/// in sync for code that only needs `f64` precision (HUD, minimap, etc.).
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// `center_big` is the authoritative center, with enough bits to resolve a
+/// pixel at any depth (see [`center_precision`](Self::center_precision)).
+/// `center_qd`, `center_dd` and `center` are its quad-double, double-double
+/// and `f64` roundings, kept in sync for code that needs less precision
+/// (QD/DD iteration paths, HUD, minimap, etc.).
+#[derive(Debug, Clone, PartialEq)]
 pub struct Viewport {
-    /// Centre of the viewport (`f64` approximation of `center_dd`).
+    /// Centre of the viewport (`f64` approximation of `center_qd`).
     pub center: Complex,
 
     /// Centre of the viewport in double-double precision (~31 digits).
     pub center_dd: ComplexDD,
 
+    /// Centre of the viewport in quad-double precision (~62 digits).
+    pub center_qd: ComplexQD,
+
+    /// Centre of the viewport in arbitrary precision.
+    pub center_big: ComplexBig,
+
     /// Complex-plane units per pixel.
-    pub scale: f64,
+    ///
+    /// Carries its own exponent so zoom depth is not capped by the `f64`
+    /// range; only the magnitude needs to go that deep.
+    pub scale: FloatExp,
 
     /// Viewport width in pixels.
     pub width: u32,
@@ -38,17 +62,7 @@ impl Viewport {
     /// ratio, with a small margin for breathing room.
     pub fn default_mandelbrot(width: u32, height: u32) -> Self {
         // Bounding box of the interesting region, plus ~5 % padding.
-        let target_re = 3.6; // real span
-        let target_im = 2.6; // imaginary span
-        let scale = (target_re / width as f64).max(target_im / height as f64);
-        let center = Complex::new(-0.75, 0.0);
-        Self {
-            center,
-            center_dd: ComplexDD::from(center),
-            scale,
-            width,
-            height,
-        }
+        Self::framing(Complex::new(-0.75, 0.0), 3.6, 2.6, width, height)
     }
 
     /// Default view for Julia sets, centred on the origin.
@@ -56,11 +70,25 @@ impl Viewport {
     /// Most Julia sets for typical parameters fit within `|z| < 2`, so the
     /// viewport spans roughly `[-2, 2] × [-2, 2]` with a small margin.
     pub fn default_julia(width: u32, height: u32) -> Self {
-        let extent = 4.2; // 4.0 + padding
-        let scale = (extent / width as f64).max(extent / height as f64);
+        Self::framing(Complex::ZERO, 4.2, 4.2, width, height) // 4.0 + padding
+    }
+
+    /// The closest view centred on `center` that shows at least a
+    /// `span_re × span_im` region, whatever the aspect ratio.
+    pub fn framing(center: Complex, span_re: f64, span_im: f64, width: u32, height: u32) -> Self {
+        let scale = FloatExp::from((span_re / width as f64).max(span_im / height as f64));
+        let center_big = ComplexBig::from_dd(ComplexDD::from(center), center_precision(scale));
+        Self::from_parts(center_big, scale, width, height)
+    }
+
+    /// Build from the authoritative center, deriving its roundings.
+    fn from_parts(center_big: ComplexBig, scale: FloatExp, width: u32, height: u32) -> Self {
+        let center_qd = center_big.to_complex_qd();
         Self {
-            center: Complex::ZERO,
-            center_dd: ComplexDD::ZERO,
+            center: center_qd.to_complex(),
+            center_dd: center_qd.to_complex_dd(),
+            center_qd,
+            center_big,
             scale,
             width,
             height,
@@ -69,13 +97,42 @@ impl Viewport {
 
     /// Create a viewport with explicit parameters (`f64` center).
     pub fn new(center: Complex, scale: f64, width: u32, height: u32) -> crate::Result<Self> {
-        Self::new_dd(ComplexDD::from(center), scale, width, height)
+        Self::new_dd(
+            ComplexDD::from(center),
+            FloatExp::from(scale),
+            width,
+            height,
+        )
     }
 
-    /// Create a viewport with a double-double precision center.
+    /// Create a viewport with a double-double precision center and an
+    /// extended-range scale.
     pub fn new_dd(
         center_dd: ComplexDD,
-        scale: f64,
+        scale: FloatExp,
+        width: u32,
+        height: u32,
+    ) -> crate::Result<Self> {
+        Self::new_qd(ComplexQD::from(center_dd), scale, width, height)
+    }
+
+    /// Create a viewport with a quad-double precision center and an
+    /// extended-range scale.
+    pub fn new_qd(
+        center_qd: ComplexQD,
+        scale: FloatExp,
+        width: u32,
+        height: u32,
+    ) -> crate::Result<Self> {
+        let center_big = ComplexBig::from_qd(center_qd, center_precision(scale));
+        Self::new_big(center_big, scale, width, height)
+    }
+
+    /// Create a viewport with an arbitrary-precision center and an
+    /// extended-range scale.
+    pub fn new_big(
+        center_big: ComplexBig,
+        scale: FloatExp,
         width: u32,
         height: u32,
     ) -> crate::Result<Self> {
@@ -84,34 +141,71 @@ impl Viewport {
                 reason: format!("dimensions must be > 0, got {width}×{height}"),
             });
         }
-        if scale <= 0.0 || !scale.is_finite() {
+        if !scale.is_positive() || !scale.is_finite() {
             return Err(CoreError::InvalidViewport {
                 reason: format!("scale must be positive and finite, got {scale}"),
             });
         }
-        Ok(Self {
-            center: center_dd.to_complex(),
-            center_dd,
-            scale,
-            width,
-            height,
-        })
+        Ok(Self::from_parts(center_big, scale, width, height))
+    }
+
+    /// Bits the center needs at the current scale: enough to place every
+    /// pixel exactly, with [`CENTER_GUARD_BITS`] to spare.
+    pub fn center_precision(&self) -> u32 {
+        center_precision(self.scale)
     }
 
     /// Update the center using double-double precision.
-    /// Also updates the `f64` `center` field.
+    /// Also updates the other center fields.
     #[inline]
     pub fn set_center_dd(&mut self, center_dd: ComplexDD) {
-        self.center_dd = center_dd;
-        self.center = center_dd.to_complex();
+        self.set_center_qd(ComplexQD::from(center_dd));
     }
 
-    /// Offset the center by a delta (in `f64`), preserving DD precision.
+    /// Update the center using quad-double precision.
+    /// Also updates the other center fields.
     #[inline]
+    pub fn set_center_qd(&mut self, center_qd: ComplexQD) {
+        self.set_center_big(ComplexBig::from_qd(center_qd, self.center_precision()));
+    }
+
+    /// Update the center using arbitrary precision.
+    /// Also updates the `f64`, double-double and quad-double fields.
+    pub fn set_center_big(&mut self, center_big: ComplexBig) {
+        *self = Self::from_parts(center_big, self.scale, self.width, self.height);
+    }
+
+    /// Offset the center by a delta (in `f64`), preserving the full center
+    /// precision.
     pub fn offset_center(&mut self, dre: f64, dim: f64) {
-        self.center_dd.re += DoubleDouble::from(dre);
-        self.center_dd.im += DoubleDouble::from(dim);
-        self.center = self.center_dd.to_complex();
+        let precision = self.center_precision();
+        let delta = ComplexBig::new(
+            BigFloat::from_f64(dre, precision),
+            BigFloat::from_f64(dim, precision),
+        );
+        self.set_center_big(&self.center_big + &delta);
+    }
+
+    /// Move the center `dx` pixels right and `dy` pixels down.
+    ///
+    /// The offset is taken in the scale's own exponent, so this keeps
+    /// working where a pixel is too small for an `f64`.
+    pub fn pan_pixels(&mut self, dx: f64, dy: f64) {
+        let precision = self.center_precision();
+        let delta = ComplexBig::new(
+            BigFloat::from_float_exp(self.scale * dx, precision),
+            BigFloat::from_float_exp(self.scale * -dy, precision),
+        );
+        self.set_center_big(&self.center_big + &delta);
+    }
+
+    /// Multiply the scale by `factor`, keeping the point under the
+    /// (fractional) pixel `(px, py)` in place.
+    pub fn zoom_about(&mut self, px: f64, py: f64, factor: f64) {
+        let dx = px - self.width as f64 / 2.0;
+        let dy = py - self.height as f64 / 2.0;
+        self.pan_pixels(dx * (1.0 - factor), dy * (1.0 - factor));
+        self.scale *= factor;
     }
 
     /// Map a pixel coordinate to a point on the complex plane.
@@ -131,9 +225,29 @@ impl Viewport {
     pub fn subpixel_to_complex(&self, px: f64, py: f64) -> Complex {
         let half_w = self.width as f64 / 2.0;
         let half_h = self.height as f64 / 2.0;
+        let scale = self.scale.to_f64();
         Complex::new(
-            self.center.re + (px - half_w) * self.scale,
-            self.center.im - (py - half_h) * self.scale,
+            self.center.re + (px - half_w) * scale,
+            self.center.im - (py - half_h) * scale,
+        )
+    }
+
+    /// Map a pixel coordinate to a complex-plane point in double-double,
+    /// for picking values (e.g. a Julia constant) at zooms beyond `f64`.
+    #[inline]
+    pub fn pixel_to_complex_dd(&self, px: u32, py: u32) -> ComplexDD {
+        (self.center_qd + self.pixel_to_delta(px, py)).to_complex_dd()
+    }
+
+    /// Map a complex-plane point back to fractional pixel coordinates, for
+    /// drawing overlays. The inverse of
+    /// [`pixel_to_complex_dd`](Self::pixel_to_complex_dd).
+    pub fn complex_dd_to_pixel(&self, c: ComplexDD) -> (f64, f64) {
+        let delta = (ComplexQD::from(c) - self.center_qd).to_complex();
+        let scale = self.scale.to_f64();
+        (
+            self.width as f64 / 2.0 + delta.re / scale,
+            self.height as f64 / 2.0 - delta.im / scale,
         )
     }
 
@@ -150,9 +264,47 @@ impl Viewport {
     /// pixel coordinates for sub-pixel sampling.
     #[inline]
     pub fn subpixel_to_delta(&self, px: f64, py: f64) -> Complex {
+        self.subpixel_to_delta_scaled(px, py, 0)
+    }
+
+    /// Map a pixel coordinate to a delta from the center, divided by
+    /// `2^exponent`.
+    ///
+    /// With `exponent = 0` this is [`pixel_to_delta`](Self::pixel_to_delta).
+    /// Passing [`delta_exponent`](Self::delta_exponent) keeps the result in
+    /// the normal `f64` range even when the scale itself is not.
+    #[inline]
+    pub fn pixel_to_delta_scaled(&self, px: u32, py: u32, exponent: i64) -> Complex {
+        self.subpixel_to_delta_scaled(px as f64, py as f64, exponent)
+    }
+
+    /// Like [`pixel_to_delta_scaled`](Self::pixel_to_delta_scaled) but accepts
+    /// fractional pixel coordinates for sub-pixel sampling.
+    #[inline]
+    pub fn subpixel_to_delta_scaled(&self, px: f64, py: f64, exponent: i64) -> Complex {
         let half_w = self.width as f64 / 2.0;
         let half_h = self.height as f64 / 2.0;
-        Complex::new((px - half_w) * self.scale, -(py - half_h) * self.scale)
+        let m = self.scale.mantissa();
+        let e = self.scale.exponent() - exponent;
+        Complex::new(ldexp((px - half_w) * m, e), ldexp(-(py - half_h) * m, e))
+    }
+
+    /// Binary exponent that pixel deltas should be expressed relative to.
+    ///
+    /// Zero while plain `f64` deltas are safe; at deeper zooms, the scale's
+    /// own exponent, so scaled deltas are roughly pixel offsets.
+    pub fn delta_exponent(&self) -> i64 {
+        if self.scale.exponent() > DELTA_RESCALE_EXPONENT {
+            0
+        } else {
+            self.scale.exponent()
+        }
+    }
+
+    /// Upper bound on `|delta|` for any sample of this viewport, divided by
+    /// `2^exponent`. Includes a one-pixel margin for sub-pixel offsets.
+    pub fn delta_radius_scaled(&self, exponent: i64) -> f64 {
+        self.subpixel_to_delta_scaled(-1.0, -1.0, exponent).norm()
     }
 
     /// The aspect ratio of the viewport (width / height).
@@ -169,6 +321,8 @@ impl Viewport {
         Self {
             center: self.center,
             center_dd: self.center_dd,
+            center_qd: self.center_qd,
+            center_big: self.center_big.clone(),
             scale: self.scale * f as f64,
             width: self.width.div_ceil(f),
             height: self.height.div_ceil(f),
@@ -177,18 +331,26 @@ impl Viewport {
 
     /// The total extent of the viewport in complex-plane units.
     pub fn complex_width(&self) -> f64 {
-        self.width as f64 * self.scale
+        self.width as f64 * self.scale.to_f64()
     }
 
     /// The total extent of the viewport in complex-plane units.
     pub fn complex_height(&self) -> f64 {
-        self.height as f64 * self.scale
+        self.height as f64 * self.scale.to_f64()
     }
 }
 
+/// See [`Viewport::center_precision`]. Whole limbs, and never below
+/// [`BigFloat::DEFAULT_PRECISION`].
+fn center_precision(scale: FloatExp) -> u32 {
+    let bits = (CENTER_GUARD_BITS - scale.exponent()).max(BigFloat::DEFAULT_PRECISION as i64);
+    (bits as u32).div_ceil(64) * 64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::double_double::DoubleDouble;
 
     const EPSILON: f64 = 1e-10;
 
@@ -264,6 +426,99 @@ mod tests {
         assert!(Viewport::new(Complex::ZERO, -1.0, 100, 100).is_err());
     }
 
+    #[test]
+    fn deep_scale_deltas() {
+        let scale = FloatExp::powi10(-1000);
+        let vp = Viewport::new_dd(ComplexDD::ZERO, scale, 100, 100).unwrap();
+        // Plain deltas underflow, scaled ones stay representable.
+        assert_eq!(vp.pixel_to_delta(0, 0), Complex::ZERO);
+        let e = vp.delta_exponent();
+        assert_eq!(e, scale.exponent());
+        let d = vp.pixel_to_delta_scaled(0, 0, e);
+        assert!((d.re + 50.0 * scale.mantissa()).abs() < EPSILON);
+        assert!((d.im - 50.0 * scale.mantissa()).abs() < EPSILON);
+        // Shallow viewports keep plain deltas.
+        let shallow = Viewport::new(Complex::ZERO, 0.01, 100, 100).unwrap();
+        assert_eq!(shallow.delta_exponent(), 0);
+        assert_eq!(
+            shallow.pixel_to_delta_scaled(10, 20, 0),
+            shallow.pixel_to_delta(10, 20)
+        );
+    }
+
+    #[test]
+    fn offset_center_keeps_quad_double_precision() {
+        let mut vp = Viewport::new(Complex::new(-0.75, 0.1), 1e-50, 100, 100).unwrap();
+        vp.offset_center(1e-45, -2e-45);
+        vp.offset_center(1e-45, -2e-45);
+        let moved = vp.center_qd - ComplexQD::from(Complex::new(-0.75, 0.1));
+        assert!((moved.re.to_f64() - 2e-45).abs() < 1e-58);
+        assert!((moved.im.to_f64() + 4e-45).abs() < 1e-58);
+        // The lower-precision fields are roundings of the QD center.
+        assert_eq!(vp.center_dd, vp.center_qd.to_complex_dd());
+        assert_eq!(vp.center, vp.center_qd.to_complex());
+    }
+
+    #[test]
+    fn pixel_to_complex_dd_keeps_digits_beyond_f64() {
+        let center = ComplexDD::new(
+            DoubleDouble::new(-0.75, 1e-17),
+            DoubleDouble::new(0.1, -3e-18),
+        );
+        let vp = Viewport::new_dd(center, FloatExp::from(1e-25), 100, 100).unwrap();
+        let c = vp.pixel_to_complex_dd(60, 50);
+        let offset = c - center;
+        assert!((offset.re.to_f64() - 10.0 * 1e-25).abs() < 1e-31);
+        assert_eq!(offset.im.to_f64(), 0.0);
+        assert_eq!(c.to_complex(), vp.pixel_to_complex(60, 50));
+    }
+
+    #[test]
+    fn complex_dd_to_pixel_inverts_pixel_to_complex_dd() {
+        let center = ComplexDD::new(
+            DoubleDouble::new(-0.75, 1e-17),
+            DoubleDouble::new(0.1, -3e-18),
+        );
+        let vp = Viewport::new_dd(center, FloatExp::from(1e-25), 100, 80).unwrap();
+        let (px, py) = vp.complex_dd_to_pixel(vp.pixel_to_complex_dd(13, 71));
+        assert!((px - 13.0).abs() < 1e-3 && (py - 71.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn center_keeps_offsets_below_quad_double() {
+        let third = format!("0.{}", "3".repeat(120));
+        let re = BigFloat::parse(&third, 512).unwrap();
+        let center = ComplexBig::new(re.clone(), BigFloat::zero(512));
+        let mut vp = Viewport::new_big(center, FloatExp::powi10(-100), 100, 100).unwrap();
+        assert!(vp.center_precision() > 332);
+        let qd = vp.center_qd;
+        vp.offset_center(1e-90, 0.0);
+        assert_eq!(vp.center_qd, qd);
+        let moved = &vp.center_big.re - &re;
+        assert!((moved.to_f64() / 1e-90 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pan_and_zoom_move_the_center_below_f64() {
+        let pixel = FloatExp::powi10(-400);
+        let start = ComplexBig::from_qd(ComplexQD::from(Complex::new(-0.75, 0.1)), 1472);
+        let mut vp = Viewport::new_big(start.clone(), pixel, 100, 100).unwrap();
+        assert_eq!(vp.scale.to_f64(), 0.0);
+        let in_pixels = |x: &BigFloat| (x.to_float_exp() / pixel).to_f64();
+
+        vp.pan_pixels(10.0, -20.0);
+        let panned = vp.center_big.clone();
+        assert!((in_pixels(&(&panned.re - &start.re)) - 10.0).abs() < 1e-9);
+        assert!((in_pixels(&(&panned.im - &start.im)) - 20.0).abs() < 1e-9);
+
+        // Zooming in 2× about a pixel 30 right and 10 down of the center
+        // moves the center halfway towards it.
+        vp.zoom_about(80.0, 60.0, 0.5);
+        assert!((in_pixels(&(&vp.center_big.re - &panned.re)) - 15.0).abs() < 1e-9);
+        assert!((in_pixels(&(&vp.center_big.im - &panned.im)) + 5.0).abs() < 1e-9);
+        assert!(((vp.scale / pixel).to_f64() - 0.5).abs() < 1e-12);
+    }
+
     #[test]
     fn aspect_ratio() {
         let vp = Viewport::default_mandelbrot(1920, 1080);
@@ -140,7 +140,7 @@ fn detect_boundaries(iter_buf: &IterationBuffer) -> Vec<bool> {
 ///
 /// Returns `None` if there are no boundary pixels or the render was
 /// cancelled.
-pub fn compute_aa<F: Fractal + Sync>(
+pub fn compute_aa<F: Fractal + Sync + ?Sized>(
     fractal: &F,
     viewport: &Viewport,
     iter_buf: &IterationBuffer,
@@ -198,7 +198,7 @@ pub fn compute_aa<F: Fractal + Sync>(
                     let px = x as f64 + (sx as f64 + 0.5) * inv;
                     let py = y as f64 + (sy as f64 + 0.5) * inv;
                     let c = if fractal.uses_delta_coordinates() {
-                        viewport.subpixel_to_delta(px, py)
+                        viewport.subpixel_to_delta_scaled(px, py, fractal.delta_exponent())
                     } else {
                         viewport.subpixel_to_complex(px, py)
                     };
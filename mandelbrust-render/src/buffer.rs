@@ -78,11 +78,11 @@ mod tests {
             width: 3,
             height: 2,
         };
-        let red = vec![255, 0, 0, 255].repeat(tile.pixel_count());
+        let red = [255, 0, 0, 255].repeat(tile.pixel_count());
         buf.blit_tile(&tile, &red);
 
         // Check a pixel inside the tile.
-        let idx = ((1 * 8) + 2) * 4;
+        let idx = (8 + 2) * 4;
         assert_eq!(&buf.pixels[idx..idx + 4], &[255, 0, 0, 255]);
 
         // Check a pixel outside the tile is still black.
@@ -0,0 +1,332 @@
+//! Hyperbolic component labels: the nuclei of the Mandelbrot components
+//! visible in a viewport, found from the cycles the periodicity check
+//! reports for interior points.
+//!
+//! A grid of sample pixels is iterated with interior analysis on. Adjacent
+//! samples settling on cycles of the same period form a patch lying in one
+//! component; Newton's method started from the sample with the smallest
+//! multiplier in the patch solves for its nucleus, and the atom size decides
+//! whether the component is big enough on screen to be labeled.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use tracing::{debug, info};
+
+use mandelbrust_core::minibrot::{atom_size, boundary, nucleus};
+use mandelbrust_core::{ComplexDD, ExtrasOptions, Fractal, IterationResult, Viewport};
+
+use crate::renderer::{map_pixel, RenderCancel};
+
+/// Points traced around each component boundary.
+pub const BOUNDARY_POINTS: usize = 96;
+
+/// Nuclei of the same period closer than this fraction of the atom size are
+/// one component found from two patches.
+const SAME_NUCLEUS: f64 = 0.01;
+
+/// Settings of a component scan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentOptions {
+    /// Highest period labeled.
+    pub max_period: u32,
+    /// Smallest component labeled: its atom size in pixels.
+    pub min_size_px: f64,
+    /// Spacing of the sample grid in pixels. Components smaller than this
+    /// can be missed.
+    pub sample_step_px: u32,
+    /// Trace the boundary of each component through the multiplier map.
+    pub boundaries: bool,
+}
+
+impl Default for ComponentOptions {
+    fn default() -> Self {
+        Self {
+            max_period: 64,
+            min_size_px: 8.0,
+            sample_step_px: 4,
+            boundaries: false,
+        }
+    }
+}
+
+/// A hyperbolic component found in the viewport.
+#[derive(Debug, Clone)]
+pub struct LabeledComponent {
+    pub nucleus: ComplexDD,
+    pub period: u32,
+    /// Atom size on the complex plane, roughly the component's radius.
+    pub size: f64,
+    /// Closed boundary curve starting at the root; empty unless requested
+    /// or if tracing it failed.
+    pub boundary: Vec<ComplexDD>,
+}
+
+/// Outcome of [`find_components`].
+#[derive(Debug, Clone)]
+pub struct ComponentScan {
+    /// Components by increasing period.
+    pub components: Vec<LabeledComponent>,
+    pub elapsed: Duration,
+    pub cancelled: bool,
+}
+
+/// Period and multiplier magnitude of an interior sample.
+type Sample = Option<(u32, f64)>;
+
+/// Find the hyperbolic components of `fractal` (a Mandelbrot set) visible in
+/// `viewport` that are at least `opts.min_size_px` across.
+///
+/// `cancel` stops the scan between rows and components; progress counts
+/// sample rows.
+pub fn find_components<F: Fractal + Sync + ?Sized>(
+    fractal: &F,
+    viewport: &Viewport,
+    opts: &ComponentOptions,
+    cancel: &Arc<RenderCancel>,
+) -> ComponentScan {
+    let start = Instant::now();
+    let gen = cancel.generation();
+    let step = opts.sample_step_px.max(1);
+    let cols = viewport.width.div_ceil(step);
+    let rows = viewport.height.div_ceil(step);
+    cancel.reset_progress(rows as usize);
+    debug!(
+        cols,
+        rows,
+        max_period = opts.max_period,
+        "Starting component scan"
+    );
+
+    let extras = ExtrasOptions {
+        analyze_interior: true,
+        ..ExtrasOptions::default()
+    };
+    let samples: Vec<Sample> = (0..rows)
+        .into_par_iter()
+        .flat_map_iter(|row| {
+            let live = cancel.generation() == gen;
+            let samples = (0..cols).map(move |col| {
+                if !live {
+                    return None;
+                }
+                let point = map_pixel(fractal, viewport, col * step, row * step);
+                match fractal.iterate_with_extras(point, &extras) {
+                    (IterationResult::Interior, e) if (1..=opts.max_period).contains(&e.period) => {
+                        Some((e.period, e.multiplier))
+                    }
+                    _ => None,
+                }
+            });
+            let samples: Vec<Sample> = samples.collect();
+            cancel.inc_progress();
+            samples
+        })
+        .collect();
+
+    let scale = viewport.scale.to_f64();
+    let (width, height) = (viewport.width as f64, viewport.height as f64);
+    let found: Vec<LabeledComponent> = patch_seeds(&samples, cols as usize)
+        .into_par_iter()
+        .filter_map(|(index, period)| {
+            if cancel.generation() != gen {
+                return None;
+            }
+            let (col, row) = (index as u32 % cols, index as u32 / cols);
+            let guess = viewport.pixel_to_complex_dd(col * step, row * step);
+            let nucleus = nucleus(guess, period)?;
+            let size = atom_size(nucleus, period);
+            let (x, y) = viewport.complex_dd_to_pixel(nucleus);
+            let visible = (0.0..width).contains(&x) && (0.0..height).contains(&y);
+            (visible && size / scale >= opts.min_size_px).then_some(LabeledComponent {
+                nucleus,
+                period,
+                size,
+                boundary: Vec::new(),
+            })
+        })
+        .collect();
+
+    let mut components: Vec<LabeledComponent> = Vec::new();
+    for component in found {
+        let duplicate = components.iter().any(|other| {
+            other.period == component.period
+                && (other.nucleus - component.nucleus).to_complex().norm()
+                    < SAME_NUCLEUS * component.size
+        });
+        if !duplicate {
+            components.push(component);
+        }
+    }
+    components.sort_by_key(|c| c.period);
+
+    if opts.boundaries {
+        components.par_iter_mut().for_each(|c| {
+            if cancel.generation() == gen {
+                c.boundary = boundary(c.nucleus, c.period, BOUNDARY_POINTS).unwrap_or_default();
+            }
+        });
+    }
+
+    let cancelled = cancel.generation() != gen;
+    if cancelled {
+        components.clear();
+    }
+    let elapsed = start.elapsed();
+    info!(
+        elapsed_ms = elapsed.as_millis(),
+        components = components.len(),
+        cancelled,
+        "Component scan complete"
+    );
+    ComponentScan {
+        components,
+        elapsed,
+        cancelled,
+    }
+}
+
+/// One seed per patch of adjacent samples with the same period: the index
+/// of its sample with the smallest multiplier, nearest the nucleus.
+fn patch_seeds(samples: &[Sample], cols: usize) -> Vec<(usize, u32)> {
+    let rows = samples.len() / cols.max(1);
+    let mut visited = vec![false; samples.len()];
+    let mut seeds = Vec::new();
+    let mut queue = VecDeque::new();
+    for first in 0..samples.len() {
+        let Some((period, _)) = samples[first] else {
+            continue;
+        };
+        if visited[first] {
+            continue;
+        }
+        visited[first] = true;
+        queue.push_back(first);
+        let mut best = (first, f64::INFINITY);
+        while let Some(index) = queue.pop_front() {
+            if let Some((_, multiplier)) = samples[index] {
+                if multiplier < best.1 {
+                    best = (index, multiplier);
+                }
+            }
+            let (col, row) = (index % cols, index / cols);
+            let neighbours = [
+                (col > 0).then(|| index - 1),
+                (col + 1 < cols).then(|| index + 1),
+                (row > 0).then(|| index - cols),
+                (row + 1 < rows).then(|| index + cols),
+            ];
+            for next in neighbours.into_iter().flatten() {
+                if !visited[next] && matches!(samples[next], Some((p, _)) if p == period) {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        seeds.push((best.0, period));
+    }
+    seeds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mandelbrust_core::{Complex, FractalParams, Mandelbrot};
+
+    fn scan(opts: &ComponentOptions) -> ComponentScan {
+        let viewport = Viewport::framing(Complex::new(-0.75, 0.0), 3.0, 2.4, 200, 160);
+        let fractal = Mandelbrot::new(FractalParams::new(500, 2.0).unwrap());
+        let cancel = Arc::new(RenderCancel::new());
+        find_components(&fractal, &viewport, opts, &cancel)
+    }
+
+    #[test]
+    fn patch_seeds_pick_the_smallest_multiplier() {
+        let samples = [
+            Some((1, 0.5)),
+            Some((1, 0.1)),
+            None,
+            Some((2, 0.3)),
+            Some((1, 0.7)),
+            None,
+            None,
+            Some((2, 0.2)),
+        ];
+        assert_eq!(patch_seeds(&samples, 4), vec![(1, 1), (7, 2)]);
+    }
+
+    #[test]
+    fn labels_the_large_components_of_the_set() {
+        let result = scan(&ComponentOptions {
+            min_size_px: 2.0,
+            ..ComponentOptions::default()
+        });
+        assert!(!result.cancelled);
+        let near = |period: u32, re: f64, im: f64| {
+            result.components.iter().any(|c| {
+                c.period == period && (c.nucleus.to_complex() - Complex::new(re, im)).norm() < 1e-9
+            })
+        };
+        assert!(near(1, 0.0, 0.0));
+        assert!(near(2, -1.0, 0.0));
+        assert!(near(3, -0.122_561_166_876_654, 0.744_861_766_619_744));
+        assert!(near(3, -0.122_561_166_876_654, -0.744_861_766_619_744));
+        // Each component is labeled once, lowest periods first.
+        assert_eq!(
+            result.components.iter().filter(|c| c.period == 1).count(),
+            1
+        );
+        assert!(result
+            .components
+            .windows(2)
+            .all(|w| w[0].period <= w[1].period));
+    }
+
+    #[test]
+    fn min_size_and_max_period_filter_components() {
+        let big = scan(&ComponentOptions {
+            min_size_px: 20.0,
+            ..ComponentOptions::default()
+        });
+        assert!(big.components.iter().all(|c| c.period <= 2));
+        let low = scan(&ComponentOptions {
+            max_period: 2,
+            min_size_px: 1.0,
+            ..ComponentOptions::default()
+        });
+        assert!(low.components.iter().all(|c| c.period <= 2));
+    }
+
+    #[test]
+    fn boundaries_are_traced_on_request() {
+        let result = scan(&ComponentOptions {
+            max_period: 2,
+            boundaries: true,
+            ..ComponentOptions::default()
+        });
+        let bulb = result.components.iter().find(|c| c.period == 2).unwrap();
+        assert_eq!(bulb.boundary.len(), BOUNDARY_POINTS);
+        for c in &bulb.boundary {
+            let r = (c.to_complex() + Complex::new(1.0, 0.0)).norm();
+            assert!((r - 0.25).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn cancelled_scans_return_nothing() {
+        let viewport = Viewport::framing(Complex::new(-0.75, 0.0), 3.0, 2.4, 200, 160);
+        let fractal = Mandelbrot::new(FractalParams::new(500, 2.0).unwrap());
+        let cancel = Arc::new(RenderCancel::new());
+        let scan = std::thread::scope(|s| {
+            let handle = s.spawn(|| {
+                find_components(&fractal, &viewport, &ComponentOptions::default(), &cancel)
+            });
+            cancel.cancel();
+            handle.join().unwrap()
+        });
+        // Cancelled mid-scan or finished before the cancel landed.
+        assert!(!scan.cancelled || scan.components.is_empty());
+    }
+}
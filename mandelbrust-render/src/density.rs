@@ -0,0 +1,631 @@
+//! Density rendering: the Buddhabrot, Anti-Buddhabrot and Nebulabrot.
+//!
+//! Escape-time views color each pixel by the orbit that starts there. A
+//! density view instead samples points `c` over the parameter plane, iterates
+//! `z → z² + c` from zero, and adds a hit to every pixel an orbit passes
+//! through. The result is a histogram of orbit points over the viewport,
+//! tone-mapped to color by [`Palette::colorize_density`].
+//!
+//! Sampling runs in passes so the render thread can show the image building
+//! up; each pass draws fresh samples from a generator seeded by
+//! [`DensityOptions::seed`], so a run is reproducible whatever the thread
+//! count.
+//!
+//! [`Palette::colorize_density`]: crate::Palette::colorize_density
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use tracing::{debug, info};
+
+use mandelbrust_core::{Complex, FractalParams, Viewport};
+
+use crate::renderer::RenderCancel;
+
+/// Samples handed to one Rayon task; each task has its own generator.
+const CHUNK_SAMPLES: u32 = 4096;
+
+/// Half-width of the sampled square of `c`. Every orbit of a point outside
+/// the disc of radius 2 escapes on its first step.
+const SAMPLE_EXTENT: f64 = 2.0;
+
+// ---------------------------------------------------------------------------
+// Options
+// ---------------------------------------------------------------------------
+
+/// Which orbits feed the density buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DensityVariant {
+    /// Orbits that escape within the iteration limit.
+    Buddhabrot,
+    /// Orbits that stay bounded for the whole iteration limit.
+    AntiBuddhabrot,
+    /// Escaping orbits, counted once per red, green and blue channel with
+    /// its own iteration limit.
+    Nebulabrot { limits: [u32; 3] },
+}
+
+impl DensityVariant {
+    /// Classic Nebulabrot limits: long orbits in red, short ones in blue.
+    pub const DEFAULT_NEBULA_LIMITS: [u32; 3] = [5000, 500, 50];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Buddhabrot => "Buddhabrot",
+            Self::AntiBuddhabrot => "Anti-Buddhabrot",
+            Self::Nebulabrot { .. } => "Nebulabrot",
+        }
+    }
+
+    /// Number of density channels the variant accumulates.
+    pub fn channel_count(self) -> usize {
+        match self {
+            Self::Nebulabrot { .. } => 3,
+            Self::Buddhabrot | Self::AntiBuddhabrot => 1,
+        }
+    }
+}
+
+/// How sample points `c` are spread over the parameter plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sampling {
+    /// Independent uniform samples.
+    Random,
+    /// One jittered sample per cell of a square grid, which lowers the
+    /// noise of short runs.
+    Stratified,
+}
+
+impl Sampling {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Stratified => "stratified",
+        }
+    }
+}
+
+/// Settings of a density render.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DensityOptions {
+    pub variant: DensityVariant,
+    pub sampling: Sampling,
+    /// Samples drawn per pass. Stratified sampling rounds it down to a
+    /// square number.
+    pub samples_per_pass: u32,
+    pub passes: u32,
+    /// Shortest escaping orbit that is counted; skipping short orbits
+    /// removes the haze around the set.
+    pub min_iterations: u32,
+    pub seed: u64,
+}
+
+impl Default for DensityOptions {
+    fn default() -> Self {
+        Self {
+            variant: DensityVariant::Buddhabrot,
+            sampling: Sampling::Stratified,
+            samples_per_pass: 1_000_000,
+            passes: 16,
+            min_iterations: 0,
+            seed: 1,
+        }
+    }
+}
+
+impl fmt::Display for DensityOptions {
+    /// E.g. `Nebulabrot (limits 5000/500/50), 16 × 1000000 stratified
+    /// samples, seed 1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.variant.label())?;
+        if let DensityVariant::Nebulabrot { limits: [r, g, b] } = self.variant {
+            write!(f, " (limits {r}/{g}/{b})")?;
+        }
+        write!(
+            f,
+            ", {} × {} {} samples",
+            self.passes,
+            self.samples_per_pass,
+            self.sampling.label()
+        )?;
+        if self.min_iterations > 0 {
+            write!(f, ", min {} iterations", self.min_iterations)?;
+        }
+        write!(f, ", seed {}", self.seed)
+    }
+}
+
+/// How hit counts are mapped to brightness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DensityToneMap {
+    /// Compress counts with `ln(1 + n)` before normalizing.
+    pub log: bool,
+    /// Brightness is `t^(1/gamma)` for the normalized count `t`.
+    pub gamma: f64,
+}
+
+impl DensityToneMap {
+    /// Brightness in `[0, 1]` of `count` hits when the densest pixel of the
+    /// channel has `max`.
+    pub fn brightness(&self, count: u64, max: u64) -> f64 {
+        if count == 0 || max == 0 {
+            return 0.0;
+        }
+        let t = if self.log {
+            (count as f64).ln_1p() / (max as f64).ln_1p()
+        } else {
+            count as f64 / max as f64
+        };
+        t.clamp(0.0, 1.0).powf(1.0 / self.gamma.max(1e-3))
+    }
+}
+
+impl Default for DensityToneMap {
+    fn default() -> Self {
+        Self {
+            log: true,
+            gamma: 2.0,
+        }
+    }
+}
+
+impl fmt::Display for DensityToneMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let curve = if self.log { "log" } else { "linear" };
+        write!(f, "{curve}, gamma {}", self.gamma)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Buffer and result
+// ---------------------------------------------------------------------------
+
+/// Orbit hit counts over a viewport.
+#[derive(Debug, Clone)]
+pub struct DensityBuffer {
+    pub width: u32,
+    pub height: u32,
+    /// One row-major plane of counts per channel: a single plane for the
+    /// Buddhabrot variants, red, green and blue for the Nebulabrot.
+    pub channels: Vec<Vec<u64>>,
+    /// Samples drawn so far.
+    pub samples: u64,
+}
+
+impl DensityBuffer {
+    /// Largest count of each channel.
+    pub fn channel_max(&self) -> Vec<u64> {
+        self.channels
+            .iter()
+            .map(|c| c.iter().copied().max().unwrap_or(0))
+            .collect()
+    }
+}
+
+/// The result of a density render.
+pub struct DensityResult {
+    pub density: DensityBuffer,
+    pub elapsed: Duration,
+    pub cancelled: bool,
+    /// Passes completed, up to [`DensityOptions::passes`].
+    pub passes: u32,
+}
+
+// ---------------------------------------------------------------------------
+// Sampling
+// ---------------------------------------------------------------------------
+
+/// SplitMix64: small, fast, and good enough to place sample points.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+/// Generator of one chunk of one pass, independent of scheduling.
+fn chunk_rng(seed: u64, pass: u32, chunk: u32) -> SplitMix64 {
+    let mut mix = SplitMix64(seed ^ ((pass as u64) << 32 | chunk as u64));
+    SplitMix64(mix.next_u64())
+}
+
+/// The `index`-th sample `c` of a pass, in the upper half-plane: the
+/// conjugate orbit is accumulated alongside.
+fn sample_point(sampling: Sampling, grid: u32, index: u32, rng: &mut SplitMix64) -> Complex {
+    let (u, v) = match sampling {
+        Sampling::Random => (rng.next_f64(), rng.next_f64()),
+        Sampling::Stratified => {
+            let (col, row) = (index % grid, index / grid);
+            (
+                (col as f64 + rng.next_f64()) / grid as f64,
+                (row as f64 + rng.next_f64()) / grid as f64,
+            )
+        }
+    };
+    Complex::new(SAMPLE_EXTENT * (2.0 * u - 1.0), SAMPLE_EXTENT * v)
+}
+
+/// Inside the main cardioid or the period-2 bulb, where no orbit escapes.
+fn in_main_components(c: Complex) -> bool {
+    let q = (c.re - 0.25) * (c.re - 0.25) + c.im * c.im;
+    let cardioid = q * (q + (c.re - 0.25)) <= 0.25 * c.im * c.im;
+    let bulb = (c.re + 1.0) * (c.re + 1.0) + c.im * c.im <= 0.0625;
+    cardioid || bulb
+}
+
+// ---------------------------------------------------------------------------
+// Accumulation
+// ---------------------------------------------------------------------------
+
+/// Shared hit counters, written by all Rayon tasks of a pass. 64 bits wide:
+/// a bounded orbit traced to a deep iteration limit can hit one pixel more
+/// than `u32::MAX` times over a render.
+struct Accumulator {
+    channels: Vec<Vec<AtomicU64>>,
+    width: u32,
+    height: u32,
+    center: Complex,
+    inv_scale: f64,
+}
+
+impl Accumulator {
+    fn new(viewport: &Viewport, channel_count: usize) -> Self {
+        let len = viewport.width as usize * viewport.height as usize;
+        Self {
+            channels: (0..channel_count)
+                .map(|_| (0..len).map(|_| AtomicU64::new(0)).collect())
+                .collect(),
+            width: viewport.width,
+            height: viewport.height,
+            center: viewport.center,
+            inv_scale: 1.0 / viewport.scale.to_f64(),
+        }
+    }
+
+    /// Pixel index of `z`, if it lies in the viewport.
+    #[inline]
+    fn pixel(&self, z: Complex) -> Option<usize> {
+        let x = (z.re - self.center.re) * self.inv_scale + self.width as f64 * 0.5;
+        let y = (self.center.im - z.im) * self.inv_scale + self.height as f64 * 0.5;
+        if x >= 0.0 && y >= 0.0 && x < self.width as f64 && y < self.height as f64 {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Count every point of `orbit` and of its mirror image in `channel`.
+    fn add_orbit(&self, channel: usize, orbit: &[Complex]) {
+        let counts = &self.channels[channel];
+        for &z in orbit {
+            for p in [z, z.conj()] {
+                if let Some(idx) = self.pixel(p) {
+                    counts[idx].fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    fn snapshot(&self, samples: u64) -> DensityBuffer {
+        DensityBuffer {
+            width: self.width,
+            height: self.height,
+            channels: self
+                .channels
+                .iter()
+                .map(|c| c.iter().map(|n| n.load(Ordering::Relaxed)).collect())
+                .collect(),
+            samples,
+        }
+    }
+}
+
+/// Iterate `z² + c` from zero into `orbit` (reused across calls) until it
+/// escapes or `limit` steps. Returns whether it escaped.
+fn trace_orbit(c: Complex, limit: u32, escape_radius_sq: f64, orbit: &mut Vec<Complex>) -> bool {
+    orbit.clear();
+    let mut z = Complex::ZERO;
+    for _ in 0..limit {
+        z = z * z + c;
+        if z.norm_sq() > escape_radius_sq {
+            return true;
+        }
+        orbit.push(z);
+    }
+    false
+}
+
+/// Trace one sample and add its orbit to the channels it belongs to.
+fn accumulate_sample(
+    acc: &Accumulator,
+    c: Complex,
+    variant: DensityVariant,
+    params: &FractalParams,
+    min_iterations: u32,
+    orbit: &mut Vec<Complex>,
+) {
+    let escape_radius_sq = params.escape_radius_sq();
+    match variant {
+        DensityVariant::Buddhabrot => {
+            if in_main_components(c) {
+                return;
+            }
+            let escaped = trace_orbit(c, params.max_iterations, escape_radius_sq, orbit);
+            if escaped && orbit.len() as u32 >= min_iterations {
+                acc.add_orbit(0, orbit);
+            }
+        }
+        DensityVariant::AntiBuddhabrot => {
+            if !trace_orbit(c, params.max_iterations, escape_radius_sq, orbit) {
+                acc.add_orbit(0, orbit);
+            }
+        }
+        DensityVariant::Nebulabrot { limits } => {
+            if in_main_components(c) {
+                return;
+            }
+            let longest = limits.iter().copied().max().unwrap_or(0);
+            if !trace_orbit(c, longest, escape_radius_sq, orbit) {
+                return;
+            }
+            let steps = orbit.len() as u32;
+            if steps < min_iterations {
+                return;
+            }
+            for (channel, &limit) in limits.iter().enumerate() {
+                if steps < limit {
+                    acc.add_orbit(channel, orbit);
+                }
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Render
+// ---------------------------------------------------------------------------
+
+/// Accumulate a density image of the viewport, pass by pass.
+///
+/// `params` gives the iteration limit (Nebulabrot limits come from the
+/// variant) and escape radius. `on_pass` is called with the buffer so far
+/// after each completed pass; `cancel` stops the run between chunks and
+/// reports progress in passes.
+pub fn render_density(
+    viewport: &Viewport,
+    params: &FractalParams,
+    opts: &DensityOptions,
+    cancel: &Arc<RenderCancel>,
+    mut on_pass: impl FnMut(&DensityBuffer),
+) -> DensityResult {
+    let start = Instant::now();
+    let gen = cancel.generation();
+    let acc = Accumulator::new(viewport, opts.variant.channel_count());
+
+    let grid = (opts.samples_per_pass as f64).sqrt() as u32;
+    let samples_per_pass = match opts.sampling {
+        Sampling::Random => opts.samples_per_pass,
+        Sampling::Stratified => grid * grid,
+    };
+    let chunk_count = samples_per_pass.div_ceil(CHUNK_SAMPLES);
+    debug!(
+        variant = opts.variant.label(),
+        samples_per_pass,
+        passes = opts.passes,
+        "Starting density render"
+    );
+    cancel.reset_progress(opts.passes as usize);
+
+    let mut samples = 0u64;
+    let mut passes = 0;
+    let mut density = acc.snapshot(0);
+    for pass in 0..opts.passes {
+        (0..chunk_count).into_par_iter().for_each(|chunk| {
+            if cancel.generation() != gen {
+                return;
+            }
+            let mut rng = chunk_rng(opts.seed, pass, chunk);
+            let mut orbit = Vec::new();
+            let first = chunk * CHUNK_SAMPLES;
+            let last = (first + CHUNK_SAMPLES).min(samples_per_pass);
+            for index in first..last {
+                let c = sample_point(opts.sampling, grid, index, &mut rng);
+                accumulate_sample(
+                    &acc,
+                    c,
+                    opts.variant,
+                    params,
+                    opts.min_iterations,
+                    &mut orbit,
+                );
+            }
+        });
+        if cancel.generation() != gen {
+            break;
+        }
+        samples += samples_per_pass as u64;
+        passes += 1;
+        density = acc.snapshot(samples);
+        cancel.inc_progress();
+        on_pass(&density);
+    }
+
+    let cancelled = cancel.generation() != gen;
+    let elapsed = start.elapsed();
+    info!(
+        elapsed_ms = elapsed.as_millis(),
+        passes, samples, cancelled, "Density render complete"
+    );
+    DensityResult {
+        density,
+        elapsed,
+        cancelled,
+        passes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_viewport() -> Viewport {
+        Viewport::framing(Complex::new(-0.5, 0.0), 3.2, 3.2, 48, 48)
+    }
+
+    fn options(variant: DensityVariant, seed: u64) -> DensityOptions {
+        DensityOptions {
+            variant,
+            samples_per_pass: 20_000,
+            passes: 2,
+            seed,
+            ..DensityOptions::default()
+        }
+    }
+
+    fn run(opts: &DensityOptions) -> DensityResult {
+        let params = FractalParams::new(200, 2.0).unwrap();
+        let cancel = Arc::new(RenderCancel::new());
+        render_density(&small_viewport(), &params, opts, &cancel, |_| {})
+    }
+
+    fn total(buffer: &DensityBuffer, channel: usize) -> u64 {
+        buffer.channels[channel].iter().sum()
+    }
+
+    #[test]
+    fn same_seed_reproduces_the_image() {
+        for sampling in [Sampling::Random, Sampling::Stratified] {
+            let opts = DensityOptions {
+                sampling,
+                ..options(DensityVariant::Buddhabrot, 7)
+            };
+            let a = run(&opts).density;
+            let b = run(&opts).density;
+            assert_eq!(a.channels, b.channels);
+            assert!(total(&a, 0) > 0);
+
+            let c = run(&DensityOptions { seed: 8, ..opts }).density;
+            assert_ne!(a.channels, c.channels);
+        }
+    }
+
+    #[test]
+    fn image_is_mirror_symmetric() {
+        let density = run(&options(DensityVariant::Buddhabrot, 3)).density;
+        let (w, h) = (density.width as usize, density.height as usize);
+        for y in 0..h {
+            for x in 0..w {
+                let top = density.channels[0][y * w + x];
+                let bottom = density.channels[0][(h - 1 - y) * w + x];
+                assert_eq!(top, bottom, "pixel ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn anti_buddhabrot_counts_bounded_orbits() {
+        let density = run(&options(DensityVariant::AntiBuddhabrot, 5)).density;
+        // The fixed point of c = 0 sits at the origin, inside the set.
+        let (w, h) = (density.width as usize, density.height as usize);
+        let acc = Accumulator::new(&small_viewport(), 1);
+        let origin = acc.pixel(Complex::ZERO).unwrap();
+        assert!(density.channels[0][origin] > 0);
+        assert_eq!(density.channels[0].len(), w * h);
+    }
+
+    #[test]
+    fn nebulabrot_channels_nest_by_limit() {
+        let opts = options(
+            DensityVariant::Nebulabrot {
+                limits: [200, 50, 10],
+            },
+            11,
+        );
+        let density = run(&opts).density;
+        assert_eq!(density.channels.len(), 3);
+        for idx in 0..density.channels[0].len() {
+            assert!(density.channels[0][idx] >= density.channels[1][idx]);
+            assert!(density.channels[1][idx] >= density.channels[2][idx]);
+        }
+        assert!(total(&density, 2) > 0);
+    }
+
+    #[test]
+    fn min_iterations_drops_short_orbits() {
+        let all = run(&options(DensityVariant::Buddhabrot, 2)).density;
+        let long = run(&DensityOptions {
+            min_iterations: 20,
+            ..options(DensityVariant::Buddhabrot, 2)
+        })
+        .density;
+        assert!(total(&long, 0) < total(&all, 0));
+    }
+
+    #[test]
+    fn passes_report_progress_and_cancel_stops() {
+        let params = FractalParams::new(100, 2.0).unwrap();
+        let cancel = Arc::new(RenderCancel::new());
+        let opts = DensityOptions {
+            passes: 5,
+            samples_per_pass: 1000,
+            ..DensityOptions::default()
+        };
+        let mut seen = Vec::new();
+        let result = render_density(&small_viewport(), &params, &opts, &cancel, |d| {
+            seen.push(d.samples);
+            if seen.len() == 2 {
+                cancel.cancel();
+            }
+        });
+        assert!(result.cancelled);
+        assert_eq!(result.passes, 2);
+        // Stratified sampling rounds 1000 down to 31².
+        assert_eq!(seen, vec![961, 1922]);
+        assert_eq!(result.density.samples, 1922);
+    }
+
+    #[test]
+    fn tone_map_spans_black_to_full() {
+        for log in [false, true] {
+            let tone = DensityToneMap { log, gamma: 2.0 };
+            assert_eq!(tone.brightness(0, 100), 0.0);
+            assert_eq!(tone.brightness(100, 100), 1.0);
+            let mid = tone.brightness(25, 100);
+            assert!(mid > 0.0 && mid < 1.0);
+        }
+        let linear = DensityToneMap {
+            log: false,
+            gamma: 1.0,
+        };
+        assert!((linear.brightness(25, 100) - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn options_describe_the_run() {
+        let opts = DensityOptions {
+            variant: DensityVariant::Nebulabrot {
+                limits: DensityVariant::DEFAULT_NEBULA_LIMITS,
+            },
+            seed: 42,
+            ..DensityOptions::default()
+        };
+        assert_eq!(
+            opts.to_string(),
+            "Nebulabrot (limits 5000/500/50), 16 × 1000000 stratified samples, seed 42"
+        );
+        assert_eq!(DensityToneMap::default().to_string(), "log, gamma 2");
+    }
+}
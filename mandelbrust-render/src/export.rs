@@ -7,7 +7,10 @@ use tracing::debug;
 
 /// Metadata to embed in an exported PNG as tEXt chunks.
 pub struct ExportMetadata {
+    /// Display name of the fractal family.
     pub fractal_type: String,
+    /// Registry id of the fractal family.
+    pub fractal_id: String,
     pub center_re: String,
     pub center_im: String,
     pub zoom: String,
@@ -15,6 +18,17 @@ pub struct ExportMetadata {
     pub escape_radius: f64,
     pub julia_c_re: Option<String>,
     pub julia_c_im: Option<String>,
+    /// Scalar parameters of the family by key, e.g. `("exponent", 3.0)`.
+    pub params: Vec<(String, f64)>,
+    /// Definition of a user formula fractal, as shown by its `Display`.
+    pub formula: Option<String>,
+    /// A/B sequence of a Lyapunov fractal.
+    pub sequence: Option<String>,
+    /// Settings of a density (Buddhabrot) render: its [`DensityOptions`]
+    /// and tone mapping.
+    ///
+    /// [`DensityOptions`]: crate::DensityOptions
+    pub density: Option<String>,
     pub aa_level: u32,
     pub palette_name: String,
     pub smooth_coloring: bool,
@@ -74,15 +88,28 @@ fn build_description(meta: &ExportMetadata) -> String {
         "{} - Center: {} {}i, Zoom: {}, Iterations: {}",
         meta.fractal_type, meta.center_re, meta.center_im, meta.zoom, meta.max_iterations,
     );
+    for (key, value) in &meta.params {
+        desc.push_str(&format!(", {key}: {value}"));
+    }
     if let (Some(re), Some(im)) = (&meta.julia_c_re, &meta.julia_c_im) {
         desc.push_str(&format!(", Julia C: {} {}i", re, im));
     }
+    if let Some(formula) = &meta.formula {
+        desc.push_str(&format!(", Formula: {formula}"));
+    }
+    if let Some(sequence) = &meta.sequence {
+        desc.push_str(&format!(", Sequence: {sequence}"));
+    }
+    if let Some(density) = &meta.density {
+        desc.push_str(&format!(", Density: {density}"));
+    }
     desc
 }
 
 fn build_metadata_pairs(meta: &ExportMetadata) -> Vec<(String, String)> {
     let mut pairs = vec![
         ("MandelbRust.FractalType".into(), meta.fractal_type.clone()),
+        ("MandelbRust.FractalId".into(), meta.fractal_id.clone()),
         ("MandelbRust.CenterRe".into(), meta.center_re.clone()),
         ("MandelbRust.CenterIm".into(), meta.center_im.clone()),
         ("MandelbRust.Zoom".into(), meta.zoom.clone()),
@@ -105,12 +132,24 @@ fn build_metadata_pairs(meta: &ExportMetadata) -> Vec<(String, String)> {
             format!("{}x{}", meta.width, meta.height),
         ),
     ];
+    for (key, value) in &meta.params {
+        pairs.push((format!("MandelbRust.Param.{key}"), value.to_string()));
+    }
     if let Some(re) = &meta.julia_c_re {
         pairs.push(("MandelbRust.JuliaC_Re".into(), re.clone()));
     }
     if let Some(im) = &meta.julia_c_im {
         pairs.push(("MandelbRust.JuliaC_Im".into(), im.clone()));
     }
+    if let Some(formula) = &meta.formula {
+        pairs.push(("MandelbRust.Formula".into(), formula.clone()));
+    }
+    if let Some(sequence) = &meta.sequence {
+        pairs.push(("MandelbRust.Sequence".into(), sequence.clone()));
+    }
+    if let Some(density) = &meta.density {
+        pairs.push(("MandelbRust.Density".into(), density.clone()));
+    }
     pairs
 }
 
@@ -126,6 +165,7 @@ mod tests {
         let pixels = vec![128u8; (w * h * 4) as usize];
         let meta = ExportMetadata {
             fractal_type: "Mandelbrot".into(),
+            fractal_id: "mandelbrot".into(),
             center_re: "-0.5".into(),
             center_im: "0.0".into(),
             zoom: "1.0".into(),
@@ -133,6 +173,10 @@ mod tests {
             escape_radius: 2.0,
             julia_c_re: None,
             julia_c_im: None,
+            params: Vec::new(),
+            formula: None,
+            sequence: None,
+            density: None,
             aa_level: 0,
             palette_name: "Classic".into(),
             smooth_coloring: true,
@@ -158,7 +202,8 @@ mod tests {
         let h = 2u32;
         let pixels = vec![0u8; (w * h * 4) as usize];
         let meta = ExportMetadata {
-            fractal_type: "Julia".into(),
+            fractal_type: "Multijulia".into(),
+            fractal_id: "multijulia".into(),
             center_re: "0.0".into(),
             center_im: "0.0".into(),
             zoom: "1.0".into(),
@@ -166,6 +211,10 @@ mod tests {
             escape_radius: 2.0,
             julia_c_re: Some("-0.7".into()),
             julia_c_im: Some("0.27015".into()),
+            params: vec![("exponent".into(), 3.5)],
+            formula: Some("z = z^3 + c; z0 = 0; bailout = 2".into()),
+            sequence: Some("AABAB".into()),
+            density: Some("Buddhabrot, 16 × 1000000 stratified samples, seed 7".into()),
             aa_level: 4,
             palette_name: "Fire".into(),
             smooth_coloring: false,
@@ -190,13 +239,36 @@ mod tests {
         assert!(
             texts
                 .iter()
-                .any(|t| t.keyword == "MandelbRust.FractalType" && t.text == "Julia"),
+                .any(|t| t.keyword == "MandelbRust.FractalType" && t.text == "Multijulia"),
             "Should contain fractal type chunk"
         );
         assert!(
             texts.iter().any(|t| t.keyword == "MandelbRust.JuliaC_Re"),
             "Should contain Julia C Re chunk"
         );
+        assert!(
+            texts
+                .iter()
+                .any(|t| t.keyword == "MandelbRust.Param.exponent" && t.text == "3.5"),
+            "Should contain exponent chunk"
+        );
+        assert!(
+            texts.iter().any(|t| t.keyword == "MandelbRust.Formula"
+                && t.text == "z = z^3 + c; z0 = 0; bailout = 2"),
+            "Should contain formula chunk"
+        );
+        assert!(
+            texts
+                .iter()
+                .any(|t| t.keyword == "MandelbRust.Sequence" && t.text == "AABAB"),
+            "Should contain sequence chunk"
+        );
+        assert!(
+            texts
+                .iter()
+                .any(|t| t.keyword == "MandelbRust.Density" && t.text.ends_with("seed 7")),
+            "Should contain density chunk"
+        );
 
         let _ = std::fs::remove_dir_all(&dir);
     }
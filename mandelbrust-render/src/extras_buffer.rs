@@ -1,4 +1,7 @@
-//! Per-pixel extras buffer for advanced coloring (distance estimation, stripe average).
+//! Per-pixel extras buffer for advanced coloring (distance estimation, stripe
+//! average, orbit traps, exterior averages, final angle, interior cycles).
+
+use std::sync::OnceLock;
 
 use mandelbrust_core::IterationExtras;
 
@@ -12,6 +15,20 @@ pub struct ExtrasBuffer {
     pub height: u32,
     pub distance: Vec<f64>,
     pub stripe_avg: Vec<f64>,
+    /// Closest approach to the orbit trap; infinite where none was tracked.
+    pub trap: Vec<f64>,
+    /// Smoothed exterior orbit average; `NaN` where none was measured.
+    pub average: Vec<f64>,
+    /// Argument of the final `z` at escape; `NaN` for interior pixels.
+    pub final_arg: Vec<f64>,
+    /// Period of the interior attracting cycle; `0` where unknown.
+    pub period: Vec<u32>,
+    /// `|λ|` of the interior attracting cycle; `NaN` where unknown.
+    pub multiplier: Vec<f64>,
+    /// Interior distance estimate; `NaN` where unknown.
+    pub interior_distance: Vec<f64>,
+    /// Cached [`interior_distance_range`](Self::interior_distance_range).
+    interior_range: OnceLock<(f64, f64)>,
 }
 
 impl ExtrasBuffer {
@@ -22,10 +39,18 @@ impl ExtrasBuffer {
             height,
             distance: vec![0.0; size],
             stripe_avg: vec![0.0; size],
+            trap: vec![f64::INFINITY; size],
+            average: vec![f64::NAN; size],
+            final_arg: vec![f64::NAN; size],
+            period: vec![0; size],
+            multiplier: vec![f64::NAN; size],
+            interior_distance: vec![f64::NAN; size],
+            interior_range: OnceLock::new(),
         }
     }
 
     pub fn blit_tile(&mut self, tile: &Tile, tile_extras: &[IterationExtras]) {
+        self.interior_range = OnceLock::new();
         for py in 0..tile.height {
             let buf_y = tile.y + py;
             if buf_y >= self.height {
@@ -36,8 +61,15 @@ impl ExtrasBuffer {
             let copy_w = tile.width.min(self.width - tile.x) as usize;
             for i in 0..copy_w {
                 let ext = &tile_extras[src_start + i];
-                self.distance[dst_start + i] = ext.distance;
-                self.stripe_avg[dst_start + i] = ext.stripe_avg;
+                let dst = dst_start + i;
+                self.distance[dst] = ext.distance;
+                self.stripe_avg[dst] = ext.stripe_avg;
+                self.trap[dst] = ext.trap_distance;
+                self.average[dst] = ext.exterior_avg;
+                self.final_arg[dst] = ext.final_arg;
+                self.period[dst] = ext.period;
+                self.multiplier[dst] = ext.multiplier;
+                self.interior_distance[dst] = ext.interior_distance;
             }
         }
     }
@@ -46,38 +78,60 @@ impl ExtrasBuffer {
         if dx == 0 && dy == 0 {
             return;
         }
-        let w = self.width as i32;
-        let h = self.height as i32;
-        let size = self.distance.len();
-        let mut new_dist = vec![0.0f64; size];
-        let mut new_stripe = vec![0.0f64; size];
+        self.interior_range = OnceLock::new();
+        let (w, h) = (self.width, self.height);
+        shift_plane(&mut self.distance, 0.0, w, h, dx, dy);
+        shift_plane(&mut self.stripe_avg, 0.0, w, h, dx, dy);
+        shift_plane(&mut self.trap, f64::INFINITY, w, h, dx, dy);
+        shift_plane(&mut self.average, f64::NAN, w, h, dx, dy);
+        shift_plane(&mut self.final_arg, f64::NAN, w, h, dx, dy);
+        shift_plane(&mut self.period, 0, w, h, dx, dy);
+        shift_plane(&mut self.multiplier, f64::NAN, w, h, dx, dy);
+        shift_plane(&mut self.interior_distance, f64::NAN, w, h, dx, dy);
+    }
 
-        let x_start = dx.max(0) as usize;
-        let x_end = (w + dx).min(w).max(0) as usize;
-        if x_start >= x_end {
-            self.distance = new_dist;
-            self.stripe_avg = new_stripe;
-            return;
-        }
+    /// Log range of the frame's positive, finite interior distances, for
+    /// normalizing them to palette positions.
+    pub fn interior_distance_range(&self) -> (f64, f64) {
+        *self.interior_range.get_or_init(|| {
+            let (lo, hi) = self
+                .interior_distance
+                .iter()
+                .filter(|d| **d > 0.0 && d.is_finite())
+                .map(|d| d.ln())
+                .fold((f64::MAX, f64::MIN), |(lo, hi), ld| {
+                    (lo.min(ld), hi.max(ld))
+                });
+            if lo >= hi {
+                (0.0, 1.0)
+            } else {
+                (lo, hi)
+            }
+        })
+    }
+}
+
+/// Move one `width × height` plane by `(dx, dy)` pixels, filling uncovered
+/// pixels with `fill`.
+fn shift_plane<T: Copy>(plane: &mut Vec<T>, fill: T, width: u32, height: u32, dx: i32, dy: i32) {
+    let (w, h) = (width as i32, height as i32);
+    let mut shifted = vec![fill; plane.len()];
+
+    let x_start = dx.max(0) as usize;
+    let x_end = (w + dx).min(w).max(0) as usize;
+    if x_start < x_end {
         let count = x_end - x_start;
         let src_x_start = (x_start as i32 - dx) as usize;
-
         for dst_y in 0..h as usize {
             let src_y = dst_y as i32 - dy;
             if src_y < 0 || src_y >= h {
                 continue;
             }
-            let dst_row = dst_y * self.width as usize;
-            let src_row = src_y as usize * self.width as usize;
-            new_dist[dst_row + x_start..dst_row + x_start + count].copy_from_slice(
-                &self.distance[src_row + src_x_start..src_row + src_x_start + count],
-            );
-            new_stripe[dst_row + x_start..dst_row + x_start + count].copy_from_slice(
-                &self.stripe_avg[src_row + src_x_start..src_row + src_x_start + count],
-            );
+            let dst = dst_y * width as usize + x_start;
+            let src = src_y as usize * width as usize + src_x_start;
+            shifted[dst..dst + count].copy_from_slice(&plane[src..src + count]);
         }
-
-        self.distance = new_dist;
-        self.stripe_avg = new_stripe;
     }
+
+    *plane = shifted;
 }
@@ -1,5 +1,7 @@
 pub mod aa;
 pub mod buffer;
+pub mod components;
+pub mod density;
 pub mod error;
 pub mod export;
 pub mod extras_buffer;
@@ -10,11 +12,19 @@ pub mod tile;
 
 pub use aa::{compute_aa, AaSamples};
 pub use buffer::RenderBuffer;
+pub use components::{find_components, ComponentOptions, ComponentScan, LabeledComponent};
+pub use density::{
+    render_density, DensityBuffer, DensityOptions, DensityResult, DensityToneMap, DensityVariant,
+    Sampling,
+};
 pub use error::RenderError;
 pub use export::{export_png, ExportMetadata};
 pub use extras_buffer::ExtrasBuffer;
 pub use iteration_buffer::IterationBuffer;
-pub use palette::{builtin_palettes, ColorParams, ColoringMode, InteriorMode, Palette, StartFrom};
+pub use palette::{
+    builtin_palettes, smooth_iteration, ColorParams, ColoringMode, InteriorMode, Palette,
+    StartFrom, DEFAULT_FIELD_LINES,
+};
 pub use renderer::{render, RenderCancel, RenderOptions, RenderResult};
 pub use tile::TILE_SIZE;
 
@@ -1,13 +1,19 @@
+use std::sync::LazyLock;
+
 use mandelbrust_core::IterationResult;
 use rayon::prelude::*;
 
 use crate::aa::AaSamples;
 use crate::buffer::RenderBuffer;
+use crate::density::{DensityBuffer, DensityToneMap};
 use crate::extras_buffer::ExtrasBuffer;
 use crate::iteration_buffer::IterationBuffer;
 
 const LUT_SIZE: usize = 256;
 
+/// Default [`ColorParams::field_lines`].
+pub const DEFAULT_FIELD_LINES: u32 = 16;
+
 // ---------------------------------------------------------------------------
 // Color params (cycle mode, start-from black/white)
 // ---------------------------------------------------------------------------
@@ -32,6 +38,23 @@ pub enum ColoringMode {
     /// Distance estimation: the boundary distance `d = |z|·ln|z| / |dz|`
     /// is mapped to palette position. Requires extras buffer.
     DistanceEstimation,
+    /// Orbit trap: the closest approach of each orbit to a trap shape is
+    /// mapped to palette position, inside and outside the set alike.
+    /// Requires extras buffer.
+    OrbitTrap,
+    /// Exterior orbit average (stripe, triangle inequality or curvature,
+    /// chosen when rendering) stretched over the frame's range. Requires
+    /// extras buffer.
+    OrbitAverage,
+    /// Smooth coloring darkened where the final `z` lies below the real
+    /// axis, splitting every escape band in two. Requires extras buffer.
+    BinaryDecomposition,
+    /// Palette position taken from the argument of the final `z`.
+    /// Requires extras buffer.
+    AngleHue,
+    /// Smooth coloring overlaid with dark lines along constant final `z`
+    /// argument, which follow the external rays. Requires extras buffer.
+    FieldLines,
 }
 
 /// How interior (non-escaping) pixels are colored.
@@ -43,6 +66,15 @@ pub enum InteriorMode {
     /// Stripe average coloring reveals orbital structure.
     /// Requires extras buffer.
     StripeAverage,
+    /// One hue per period of the attracting cycle, darkening toward the
+    /// edge of each hyperbolic component. Requires extras buffer.
+    Period,
+    /// Palette position from the cycle multiplier `|λ|`: `0` at the
+    /// nucleus, `1` on the component boundary. Requires extras buffer.
+    Multiplier,
+    /// Interior distance estimate, log-mapped over the frame's range.
+    /// Requires extras buffer.
+    InteriorDistance,
 }
 
 /// Parameters for mapping iterations to palette color (cycle length, smooth, start-from).
@@ -56,6 +88,12 @@ pub struct ColorParams {
     pub low_threshold_end: u32,
     pub coloring_mode: ColoringMode,
     pub interior_mode: InteriorMode,
+    /// Degree `d` of the escape-time formula (`z^d + c`); the smooth
+    /// iteration count takes its logarithm base from it.
+    pub degree: f64,
+    /// Field lines per full turn of the final `z` argument, for
+    /// [`ColoringMode::FieldLines`].
+    pub field_lines: u32,
 }
 
 impl ColorParams {
@@ -69,6 +107,8 @@ impl ColorParams {
             low_threshold_end: 30,
             coloring_mode: ColoringMode::Standard,
             interior_mode: InteriorMode::Black,
+            degree: 2.0,
+            field_lines: DEFAULT_FIELD_LINES,
         }
     }
 }
@@ -86,6 +126,8 @@ impl ColorParams {
 pub struct Palette {
     pub name: String,
     colors: Vec<[u8; 4]>,
+    /// Gradient for chaotic measured values; [`DEFAULT_CHAOS`] when unset.
+    chaos: Option<Vec<[u8; 4]>>,
 }
 
 impl Palette {
@@ -94,9 +136,17 @@ impl Palette {
         Self {
             name: name.into(),
             colors,
+            chaos: None,
         }
     }
 
+    /// Use the gradient of `chaos` for the chaotic side of measured values
+    /// (see [`color`](Self::color)).
+    pub fn with_chaos_gradient(mut self, chaos: &Palette) -> Self {
+        self.chaos = Some(chaos.colors.clone());
+        self
+    }
+
     /// Build a `Palette` from a [`PaletteDefinition`] by sampling its gradient
     /// into a LUT of `LUT_SIZE` entries.
     pub fn from_definition(def: &mandelbrust_core::palette_data::PaletteDefinition) -> Self {
@@ -109,6 +159,7 @@ impl Palette {
         Self {
             name: def.name.clone(),
             colors,
+            chaos: None,
         }
     }
 
@@ -116,12 +167,17 @@ impl Palette {
     pub fn color(&self, result: IterationResult, params: &ColorParams) -> [u8; 4] {
         match result {
             IterationResult::Interior => [0, 0, 0, 255],
+            IterationResult::Converged {
+                root_index,
+                iterations,
+            } => basin_color(root_index, iterations),
+            IterationResult::Measured { value } => self.measured_color(value),
             IterationResult::Escaped {
                 iterations,
                 norm_sq,
             } => {
                 let t = if params.smooth {
-                    smooth_iteration(iterations, norm_sq)
+                    smooth_iteration(iterations, norm_sq, params.degree)
                 } else {
                     iterations as f64
                 };
@@ -256,13 +312,17 @@ impl Palette {
                 let result = iter_buf.data[idx];
                 let c = match result {
                     IterationResult::Interior => color_interior(self, extras, idx, params),
+                    IterationResult::Converged { .. } | IterationResult::Measured { .. } => {
+                        self.color(result, params)
+                    }
                     IterationResult::Escaped {
                         iterations,
                         norm_sq,
                     } => {
                         let base_t = cdf[iterations as usize] as f64 / total;
                         let t = if params.smooth {
-                            let frac = smooth_iteration(iterations, norm_sq) - iterations as f64;
+                            let frac = smooth_iteration(iterations, norm_sq, params.degree)
+                                - iterations as f64;
                             let next_idx =
                                 (iterations as usize + 1).min(cdf.len().saturating_sub(1));
                             let next_t = cdf[next_idx] as f64 / total;
@@ -309,15 +369,17 @@ impl Palette {
                     let (mut r, mut g, mut b) = (0u32, 0u32, 0u32);
                     for &s in samples {
                         let sc = match s {
-                            IterationResult::Interior => [0, 0, 0, 255],
+                            IterationResult::Interior
+                            | IterationResult::Converged { .. }
+                            | IterationResult::Measured { .. } => self.color(s, params),
                             IterationResult::Escaped {
                                 iterations,
                                 norm_sq,
                             } => {
                                 let base_t = cdf[iterations as usize] as f64 / total;
                                 let t = if params.smooth {
-                                    let frac =
-                                        smooth_iteration(iterations, norm_sq) - iterations as f64;
+                                    let frac = smooth_iteration(iterations, norm_sq, params.degree)
+                                        - iterations as f64;
                                     let next_idx =
                                         (iterations as usize + 1).min(cdf.len().saturating_sub(1));
                                     let next_t = cdf[next_idx] as f64 / total;
@@ -337,14 +399,17 @@ impl Palette {
                     let result = iter_buf.data[idx];
                     match result {
                         IterationResult::Interior => color_interior(self, extras, idx, params),
+                        IterationResult::Converged { .. } | IterationResult::Measured { .. } => {
+                            self.color(result, params)
+                        }
                         IterationResult::Escaped {
                             iterations,
                             norm_sq,
                         } => {
                             let base_t = cdf[iterations as usize] as f64 / total;
                             let t = if params.smooth {
-                                let frac =
-                                    smooth_iteration(iterations, norm_sq) - iterations as f64;
+                                let frac = smooth_iteration(iterations, norm_sq, params.degree)
+                                    - iterations as f64;
                                 let next_idx =
                                     (iterations as usize + 1).min(cdf.len().saturating_sub(1));
                                 let next_t = cdf[next_idx] as f64 / total;
@@ -385,6 +450,8 @@ impl Palette {
             .for_each(|(idx, pixel)| {
                 let c = match iter_buf.data[idx] {
                     IterationResult::Interior => color_interior(self, Some(extras), idx, params),
+                    result @ (IterationResult::Converged { .. }
+                    | IterationResult::Measured { .. }) => self.color(result, params),
                     IterationResult::Escaped { .. } => {
                         let d = extras.distance[idx];
                         let t = log_normalize(d, d_min, d_max);
@@ -401,6 +468,165 @@ impl Palette {
         }
     }
 
+    /// Colorize by orbit trap distance, log-mapped to palette position over
+    /// the frame's range. Pixels without a trap distance (families that do
+    /// not track traps) keep their escape-time color.
+    pub fn colorize_orbit_trap(
+        &self,
+        iter_buf: &IterationBuffer,
+        extras: &ExtrasBuffer,
+        params: &ColorParams,
+    ) -> RenderBuffer {
+        let len = iter_buf.data.len();
+        let mut pixels = vec![0u8; len * 4];
+
+        let (d_min, d_max) = log_range(&extras.trap);
+
+        pixels
+            .par_chunks_mut(4)
+            .enumerate()
+            .for_each(|(idx, pixel)| {
+                let d = extras.trap[idx];
+                let c = if d.is_finite() {
+                    let t = log_normalize(d, d_min, d_max);
+                    self.sample(t * (self.colors.len() - 1) as f64)
+                } else {
+                    self.color(iter_buf.data[idx], params)
+                };
+                pixel.copy_from_slice(&c);
+            });
+
+        RenderBuffer {
+            width: iter_buf.width,
+            height: iter_buf.height,
+            pixels,
+        }
+    }
+
+    /// Colorize escaped pixels by their exterior orbit average, linearly
+    /// stretched over the frame's range. Interior pixels use the interior
+    /// mode; escaped pixels without an average keep their escape-time color.
+    pub fn colorize_orbit_average(
+        &self,
+        iter_buf: &IterationBuffer,
+        extras: &ExtrasBuffer,
+        params: &ColorParams,
+    ) -> RenderBuffer {
+        let len = iter_buf.data.len();
+        let mut pixels = vec![0u8; len * 4];
+
+        let (lo, hi) = linear_range(&extras.average);
+
+        pixels
+            .par_chunks_mut(4)
+            .enumerate()
+            .for_each(|(idx, pixel)| {
+                let c = match iter_buf.data[idx] {
+                    IterationResult::Interior => color_interior(self, Some(extras), idx, params),
+                    IterationResult::Escaped { .. } if extras.average[idx].is_finite() => {
+                        let t = ((extras.average[idx] - lo) / (hi - lo)).clamp(0.0, 1.0);
+                        self.sample(t * (self.colors.len() - 1) as f64)
+                    }
+                    result => self.color(result, params),
+                };
+                pixel.copy_from_slice(&c);
+            });
+
+        RenderBuffer {
+            width: iter_buf.width,
+            height: iter_buf.height,
+            pixels,
+        }
+    }
+
+    /// Colorize escaped pixels by the argument of their final `z` for the
+    /// binary decomposition, angle hue and field line modes. Pixels without
+    /// an argument keep their escape-time color.
+    pub fn colorize_final_angle(
+        &self,
+        iter_buf: &IterationBuffer,
+        extras: &ExtrasBuffer,
+        params: &ColorParams,
+    ) -> RenderBuffer {
+        let len = iter_buf.data.len();
+        let mut pixels = vec![0u8; len * 4];
+        let lines = params.field_lines.max(1) as f64;
+
+        pixels
+            .par_chunks_mut(4)
+            .enumerate()
+            .for_each(|(idx, pixel)| {
+                let result = iter_buf.data[idx];
+                let arg = extras.final_arg[idx];
+                let c = match result {
+                    IterationResult::Interior => color_interior(self, Some(extras), idx, params),
+                    IterationResult::Escaped { .. } if arg.is_finite() => {
+                        let turn = arg / std::f64::consts::TAU;
+                        match params.coloring_mode {
+                            ColoringMode::BinaryDecomposition if arg < 0.0 => {
+                                scale_color(self.color(result, params), BINARY_SHADE)
+                            }
+                            ColoringMode::AngleHue => {
+                                self.sample(turn.rem_euclid(1.0) * self.colors.len() as f64)
+                            }
+                            ColoringMode::FieldLines => {
+                                // Distance to the nearest line, in half-spacings.
+                                let offset = (turn * lines).rem_euclid(1.0);
+                                let d = 2.0 * offset.min(1.0 - offset);
+                                let shade = (d / FIELD_LINE_WIDTH).min(1.0);
+                                scale_color(self.color(result, params), 0.2 + 0.8 * shade)
+                            }
+                            _ => self.color(result, params),
+                        }
+                    }
+                    _ => self.color(result, params),
+                };
+                pixel.copy_from_slice(&c);
+            });
+
+        RenderBuffer {
+            width: iter_buf.width,
+            height: iter_buf.height,
+            pixels,
+        }
+    }
+
+    /// Tone-map a density buffer. A single channel is spread along the
+    /// palette and darkened toward zero hits; three channels (Nebulabrot)
+    /// are written straight to red, green and blue.
+    pub fn colorize_density(&self, density: &DensityBuffer, tone: &DensityToneMap) -> RenderBuffer {
+        let len = density.width as usize * density.height as usize;
+        let max = density.channel_max();
+        let mut pixels = vec![0u8; len * 4];
+
+        pixels
+            .par_chunks_mut(4)
+            .enumerate()
+            .for_each(|(idx, pixel)| {
+                let c = if let [r, g, b] = density.channels.as_slice() {
+                    let [r, g, b] = [(r, max[0]), (g, max[1]), (b, max[2])]
+                        .map(|(plane, max)| (tone.brightness(plane[idx], max) * 255.0) as u8);
+                    [r, g, b, 255]
+                } else {
+                    let t = tone.brightness(density.channels[0][idx], max[0]);
+                    let color = self.sample(t * (self.colors.len() - 1) as f64);
+                    [
+                        (color[0] as f64 * t) as u8,
+                        (color[1] as f64 * t) as u8,
+                        (color[2] as f64 * t) as u8,
+                        255,
+                    ]
+                };
+                pixel.copy_from_slice(&c);
+            });
+
+        RenderBuffer {
+            width: density.width,
+            height: density.height,
+            pixels,
+        }
+    }
+
     /// Unified colorize dispatch that selects the correct method based on
     /// `params.coloring_mode` and `params.interior_mode`.
     pub fn colorize_advanced(
@@ -427,6 +653,35 @@ impl Palette {
                     self.colorize(iter_buf, params)
                 }
             }
+            ColoringMode::BinaryDecomposition
+            | ColoringMode::AngleHue
+            | ColoringMode::FieldLines => {
+                if let Some(ext) = extras {
+                    self.colorize_final_angle(iter_buf, ext, params)
+                } else if let Some(aa) = aa {
+                    self.colorize_aa(iter_buf, aa, params)
+                } else {
+                    self.colorize(iter_buf, params)
+                }
+            }
+            ColoringMode::OrbitAverage => {
+                if let Some(ext) = extras {
+                    self.colorize_orbit_average(iter_buf, ext, params)
+                } else if let Some(aa) = aa {
+                    self.colorize_aa(iter_buf, aa, params)
+                } else {
+                    self.colorize(iter_buf, params)
+                }
+            }
+            ColoringMode::OrbitTrap => {
+                if let Some(ext) = extras {
+                    self.colorize_orbit_trap(iter_buf, ext, params)
+                } else if let Some(aa) = aa {
+                    self.colorize_aa(iter_buf, aa, params)
+                } else {
+                    self.colorize(iter_buf, params)
+                }
+            }
             ColoringMode::Standard => {
                 if params.interior_mode != InteriorMode::Black {
                     if let Some(ext) = extras {
@@ -511,13 +766,26 @@ impl Palette {
             .collect()
     }
 
+    /// Color a measured exponent `λ`. Stable points (`λ < 0`) run along the
+    /// palette, deeper stability further in; chaotic points (`λ > 0`) fade
+    /// from black into the chaos gradient. A diverged orbit counts as fully
+    /// chaotic.
+    fn measured_color(&self, lambda: f64) -> [u8; 4] {
+        if lambda < 0.0 {
+            let t = 1.0 - lambda.exp();
+            return self.sample(t * (self.colors.len() - 1) as f64);
+        }
+        let s = if lambda.is_finite() {
+            1.0 - (-lambda).exp()
+        } else {
+            1.0
+        };
+        let chaos = self.chaos.as_deref().unwrap_or(&DEFAULT_CHAOS.colors);
+        scale_color(sample_lut(chaos, s * (chaos.len() - 1) as f64), s)
+    }
+
     fn sample(&self, t: f64) -> [u8; 4] {
-        let len = self.colors.len() as f64;
-        let idx = t.rem_euclid(len);
-        let lo = idx.floor() as usize % self.colors.len();
-        let hi = (lo + 1) % self.colors.len();
-        let frac = idx - idx.floor();
-        lerp_color(self.colors[lo], self.colors[hi], frac)
+        sample_lut(&self.colors, t)
     }
 }
 
@@ -533,14 +801,61 @@ impl Default for Palette {
 
 /// Compute the smooth (continuous) iteration count.
 ///
-/// Uses the standard renormalization formula:
-///   ν = n + 1 − log₂(ln(|zₙ|))
-fn smooth_iteration(iterations: u32, norm_sq: f64) -> f64 {
+/// Uses the standard renormalization formula for a degree-`d` map:
+///   ν = n + 1 − log_d(ln(|zₙ|))
+pub fn smooth_iteration(iterations: u32, norm_sq: f64, degree: f64) -> f64 {
     let log_zn = norm_sq.ln() * 0.5; // ln(|z_n|)
     if log_zn <= 0.0 {
         return iterations as f64;
     }
-    iterations as f64 + 1.0 - log_zn.ln() / std::f64::consts::LN_2
+    iterations as f64 + 1.0 - log_zn.ln() / degree.ln()
+}
+
+/// Iterations over which a basin fades from full brightness towards its
+/// darkest shade.
+const BASIN_SHADE_ITERATIONS: f64 = 16.0;
+
+/// Chaos gradient of palettes without one of their own.
+static DEFAULT_CHAOS: LazyLock<Palette> = LazyLock::new(ocean);
+
+/// Brightness of the lower half of each band in binary decomposition.
+const BINARY_SHADE: f64 = 0.4;
+
+/// Width of a field line, as a fraction of the spacing between lines.
+const FIELD_LINE_WIDTH: f64 = 0.15;
+
+/// Hue step between consecutive roots or periods, so any number of them
+/// stays distinguishable.
+const GOLDEN_RATIO_CONJUGATE: f64 = 0.618_033_988_749_894_8;
+
+/// Color a pixel that converged to a root: one hue per root, spaced by the
+/// golden ratio so any number of roots stays distinguishable, darkened the
+/// longer the orbit took to settle.
+fn basin_color(root_index: u32, iterations: u32) -> [u8; 4] {
+    let hue = (root_index as f64 * GOLDEN_RATIO_CONJUGATE).fract();
+    let value = 0.15 + 0.85 * (-(iterations as f64) / BASIN_SHADE_ITERATIONS).exp();
+    let [r, g, b] = hsv_to_rgb(hue, 0.75, value);
+    [r, g, b, 255]
+}
+
+/// HSV (all components in `[0, 1]`) to 8-bit RGB.
+fn hsv_to_rgb(h: f64, s: f64, v: f64) -> [u8; 3] {
+    let sector = h.rem_euclid(1.0) * 6.0;
+    let f = sector.fract();
+    let (p, q, t) = (v * (1.0 - s), v * (1.0 - s * f), v * (1.0 - s * (1.0 - f)));
+    let (r, g, b) = match sector as u32 {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    };
+    [
+        (r * 255.0).round() as u8,
+        (g * 255.0).round() as u8,
+        (b * 255.0).round() as u8,
+    ]
 }
 
 /// Color an interior pixel according to the active interior mode.
@@ -550,16 +865,37 @@ fn color_interior(
     idx: usize,
     params: &ColorParams,
 ) -> [u8; 4] {
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+    let Some(ext) = extras else {
+        return BLACK;
+    };
+    let last = (palette.colors.len() - 1) as f64;
     match params.interior_mode {
-        InteriorMode::Black => [0, 0, 0, 255],
+        InteriorMode::Black => BLACK,
         InteriorMode::StripeAverage => {
-            if let Some(ext) = extras {
-                let s = ext.stripe_avg[idx].clamp(0.0, 1.0);
-                palette.sample(s * palette.colors.len() as f64)
-            } else {
-                [0, 0, 0, 255]
-            }
+            let s = ext.stripe_avg[idx].clamp(0.0, 1.0);
+            palette.sample(s * palette.colors.len() as f64)
         }
+        InteriorMode::Period => match ext.period[idx] {
+            0 => BLACK,
+            period => {
+                let hue = (period as f64 * GOLDEN_RATIO_CONJUGATE).fract();
+                let edge = ext.multiplier[idx].clamp(0.0, 1.0);
+                let [r, g, b] = hsv_to_rgb(hue, 0.75, 1.0 - 0.7 * edge);
+                [r, g, b, 255]
+            }
+        },
+        InteriorMode::Multiplier => match ext.multiplier[idx] {
+            m if m.is_finite() => palette.sample(m.clamp(0.0, 1.0) * last),
+            _ => BLACK,
+        },
+        InteriorMode::InteriorDistance => match ext.interior_distance[idx] {
+            d if d > 0.0 && d.is_finite() => {
+                let (lo, hi) = ext.interior_distance_range();
+                palette.sample(log_normalize(d, lo, hi) * last)
+            }
+            _ => BLACK,
+        },
     }
 }
 
@@ -601,6 +937,35 @@ fn distance_range(distances: &[f64], data: &[IterationResult]) -> (f64, f64) {
     (d_min, d_max)
 }
 
+/// Log range of every positive, finite value, e.g. orbit trap distances.
+fn log_range(values: &[f64]) -> (f64, f64) {
+    let (d_min, d_max) = values
+        .iter()
+        .filter(|d| **d > 0.0 && d.is_finite())
+        .map(|d| d.ln())
+        .fold((f64::MAX, f64::MIN), |(lo, hi), ld| {
+            (lo.min(ld), hi.max(ld))
+        });
+    if d_min >= d_max {
+        (0.0, 1.0)
+    } else {
+        (d_min, d_max)
+    }
+}
+
+/// Range of every finite value, e.g. exterior orbit averages.
+fn linear_range(values: &[f64]) -> (f64, f64) {
+    let (lo, hi) = values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    if lo >= hi {
+        (0.0, 1.0)
+    } else {
+        (lo, hi)
+    }
+}
+
 /// Normalize a distance value to [0, 1] using log mapping.
 fn log_normalize(d: f64, d_min: f64, d_max: f64) -> f64 {
     if d <= 0.0 || !d.is_finite() {
@@ -610,6 +975,25 @@ fn log_normalize(d: f64, d_min: f64, d_max: f64) -> f64 {
     ((ld - d_min) / (d_max - d_min)).clamp(0.0, 1.0)
 }
 
+fn scale_color(c: [u8; 4], k: f64) -> [u8; 4] {
+    [
+        (c[0] as f64 * k) as u8,
+        (c[1] as f64 * k) as u8,
+        (c[2] as f64 * k) as u8,
+        c[3],
+    ]
+}
+
+/// Interpolated color at fractional index `t` of a ring of colors.
+fn sample_lut(colors: &[[u8; 4]], t: f64) -> [u8; 4] {
+    let len = colors.len() as f64;
+    let idx = t.rem_euclid(len);
+    let lo = idx.floor() as usize % colors.len();
+    let hi = (lo + 1) % colors.len();
+    let frac = idx - idx.floor();
+    lerp_color(colors[lo], colors[hi], frac)
+}
+
 fn lerp_color(a: [u8; 4], b: [u8; 4], t: f64) -> [u8; 4] {
     let inv = 1.0 - t;
     [
@@ -724,6 +1108,63 @@ mod tests {
         assert_eq!(p.color(IterationResult::Interior, &params), [0, 0, 0, 255]);
     }
 
+    #[test]
+    fn basins_get_distinct_hues_and_darken_with_iterations() {
+        let p = Palette::default();
+        let params = ColorParams::from_smooth(true);
+        let basin = |root_index, iterations| {
+            p.color(
+                IterationResult::Converged {
+                    root_index,
+                    iterations,
+                },
+                &params,
+            )
+        };
+        let roots: Vec<_> = (0..5).map(|i| basin(i, 0)).collect();
+        for (i, a) in roots.iter().enumerate() {
+            for b in &roots[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        let brightness = |c: [u8; 4]| c[0] as u32 + c[1] as u32 + c[2] as u32;
+        assert!(brightness(basin(1, 2)) > brightness(basin(1, 20)));
+        assert!(brightness(basin(1, 1000)) > 0, "slow basins stay visible");
+    }
+
+    #[test]
+    fn measured_values_split_into_stable_and_chaotic_gradients() {
+        let p = Palette::default();
+        let params = ColorParams::from_smooth(true);
+        let measured = |value| p.color(IterationResult::Measured { value }, &params);
+        assert_eq!(measured(0.0), [0, 0, 0, 255]);
+        let stable_t = 1.0 - (-0.5f64).exp();
+        assert_eq!(measured(-0.5), p.sample(stable_t * (LUT_SIZE - 1) as f64));
+        assert_eq!(
+            measured(f64::INFINITY),
+            ocean().sample((LUT_SIZE - 1) as f64)
+        );
+        assert_eq!(measured(f64::NAN), measured(f64::INFINITY));
+        let blue = |v| measured(v)[2];
+        assert!(blue(0.1) < blue(1.0) && blue(1.0) < blue(10.0));
+    }
+
+    #[test]
+    fn chaos_gradient_comes_from_another_palette() {
+        let params = ColorParams::from_smooth(true);
+        let p = Palette::default().with_chaos_gradient(&fire());
+        let measured = |value| p.color(IterationResult::Measured { value }, &params);
+        assert_eq!(
+            measured(f64::INFINITY),
+            fire().sample((LUT_SIZE - 1) as f64)
+        );
+        assert_eq!(measured(0.0), [0, 0, 0, 255]);
+        assert_eq!(
+            measured(-0.5),
+            Palette::default().color(IterationResult::Measured { value: -0.5 }, &params)
+        );
+    }
+
     #[test]
     fn escaped_is_not_black() {
         let p = Palette::default();
@@ -755,6 +1196,8 @@ mod tests {
             low_threshold_end: 30,
             coloring_mode: ColoringMode::Standard,
             interior_mode: InteriorMode::Black,
+            degree: 2.0,
+            field_lines: DEFAULT_FIELD_LINES,
         };
         let params_raw = ColorParams {
             smooth: false,
@@ -764,6 +1207,8 @@ mod tests {
             low_threshold_end: 30,
             coloring_mode: ColoringMode::Standard,
             interior_mode: InteriorMode::Black,
+            degree: 2.0,
+            field_lines: DEFAULT_FIELD_LINES,
         };
         let smooth = p.color(result, &params_smooth);
         let raw = p.color(result, &params_raw);
@@ -773,6 +1218,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn smooth_iteration_is_continuous_for_any_degree() {
+        // Far from the set |z_{n+1}| ≈ |z_n|^d, so one more iteration with
+        // the magnitude raised to the d-th power must give the same ν.
+        for degree in [2.0, 3.0, 4.5] {
+            let norm_sq: f64 = 1e4;
+            let here = smooth_iteration(7, norm_sq, degree);
+            let next = smooth_iteration(8, norm_sq.powf(degree), degree);
+            assert!(
+                (here - next).abs() < 1e-9,
+                "degree {degree}: {here} vs {next}"
+            );
+        }
+    }
+
     #[test]
     fn builtin_palettes_have_correct_size() {
         for pal in builtin_palettes() {
@@ -808,6 +1268,8 @@ mod tests {
             low_threshold_end: 30,
             coloring_mode: ColoringMode::Standard,
             interior_mode: InteriorMode::Black,
+            degree: 2.0,
+            field_lines: DEFAULT_FIELD_LINES,
         };
         let c0 = p.color(
             IterationResult::Escaped {
@@ -837,6 +1299,8 @@ mod tests {
             low_threshold_end: 30,
             coloring_mode: ColoringMode::Standard,
             interior_mode: InteriorMode::Black,
+            degree: 2.0,
+            field_lines: DEFAULT_FIELD_LINES,
         };
         let c = p.color(
             IterationResult::Escaped {
@@ -859,6 +1323,8 @@ mod tests {
             low_threshold_end: 30,
             coloring_mode: ColoringMode::Standard,
             interior_mode: InteriorMode::Black,
+            degree: 2.0,
+            field_lines: DEFAULT_FIELD_LINES,
         };
         let c = p.color(
             IterationResult::Escaped {
@@ -881,6 +1347,8 @@ mod tests {
             low_threshold_end: 30,
             coloring_mode: ColoringMode::Standard,
             interior_mode: InteriorMode::Black,
+            degree: 2.0,
+            field_lines: DEFAULT_FIELD_LINES,
         };
         let c_low = p.color(
             IterationResult::Escaped {
@@ -899,4 +1367,157 @@ mod tests {
         assert_eq!(c_low, [0, 0, 0, 255]);
         assert!(c_high[0] > 0 || c_high[1] > 0 || c_high[2] > 0);
     }
+
+    #[test]
+    fn orbit_trap_spans_palette_and_falls_back_without_distance() {
+        let p = Palette::default();
+        let params = ColorParams::from_smooth(false);
+        let mut iter_buf = IterationBuffer::new(3, 1, 100);
+        let escaped = IterationResult::Escaped {
+            iterations: 7,
+            norm_sq: 9.0,
+        };
+        iter_buf.data = vec![IterationResult::Interior, escaped, escaped];
+        let mut extras = ExtrasBuffer::new(3, 1);
+        extras.trap = vec![1e-3, 1.0, f64::INFINITY];
+
+        let rb = p.colorize_orbit_trap(&iter_buf, &extras, &params);
+        let px = |i: usize| -> [u8; 4] { rb.pixels[i * 4..i * 4 + 4].try_into().unwrap() };
+        assert_eq!(px(0), p.sample(0.0));
+        assert_eq!(px(1), p.sample((LUT_SIZE - 1) as f64));
+        assert_eq!(px(2), p.color(escaped, &params));
+    }
+
+    #[test]
+    fn orbit_average_stretches_escaped_pixels_over_frame_range() {
+        let p = Palette::default();
+        let params = ColorParams::from_smooth(false);
+        let mut iter_buf = IterationBuffer::new(4, 1, 100);
+        let escaped = IterationResult::Escaped {
+            iterations: 7,
+            norm_sq: 9.0,
+        };
+        iter_buf.data = vec![IterationResult::Interior, escaped, escaped, escaped];
+        let mut extras = ExtrasBuffer::new(4, 1);
+        extras.average = vec![f64::NAN, 0.2, 0.6, f64::NAN];
+
+        let rb = p.colorize_orbit_average(&iter_buf, &extras, &params);
+        let px = |i: usize| -> [u8; 4] { rb.pixels[i * 4..i * 4 + 4].try_into().unwrap() };
+        assert_eq!(px(0), color_interior(&p, Some(&extras), 0, &params));
+        assert_eq!(px(1), p.sample(0.0));
+        assert_eq!(px(2), p.sample((LUT_SIZE - 1) as f64));
+        assert_eq!(px(3), p.color(escaped, &params));
+    }
+
+    #[test]
+    fn final_angle_modes_split_shade_and_rotate() {
+        let p = Palette::default();
+        let escaped = IterationResult::Escaped {
+            iterations: 7,
+            norm_sq: 9.0,
+        };
+        let mut iter_buf = IterationBuffer::new(4, 1, 100);
+        iter_buf.data = vec![escaped; 4];
+        let mut extras = ExtrasBuffer::new(4, 1);
+        let half_spacing = std::f64::consts::PI / DEFAULT_FIELD_LINES as f64;
+        extras.final_arg = vec![0.0, half_spacing, -half_spacing, f64::NAN];
+        let base = p.color(escaped, &ColorParams::from_smooth(true));
+
+        let colorize = |mode| {
+            let params = ColorParams {
+                coloring_mode: mode,
+                ..ColorParams::from_smooth(true)
+            };
+            let rb = p.colorize_final_angle(&iter_buf, &extras, &params);
+            (0..4)
+                .map(|i| <[u8; 4]>::try_from(&rb.pixels[i * 4..i * 4 + 4]).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let binary = colorize(ColoringMode::BinaryDecomposition);
+        assert_eq!(binary[..2], [base, base]);
+        assert_eq!(binary[2], scale_color(base, BINARY_SHADE));
+
+        // On a line at angle zero, untouched halfway between two lines.
+        let lines = colorize(ColoringMode::FieldLines);
+        assert_eq!(lines[0], scale_color(base, 0.2));
+        assert_eq!(lines[1], base);
+        assert_eq!(lines[2], base);
+
+        let hue = colorize(ColoringMode::AngleHue);
+        assert_eq!(hue[0], p.sample(0.0));
+        assert_ne!(hue[1], hue[2]);
+        assert!(hue.iter().all(|c| c[3] == 255));
+        assert_eq!(hue[3], base);
+    }
+
+    #[test]
+    fn interior_modes_read_cycle_extras() {
+        let p = Palette::default();
+        let mut extras = ExtrasBuffer::new(3, 1);
+        extras.period = vec![2, 3, 0];
+        extras.multiplier = vec![0.0, 1.0, f64::NAN];
+        extras.interior_distance = vec![0.01, 0.1, f64::NAN];
+        let with = |mode| ColorParams {
+            interior_mode: mode,
+            ..ColorParams::from_smooth(true)
+        };
+
+        let period = with(InteriorMode::Period);
+        let (nucleus, edge) = (
+            color_interior(&p, Some(&extras), 0, &period),
+            color_interior(&p, Some(&extras), 1, &period),
+        );
+        assert_ne!(nucleus, edge);
+        assert!(nucleus.iter().take(3).max() > edge.iter().take(3).max());
+        assert_eq!(
+            color_interior(&p, Some(&extras), 2, &period),
+            [0, 0, 0, 255]
+        );
+
+        let multiplier = with(InteriorMode::Multiplier);
+        assert_eq!(
+            color_interior(&p, Some(&extras), 0, &multiplier),
+            p.sample(0.0)
+        );
+        assert_eq!(
+            color_interior(&p, Some(&extras), 1, &multiplier),
+            p.sample((LUT_SIZE - 1) as f64)
+        );
+
+        let distance = with(InteriorMode::InteriorDistance);
+        assert_eq!(
+            color_interior(&p, Some(&extras), 0, &distance),
+            p.sample(0.0)
+        );
+        assert_eq!(
+            color_interior(&p, Some(&extras), 1, &distance),
+            p.sample((LUT_SIZE - 1) as f64)
+        );
+        assert_eq!(color_interior(&p, None, 1, &distance), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn density_maps_empty_to_black_and_channels_to_rgb() {
+        let p = Palette::default();
+        let tone = DensityToneMap::default();
+        let single = DensityBuffer {
+            width: 2,
+            height: 1,
+            channels: vec![vec![0, 40]],
+            samples: 1,
+        };
+        let buf = p.colorize_density(&single, &tone);
+        assert_eq!(&buf.pixels[..4], &[0, 0, 0, 255]);
+        assert!(buf.pixels[4..7].iter().any(|&v| v > 0));
+
+        let nebula = DensityBuffer {
+            width: 1,
+            height: 1,
+            channels: vec![vec![9], vec![0], vec![3]],
+            samples: 1,
+        };
+        let buf = p.colorize_density(&nebula, &tone);
+        assert_eq!(buf.pixels, vec![255, 0, 255, 255]);
+    }
 }
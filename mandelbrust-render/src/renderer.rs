@@ -5,7 +5,9 @@ use std::time::{Duration, Instant};
 use rayon::prelude::*;
 use tracing::{debug, info};
 
-use mandelbrust_core::{Complex, Fractal, IterationExtras, IterationResult, Viewport};
+use mandelbrust_core::{
+    Complex, ExtrasOptions, Fractal, IterationExtras, IterationResult, Viewport,
+};
 
 use crate::aa::AaSamples;
 use crate::extras_buffer::ExtrasBuffer;
@@ -89,15 +91,18 @@ pub struct RenderResult {
     pub tiles_rendered: usize,
     pub tiles_mirrored: usize,
     pub tiles_border_traced: usize,
+    /// Iterations each pixel skipped via series approximation (deep zoom).
+    pub skipped_iterations: u32,
 }
 
 /// Render-time options controlling optional features.
 #[derive(Debug, Clone)]
 pub struct RenderOptions {
-    /// Enable real-axis symmetry optimisation (Mandelbrot only).
+    /// Enable real-axis symmetry optimisation. Only takes effect for
+    /// fractals whose [`Fractal::symmetry`] reports `conjugate`.
     pub use_real_axis_symmetry: bool,
-    /// Compute per-pixel extras (distance estimate, stripe average).
-    /// Disables border tracing and symmetry when true.
+    /// Compute per-pixel extras (distance estimate, stripe average, orbit
+    /// trap distance). Disables border tracing and symmetry when true.
     pub compute_extras: bool,
     /// Allow border-trace flood fill optimization.
     ///
@@ -105,8 +110,8 @@ pub struct RenderOptions {
     /// coloring relies on per-pixel continuous values (`norm_sq`), which are
     /// lost when a full tile is filled from a single representative sample.
     pub allow_border_tracing: bool,
-    /// Stripe density for interior stripe average coloring.
-    pub stripe_density: f64,
+    /// What the extras pass measures.
+    pub extras: ExtrasOptions,
 }
 
 impl Default for RenderOptions {
@@ -115,7 +120,7 @@ impl Default for RenderOptions {
             use_real_axis_symmetry: false,
             compute_extras: false,
             allow_border_tracing: true,
-            stripe_density: 1.0,
+            extras: ExtrasOptions::default(),
         }
     }
 }
@@ -127,9 +132,14 @@ impl Default for RenderOptions {
 /// Map a pixel to the coordinate expected by the fractal: either an absolute
 /// complex-plane point or a delta from the fractal's internal center.
 #[inline]
-fn map_pixel<F: Fractal>(fractal: &F, viewport: &Viewport, px: u32, py: u32) -> Complex {
+pub(crate) fn map_pixel<F: Fractal + ?Sized>(
+    fractal: &F,
+    viewport: &Viewport,
+    px: u32,
+    py: u32,
+) -> Complex {
     if fractal.uses_delta_coordinates() {
-        viewport.pixel_to_delta(px, py)
+        viewport.pixel_to_delta_scaled(px, py, fractal.delta_exponent())
     } else {
         viewport.pixel_to_complex(px, py)
     }
@@ -137,7 +147,7 @@ fn map_pixel<F: Fractal>(fractal: &F, viewport: &Viewport, px: u32, py: u32) ->
 
 /// If every border pixel of the tile shares the same iteration class,
 /// return the representative `IterationResult` so we can flood-fill.
-fn check_border_uniform<F: Fractal>(
+fn check_border_uniform<F: Fractal + ?Sized>(
     fractal: &F,
     viewport: &Viewport,
     tile: &Tile,
@@ -199,7 +209,7 @@ struct TileData {
 ///
 /// When `compute_extras` is true, border tracing is skipped and per-pixel
 /// extras (distance, stripe average) are computed alongside iteration data.
-fn render_tile<F: Fractal>(
+fn render_tile<F: Fractal + ?Sized>(
     fractal: &F,
     viewport: &Viewport,
     tile: &Tile,
@@ -228,7 +238,7 @@ fn render_tile<F: Fractal>(
         for px in 0..tile.width {
             let c = map_pixel(fractal, viewport, tile.x + px, tile.y + py);
             if opts.compute_extras {
-                let (result, ext) = fractal.iterate_with_extras(c, opts.stripe_density);
+                let (result, ext) = fractal.iterate_with_extras(c, &opts.extras);
                 iter_data.push(result);
                 extras_data.as_mut().unwrap().push(ext);
             } else {
@@ -254,7 +264,7 @@ fn render_tile<F: Fractal>(
 /// can be used from another thread to abort the render.
 ///
 /// Returns raw iteration data — apply a `Palette` to get displayable pixels.
-pub fn render<F: Fractal + Sync>(
+pub fn render<F: Fractal + Sync + ?Sized>(
     fractal: &F,
     viewport: &Viewport,
     cancel: &Arc<RenderCancel>,
@@ -276,7 +286,8 @@ pub fn render<F: Fractal + Sync>(
     );
 
     // Symmetry disabled when extras are on (stripe avg is not symmetric).
-    let use_symmetry = opts.use_real_axis_symmetry && !opts.compute_extras;
+    let use_symmetry =
+        opts.use_real_axis_symmetry && fractal.symmetry().conjugate && !opts.compute_extras;
     let classified = if use_symmetry {
         classify_tiles_for_symmetry(&tiles, viewport.height, viewport.center.im)
     } else {
@@ -312,10 +323,16 @@ pub fn render<F: Fractal + Sync>(
     }
 
     let tiles_border_traced = bt_count.load(Ordering::Relaxed);
+    let skipped_iterations = fractal.skipped_iterations();
     let elapsed = start.elapsed();
     info!(
         elapsed_ms = elapsed.as_millis(),
-        tiles_rendered, tiles_mirrored, tiles_border_traced, cancelled, "Render complete"
+        tiles_rendered,
+        tiles_mirrored,
+        tiles_border_traced,
+        skipped_iterations,
+        cancelled,
+        "Render complete"
     );
 
     RenderResult {
@@ -327,10 +344,11 @@ pub fn render<F: Fractal + Sync>(
         tiles_rendered,
         tiles_mirrored,
         tiles_border_traced,
+        skipped_iterations,
     }
 }
 
-fn render_all_tiles<F: Fractal + Sync>(
+fn render_all_tiles<F: Fractal + Sync + ?Sized>(
     fractal: &F,
     viewport: &Viewport,
     tiles: &[Tile],
@@ -356,7 +374,7 @@ fn render_all_tiles<F: Fractal + Sync>(
     (results, cancelled, rendered, 0)
 }
 
-fn render_with_symmetry<F: Fractal + Sync>(
+fn render_with_symmetry<F: Fractal + Sync + ?Sized>(
     fractal: &F,
     viewport: &Viewport,
     classified: &[ClassifiedTile],
@@ -434,7 +452,10 @@ fn assemble_symmetric(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mandelbrust_core::{FractalParams, Mandelbrot};
+    use mandelbrust_core::{
+        AbsMandelbrot, AbsVariant, ComplexDD, DoubleDouble, FloatExp, FractalParams, Mandelbrot,
+        MandelbrotPerturb,
+    };
 
     fn opts_standard() -> RenderOptions {
         RenderOptions {
@@ -455,6 +476,24 @@ mod tests {
         assert_eq!(result.iterations.data.len(), 128 * 128);
         assert!(result.tiles_rendered > 0);
         assert!(result.extras.is_none());
+        assert_eq!(result.skipped_iterations, 0);
+    }
+
+    #[test]
+    fn deep_render_reports_skipped_iterations() {
+        let center = ComplexDD::new(
+            DoubleDouble::new(-0.743_643_887_037_151, 1.2e-17),
+            DoubleDouble::new(0.131_825_904_205_33, -3.4e-18),
+        );
+        let viewport = Viewport::new_dd(center, FloatExp::from(1e-25), 64, 64).unwrap();
+        let fractal =
+            MandelbrotPerturb::for_viewport(FractalParams::new(1000, 2.0).unwrap(), &viewport);
+        let cancel = Arc::new(RenderCancel::new());
+
+        let result = render(&fractal, &viewport, &cancel, &RenderOptions::default());
+
+        assert!(result.skipped_iterations > 0);
+        assert_eq!(result.skipped_iterations, fractal.skipped_iterations());
     }
 
     #[test]
@@ -474,6 +513,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn symmetry_skipped_for_asymmetric_fractal() {
+        let params = FractalParams::new(64, 2.0).unwrap();
+        let ship = AbsMandelbrot::new(AbsVariant::BurningShip, params);
+        let viewport =
+            Viewport::new(mandelbrust_core::Complex::new(-0.5, 0.0), 0.01, 128, 128).unwrap();
+        let cancel = Arc::new(RenderCancel::new());
+
+        let result = render(&ship, &viewport, &cancel, &opts_standard());
+
+        assert!(!result.cancelled);
+        assert_eq!(result.tiles_mirrored, 0);
+    }
+
     #[test]
     fn border_tracing_fills_uniform_tiles() {
         let params = FractalParams::new(256, 2.0).unwrap();
@@ -500,7 +553,7 @@ mod tests {
             use_real_axis_symmetry: false,
             compute_extras: true,
             allow_border_tracing: false,
-            stripe_density: 1.0,
+            extras: ExtrasOptions::default(),
         };
 
         let result = render(&mandelbrot, &viewport, &cancel, &opts);
@@ -509,6 +562,7 @@ mod tests {
         let ext = result.extras.as_ref().expect("extras should be present");
         assert_eq!(ext.distance.len(), 128 * 128);
         assert_eq!(ext.stripe_avg.len(), 128 * 128);
+        assert_eq!(ext.trap.len(), 128 * 128);
     }
 
     #[test]
@@ -525,7 +579,7 @@ mod tests {
 
         let result = render(&mandelbrot, &viewport, &cancel, &opts_standard());
         if result.cancelled {
-            let total_tiles = ((1024 + 63) / 64) * ((1024 + 63) / 64);
+            let total_tiles = 1024_usize.div_ceil(64) * 1024_usize.div_ceil(64);
             assert!(
                 result.tiles_rendered < total_tiles,
                 "not all tiles should have been rendered"